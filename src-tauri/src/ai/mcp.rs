@@ -0,0 +1,677 @@
+/**
+ * MCP 服务注册表
+ * 注册 MCP 服务的版本、协议、工具定义和端点；端点可以引用已有的命名服务（REF，按健康实例解析地址）
+ * 或直接指定地址（DIRECT）。stdio 协议的服务在本地启动，没有远程端点
+ */
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::service::{get_service_detail, get_service_instances};
+
+/// 支持的 MCP 协议
+const MCP_PROTOCOLS: [&str; 3] = ["stdio", "mcp-sse", "mcp-streamable"];
+
+/// 本地启动的 MCP 协议（没有远程端点）
+const STDIO_PROTOCOL: &str = "stdio";
+
+/// MCP 服务端点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "UPPERCASE")]
+pub enum McpEndpointSpec {
+    /// 引用命名服务，端点为服务下的健康实例
+    #[serde(rename_all = "camelCase")]
+    Ref {
+        #[serde(default)]
+        namespace_id: Option<String>,
+        #[serde(default)]
+        group_name: Option<String>,
+        service_name: String,
+    },
+    /// 直连地址
+    Direct { address: String, port: i32 },
+}
+
+/// MCP 服务定义（创建或更新版本时提交）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServerSpec {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub protocol: String,
+    /// 对外暴露的协议，默认与 protocol 相同
+    #[serde(default)]
+    pub front_protocol: Option<String>,
+    /// 远程服务的访问路径，例如 /sse
+    #[serde(default)]
+    pub export_path: Option<String>,
+    /// stdio 服务的本地启动配置（command、args、env 等）
+    #[serde(default)]
+    pub local_server_config: Option<serde_json::Value>,
+}
+
+/// 创建或更新 MCP 服务请求
+#[derive(Debug, Deserialize)]
+pub struct McpServerRequest {
+    pub namespace_id: String,
+    pub server_spec: McpServerSpec,
+    /// 工具定义：{"tools": [{"name", "description", "inputSchema"}], "toolsMeta": {"<tool>": {"enabled": bool}}}
+    pub tool_spec: Option<serde_json::Value>,
+    pub endpoint_spec: Option<McpEndpointSpec>,
+}
+
+/// MCP 服务列表查询参数
+#[derive(Debug, Deserialize)]
+pub struct McpServerQuery {
+    pub namespace_id: String,
+    pub name: Option<String>,
+    /// "accurate" 精确匹配名称，其他值模糊匹配
+    pub search: Option<String>,
+    pub page_no: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+/// MCP 服务基本信息
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerInfo {
+    pub id: i64,
+    pub namespace_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub protocol: String,
+    pub enabled: bool,
+    pub latest_version: String,
+    pub gmt_create: i64,
+    pub gmt_modified: i64,
+}
+
+/// MCP 服务列表响应
+#[derive(Debug, Serialize)]
+pub struct McpServerListResponse {
+    pub total_count: i64,
+    pub page_number: i64,
+    pub pages_available: i64,
+    pub page_items: Vec<McpServerInfo>,
+}
+
+/// MCP 服务版本概要
+#[derive(Debug, Clone, Serialize)]
+pub struct McpVersionSummary {
+    pub version: String,
+    pub is_latest: bool,
+    pub gmt_create: i64,
+    pub gmt_modified: i64,
+}
+
+/// MCP 服务版本详情
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerVersionInfo {
+    pub version: String,
+    pub description: Option<String>,
+    pub protocol: String,
+    pub front_protocol: String,
+    pub export_path: Option<String>,
+    pub local_server_config: Option<serde_json::Value>,
+    pub endpoint_spec: Option<McpEndpointSpec>,
+    pub tool_spec: Option<serde_json::Value>,
+    pub gmt_create: i64,
+    pub gmt_modified: i64,
+}
+
+/// 解析后的后端端点
+#[derive(Debug, Clone, Serialize)]
+pub struct McpBackendEndpoint {
+    pub address: String,
+    pub port: i32,
+}
+
+/// MCP 服务详情
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerDetail {
+    pub server: McpServerInfo,
+    pub version: McpServerVersionInfo,
+    pub all_versions: Vec<McpVersionSummary>,
+    /// 当前版本解析出的端点（REF 为命名服务下健康且启用的实例）
+    pub backend_endpoints: Vec<McpBackendEndpoint>,
+}
+
+/// 获取当前时间戳（秒）
+fn current_timestamp() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+/// 校验 MCP 服务定义
+fn validate_server_spec(spec: &McpServerSpec) -> Result<(), String> {
+    if spec.name.trim().is_empty() {
+        return Err("Invalid serverSpecification: name is required".to_string());
+    }
+    if spec.version.trim().is_empty() {
+        return Err("Invalid serverSpecification: version is required".to_string());
+    }
+    if !MCP_PROTOCOLS.contains(&spec.protocol.as_str()) {
+        return Err(format!(
+            "Invalid serverSpecification: unsupported protocol {}, expected one of {}",
+            spec.protocol,
+            MCP_PROTOCOLS.join(", ")
+        ));
+    }
+    if let Some(front_protocol) = spec.front_protocol.as_deref().filter(|p| !p.is_empty()) {
+        if !MCP_PROTOCOLS.contains(&front_protocol) {
+            return Err(format!("Invalid serverSpecification: unsupported frontProtocol {}", front_protocol));
+        }
+    }
+    Ok(())
+}
+
+/// 校验工具定义：tools 为数组，每个工具必须有不重复的 name；toolsMeta 的键必须是已定义的工具
+fn validate_tool_spec(tool_spec: &serde_json::Value) -> Result<(), String> {
+    let tools = tool_spec
+        .get("tools")
+        .and_then(|t| t.as_array())
+        .ok_or_else(|| "Invalid toolSpecification: tools must be an array".to_string())?;
+
+    let mut names = std::collections::HashSet::new();
+    for tool in tools {
+        let name = tool
+            .get("name")
+            .and_then(|n| n.as_str())
+            .filter(|n| !n.is_empty())
+            .ok_or_else(|| "Invalid toolSpecification: every tool requires a name".to_string())?;
+        if !names.insert(name) {
+            return Err(format!("Invalid toolSpecification: duplicate tool {}", name));
+        }
+    }
+
+    if let Some(meta) = tool_spec.get("toolsMeta") {
+        let meta = meta
+            .as_object()
+            .ok_or_else(|| "Invalid toolSpecification: toolsMeta must be an object".to_string())?;
+        if let Some(unknown) = meta.keys().find(|k| !names.contains(k.as_str())) {
+            return Err(format!("Invalid toolSpecification: toolsMeta references unknown tool {}", unknown));
+        }
+    }
+    Ok(())
+}
+
+/// 校验端点：远程协议必须指定端点，引用的命名服务必须存在
+async fn validate_endpoint_spec(
+    app: &AppHandle,
+    namespace_id: &str,
+    protocol: &str,
+    endpoint_spec: Option<&McpEndpointSpec>,
+) -> Result<(), String> {
+    match endpoint_spec {
+        None if protocol != STDIO_PROTOCOL => {
+            Err(format!("Invalid endpointSpecification: protocol {} requires an endpoint", protocol))
+        }
+        None => Ok(()),
+        Some(McpEndpointSpec::Direct { address, port }) => {
+            if address.trim().is_empty() || !(1..=65535).contains(port) {
+                return Err("Invalid endpointSpecification: address and port are required".to_string());
+            }
+            Ok(())
+        }
+        Some(spec @ McpEndpointSpec::Ref { service_name, .. }) => {
+            let (ref_namespace, ref_group) = ref_service_scope(spec, namespace_id);
+            if get_service_detail(app, &ref_namespace, &ref_group, service_name).await?.is_none() {
+                return Err(format!(
+                    "Invalid endpointSpecification: referenced service {}@@{} does not exist in namespace {}",
+                    ref_group, service_name, ref_namespace
+                ));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// 引用服务所在的命名空间和分组（未指定时使用 MCP 服务的命名空间和 DEFAULT_GROUP）
+fn ref_service_scope(spec: &McpEndpointSpec, namespace_id: &str) -> (String, String) {
+    match spec {
+        McpEndpointSpec::Ref { namespace_id: ref_namespace, group_name, .. } => (
+            ref_namespace.clone().filter(|n| !n.is_empty()).unwrap_or_else(|| namespace_id.to_string()),
+            group_name.clone().filter(|g| !g.is_empty()).unwrap_or_else(|| "DEFAULT_GROUP".to_string()),
+        ),
+        McpEndpointSpec::Direct { .. } => (namespace_id.to_string(), "DEFAULT_GROUP".to_string()),
+    }
+}
+
+/// 解析后端端点
+async fn resolve_endpoints(
+    app: &AppHandle,
+    namespace_id: &str,
+    endpoint_spec: Option<&McpEndpointSpec>,
+) -> Result<Vec<McpBackendEndpoint>, String> {
+    match endpoint_spec {
+        None => Ok(Vec::new()),
+        Some(McpEndpointSpec::Direct { address, port }) => {
+            Ok(vec![McpBackendEndpoint { address: address.clone(), port: *port }])
+        }
+        Some(spec @ McpEndpointSpec::Ref { service_name, .. }) => {
+            let (ref_namespace, ref_group) = ref_service_scope(spec, namespace_id);
+            let response = get_service_instances(app, &ref_namespace, &ref_group, service_name).await?;
+            Ok(response
+                .instances
+                .into_iter()
+                .filter(|inst| inst.healthy && inst.enabled)
+                .map(|inst| McpBackendEndpoint { address: inst.ip, port: inst.port })
+                .collect())
+        }
+    }
+}
+
+/// 查询 MCP 服务基本信息
+async fn find_mcp_server(app: &AppHandle, namespace_id: &str, name: &str) -> Result<Option<McpServerInfo>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let row: Option<(i64, String, String, Option<String>, String, bool, String, i64, i64)> = db
+        .query_one(
+            "SELECT id, namespace_id, name, description, protocol, enabled, latest_version, gmt_create, gmt_modified FROM mcp_server WHERE namespace_id = ?1 AND name = ?2",
+            &[("?1", namespace_id), ("?2", name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query mcp server: {}", e))?;
+
+    Ok(row.map(|(id, namespace_id, name, description, protocol, enabled, latest_version, gmt_create, gmt_modified)| {
+        McpServerInfo { id, namespace_id, name, description, protocol, enabled, latest_version, gmt_create, gmt_modified }
+    }))
+}
+
+/// 查询 MCP 服务版本详情
+async fn find_mcp_version(
+    app: &AppHandle,
+    namespace_id: &str,
+    name: &str,
+    version: &str,
+) -> Result<Option<McpServerVersionInfo>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let row: Option<(String, Option<String>, String, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, i64, i64)> = db
+        .query_one(
+            "SELECT version, description, protocol, front_protocol, export_path, local_server_config, endpoint_spec, tool_spec, gmt_create, gmt_modified FROM mcp_server_version WHERE namespace_id = ?1 AND name = ?2 AND version = ?3",
+            &[("?1", namespace_id), ("?2", name), ("?3", version)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query mcp server version: {}", e))?;
+
+    Ok(row.map(|(version, description, protocol, front_protocol, export_path, local_server_config, endpoint_spec, tool_spec, gmt_create, gmt_modified)| {
+        McpServerVersionInfo {
+            version,
+            description,
+            front_protocol: front_protocol.unwrap_or_else(|| protocol.clone()),
+            protocol,
+            export_path,
+            local_server_config: local_server_config.and_then(|s| serde_json::from_str(&s).ok()),
+            endpoint_spec: endpoint_spec.and_then(|s| serde_json::from_str(&s).ok()),
+            tool_spec: tool_spec.and_then(|s| serde_json::from_str(&s).ok()),
+            gmt_create,
+            gmt_modified,
+        }
+    }))
+}
+
+/// 查询 MCP 服务的版本列表（按创建时间倒序）
+pub async fn list_mcp_server_versions(
+    app: &AppHandle,
+    namespace_id: &str,
+    name: &str,
+) -> Result<Vec<McpVersionSummary>, String> {
+    let server = find_mcp_server(app, namespace_id, name)
+        .await?
+        .ok_or_else(|| format!("MCP server {} not found", name))?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let rows: Vec<(String, i64, i64)> = db
+        .query(
+            "SELECT version, gmt_create, gmt_modified FROM mcp_server_version WHERE namespace_id = ?1 AND name = ?2 ORDER BY gmt_create DESC, id DESC",
+            &[("?1", namespace_id), ("?2", name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query mcp server versions: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(version, gmt_create, gmt_modified)| McpVersionSummary {
+            is_latest: version == server.latest_version,
+            version,
+            gmt_create,
+            gmt_modified,
+        })
+        .collect())
+}
+
+/// 查询 MCP 服务详情，version 为空时返回最新版本
+pub async fn get_mcp_server(
+    app: &AppHandle,
+    namespace_id: &str,
+    name: &str,
+    version: Option<&str>,
+) -> Result<Option<McpServerDetail>, String> {
+    let Some(server) = find_mcp_server(app, namespace_id, name).await? else {
+        return Ok(None);
+    };
+    let version_name = version.filter(|v| !v.is_empty()).unwrap_or(&server.latest_version).to_string();
+    let Some(version) = find_mcp_version(app, namespace_id, name, &version_name).await? else {
+        return Ok(None);
+    };
+
+    let all_versions = list_mcp_server_versions(app, namespace_id, name).await?;
+    let backend_endpoints = resolve_endpoints(app, namespace_id, version.endpoint_spec.as_ref()).await?;
+
+    Ok(Some(McpServerDetail { server, version, all_versions, backend_endpoints }))
+}
+
+/// 写入版本（已存在时覆盖）
+async fn upsert_mcp_version(app: &AppHandle, request: &McpServerRequest) -> Result<(), String> {
+    let spec = &request.server_spec;
+    let local_server_config = spec.local_server_config.as_ref().map(|v| v.to_string()).unwrap_or_default();
+    let endpoint_spec = match request.endpoint_spec.as_ref() {
+        Some(endpoint) => serde_json::to_string(endpoint).map_err(|e| format!("Failed to serialize endpoint: {}", e))?,
+        None => String::new(),
+    };
+    let tool_spec = request.tool_spec.as_ref().map(|v| v.to_string()).unwrap_or_default();
+    let now = current_timestamp().to_string();
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "INSERT INTO mcp_server_version (namespace_id, name, version, description, protocol, front_protocol, export_path, local_server_config, endpoint_spec, tool_spec, gmt_create, gmt_modified) \
+         VALUES (?1, ?2, ?3, NULLIF(?4, ''), ?5, NULLIF(?6, ''), NULLIF(?7, ''), NULLIF(?8, ''), NULLIF(?9, ''), NULLIF(?10, ''), ?11, ?11) \
+         ON CONFLICT(namespace_id, name, version) DO UPDATE SET description = excluded.description, protocol = excluded.protocol, \
+         front_protocol = excluded.front_protocol, export_path = excluded.export_path, local_server_config = excluded.local_server_config, \
+         endpoint_spec = excluded.endpoint_spec, tool_spec = excluded.tool_spec, gmt_modified = excluded.gmt_modified",
+        &[
+            ("?1", request.namespace_id.as_str()),
+            ("?2", spec.name.as_str()),
+            ("?3", spec.version.as_str()),
+            ("?4", spec.description.as_deref().unwrap_or("")),
+            ("?5", spec.protocol.as_str()),
+            ("?6", spec.front_protocol.as_deref().unwrap_or("")),
+            ("?7", spec.export_path.as_deref().unwrap_or("")),
+            ("?8", local_server_config.as_str()),
+            ("?9", endpoint_spec.as_str()),
+            ("?10", tool_spec.as_str()),
+            ("?11", now.as_str()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to save mcp server version: {}", e))?;
+
+    Ok(())
+}
+
+/// 校验请求中的服务定义、工具定义和端点
+async fn validate_request(app: &AppHandle, request: &McpServerRequest) -> Result<(), String> {
+    validate_server_spec(&request.server_spec)?;
+    if let Some(tool_spec) = request.tool_spec.as_ref() {
+        validate_tool_spec(tool_spec)?;
+    }
+    validate_endpoint_spec(app, &request.namespace_id, &request.server_spec.protocol, request.endpoint_spec.as_ref()).await
+}
+
+/// 创建 MCP 服务（包含第一个版本）
+pub async fn create_mcp_server(app: &AppHandle, request: McpServerRequest) -> Result<McpServerDetail, String> {
+    validate_request(app, &request).await?;
+    let spec = &request.server_spec;
+    if find_mcp_server(app, &request.namespace_id, &spec.name).await?.is_some() {
+        return Err(format!("MCP server {} already exists", spec.name));
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let now = current_timestamp().to_string();
+    db.execute(
+        "INSERT INTO mcp_server (namespace_id, name, description, protocol, enabled, latest_version, gmt_create, gmt_modified) VALUES (?1, ?2, NULLIF(?3, ''), ?4, 1, ?5, ?6, ?6)",
+        &[
+            ("?1", request.namespace_id.as_str()),
+            ("?2", spec.name.as_str()),
+            ("?3", spec.description.as_deref().unwrap_or("")),
+            ("?4", spec.protocol.as_str()),
+            ("?5", spec.version.as_str()),
+            ("?6", now.as_str()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to create mcp server: {}", e))?;
+
+    upsert_mcp_version(app, &request).await?;
+
+    get_mcp_server(app, &request.namespace_id, &spec.name, None)
+        .await?
+        .ok_or_else(|| format!("MCP server {} not found", spec.name))
+}
+
+/// 更新 MCP 服务：版本已存在时覆盖该版本，否则新增版本；latest 为 true 时将该版本设为最新版本
+pub async fn update_mcp_server(app: &AppHandle, request: McpServerRequest, latest: bool) -> Result<McpServerDetail, String> {
+    validate_request(app, &request).await?;
+    let spec = &request.server_spec;
+    let server = find_mcp_server(app, &request.namespace_id, &spec.name)
+        .await?
+        .ok_or_else(|| format!("MCP server {} not found", spec.name))?;
+
+    upsert_mcp_version(app, &request).await?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    // 服务的描述和协议跟随最新版本
+    let now = current_timestamp().to_string();
+    if latest || spec.version == server.latest_version {
+        db.execute(
+            "UPDATE mcp_server SET description = NULLIF(?1, ''), protocol = ?2, latest_version = ?3, gmt_modified = ?4 WHERE id = ?5",
+            &[
+                ("?1", spec.description.as_deref().unwrap_or("")),
+                ("?2", spec.protocol.as_str()),
+                ("?3", spec.version.as_str()),
+                ("?4", now.as_str()),
+                ("?5", server.id.to_string().as_str()),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to update mcp server: {}", e))?;
+    } else {
+        db.execute(
+            "UPDATE mcp_server SET gmt_modified = ?1 WHERE id = ?2",
+            &[("?1", now.as_str()), ("?2", server.id.to_string().as_str())],
+        )
+        .await
+        .map_err(|e| format!("Failed to update mcp server: {}", e))?;
+    }
+
+    get_mcp_server(app, &request.namespace_id, &spec.name, Some(&spec.version))
+        .await?
+        .ok_or_else(|| format!("MCP server {} not found", spec.name))
+}
+
+/// 修改版本的工具定义，version 为空时修改最新版本
+pub async fn update_mcp_tool_spec(
+    app: &AppHandle,
+    namespace_id: &str,
+    name: &str,
+    version: Option<&str>,
+    tool_spec: serde_json::Value,
+) -> Result<McpServerDetail, String> {
+    validate_tool_spec(&tool_spec)?;
+    let server = find_mcp_server(app, namespace_id, name)
+        .await?
+        .ok_or_else(|| format!("MCP server {} not found", name))?;
+    let version = version.filter(|v| !v.is_empty()).unwrap_or(&server.latest_version).to_string();
+    if find_mcp_version(app, namespace_id, name, &version).await?.is_none() {
+        return Err(format!("MCP server {} version {} not found", name, version));
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "UPDATE mcp_server_version SET tool_spec = ?1, gmt_modified = ?2 WHERE namespace_id = ?3 AND name = ?4 AND version = ?5",
+        &[
+            ("?1", tool_spec.to_string().as_str()),
+            ("?2", current_timestamp().to_string().as_str()),
+            ("?3", namespace_id),
+            ("?4", name),
+            ("?5", version.as_str()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to update mcp tool specification: {}", e))?;
+
+    get_mcp_server(app, namespace_id, name, Some(&version))
+        .await?
+        .ok_or_else(|| format!("MCP server {} not found", name))
+}
+
+/// 启用或禁用 MCP 服务
+pub async fn set_mcp_server_enabled(app: &AppHandle, namespace_id: &str, name: &str, enabled: bool) -> Result<(), String> {
+    if find_mcp_server(app, namespace_id, name).await?.is_none() {
+        return Err(format!("MCP server {} not found", name));
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "UPDATE mcp_server SET enabled = ?1, gmt_modified = ?2 WHERE namespace_id = ?3 AND name = ?4",
+        &[
+            ("?1", if enabled { "1" } else { "0" }),
+            ("?2", current_timestamp().to_string().as_str()),
+            ("?3", namespace_id),
+            ("?4", name),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to update mcp server status: {}", e))?;
+
+    Ok(())
+}
+
+/// 删除 MCP 服务及其所有版本
+pub async fn delete_mcp_server(app: &AppHandle, namespace_id: &str, name: &str) -> Result<(), String> {
+    if find_mcp_server(app, namespace_id, name).await?.is_none() {
+        return Err(format!("MCP server {} not found", name));
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "DELETE FROM mcp_server WHERE namespace_id = ?1 AND name = ?2",
+        &[("?1", namespace_id), ("?2", name)],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete mcp server: {}", e))?;
+
+    db.execute(
+        "DELETE FROM mcp_server_version WHERE namespace_id = ?1 AND name = ?2",
+        &[("?1", namespace_id), ("?2", name)],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete mcp server versions: {}", e))?;
+
+    Ok(())
+}
+
+/// 分页查询 MCP 服务
+pub async fn list_mcp_servers(app: &AppHandle, query: McpServerQuery) -> Result<McpServerListResponse, String> {
+    let page_no = query.page_no.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(10).max(1);
+    let offset = (page_no - 1) * page_size;
+
+    let name = query.name.as_deref().unwrap_or("").trim().to_string();
+    let (name_clause, name_param) = if name.is_empty() {
+        ("", String::new())
+    } else if query.search.as_deref() == Some("accurate") {
+        (" AND name = ?2", name)
+    } else {
+        (" AND name LIKE ?2", format!("%{}%", name))
+    };
+
+    let mut params: Vec<(&str, &str)> = vec![("?1", query.namespace_id.as_str())];
+    if !name_clause.is_empty() {
+        params.push(("?2", name_param.as_str()));
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let total_count: Option<(i64,)> = db
+        .query_one(
+            &format!("SELECT COUNT(*) FROM mcp_server WHERE namespace_id = ?1{}", name_clause),
+            &params,
+        )
+        .await
+        .map_err(|e| format!("Failed to count mcp servers: {}", e))?;
+    let total_count = total_count.map(|(c,)| c).unwrap_or(0);
+    let pages_available = (total_count + page_size - 1) / page_size;
+
+    let page_size_param = page_size.to_string();
+    let offset_param = offset.to_string();
+    params.push(("?3", page_size_param.as_str()));
+    params.push(("?4", offset_param.as_str()));
+
+    let rows: Vec<(i64, String, String, Option<String>, String, bool, String, i64, i64)> = db
+        .query(
+            &format!(
+                "SELECT id, namespace_id, name, description, protocol, enabled, latest_version, gmt_create, gmt_modified FROM mcp_server WHERE namespace_id = ?1{} ORDER BY name LIMIT ?3 OFFSET ?4",
+                name_clause
+            ),
+            &params,
+        )
+        .await
+        .map_err(|e| format!("Failed to query mcp servers: {}", e))?;
+
+    let page_items = rows
+        .into_iter()
+        .map(|(id, namespace_id, name, description, protocol, enabled, latest_version, gmt_create, gmt_modified)| {
+            McpServerInfo { id, namespace_id, name, description, protocol, enabled, latest_version, gmt_create, gmt_modified }
+        })
+        .collect();
+
+    Ok(McpServerListResponse {
+        total_count,
+        page_number: page_no,
+        pages_available,
+        page_items,
+    })
+}
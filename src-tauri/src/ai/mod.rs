@@ -0,0 +1,12 @@
+/**
+ * AI 注册中心模块
+ * 对应 Nacos 3 的 AI 能力，目前提供 MCP 服务注册表
+ */
+
+mod mcp;
+
+pub use mcp::{
+    create_mcp_server, update_mcp_server, delete_mcp_server, get_mcp_server, list_mcp_servers,
+    list_mcp_server_versions, update_mcp_tool_spec, set_mcp_server_enabled,
+    McpServerRequest, McpServerQuery, McpServerListResponse, McpServerDetail, McpVersionSummary,
+};
@@ -0,0 +1,203 @@
+/**
+ * Consul KV 存储模块
+ * 键值对保存在 config_info 表中（public 命名空间 CONSUL_KV 分组，dataId 即键），
+ * 可在配置管理中查看和修改；ModifyIndex 保存在 consul_kv_index 表中，内容变化（包括通过配置管理修改）时递增
+ */
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use super::{create_config, delete_config, update_config, CreateConfigRequest, UpdateConfigRequest};
+
+/// KV 所在的配置分组
+pub const CONSUL_KV_GROUP: &str = "CONSUL_KV";
+
+/// KV 所在的命名空间
+pub const CONSUL_KV_TENANT: &str = "public";
+
+/// 键值对
+#[derive(Debug, Clone)]
+pub struct KvEntry {
+    pub key: String,
+    pub value: String,
+    pub create_index: u64,
+    pub modify_index: u64,
+}
+
+/// KV 锁：分配修改索引，以及 CAS 从检查到写入的整个过程都在锁内完成
+#[derive(Default)]
+struct KvLock(Mutex<()>);
+
+/// 获取 KV 锁
+fn get_kv_lock(app: &AppHandle) -> Arc<KvLock> {
+    if let Some(state) = app.try_state::<Arc<KvLock>>() {
+        return state.inner().clone();
+    }
+    app.manage(Arc::new(KvLock::default()));
+    app.state::<Arc<KvLock>>().inner().clone()
+}
+
+/// 查询键的修改索引，内容与上次记录的不同时分配新的索引（调用方需持有 KV 锁）
+async fn modify_index(app: &AppHandle, key: &str, md5: &str) -> Result<u64, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let known: Option<(String, i64)> = db
+        .query_one(
+            "SELECT md5, modify_index FROM consul_kv_index WHERE kv_key = ?1",
+            &[("?1", key)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query kv index: {}", e))?;
+    if let Some((known_md5, index)) = known {
+        if known_md5 == md5 {
+            return Ok(index as u64);
+        }
+    }
+
+    // 新索引取所有键（包括已删除的键）的最大索引加一，保证单调递增
+    db.execute(
+        "INSERT INTO consul_kv_index (kv_key, md5, modify_index) SELECT ?1, ?2, COALESCE(MAX(modify_index), 0) + 1 FROM consul_kv_index WHERE 1 ON CONFLICT(kv_key) DO UPDATE SET md5 = excluded.md5, modify_index = excluded.modify_index",
+        &[("?1", key), ("?2", md5)],
+    )
+    .await
+    .map_err(|e| format!("Failed to update kv index: {}", e))?;
+
+    let (index,): (i64,) = db
+        .query_one("SELECT modify_index FROM consul_kv_index WHERE kv_key = ?1", &[("?1", key)])
+        .await
+        .map_err(|e| format!("Failed to query kv index: {}", e))?
+        .ok_or_else(|| format!("Kv index not found: {}", key))?;
+    Ok(index as u64)
+}
+
+/// 查询键值对，recurse 为 true 时按前缀查询
+pub async fn get_kv_entries(app: &AppHandle, key: &str, recurse: bool) -> Result<Vec<KvEntry>, String> {
+    let lock = get_kv_lock(app);
+    let _guard = lock.0.lock().await;
+    load_kv_entries(app, key, recurse).await
+}
+
+/// 查询键值对并分配修改索引（调用方需持有 KV 锁）
+async fn load_kv_entries(app: &AppHandle, key: &str, recurse: bool) -> Result<Vec<KvEntry>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let sql = if recurse {
+        "SELECT id, data_id, content, md5 FROM config_info WHERE group_id = ?1 AND tenant_id = ?2 AND substr(data_id, 1, length(?3)) = ?3 ORDER BY data_id"
+    } else {
+        "SELECT id, data_id, content, md5 FROM config_info WHERE group_id = ?1 AND tenant_id = ?2 AND data_id = ?3"
+    };
+    let rows: Vec<(i64, String, String, Option<String>)> = db
+        .query(sql, &[("?1", CONSUL_KV_GROUP), ("?2", CONSUL_KV_TENANT), ("?3", key)])
+        .await
+        .map_err(|e| format!("Failed to query kv: {}", e))?;
+
+    let mut entries = Vec::new();
+    for (id, data_id, content, md5) in rows {
+        let md5 = md5.unwrap_or_else(|| format!("{:x}", md5::compute(content.as_bytes())));
+        entries.push(KvEntry {
+            modify_index: modify_index(app, &data_id, &md5).await?,
+            key: data_id,
+            value: content,
+            create_index: id as u64,
+        });
+    }
+    Ok(entries)
+}
+
+/// 写入键值对，返回是否写入
+/// cas 为 0 时仅在键不存在时写入，否则仅在修改索引一致时写入
+pub async fn put_kv(app: &AppHandle, key: &str, value: &str, cas: Option<u64>) -> Result<bool, String> {
+    let lock = get_kv_lock(app);
+    let _guard = lock.0.lock().await;
+
+    let existing = load_kv_entries(app, key, false).await?.into_iter().next();
+    match (cas, &existing) {
+        (Some(0), Some(_)) => return Ok(false),
+        (Some(cas), Some(entry)) if cas != 0 && entry.modify_index != cas => return Ok(false),
+        (Some(cas), None) if cas != 0 => return Ok(false),
+        _ => {}
+    }
+
+    if existing.is_some() {
+        update_config(
+            app,
+            UpdateConfigRequest {
+                data_id: key.to_string(),
+                group_id: CONSUL_KV_GROUP.to_string(),
+                tenant_id: CONSUL_KV_TENANT.to_string(),
+                content: value.to_string(),
+                app_name: None,
+                c_desc: None,
+                c_use: None,
+                effect: None,
+                r#type: Some("text".to_string()),
+                c_schema: None,
+                encrypted_data_key: None,
+            },
+            Some("consul".to_string()),
+            None,
+        )
+        .await?;
+    } else {
+        create_config(
+            app,
+            CreateConfigRequest {
+                data_id: key.to_string(),
+                group_id: CONSUL_KV_GROUP.to_string(),
+                tenant_id: CONSUL_KV_TENANT.to_string(),
+                content: value.to_string(),
+                app_name: None,
+                c_desc: None,
+                c_use: None,
+                effect: None,
+                r#type: Some("text".to_string()),
+                c_schema: None,
+                encrypted_data_key: None,
+            },
+            Some("consul".to_string()),
+            None,
+        )
+        .await?;
+    }
+
+    // 写入后立即分配新的修改索引
+    load_kv_entries(app, key, false).await?;
+    Ok(true)
+}
+
+/// 删除键值对，recurse 为 true 时删除前缀下的所有键；cas 不一致时返回 false
+pub async fn delete_kv(app: &AppHandle, key: &str, recurse: bool, cas: Option<u64>) -> Result<bool, String> {
+    let lock = get_kv_lock(app);
+    let _guard = lock.0.lock().await;
+
+    let entries = load_kv_entries(app, key, recurse).await?;
+    if let Some(cas) = cas {
+        if recurse || entries.first().map_or(true, |entry| entry.modify_index != cas) {
+            return Ok(false);
+        }
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+    for entry in entries {
+        delete_config(app, &entry.key, CONSUL_KV_GROUP, CONSUL_KV_TENANT, Some("consul".to_string()), None).await?;
+        // 保留索引记录并清空 MD5，重新创建同名键时分配新的索引
+        db.execute("UPDATE consul_kv_index SET md5 = '' WHERE kv_key = ?1", &[("?1", &entry.key)])
+            .await
+            .map_err(|e| format!("Failed to update kv index: {}", e))?;
+    }
+    Ok(true)
+}
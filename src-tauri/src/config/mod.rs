@@ -1,10 +1,16 @@
 /**
  * 配置管理模块
- * 负责配置的 CRUD 操作和历史记录管理
+ * 负责配置的 CRUD 操作和历史记录管理；配置写入后广播配置变更事件，gRPC 推送任务据此通知监听的客户端
  */
 
+mod kv;
+mod subscriber;
+
 use serde::{Deserialize, Serialize};
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast;
+pub use kv::{delete_kv, get_kv_entries, put_kv, KvEntry, CONSUL_KV_GROUP, CONSUL_KV_TENANT};
+pub use subscriber::{record_config_subscriber, remove_config_subscriber};
 
 /// 配置信息
 #[derive(Debug, Serialize, Deserialize)]
@@ -97,6 +103,40 @@ pub struct UpdateConfigRequest {
     pub encrypted_data_key: Option<String>,
 }
 
+/// 配置变更事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChangeEvent {
+    pub data_id: String,
+    pub group_id: String,
+    pub tenant_id: String,
+}
+
+/// 配置变更事件通道容量，订阅方落后超过容量时需要全量检查
+const CONFIG_CHANGE_CHANNEL_CAPACITY: usize = 1024;
+
+/// 获取配置变更事件通道（首次调用时创建；并发创建时以先注册的通道为准）
+fn get_config_change_sender(app: &AppHandle) -> broadcast::Sender<ConfigChangeEvent> {
+    if let Some(state) = app.try_state::<broadcast::Sender<ConfigChangeEvent>>() {
+        return state.inner().clone();
+    }
+    app.manage(broadcast::channel::<ConfigChangeEvent>(CONFIG_CHANGE_CHANNEL_CAPACITY).0);
+    app.state::<broadcast::Sender<ConfigChangeEvent>>().inner().clone()
+}
+
+/// 订阅配置变更事件
+pub fn subscribe_config_changes(app: &AppHandle) -> broadcast::Receiver<ConfigChangeEvent> {
+    get_config_change_sender(app).subscribe()
+}
+
+/// 广播配置变更事件（没有订阅方时忽略）
+pub fn notify_config_change(app: &AppHandle, data_id: &str, group_id: &str, tenant_id: &str) {
+    let _ = get_config_change_sender(app).send(ConfigChangeEvent {
+        data_id: data_id.to_string(),
+        group_id: group_id.to_string(),
+        tenant_id: tenant_id.to_string(),
+    });
+}
+
 /// 计算 MD5 哈希
 fn calculate_md5(content: &str) -> String {
     let hash = md5::compute(content.as_bytes());
@@ -357,6 +397,8 @@ pub async fn create_config(
         .map_err(|e| format!("Failed to insert config history: {}", e))?;
     }
 
+    notify_config_change(app, &request.data_id, &request.group_id, &request.tenant_id);
+
     // 返回创建的配置
     get_config_detail(app, &request.data_id, &request.group_id, &request.tenant_id)
         .await?
@@ -438,6 +480,8 @@ pub async fn update_config(
     .await
     .map_err(|e| format!("Failed to insert config history: {}", e))?;
 
+    notify_config_change(app, &request.data_id, &request.group_id, &request.tenant_id);
+
     // 返回更新的配置
     get_config_detail(app, &request.data_id, &request.group_id, &request.tenant_id)
         .await?
@@ -499,6 +543,8 @@ pub async fn delete_config(
         )
         .await
         .map_err(|e| format!("Failed to delete config: {}", e))?;
+
+        notify_config_change(app, data_id, group_id, tenant_id);
     }
 
     Ok(())
@@ -0,0 +1,82 @@
+/**
+ * 配置订阅者模块
+ * 记录监听配置的客户端（HTTP 长轮询和 gRPC 批量监听），用于监听查询
+ */
+
+use tauri::AppHandle;
+
+/// 记录配置订阅者
+/// 同一客户端（IP、端口）重复监听时更新 MD5 和最后轮询时间
+pub async fn record_config_subscriber(
+    app: &AppHandle,
+    data_id: &str,
+    group_id: &str,
+    tenant_id: &str,
+    client_ip: &str,
+    client_port: Option<i32>,
+    user_agent: Option<&str>,
+    app_name: Option<&str>,
+    md5: &str,
+) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let current_time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    // 使用 INSERT OR REPLACE 来更新或插入订阅者信息
+    db.execute(
+        "INSERT OR REPLACE INTO subscribers (data_id, group_id, tenant_id, client_ip, client_port, user_agent, app_name, md5, last_poll_time, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, COALESCE((SELECT created_at FROM subscribers WHERE data_id = ?1 AND group_id = ?2 AND tenant_id = ?3 AND client_ip = ?4 AND client_port = ?5), ?9))",
+        &[
+            ("?1", data_id),
+            ("?2", group_id),
+            ("?3", tenant_id),
+            ("?4", client_ip),
+            ("?5", &client_port.map(|p| p.to_string()).unwrap_or_default()),
+            ("?6", user_agent.unwrap_or("")),
+            ("?7", app_name.unwrap_or("")),
+            ("?8", md5),
+            ("?9", &current_time.to_string()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to record subscriber: {}", e))?;
+
+    Ok(())
+}
+
+/// 移除配置订阅者（客户端取消监听或 gRPC 连接断开时调用）
+pub async fn remove_config_subscriber(
+    app: &AppHandle,
+    data_id: &str,
+    group_id: &str,
+    tenant_id: &str,
+    client_ip: &str,
+    client_port: i32,
+) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "DELETE FROM subscribers WHERE data_id = ?1 AND group_id = ?2 AND tenant_id = ?3 AND client_ip = ?4 AND client_port = ?5",
+        &[
+            ("?1", data_id),
+            ("?2", group_id),
+            ("?3", tenant_id),
+            ("?4", client_ip),
+            ("?5", &client_port.to_string()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to remove subscriber: {}", e))?;
+
+    Ok(())
+}
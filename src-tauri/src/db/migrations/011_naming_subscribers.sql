@@ -0,0 +1,21 @@
+-- Migration 11: 服务订阅者表（naming_subscribers）
+-- 记录通过 /v1/ns/instance/list 查询实例的消费者，用于订阅者查询
+
+CREATE TABLE IF NOT EXISTS naming_subscribers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    namespace_id TEXT NOT NULL,
+    group_name TEXT NOT NULL,
+    service_name TEXT NOT NULL,
+    client_ip TEXT NOT NULL,
+    client_port INTEGER NOT NULL DEFAULT 0,
+    app_name TEXT,
+    agent TEXT,
+    clusters TEXT NOT NULL DEFAULT '',
+    last_ref_time INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    UNIQUE(namespace_id, group_name, service_name, client_ip, client_port, clusters)
+);
+
+CREATE INDEX IF NOT EXISTS idx_naming_subscribers_service ON naming_subscribers(namespace_id, group_name, service_name);
+CREATE INDEX IF NOT EXISTS idx_naming_subscribers_client_ip ON naming_subscribers(client_ip);
+CREATE INDEX IF NOT EXISTS idx_naming_subscribers_last_ref ON naming_subscribers(last_ref_time);
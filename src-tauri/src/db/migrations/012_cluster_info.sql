@@ -0,0 +1,19 @@
+-- Migration 12: 集群信息表（cluster_info）
+-- 保存集群的健康检查器类型、检查端口、是否使用实例端口以及集群元数据
+
+CREATE TABLE IF NOT EXISTS cluster_info (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    namespace_id VARCHAR(128) NOT NULL DEFAULT '',
+    group_name VARCHAR(128) NOT NULL DEFAULT 'DEFAULT_GROUP',
+    service_name VARCHAR(128) NOT NULL,
+    cluster_name VARCHAR(128) NOT NULL DEFAULT 'DEFAULT',
+    health_checker TEXT NOT NULL DEFAULT '{"type":"TCP"}',
+    check_port INTEGER NOT NULL DEFAULT 80,
+    use_instance_port BOOLEAN NOT NULL DEFAULT 1,
+    metadata TEXT DEFAULT NULL,
+    gmt_create INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    gmt_modified INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    UNIQUE(namespace_id, group_name, service_name, cluster_name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cluster_info_service ON cluster_info(namespace_id, group_name, service_name);
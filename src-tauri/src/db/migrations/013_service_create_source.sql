@@ -0,0 +1,5 @@
+-- Migration 13: 服务创建来源（service_info.create_source）
+-- API 表示通过接口或控制台显式创建，REGISTER 表示注册实例时自动创建；
+-- 只有自动创建的服务会被空服务清理任务删除
+
+ALTER TABLE service_info ADD COLUMN create_source VARCHAR(16) NOT NULL DEFAULT 'API';
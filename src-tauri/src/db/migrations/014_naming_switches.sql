@@ -0,0 +1,8 @@
+-- Migration 14: 命名服务系统开关表（naming_switches）
+-- 保存通过 /v1/ns/operator/switches 修改的开关，未保存的开关使用默认值
+
+CREATE TABLE IF NOT EXISTS naming_switches (
+    entry VARCHAR(128) PRIMARY KEY,
+    value TEXT NOT NULL,
+    gmt_modified INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
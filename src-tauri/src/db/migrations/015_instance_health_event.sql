@@ -0,0 +1,17 @@
+-- Migration 15: 实例健康时间线表（instance_health_event）
+-- 记录实例注册、注销和健康状态变更（含原因和来源），用于健康时间线和可用率统计
+
+CREATE TABLE IF NOT EXISTS instance_health_event (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    namespace_id VARCHAR(128) NOT NULL DEFAULT '',
+    group_name VARCHAR(128) NOT NULL DEFAULT 'DEFAULT_GROUP',
+    service_name VARCHAR(128) NOT NULL,
+    address VARCHAR(255) NOT NULL,
+    healthy INTEGER NOT NULL,
+    source VARCHAR(32) NOT NULL,
+    reason TEXT DEFAULT NULL,
+    gmt_create INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_instance_health_event_service ON instance_health_event(namespace_id, group_name, service_name);
+CREATE INDEX IF NOT EXISTS idx_instance_health_event_gmt_create ON instance_health_event(gmt_create);
@@ -0,0 +1,35 @@
+-- Migration 16: MCP 服务注册表（mcp_server、mcp_server_version）
+-- mcp_server 保存 MCP 服务的基本信息、启用状态和最新版本，mcp_server_version 保存每个版本的
+-- 协议、端点（引用命名服务或直连地址）和工具定义
+
+CREATE TABLE IF NOT EXISTS mcp_server (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    namespace_id VARCHAR(128) NOT NULL DEFAULT 'public',
+    name VARCHAR(255) NOT NULL,
+    description TEXT DEFAULT NULL,
+    protocol VARCHAR(32) NOT NULL,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    latest_version VARCHAR(64) NOT NULL,
+    gmt_create INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    gmt_modified INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    UNIQUE(namespace_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS mcp_server_version (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    namespace_id VARCHAR(128) NOT NULL DEFAULT 'public',
+    name VARCHAR(255) NOT NULL,
+    version VARCHAR(64) NOT NULL,
+    description TEXT DEFAULT NULL,
+    protocol VARCHAR(32) NOT NULL,
+    front_protocol VARCHAR(32) DEFAULT NULL,
+    export_path VARCHAR(255) DEFAULT NULL,
+    local_server_config TEXT DEFAULT NULL,
+    endpoint_spec TEXT DEFAULT NULL,
+    tool_spec TEXT DEFAULT NULL,
+    gmt_create INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    gmt_modified INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    UNIQUE(namespace_id, name, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_mcp_server_version_server ON mcp_server_version(namespace_id, name);
@@ -0,0 +1,17 @@
+-- Migration 17: 服务导出（service_export）
+-- 将一个命名空间中的服务以只读别名导出到其他命名空间（可使用不同的分组和服务名），
+-- 查询导出目标时解析为源服务的实例
+
+CREATE TABLE IF NOT EXISTS service_export (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    source_namespace_id VARCHAR(128) NOT NULL,
+    source_group_name VARCHAR(128) NOT NULL,
+    source_service_name VARCHAR(255) NOT NULL,
+    target_namespace_id VARCHAR(128) NOT NULL,
+    target_group_name VARCHAR(128) NOT NULL,
+    target_service_name VARCHAR(255) NOT NULL,
+    gmt_create INTEGER NOT NULL DEFAULT (strftime('%s', 'now')),
+    UNIQUE(target_namespace_id, target_group_name, target_service_name)
+);
+
+CREATE INDEX IF NOT EXISTS idx_service_export_source ON service_export(source_namespace_id, source_group_name, source_service_name);
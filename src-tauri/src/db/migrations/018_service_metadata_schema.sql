@@ -0,0 +1,5 @@
+-- Migration 18: 实例元数据约定（service_info.metadata_schema）
+-- JSON 格式：{"mode":"enforce|warn","rules":[{"key","required","allowedValues","pattern"}]}，
+-- 为空表示服务没有约定
+
+ALTER TABLE service_info ADD COLUMN metadata_schema TEXT DEFAULT NULL;
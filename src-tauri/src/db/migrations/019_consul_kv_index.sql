@@ -0,0 +1,9 @@
+-- Migration 19: Consul KV 修改索引（consul_kv_index）
+-- 记录每个键最近一次内容的 MD5 和 ModifyIndex，重启后索引保持不变；
+-- 键被删除后保留记录并清空 MD5，重新创建时分配新的索引
+
+CREATE TABLE IF NOT EXISTS consul_kv_index (
+    kv_key VARCHAR(255) PRIMARY KEY,
+    md5 VARCHAR(32) NOT NULL DEFAULT '',
+    modify_index INTEGER NOT NULL
+);
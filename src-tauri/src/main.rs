@@ -6,6 +6,7 @@ mod service;
 mod tenant;
 mod db;
 mod server;
+mod ai;
 
 use auth::{
     handle_login, handle_register, has_admin_user,
@@ -27,9 +28,23 @@ use config::{
 };
 use service::{
     get_service_list, get_service_detail, create_service, update_service, delete_service,
-    get_service_instances, register_instance, deregister_instance, update_instance_health,
+    get_service_instances, register_instance, deregister_instance, update_instance_health, HealthChangeSource,
+    get_naming_subscribers, get_service_clusters, update_cluster, select_one_healthy_instance, resolve_consumer_zone,
+    get_service_history, get_health_timeline, batch_register_instances, batch_deregister_instances,
+    get_naming_switches, update_naming_switch,
+    start_simulation, stop_simulation, get_simulation, list_simulations, get_host_view,
+    list_catalog_services, list_catalog_instances, get_catalog_service,
+    list_service_exports, create_service_export, revoke_service_export,
+    get_metadata_schema, update_metadata_schema, get_metadata_violations, export_failover_snapshot, manage_ephemeral_registry,
     ServiceQueryParams, CreateServiceRequest, UpdateServiceRequest, RegisterInstanceRequest,
     ServiceInfo, ServiceListResponse, InstanceInfo, InstanceListResponse,
+    NamingSubscriberListResponse, ClusterInfo, UpdateClusterRequest, SelectInstanceOptions,
+    ServiceHistoryQuery, ServiceHistoryListResponse, HealthTimelineQuery, HealthTimelineResponse,
+    SimulationDefinition, SimulationStatus, HostView,
+    CatalogServiceQuery, CatalogServiceListResponse, CatalogInstanceQuery, CatalogInstanceListResponse, CatalogServiceDetail,
+    BatchInstanceItem, BatchInstanceRef, BatchInstanceResult, NamingSwitches,
+    ServiceExport, ServiceExportQuery, CreateServiceExportRequest,
+    MetadataSchema, MetadataViolationReport, FailoverExportRequest, FailoverExportSummary,
 };
 use tenant::{
     get_namespace_list, create_namespace, update_namespace, delete_namespace,
@@ -47,6 +62,11 @@ use server::{
     get_server_metrics, get_server_detailed_status,
     ServerStatus, ServerConfig, ServerMetrics, ServerDetailedStatus,
 };
+use ai::{
+    create_mcp_server, update_mcp_server, delete_mcp_server, get_mcp_server, list_mcp_servers,
+    list_mcp_server_versions, update_mcp_tool_spec, set_mcp_server_enabled,
+    McpServerRequest, McpServerQuery, McpServerListResponse, McpServerDetail, McpVersionSummary,
+};
 use tauri::Manager;
 
 /// Tauri 命令：用户登录
@@ -172,6 +192,35 @@ async fn get_service_list_cmd(
     get_service_list(&app, params).await
 }
 
+/// Tauri 命令：分页查询服务目录（带集群数、实例数和健康实例数）
+#[tauri::command]
+async fn list_catalog_services_cmd(
+    query: CatalogServiceQuery,
+    app: tauri::AppHandle,
+) -> Result<CatalogServiceListResponse, String> {
+    list_catalog_services(&app, query).await
+}
+
+/// Tauri 命令：分页查询服务目录实例
+#[tauri::command]
+async fn list_catalog_instances_cmd(
+    query: CatalogInstanceQuery,
+    app: tauri::AppHandle,
+) -> Result<CatalogInstanceListResponse, String> {
+    list_catalog_instances(&app, query).await
+}
+
+/// Tauri 命令：查询服务目录详情
+#[tauri::command]
+async fn get_catalog_service_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    app: tauri::AppHandle,
+) -> Result<Option<CatalogServiceDetail>, String> {
+    get_catalog_service(&app, &namespace_id, &group_name, &service_name).await
+}
+
 /// Tauri 命令：查询服务详情
 #[tauri::command]
 async fn get_service_detail_cmd(
@@ -254,7 +303,328 @@ async fn update_instance_health_cmd(
     healthy: bool,
     app: tauri::AppHandle,
 ) -> Result<(), String> {
-    update_instance_health(&app, &namespace_id, &group_name, &service_name, &instance_id, healthy).await
+    update_instance_health(&app, &namespace_id, &group_name, &service_name, &instance_id, healthy, HealthChangeSource::Manual, "updated from console").await
+}
+
+/// Tauri 命令：批量注册实例
+#[tauri::command]
+async fn batch_register_instances_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    instances: Vec<BatchInstanceItem>,
+    app: tauri::AppHandle,
+) -> Result<Vec<BatchInstanceResult>, String> {
+    batch_register_instances(&app, &namespace_id, &group_name, &service_name, instances).await
+}
+
+/// Tauri 命令：批量注销实例
+#[tauri::command]
+async fn batch_deregister_instances_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    instances: Vec<BatchInstanceRef>,
+    app: tauri::AppHandle,
+) -> Result<Vec<BatchInstanceResult>, String> {
+    batch_deregister_instances(&app, &namespace_id, &group_name, &service_name, instances).await
+}
+
+/// Tauri 命令：查询服务订阅者
+#[tauri::command]
+async fn get_naming_subscribers_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    page_no: Option<i64>,
+    page_size: Option<i64>,
+    aggregation: Option<bool>,
+    app: tauri::AppHandle,
+) -> Result<NamingSubscriberListResponse, String> {
+    get_naming_subscribers(
+        &app,
+        &namespace_id,
+        &group_name,
+        &service_name,
+        page_no.unwrap_or(1),
+        page_size.unwrap_or(10),
+        aggregation.unwrap_or(true),
+    )
+    .await
+}
+
+/// Tauri 命令：按权重选择单个健康实例
+#[tauri::command]
+async fn select_one_healthy_instance_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    clusters: Option<Vec<String>>,
+    hash_key: Option<String>,
+    zone: Option<String>,
+    client_ip: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<Option<InstanceInfo>, String> {
+    let options = SelectInstanceOptions {
+        clusters: clusters.unwrap_or_default(),
+        hash_key,
+        zone: resolve_consumer_zone(&app, zone, client_ip.as_deref()).await,
+    };
+    select_one_healthy_instance(&app, &namespace_id, &group_name, &service_name, options).await
+}
+
+/// Tauri 命令：查询服务集群列表
+#[tauri::command]
+async fn get_service_clusters_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    app: tauri::AppHandle,
+) -> Result<Vec<ClusterInfo>, String> {
+    get_service_clusters(&app, &namespace_id, &group_name, &service_name).await
+}
+
+/// Tauri 命令：更新集群
+#[tauri::command]
+async fn update_cluster_cmd(
+    request: UpdateClusterRequest,
+    app: tauri::AppHandle,
+) -> Result<ClusterInfo, String> {
+    update_cluster(&app, request).await
+}
+
+/// Tauri 命令：查询服务变更历史
+#[tauri::command]
+async fn get_service_history_cmd(
+    query: ServiceHistoryQuery,
+    app: tauri::AppHandle,
+) -> Result<ServiceHistoryListResponse, String> {
+    get_service_history(&app, query).await
+}
+
+/// Tauri 命令：查询实例健康时间线和可用率
+#[tauri::command]
+async fn get_health_timeline_cmd(
+    query: HealthTimelineQuery,
+    app: tauri::AppHandle,
+) -> Result<HealthTimelineResponse, String> {
+    get_health_timeline(&app, query).await
+}
+
+/// Tauri 命令：查询服务导出列表
+#[tauri::command]
+async fn list_service_exports_cmd(
+    query: ServiceExportQuery,
+    app: tauri::AppHandle,
+) -> Result<Vec<ServiceExport>, String> {
+    list_service_exports(&app, query).await
+}
+
+/// Tauri 命令：导出服务到其他命名空间
+#[tauri::command]
+async fn create_service_export_cmd(
+    request: CreateServiceExportRequest,
+    app: tauri::AppHandle,
+) -> Result<ServiceExport, String> {
+    create_service_export(&app, request).await
+}
+
+/// Tauri 命令：撤销服务导出
+#[tauri::command]
+async fn revoke_service_export_cmd(
+    target_namespace_id: String,
+    target_group_name: String,
+    target_service_name: String,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    revoke_service_export(&app, &target_namespace_id, &target_group_name, &target_service_name).await
+}
+
+/// Tauri 命令：查询服务的实例元数据约定
+#[tauri::command]
+async fn get_metadata_schema_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    app: tauri::AppHandle,
+) -> Result<Option<MetadataSchema>, String> {
+    get_metadata_schema(&app, &namespace_id, &group_name, &service_name).await
+}
+
+/// Tauri 命令：设置服务的实例元数据约定（schema 为空时清除约定）
+#[tauri::command]
+async fn update_metadata_schema_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    schema: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    update_metadata_schema(&app, &namespace_id, &group_name, &service_name, schema.as_deref()).await
+}
+
+/// Tauri 命令：查询违反实例元数据约定的实例
+#[tauri::command]
+async fn get_metadata_violations_cmd(
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+    app: tauri::AppHandle,
+) -> Result<Option<MetadataViolationReport>, String> {
+    get_metadata_violations(&app, &namespace_id, &group_name, &service_name, &[]).await
+}
+
+/// Tauri 命令：导出命名空间的容灾快照到 zip 文件或目录（Nacos 客户端 failover 格式）
+#[tauri::command]
+async fn export_failover_snapshot_cmd(
+    request: FailoverExportRequest,
+    app: tauri::AppHandle,
+) -> Result<FailoverExportSummary, String> {
+    export_failover_snapshot(&app, request).await
+}
+
+/// Tauri 命令：按 IP 查询主机视图（提供的实例、订阅的服务和监听的配置）
+#[tauri::command]
+async fn get_host_view_cmd(
+    ip: String,
+    app: tauri::AppHandle,
+) -> Result<HostView, String> {
+    get_host_view(&app, &ip).await
+}
+
+/// Tauri 命令：创建并启动服务模拟
+#[tauri::command]
+async fn start_simulation_cmd(
+    definition: SimulationDefinition,
+    app: tauri::AppHandle,
+) -> Result<SimulationStatus, String> {
+    start_simulation(&app, definition).await
+}
+
+/// Tauri 命令：停止服务模拟并注销其实例
+#[tauri::command]
+async fn stop_simulation_cmd(
+    name: String,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    stop_simulation(&app, &name).await
+}
+
+/// Tauri 命令：查询服务模拟状态
+#[tauri::command]
+async fn get_simulation_cmd(
+    name: String,
+    app: tauri::AppHandle,
+) -> Result<SimulationStatus, String> {
+    get_simulation(&app, &name).await
+}
+
+/// Tauri 命令：查询所有服务模拟
+#[tauri::command]
+async fn list_simulations_cmd(
+    app: tauri::AppHandle,
+) -> Result<Vec<SimulationStatus>, String> {
+    list_simulations(&app).await
+}
+
+/// Tauri 命令：分页查询 MCP 服务
+#[tauri::command]
+async fn list_mcp_servers_cmd(
+    query: McpServerQuery,
+    app: tauri::AppHandle,
+) -> Result<McpServerListResponse, String> {
+    list_mcp_servers(&app, query).await
+}
+
+/// Tauri 命令：查询 MCP 服务详情（version 为空时返回最新版本）
+#[tauri::command]
+async fn get_mcp_server_cmd(
+    namespace_id: String,
+    name: String,
+    version: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<Option<McpServerDetail>, String> {
+    get_mcp_server(&app, &namespace_id, &name, version.as_deref()).await
+}
+
+/// Tauri 命令：查询 MCP 服务版本列表
+#[tauri::command]
+async fn list_mcp_server_versions_cmd(
+    namespace_id: String,
+    name: String,
+    app: tauri::AppHandle,
+) -> Result<Vec<McpVersionSummary>, String> {
+    list_mcp_server_versions(&app, &namespace_id, &name).await
+}
+
+/// Tauri 命令：创建 MCP 服务
+#[tauri::command]
+async fn create_mcp_server_cmd(
+    request: McpServerRequest,
+    app: tauri::AppHandle,
+) -> Result<McpServerDetail, String> {
+    create_mcp_server(&app, request).await
+}
+
+/// Tauri 命令：更新 MCP 服务（版本不存在时新增版本）
+#[tauri::command]
+async fn update_mcp_server_cmd(
+    request: McpServerRequest,
+    latest: bool,
+    app: tauri::AppHandle,
+) -> Result<McpServerDetail, String> {
+    update_mcp_server(&app, request, latest).await
+}
+
+/// Tauri 命令：删除 MCP 服务
+#[tauri::command]
+async fn delete_mcp_server_cmd(
+    namespace_id: String,
+    name: String,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    delete_mcp_server(&app, &namespace_id, &name).await
+}
+
+/// Tauri 命令：修改 MCP 服务的工具定义
+#[tauri::command]
+async fn update_mcp_tool_spec_cmd(
+    namespace_id: String,
+    name: String,
+    version: Option<String>,
+    tool_spec: serde_json::Value,
+    app: tauri::AppHandle,
+) -> Result<McpServerDetail, String> {
+    update_mcp_tool_spec(&app, &namespace_id, &name, version.as_deref(), tool_spec).await
+}
+
+/// Tauri 命令：启用或禁用 MCP 服务
+#[tauri::command]
+async fn set_mcp_server_enabled_cmd(
+    namespace_id: String,
+    name: String,
+    enabled: bool,
+    app: tauri::AppHandle,
+) -> Result<(), String> {
+    set_mcp_server_enabled(&app, &namespace_id, &name, enabled).await
+}
+
+/// Tauri 命令：查询系统开关
+#[tauri::command]
+async fn get_naming_switches_cmd(
+    app: tauri::AppHandle,
+) -> Result<NamingSwitches, String> {
+    Ok(get_naming_switches(&app).await)
+}
+
+/// Tauri 命令：修改系统开关
+#[tauri::command]
+async fn update_naming_switch_cmd(
+    entry: String,
+    value: String,
+    app: tauri::AppHandle,
+) -> Result<NamingSwitches, String> {
+    update_naming_switch(&app, &entry, &value).await
 }
 
 /// Tauri 命令：查询命名空间列表
@@ -598,11 +968,76 @@ fn main() {
                             sql: include_str!("db/migrations/006_subscribers_table.sql"),
                             kind: tauri_plugin_sql::MigrationKind::Up,
                         },
+                        // Migration 11: 服务订阅者表
+                        tauri_plugin_sql::Migration {
+                            version: 11,
+                            description: "create naming_subscribers table",
+                            sql: include_str!("db/migrations/011_naming_subscribers.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        // Migration 12: 集群信息表
+                        tauri_plugin_sql::Migration {
+                            version: 12,
+                            description: "create cluster_info table",
+                            sql: include_str!("db/migrations/012_cluster_info.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        // Migration 13: 服务创建来源
+                        tauri_plugin_sql::Migration {
+                            version: 13,
+                            description: "add service_info create_source column",
+                            sql: include_str!("db/migrations/013_service_create_source.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        // Migration 14: 系统开关表
+                        tauri_plugin_sql::Migration {
+                            version: 14,
+                            description: "create naming_switches table",
+                            sql: include_str!("db/migrations/014_naming_switches.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        // Migration 15: 实例健康时间线表
+                        tauri_plugin_sql::Migration {
+                            version: 15,
+                            description: "create instance_health_event table",
+                            sql: include_str!("db/migrations/015_instance_health_event.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        // Migration 16: MCP 服务注册表
+                        tauri_plugin_sql::Migration {
+                            version: 16,
+                            description: "create mcp_server tables",
+                            sql: include_str!("db/migrations/016_mcp_server.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        // Migration 17: 服务导出
+                        tauri_plugin_sql::Migration {
+                            version: 17,
+                            description: "create service_export table",
+                            sql: include_str!("db/migrations/017_service_export.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        // Migration 18: 实例元数据约定
+                        tauri_plugin_sql::Migration {
+                            version: 18,
+                            description: "add service_info metadata_schema column",
+                            sql: include_str!("db/migrations/018_service_metadata_schema.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 19,
+                            description: "create consul_kv_index table",
+                            sql: include_str!("db/migrations/019_consul_kv_index.sql"),
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
                     ],
                 )
                 .build(),
         )
         .setup(|app| {
+            // 临时实例注册表在启动时注册，避免并发访问时重复创建
+            manage_ephemeral_registry(app.handle());
+
             // 初始化数据库（延迟执行，确保迁移完成）
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -665,6 +1100,9 @@ fn main() {
             // 服务管理 API
             get_service_list_cmd,
             get_service_detail_cmd,
+            list_catalog_services_cmd,
+            list_catalog_instances_cmd,
+            get_catalog_service_cmd,
             create_service_cmd,
             update_service_cmd,
             delete_service_cmd,
@@ -672,6 +1110,37 @@ fn main() {
             register_instance_cmd,
             deregister_instance_cmd,
             update_instance_health_cmd,
+            batch_register_instances_cmd,
+            batch_deregister_instances_cmd,
+            get_naming_switches_cmd,
+            update_naming_switch_cmd,
+            get_naming_subscribers_cmd,
+            select_one_healthy_instance_cmd,
+            get_service_clusters_cmd,
+            update_cluster_cmd,
+            get_service_history_cmd,
+            list_service_exports_cmd,
+            create_service_export_cmd,
+            revoke_service_export_cmd,
+            get_metadata_schema_cmd,
+            update_metadata_schema_cmd,
+            get_metadata_violations_cmd,
+            export_failover_snapshot_cmd,
+            get_health_timeline_cmd,
+            get_host_view_cmd,
+            start_simulation_cmd,
+            stop_simulation_cmd,
+            get_simulation_cmd,
+            list_simulations_cmd,
+            // MCP 服务注册表 API
+            list_mcp_servers_cmd,
+            get_mcp_server_cmd,
+            list_mcp_server_versions_cmd,
+            create_mcp_server_cmd,
+            update_mcp_server_cmd,
+            delete_mcp_server_cmd,
+            update_mcp_tool_spec_cmd,
+            set_mcp_server_enabled_cmd,
             // 命名空间管理 API
             get_namespace_list_cmd,
             create_namespace_cmd,
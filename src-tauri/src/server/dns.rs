@@ -0,0 +1,426 @@
+/**
+ * DNS 服务发现接口
+ * 为只能解析主机名的旧组件提供 UDP / TCP DNS 监听，解析 <service>.<group>.<namespace>.nacos.local 形式的域名：
+ * A / AAAA 查询返回健康且启用的实例 IP，SRV 查询返回实例端口，实例权重映射为 SRV 权重
+ */
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use tauri::AppHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, UdpSocket};
+use tokio::task::JoinHandle;
+
+use crate::service::{get_service_detail, get_service_instances, InstanceInfo};
+
+/// DNS 域名后缀
+pub const DNS_DOMAIN_SUFFIX: &str = "nacos.local";
+
+/// SRV 记录目标主机名使用的子域，形如 10-0-0-1.addr.nacos.local
+pub const DNS_ADDR_SUBDOMAIN: &str = "addr";
+
+/// 默认 DNS 监听端口
+pub const DEFAULT_DNS_PORT: u16 = 8853;
+
+/// 默认记录 TTL（秒）
+pub const DEFAULT_DNS_TTL_SECONDS: u32 = 6;
+
+/// UDP 响应最大长度（超过时设置 TC 位，客户端改用 TCP 重试）
+const UDP_MAX_RESPONSE_LEN: usize = 512;
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+const CLASS_IN: u16 = 1;
+
+const RCODE_NO_ERROR: u8 = 0;
+const RCODE_FORMAT_ERROR: u8 = 1;
+const RCODE_NAME_ERROR: u8 = 3;
+const RCODE_NOT_IMPLEMENTED: u8 = 4;
+const RCODE_REFUSED: u8 = 5;
+
+/// DNS 查询问题
+#[derive(Debug, Clone)]
+struct DnsQuestion {
+    name: String,
+    qtype: u16,
+    qclass: u16,
+}
+
+/// DNS 资源记录
+#[derive(Debug, Clone)]
+struct DnsRecord {
+    name: String,
+    rtype: u16,
+    ttl: u32,
+    rdata: Vec<u8>,
+}
+
+/// 域名解析出的服务
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DnsServiceName {
+    namespace_id: String,
+    group_name: String,
+    service_name: String,
+}
+
+/// 启动 DNS 监听（UDP 和 TCP 使用同一端口），绑定失败时返回错误
+pub async fn start_dns_server(app: AppHandle, port: u16, ttl: u32) -> Result<JoinHandle<()>, String> {
+    let addr = format!("127.0.0.1:{}", port);
+    let udp_socket = UdpSocket::bind(&addr)
+        .await
+        .map_err(|e| format!("无法绑定 DNS UDP 端口 {}: {}", port, e))?;
+    let tcp_listener = TcpListener::bind(&addr)
+        .await
+        .map_err(|e| format!("无法绑定 DNS TCP 端口 {}: {}", port, e))?;
+
+    let udp_app = app.clone();
+    Ok(tokio::spawn(async move {
+        tokio::join!(
+            serve_udp(udp_app, udp_socket, ttl),
+            serve_tcp(app, tcp_listener, ttl),
+        );
+    }))
+}
+
+/// 处理 UDP 查询
+async fn serve_udp(app: AppHandle, socket: UdpSocket, ttl: u32) {
+    let mut buf = [0u8; 1500];
+    loop {
+        let (len, peer) = match socket.recv_from(&mut buf).await {
+            Ok(received) => received,
+            Err(e) => {
+                tracing::warn!("DNS UDP receive failed: {}", e);
+                continue;
+            }
+        };
+        if let Some(response) = handle_dns_packet(&app, &buf[..len], ttl, Some(UDP_MAX_RESPONSE_LEN)).await {
+            if let Err(e) = socket.send_to(&response, peer).await {
+                tracing::warn!("DNS UDP send to {} failed: {}", peer, e);
+            }
+        }
+    }
+}
+
+/// 处理 TCP 查询（每条消息前有 2 字节长度前缀）
+async fn serve_tcp(app: AppHandle, listener: TcpListener, ttl: u32) {
+    loop {
+        let (mut stream, _) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                tracing::warn!("DNS TCP accept failed: {}", e);
+                continue;
+            }
+        };
+        let app = app.clone();
+        tokio::spawn(async move {
+            loop {
+                let mut len_buf = [0u8; 2];
+                if stream.read_exact(&mut len_buf).await.is_err() {
+                    break;
+                }
+                let mut packet = vec![0u8; u16::from_be_bytes(len_buf) as usize];
+                if stream.read_exact(&mut packet).await.is_err() {
+                    break;
+                }
+                let Some(response) = handle_dns_packet(&app, &packet, ttl, None).await else {
+                    break;
+                };
+                let mut framed = (response.len() as u16).to_be_bytes().to_vec();
+                framed.extend_from_slice(&response);
+                if stream.write_all(&framed).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
+
+/// 处理一条 DNS 查询报文，返回响应报文；报文头无法解析时返回 None（直接丢弃）
+pub async fn handle_dns_packet(app: &AppHandle, packet: &[u8], ttl: u32, max_len: Option<usize>) -> Option<Vec<u8>> {
+    if packet.len() < 12 {
+        return None;
+    }
+    let id = u16::from_be_bytes([packet[0], packet[1]]);
+    let flags = u16::from_be_bytes([packet[2], packet[3]]);
+    // 只处理查询报文
+    if flags & 0x8000 != 0 {
+        return None;
+    }
+    let opcode = ((flags >> 11) & 0x0f) as u8;
+    let recursion_desired = flags & 0x0100 != 0;
+    let qdcount = u16::from_be_bytes([packet[4], packet[5]]);
+
+    if opcode != 0 {
+        return Some(encode_response(id, recursion_desired, RCODE_NOT_IMPLEMENTED, None, &[], &[], max_len));
+    }
+    let question = match (qdcount, parse_question(packet)) {
+        (1, Some(question)) => question,
+        _ => return Some(encode_response(id, recursion_desired, RCODE_FORMAT_ERROR, None, &[], &[], max_len)),
+    };
+
+    let (rcode, answers, additionals) = resolve_question(app, &question, ttl).await;
+    Some(encode_response(id, recursion_desired, rcode, Some(&question), &answers, &additionals, max_len))
+}
+
+/// 解析报文中的第一个查询问题
+fn parse_question(packet: &[u8]) -> Option<DnsQuestion> {
+    let (name, offset) = read_name(packet, 12)?;
+    let fixed = packet.get(offset..offset + 4)?;
+    Some(DnsQuestion {
+        name,
+        qtype: u16::from_be_bytes([fixed[0], fixed[1]]),
+        qclass: u16::from_be_bytes([fixed[2], fixed[3]]),
+    })
+}
+
+/// 读取域名（支持压缩指针），返回域名和域名之后的位置
+fn read_name(packet: &[u8], start: usize) -> Option<(String, usize)> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut offset = start;
+    let mut end = None;
+    // 限制跳转次数，防止恶意报文构造指针循环
+    for _ in 0..128 {
+        let len = *packet.get(offset)? as usize;
+        if len == 0 {
+            return Some((labels.join("."), end.unwrap_or(offset + 1)));
+        }
+        if len & 0xc0 == 0xc0 {
+            let pointer = ((len & 0x3f) << 8) | *packet.get(offset + 1)? as usize;
+            end.get_or_insert(offset + 2);
+            offset = pointer;
+            continue;
+        }
+        let label = packet.get(offset + 1..offset + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).into_owned());
+        offset += 1 + len;
+    }
+    None
+}
+
+/// 解析域名中的服务：<service>[.<group>[.<namespace>]].nacos.local
+/// 服务名本身包含 "." 时需要写全 group 和 namespace，其余标签都归入服务名
+fn parse_service_name(labels: &[&str]) -> Option<DnsServiceName> {
+    let (service_labels, group_name, namespace_id) = match labels.len() {
+        0 => return None,
+        1 => (labels, "DEFAULT_GROUP", "public"),
+        2 => (&labels[..1], labels[1], "public"),
+        n => (&labels[..n - 2], labels[n - 2], labels[n - 1]),
+    };
+    Some(DnsServiceName {
+        namespace_id: namespace_id.to_string(),
+        group_name: group_name.to_string(),
+        service_name: service_labels.join("."),
+    })
+}
+
+/// 将 IP 编码为 SRV 目标主机名，例如 10.0.0.1 -> 10-0-0-1.addr.nacos.local
+fn addr_host_name(ip: &IpAddr) -> String {
+    let label = match ip {
+        IpAddr::V4(v4) => v4.to_string().replace('.', "-"),
+        IpAddr::V6(v6) => v6.segments().iter().map(|s| format!("{:x}", s)).collect::<Vec<_>>().join("-"),
+    };
+    format!("{}.{}.{}", label, DNS_ADDR_SUBDOMAIN, DNS_DOMAIN_SUFFIX)
+}
+
+/// 解码 SRV 目标主机名中的 IP
+fn parse_addr_label(label: &str) -> Option<IpAddr> {
+    let parts: Vec<&str> = label.split('-').collect();
+    match parts.len() {
+        4 => {
+            let octets: Vec<u8> = parts.iter().map(|p| p.parse().ok()).collect::<Option<_>>()?;
+            Some(IpAddr::V4(Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3])))
+        }
+        8 => {
+            let segments: Vec<u16> = parts.iter().map(|p| u16::from_str_radix(p, 16).ok()).collect::<Option<_>>()?;
+            Some(IpAddr::V6(Ipv6Addr::new(
+                segments[0], segments[1], segments[2], segments[3],
+                segments[4], segments[5], segments[6], segments[7],
+            )))
+        }
+        _ => None,
+    }
+}
+
+/// 实例权重（0 - 10000）映射为 SRV 权重（0 - 65535），保留两位小数精度
+fn srv_weight(weight: f64) -> u16 {
+    (weight * 100.0).round().clamp(0.0, u16::MAX as f64) as u16
+}
+
+/// 地址记录（A 或 AAAA），类型与 IP 版本不匹配时返回 None
+fn address_record(name: &str, ip: &IpAddr, qtype: u16, ttl: u32) -> Option<DnsRecord> {
+    let (rtype, rdata) = match ip {
+        IpAddr::V4(v4) => (TYPE_A, v4.octets().to_vec()),
+        IpAddr::V6(v6) => (TYPE_AAAA, v6.octets().to_vec()),
+    };
+    (rtype == qtype).then(|| DnsRecord { name: name.to_string(), rtype, ttl, rdata })
+}
+
+/// 解析问题，返回响应码、回答记录和附加记录
+async fn resolve_question(app: &AppHandle, question: &DnsQuestion, ttl: u32) -> (u8, Vec<DnsRecord>, Vec<DnsRecord>) {
+    let name = question.name.trim_end_matches('.');
+    let suffix = format!(".{}", DNS_DOMAIN_SUFFIX);
+    let prefix = match name.len().checked_sub(suffix.len()) {
+        Some(split) if name.is_char_boundary(split) && name[split..].eq_ignore_ascii_case(&suffix) => &name[..split],
+        _ => return (RCODE_REFUSED, Vec::new(), Vec::new()),
+    };
+    if question.qclass != CLASS_IN {
+        return (RCODE_NO_ERROR, Vec::new(), Vec::new());
+    }
+
+    let labels: Vec<&str> = prefix.split('.').collect();
+
+    // SRV 目标主机名直接解码 IP
+    if labels.len() == 2 && labels[1].eq_ignore_ascii_case(DNS_ADDR_SUBDOMAIN) {
+        return match parse_addr_label(labels[0]) {
+            Some(ip) => (RCODE_NO_ERROR, address_record(&question.name, &ip, question.qtype, ttl).into_iter().collect(), Vec::new()),
+            None => (RCODE_NAME_ERROR, Vec::new(), Vec::new()),
+        };
+    }
+
+    let Some(target) = parse_service_name(&labels) else {
+        return (RCODE_NAME_ERROR, Vec::new(), Vec::new());
+    };
+    match get_service_detail(app, &target.namespace_id, &target.group_name, &target.service_name).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return (RCODE_NAME_ERROR, Vec::new(), Vec::new()),
+        Err(e) => {
+            tracing::warn!("DNS lookup for {} failed: {}", question.name, e);
+            return (RCODE_NAME_ERROR, Vec::new(), Vec::new());
+        }
+    }
+
+    let instances: Vec<InstanceInfo> = match get_service_instances(app, &target.namespace_id, &target.group_name, &target.service_name).await {
+        Ok(response) => response.instances.into_iter().filter(|inst| inst.healthy && inst.enabled).collect(),
+        Err(e) => {
+            tracing::warn!("DNS lookup for {} failed: {}", question.name, e);
+            Vec::new()
+        }
+    };
+
+    let mut answers = Vec::new();
+    let mut additionals = Vec::new();
+    for instance in &instances {
+        let Ok(ip) = instance.ip.parse::<IpAddr>() else {
+            continue;
+        };
+        match question.qtype {
+            TYPE_A | TYPE_AAAA => {
+                // 同一 IP 的多个实例只返回一条地址记录
+                if let Some(record) = address_record(&question.name, &ip, question.qtype, ttl) {
+                    if !answers.iter().any(|r: &DnsRecord| r.rdata == record.rdata) {
+                        answers.push(record);
+                    }
+                }
+            }
+            TYPE_SRV => {
+                let host = addr_host_name(&ip);
+                let mut rdata = Vec::new();
+                rdata.extend_from_slice(&0u16.to_be_bytes());
+                rdata.extend_from_slice(&srv_weight(instance.weight).to_be_bytes());
+                rdata.extend_from_slice(&(instance.port as u16).to_be_bytes());
+                write_name(&mut rdata, &host);
+                answers.push(DnsRecord { name: question.name.clone(), rtype: TYPE_SRV, ttl, rdata });
+
+                let qtype = if ip.is_ipv4() { TYPE_A } else { TYPE_AAAA };
+                if let Some(record) = address_record(&host, &ip, qtype, ttl) {
+                    if !additionals.iter().any(|r: &DnsRecord| r.name == record.name) {
+                        additionals.push(record);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (RCODE_NO_ERROR, answers, additionals)
+}
+
+/// 写入未压缩的域名
+fn write_name(buf: &mut Vec<u8>, name: &str) {
+    for label in name.trim_end_matches('.').split('.').filter(|l| !l.is_empty()) {
+        let bytes = &label.as_bytes()[..label.len().min(63)];
+        buf.push(bytes.len() as u8);
+        buf.extend_from_slice(bytes);
+    }
+    buf.push(0);
+}
+
+/// 写入资源记录；与问题同名的记录使用指向问题域名的压缩指针
+fn write_record(buf: &mut Vec<u8>, record: &DnsRecord, question_name: Option<&str>) {
+    if question_name == Some(record.name.as_str()) {
+        buf.extend_from_slice(&0xc00cu16.to_be_bytes());
+    } else {
+        write_name(buf, &record.name);
+    }
+    buf.extend_from_slice(&record.rtype.to_be_bytes());
+    buf.extend_from_slice(&CLASS_IN.to_be_bytes());
+    buf.extend_from_slice(&record.ttl.to_be_bytes());
+    buf.extend_from_slice(&(record.rdata.len() as u16).to_be_bytes());
+    buf.extend_from_slice(&record.rdata);
+}
+
+/// 编码响应报文；超过 max_len 时截断记录并设置 TC 位
+fn encode_response(
+    id: u16,
+    recursion_desired: bool,
+    rcode: u8,
+    question: Option<&DnsQuestion>,
+    answers: &[DnsRecord],
+    additionals: &[DnsRecord],
+    max_len: Option<usize>,
+) -> Vec<u8> {
+    let mut body = Vec::new();
+    if let Some(question) = question {
+        write_name(&mut body, &question.name);
+        body.extend_from_slice(&question.qtype.to_be_bytes());
+        body.extend_from_slice(&question.qclass.to_be_bytes());
+    }
+    let question_name = question.map(|q| q.name.as_str());
+    let limit = max_len.unwrap_or(usize::MAX).saturating_sub(12);
+
+    let mut truncated = false;
+    let mut answer_count = 0u16;
+    for record in answers {
+        let mut encoded = Vec::new();
+        write_record(&mut encoded, record, question_name);
+        if body.len() + encoded.len() > limit {
+            truncated = true;
+            break;
+        }
+        body.extend_from_slice(&encoded);
+        answer_count += 1;
+    }
+    // 附加记录只是优化，放不下时直接省略
+    let mut additional_count = 0u16;
+    if !truncated {
+        for record in additionals {
+            let mut encoded = Vec::new();
+            write_record(&mut encoded, record, question_name);
+            if body.len() + encoded.len() > limit {
+                break;
+            }
+            body.extend_from_slice(&encoded);
+            additional_count += 1;
+        }
+    }
+
+    // QR=1, AA=1，保留 RD，RA=0
+    let mut flags: u16 = 0x8000 | 0x0400 | (rcode as u16 & 0x0f);
+    if recursion_desired {
+        flags |= 0x0100;
+    }
+    if truncated {
+        flags |= 0x0200;
+    }
+
+    let mut packet = Vec::with_capacity(12 + body.len());
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&flags.to_be_bytes());
+    packet.extend_from_slice(&(question.is_some() as u16).to_be_bytes());
+    packet.extend_from_slice(&answer_count.to_be_bytes());
+    packet.extend_from_slice(&0u16.to_be_bytes());
+    packet.extend_from_slice(&additional_count.to_be_bytes());
+    packet.extend_from_slice(&body);
+    packet
+}
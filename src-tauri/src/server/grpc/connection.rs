@@ -0,0 +1,484 @@
+/**
+ * Nacos gRPC 连接管理模块
+ * 以 TCP 连接（客户端地址）区分 gRPC 客户端：同一个客户端的一元请求和双向流共享连接。
+ * 双向流建立后启动推送任务，收到配置变更和服务变更事件时推送监听的配置和订阅的服务，
+ * 并定期刷新该连接注册的临时实例；连接断开后立即注销这些实例（连接存活代替心跳）。
+ * 监听的配置和订阅的服务同时记录为订阅者，可在订阅者查询中看到，连接断开后移除
+ */
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use http_body::Frame;
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+
+use super::payload::{encode_frame, Payload};
+use crate::config::{
+    get_config_detail, record_config_subscriber, remove_config_subscriber, subscribe_config_changes, ConfigChangeEvent,
+};
+use crate::service::{
+    build_service_info, current_millis, deregister_instance, get_naming_switches, get_service_revision,
+    record_naming_subscriber, remove_naming_subscriber, subscribe_service_changes, touch_ephemeral_instance,
+    ServiceChangeEvent,
+};
+
+/// 连接保活间隔：刷新连接注册的临时实例和服务订阅者记录，并按内存中的修订号兜底检查订阅的服务
+/// （例如推送开关重新打开后补推关闭期间的变更）
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 配置键（dataId, group, tenant）
+pub type ConfigKey = (String, String, String);
+
+/// 服务订阅键（namespace, group, service, clusters）
+pub type SubscriptionKey = (String, String, String, String);
+
+/// 实例键（namespace, group, service, instanceId）
+pub type InstanceKey = (String, String, String, String);
+
+/// gRPC 客户端连接
+#[derive(Debug)]
+struct GrpcConnection {
+    connection_id: String,
+    client_version: String,
+    app_name: String,
+    /// 双向流发送端（双向流建立前为 None）
+    sender: Option<mpsc::UnboundedSender<Frame<Bytes>>>,
+    /// 监听的配置 -> 客户端已知的 MD5
+    listened_configs: HashMap<ConfigKey, String>,
+    /// 订阅的服务 -> 已推送的服务修订号
+    subscriptions: HashMap<SubscriptionKey, u64>,
+    /// 通过该连接注册的临时实例
+    instances: Vec<InstanceKey>,
+    /// 服务端推送请求 ID 计数器
+    next_request_id: u64,
+}
+
+/// 连接管理器（客户端地址 -> 连接）
+type GrpcConnectionManager = Arc<Mutex<HashMap<SocketAddr, GrpcConnection>>>;
+
+/// 获取或创建连接管理器
+fn get_connection_manager(app: &AppHandle) -> GrpcConnectionManager {
+    if let Some(state) = app.try_state::<GrpcConnectionManager>() {
+        state.inner().clone()
+    } else {
+        let state: GrpcConnectionManager = Arc::new(Mutex::new(HashMap::new()));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 查询或创建连接，返回连接 ID（格式与 Nacos 一致：创建时间_客户端IP_客户端端口）
+pub async fn ensure_connection(app: &AppHandle, addr: SocketAddr) -> String {
+    let manager = get_connection_manager(app);
+    let mut connections = manager.lock().await;
+    connections
+        .entry(addr)
+        .or_insert_with(|| {
+            let now = current_millis();
+            GrpcConnection {
+                connection_id: format!("{}_{}_{}", now, addr.ip(), addr.port()),
+                client_version: String::new(),
+                app_name: String::new(),
+                sender: None,
+                listened_configs: HashMap::new(),
+                subscriptions: HashMap::new(),
+                instances: Vec::new(),
+                next_request_id: 0,
+            }
+        })
+        .connection_id
+        .clone()
+}
+
+/// 双向流建立（ConnectionSetupRequest），记录客户端信息和推送通道
+pub async fn attach_stream(
+    app: &AppHandle,
+    addr: SocketAddr,
+    sender: mpsc::UnboundedSender<Frame<Bytes>>,
+    client_version: &str,
+    app_name: &str,
+) {
+    ensure_connection(app, addr).await;
+    let manager = get_connection_manager(app);
+    let mut connections = manager.lock().await;
+    if let Some(connection) = connections.get_mut(&addr) {
+        connection.sender = Some(sender);
+        connection.client_version = client_version.to_string();
+        connection.app_name = app_name.to_string();
+    }
+}
+
+/// 连接断开：移除连接，注销其注册的临时实例并移除其订阅者记录
+pub async fn close_connection(app: &AppHandle, addr: SocketAddr) {
+    let manager = get_connection_manager(app);
+    let Some(connection) = manager.lock().await.remove(&addr) else {
+        return;
+    };
+    tracing::info!(
+        "gRPC connection {} closed (app: {}, client: {})",
+        connection.connection_id,
+        connection.app_name,
+        connection.client_version
+    );
+
+    for (namespace_id, group_name, service_name, instance_id) in connection.instances {
+        if let Err(e) = deregister_instance(app, &namespace_id, &group_name, &service_name, &instance_id).await {
+            tracing::warn!("Failed to deregister instance {} of closed connection: {}", instance_id, e);
+        }
+    }
+
+    let client_ip = addr.ip().to_string();
+    let client_port = addr.port() as i32;
+    for (data_id, group, tenant) in connection.listened_configs.keys() {
+        if let Err(e) = remove_config_subscriber(app, data_id, group, tenant, &client_ip, client_port).await {
+            tracing::warn!("Failed to remove config subscriber {} of closed connection: {}", data_id, e);
+        }
+    }
+    for (namespace_id, group_name, service_name, clusters) in connection.subscriptions.keys() {
+        if let Err(e) =
+            remove_naming_subscriber(app, namespace_id, group_name, service_name, &client_ip, client_port, clusters).await
+        {
+            tracing::warn!("Failed to remove naming subscriber {} of closed connection: {}", service_name, e);
+        }
+    }
+}
+
+/// 记录连接注册的临时实例
+pub async fn add_connection_instance(app: &AppHandle, addr: SocketAddr, instance: InstanceKey) {
+    ensure_connection(app, addr).await;
+    let manager = get_connection_manager(app);
+    let mut connections = manager.lock().await;
+    if let Some(connection) = connections.get_mut(&addr) {
+        if !connection.instances.contains(&instance) {
+            connection.instances.push(instance);
+        }
+    }
+}
+
+/// 移除连接注册的临时实例
+pub async fn remove_connection_instance(app: &AppHandle, addr: SocketAddr, instance: &InstanceKey) {
+    let manager = get_connection_manager(app);
+    let mut connections = manager.lock().await;
+    if let Some(connection) = connections.get_mut(&addr) {
+        connection.instances.retain(|i| i != instance);
+    }
+}
+
+/// 添加或移除配置监听，同时记录或移除配置订阅者
+pub async fn set_config_listener(app: &AppHandle, addr: SocketAddr, key: ConfigKey, md5: Option<String>) {
+    ensure_connection(app, addr).await;
+    let (app_name, client_version) = {
+        let manager = get_connection_manager(app);
+        let mut connections = manager.lock().await;
+        let Some(connection) = connections.get_mut(&addr) else {
+            return;
+        };
+        match &md5 {
+            Some(md5) => {
+                connection.listened_configs.insert(key.clone(), md5.clone());
+            }
+            None => {
+                connection.listened_configs.remove(&key);
+            }
+        }
+        (connection.app_name.clone(), connection.client_version.clone())
+    };
+
+    let (data_id, group, tenant) = &key;
+    let client_ip = addr.ip().to_string();
+    let client_port = addr.port() as i32;
+    let result = match md5 {
+        Some(md5) => {
+            record_config_subscriber(
+                app,
+                data_id,
+                group,
+                tenant,
+                &client_ip,
+                Some(client_port),
+                Some(&client_version),
+                Some(&app_name),
+                &md5,
+            )
+            .await
+        }
+        None => remove_config_subscriber(app, data_id, group, tenant, &client_ip, client_port).await,
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to update config subscriber {} of gRPC connection: {}", data_id, e);
+    }
+}
+
+/// 添加或移除服务订阅，同时记录或移除服务订阅者
+pub async fn set_subscription(app: &AppHandle, addr: SocketAddr, key: SubscriptionKey, revision: Option<u64>) {
+    ensure_connection(app, addr).await;
+    let (app_name, client_version) = {
+        let manager = get_connection_manager(app);
+        let mut connections = manager.lock().await;
+        let Some(connection) = connections.get_mut(&addr) else {
+            return;
+        };
+        match revision {
+            Some(revision) => {
+                connection.subscriptions.insert(key.clone(), revision);
+            }
+            None => {
+                connection.subscriptions.remove(&key);
+            }
+        }
+        (connection.app_name.clone(), connection.client_version.clone())
+    };
+
+    let (namespace_id, group_name, service_name, clusters) = &key;
+    let client_ip = addr.ip().to_string();
+    let client_port = addr.port() as i32;
+    let result = match revision {
+        Some(_) => {
+            record_naming_subscriber(
+                app,
+                namespace_id,
+                group_name,
+                service_name,
+                &client_ip,
+                client_port,
+                Some(&app_name),
+                Some(&client_version),
+                clusters,
+            )
+            .await
+        }
+        None => remove_naming_subscriber(app, namespace_id, group_name, service_name, &client_ip, client_port, clusters).await,
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to update naming subscriber {} of gRPC connection: {}", service_name, e);
+    }
+}
+
+/// 刷新连接订阅服务的订阅者记录，避免连接存活期间被当作过期订阅者清理
+async fn refresh_connection_subscribers(app: &AppHandle, addr: SocketAddr) {
+    let (subscriptions, app_name, client_version) = {
+        let manager = get_connection_manager(app);
+        let connections = manager.lock().await;
+        let Some(connection) = connections.get(&addr) else {
+            return;
+        };
+        let subscriptions: Vec<SubscriptionKey> = connection.subscriptions.keys().cloned().collect();
+        (subscriptions, connection.app_name.clone(), connection.client_version.clone())
+    };
+
+    let client_ip = addr.ip().to_string();
+    for (namespace_id, group_name, service_name, clusters) in &subscriptions {
+        if let Err(e) = record_naming_subscriber(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &client_ip,
+            addr.port() as i32,
+            Some(&app_name),
+            Some(&client_version),
+            clusters,
+        )
+        .await
+        {
+            tracing::warn!("Failed to refresh naming subscriber {} of gRPC connection: {}", service_name, e);
+        }
+    }
+}
+
+/// 通过双向流向客户端推送请求，返回是否发送成功
+async fn push_request(app: &AppHandle, addr: SocketAddr, r#type: &str, mut body: serde_json::Value) -> bool {
+    let manager = get_connection_manager(app);
+    let mut connections = manager.lock().await;
+    let Some(connection) = connections.get_mut(&addr) else {
+        return false;
+    };
+    if connection.sender.is_none() {
+        return false;
+    }
+    connection.next_request_id += 1;
+    body["requestId"] = serde_json::json!(connection.next_request_id.to_string());
+    body["headers"] = serde_json::json!({});
+    let frame = encode_frame(&Payload::new(r#type, &body).encode());
+    connection
+        .sender
+        .as_ref()
+        .is_some_and(|sender| sender.send(Frame::data(Bytes::from(frame))).is_ok())
+}
+
+/// 连接是否仍然打开（双向流已建立且未关闭）
+async fn is_connection_open(app: &AppHandle, addr: SocketAddr) -> bool {
+    let manager = get_connection_manager(app);
+    let connections = manager.lock().await;
+    connections
+        .get(&addr)
+        .and_then(|connection| connection.sender.as_ref())
+        .is_some_and(|sender| !sender.is_closed())
+}
+
+/// 刷新连接注册的临时实例（连接存活即视为心跳），连接已关闭时返回 false
+async fn refresh_connection_instances(app: &AppHandle, addr: SocketAddr) -> bool {
+    if !is_connection_open(app, addr).await {
+        return false;
+    }
+    let instances = {
+        let manager = get_connection_manager(app);
+        let connections = manager.lock().await;
+        connections.get(&addr).map(|connection| connection.instances.clone()).unwrap_or_default()
+    };
+
+    for (namespace_id, group_name, service_name, instance_id) in &instances {
+        if touch_ephemeral_instance(app, namespace_id, group_name, service_name, instance_id).await.is_none() {
+            let key = (namespace_id.clone(), group_name.clone(), service_name.clone(), instance_id.clone());
+            remove_connection_instance(app, addr, &key).await;
+        }
+    }
+    true
+}
+
+/// 推送监听配置的变更，changed 为空时检查所有监听的配置；连接已关闭时返回 false
+async fn push_config_changes(app: &AppHandle, addr: SocketAddr, changed: Option<&ConfigChangeEvent>) -> bool {
+    if !is_connection_open(app, addr).await {
+        return false;
+    }
+    let configs: Vec<(ConfigKey, String)> = {
+        let manager = get_connection_manager(app);
+        let connections = manager.lock().await;
+        let Some(connection) = connections.get(&addr) else {
+            return false;
+        };
+        connection
+            .listened_configs
+            .iter()
+            .filter(|((data_id, group, tenant), _)| {
+                match changed {
+                    Some(event) => event.data_id == *data_id && event.group_id == *group && event.tenant_id == *tenant,
+                    None => true,
+                }
+            })
+            .map(|(key, md5)| (key.clone(), md5.clone()))
+            .collect()
+    };
+
+    for ((data_id, group, tenant), known_md5) in configs {
+        let md5 = match get_config_detail(app, &data_id, &group, &tenant).await {
+            Ok(config) => config.and_then(|c| c.md5).unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!("Failed to check listened config {}: {}", data_id, e);
+                continue;
+            }
+        };
+        if md5 != known_md5 {
+            let pushed = push_request(
+                app,
+                addr,
+                "ConfigChangeNotifyRequest",
+                serde_json::json!({ "dataId": data_id, "group": group, "tenant": tenant, "module": "config" }),
+            )
+            .await;
+            if pushed {
+                set_config_listener(app, addr, (data_id, group, tenant), Some(md5)).await;
+            }
+        }
+    }
+    true
+}
+
+/// 推送订阅服务的变更，changed 为空时检查所有订阅的服务；连接已关闭时返回 false
+async fn push_service_changes(app: &AppHandle, addr: SocketAddr, changed: Option<&ServiceChangeEvent>) -> bool {
+    if !is_connection_open(app, addr).await {
+        return false;
+    }
+    // 推送开关关闭时不推送服务变更
+    if !get_naming_switches(app).await.push_enabled {
+        return true;
+    }
+    let subscriptions: Vec<(SubscriptionKey, u64)> = {
+        let manager = get_connection_manager(app);
+        let connections = manager.lock().await;
+        let Some(connection) = connections.get(&addr) else {
+            return false;
+        };
+        connection
+            .subscriptions
+            .iter()
+            .filter(|((namespace_id, group_name, service_name, _), _)| {
+                match changed {
+                    Some(event) => {
+                        event.namespace_id == *namespace_id
+                            && event.group_name == *group_name
+                            && event.service_name == *service_name
+                    }
+                    None => true,
+                }
+            })
+            .map(|(key, revision)| (key.clone(), *revision))
+            .collect()
+    };
+
+    for ((namespace_id, group_name, service_name, clusters), known_revision) in subscriptions {
+        let revision = get_service_revision(app, &namespace_id, &group_name, &service_name).await.revision;
+        if revision == known_revision {
+            continue;
+        }
+        let service_info = match build_service_info(app, &namespace_id, &group_name, &service_name, &clusters).await {
+            Ok(service_info) => service_info,
+            Err(e) => {
+                tracing::warn!("Failed to build service info for {}: {}", service_name, e);
+                continue;
+            }
+        };
+        let pushed = push_request(
+            app,
+            addr,
+            "NotifySubscriberRequest",
+            serde_json::json!({ "serviceInfo": service_info, "module": "naming" }),
+        )
+        .await;
+        if pushed {
+            set_subscription(app, addr, (namespace_id, group_name, service_name, clusters), Some(revision)).await;
+        }
+    }
+    true
+}
+
+/// 启动连接的推送任务：收到配置或服务变更事件时推送，定期刷新临时实例；连接关闭后任务结束
+pub fn start_push_task(app: AppHandle, addr: SocketAddr) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut config_changes = subscribe_config_changes(&app);
+        let mut service_changes = subscribe_service_changes(&app);
+        // 订阅事件后先全量检查一次，补推订阅前已发生的变更
+        if !push_config_changes(&app, addr, None).await {
+            return;
+        }
+        let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+        loop {
+            let open = tokio::select! {
+                _ = keepalive.tick() => {
+                    refresh_connection_subscribers(&app, addr).await;
+                    refresh_connection_instances(&app, addr).await && push_service_changes(&app, addr, None).await
+                }
+                event = config_changes.recv() => match event {
+                    Ok(event) => push_config_changes(&app, addr, Some(&event)).await,
+                    // 落后过多时丢失了部分事件，全量检查一次
+                    Err(RecvError::Lagged(_)) => push_config_changes(&app, addr, None).await,
+                    Err(RecvError::Closed) => false,
+                },
+                event = service_changes.recv() => match event {
+                    Ok(event) => push_service_changes(&app, addr, Some(&event)).await,
+                    Err(RecvError::Lagged(_)) => push_service_changes(&app, addr, None).await,
+                    Err(RecvError::Closed) => false,
+                },
+            };
+            if !open {
+                break;
+            }
+        }
+    })
+}
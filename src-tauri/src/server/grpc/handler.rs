@@ -0,0 +1,416 @@
+/**
+ * Nacos gRPC 请求处理模块
+ * 按 Payload 的类型名分发请求，复用配置管理和服务注册模块；
+ * 响应使用 Nacos 的通用响应字段（resultCode、errorCode、message、requestId、success）
+ */
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use super::connection::{
+    add_connection_instance, ensure_connection, remove_connection_instance, set_config_listener, set_subscription,
+};
+use super::payload::Payload;
+use crate::config::{create_config, delete_config, get_config_detail, update_config, CreateConfigRequest, UpdateConfigRequest};
+use crate::service::{
+    build_service_info, deregister_instance, ensure_service, find_instance, get_service_revision, register_instance,
+    RegisterInstanceRequest,
+};
+
+/// 错误码（与 Nacos 一致）
+const ERROR_CODE_INVALID_PARAM: i32 = 400;
+const ERROR_CODE_NO_RIGHT: i32 = 403;
+const ERROR_CODE_SERVER_ERROR: i32 = 500;
+const ERROR_CODE_CONFIG_NOT_FOUND: i32 = 300;
+const ERROR_CODE_NO_HANDLER: i32 = 302;
+
+/// 默认命名空间和分组
+const DEFAULT_NAMESPACE_ID: &str = "public";
+const DEFAULT_GROUP: &str = "DEFAULT_GROUP";
+const DEFAULT_CLUSTER_NAME: &str = "DEFAULT";
+
+/// 成功响应
+fn response(r#type: &str, request_id: &str, mut body: Value) -> Payload {
+    body["resultCode"] = json!(200);
+    body["errorCode"] = json!(0);
+    body["message"] = Value::Null;
+    body["requestId"] = json!(request_id);
+    body["success"] = json!(true);
+    Payload::new(r#type, &body)
+}
+
+/// 失败响应
+fn error_response(r#type: &str, request_id: &str, error_code: i32, message: &str) -> Payload {
+    Payload::new(
+        r#type,
+        &json!({
+            "resultCode": 500,
+            "errorCode": error_code,
+            "message": message,
+            "requestId": request_id,
+            "success": false,
+        }),
+    )
+}
+
+/// 服务层错误转换为错误码
+fn service_error_code(e: &str) -> i32 {
+    if e.contains("is disabled") {
+        ERROR_CODE_NO_RIGHT
+    } else {
+        ERROR_CODE_SERVER_ERROR
+    }
+}
+
+/// 读取字符串字段（缺失时为空字符串）
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+/// 空值使用默认值
+fn or_default<'a>(value: &'a str, default: &'a str) -> &'a str {
+    if value.is_empty() {
+        default
+    } else {
+        value
+    }
+}
+
+/// 处理一元请求，返回响应消息
+pub async fn handle_request(app: &AppHandle, addr: SocketAddr, payload: &Payload) -> Payload {
+    let request = match payload.json() {
+        Ok(request) => request,
+        Err(e) => return error_response("ErrorResponse", "", ERROR_CODE_INVALID_PARAM, &e),
+    };
+    let request_id = str_field(&request, "requestId");
+
+    match payload.r#type.as_str() {
+        "ServerCheckRequest" => {
+            let connection_id = ensure_connection(app, addr).await;
+            response(
+                "ServerCheckResponse",
+                request_id,
+                json!({ "connectionId": connection_id, "supportAbilityNegotiation": false }),
+            )
+        }
+        "HealthCheckRequest" => response("HealthCheckResponse", request_id, json!({})),
+        "ConfigQueryRequest" => handle_config_query(app, &request, request_id).await,
+        "ConfigPublishRequest" => handle_config_publish(app, addr, &request, request_id).await,
+        "ConfigRemoveRequest" => handle_config_remove(app, addr, &request, request_id).await,
+        "ConfigBatchListenRequest" => handle_config_batch_listen(app, addr, &request, request_id).await,
+        "InstanceRequest" => handle_instance(app, addr, &request, request_id).await,
+        "SubscribeServiceRequest" => handle_subscribe_service(app, addr, &request, request_id).await,
+        "ServiceQueryRequest" => handle_service_query(app, &request, request_id).await,
+        other => error_response(
+            "ErrorResponse",
+            request_id,
+            ERROR_CODE_NO_HANDLER,
+            &format!("Unsupported request type: {}", other),
+        ),
+    }
+}
+
+/// 查询配置
+async fn handle_config_query(app: &AppHandle, request: &Value, request_id: &str) -> Payload {
+    const TYPE: &str = "ConfigQueryResponse";
+    let data_id = str_field(request, "dataId");
+    let group = str_field(request, "group");
+    let tenant = or_default(str_field(request, "tenant"), DEFAULT_NAMESPACE_ID);
+    if data_id.is_empty() || group.is_empty() {
+        return error_response(TYPE, request_id, ERROR_CODE_INVALID_PARAM, "dataId and group are required");
+    }
+
+    match get_config_detail(app, data_id, group, tenant).await {
+        Ok(Some(config)) => response(
+            TYPE,
+            request_id,
+            json!({
+                "content": config.content,
+                "encryptedDataKey": config.encrypted_data_key,
+                "contentType": config.r#type.unwrap_or_else(|| "text".to_string()),
+                "md5": config.md5,
+                "lastModified": config.gmt_modified * 1000,
+                "beta": false,
+                "tag": null,
+            }),
+        ),
+        Ok(None) => error_response(TYPE, request_id, ERROR_CODE_CONFIG_NOT_FOUND, "config data not exist"),
+        Err(e) => error_response(TYPE, request_id, service_error_code(&e), &e),
+    }
+}
+
+/// 发布配置（支持 casMd5 乐观锁）
+async fn handle_config_publish(app: &AppHandle, addr: SocketAddr, request: &Value, request_id: &str) -> Payload {
+    const TYPE: &str = "ConfigPublishResponse";
+    let data_id = str_field(request, "dataId").to_string();
+    let group = str_field(request, "group").to_string();
+    let tenant = or_default(str_field(request, "tenant"), DEFAULT_NAMESPACE_ID).to_string();
+    let content = str_field(request, "content").to_string();
+    if data_id.is_empty() || group.is_empty() || content.is_empty() {
+        return error_response(TYPE, request_id, ERROR_CODE_INVALID_PARAM, "dataId, group and content are required");
+    }
+
+    let addition: HashMap<String, String> = request
+        .get("additionMap")
+        .and_then(|m| serde_json::from_value(m.clone()).ok())
+        .unwrap_or_default();
+    let addition_field = |key: &str| addition.get(key).filter(|v| !v.is_empty()).cloned();
+
+    let existing = match get_config_detail(app, &data_id, &group, &tenant).await {
+        Ok(existing) => existing,
+        Err(e) => return error_response(TYPE, request_id, service_error_code(&e), &e),
+    };
+    let cas_md5 = str_field(request, "casMd5");
+    if !cas_md5.is_empty() && existing.as_ref().and_then(|c| c.md5.as_deref()) != Some(cas_md5) {
+        return error_response(
+            TYPE,
+            request_id,
+            ERROR_CODE_SERVER_ERROR,
+            "Cas publish fail, server md5 may have changed.",
+        );
+    }
+
+    let src_user = addition_field("src_user");
+    let src_ip = Some(addr.ip().to_string());
+    let result = if existing.is_some() {
+        update_config(
+            app,
+            UpdateConfigRequest {
+                data_id,
+                group_id: group,
+                tenant_id: tenant,
+                content,
+                app_name: addition_field("appName"),
+                c_desc: addition_field("desc"),
+                c_use: addition_field("use"),
+                effect: addition_field("effect"),
+                r#type: addition_field("type"),
+                c_schema: addition_field("schema"),
+                encrypted_data_key: addition_field("encryptedDataKey"),
+            },
+            src_user,
+            src_ip,
+        )
+        .await
+    } else {
+        create_config(
+            app,
+            CreateConfigRequest {
+                data_id,
+                group_id: group,
+                tenant_id: tenant,
+                content,
+                app_name: addition_field("appName"),
+                c_desc: addition_field("desc"),
+                c_use: addition_field("use"),
+                effect: addition_field("effect"),
+                r#type: addition_field("type"),
+                c_schema: addition_field("schema"),
+                encrypted_data_key: addition_field("encryptedDataKey"),
+            },
+            src_user,
+            src_ip,
+        )
+        .await
+    };
+
+    match result {
+        Ok(_) => response(TYPE, request_id, json!({})),
+        Err(e) => error_response(TYPE, request_id, service_error_code(&e), &e),
+    }
+}
+
+/// 删除配置
+async fn handle_config_remove(app: &AppHandle, addr: SocketAddr, request: &Value, request_id: &str) -> Payload {
+    const TYPE: &str = "ConfigRemoveResponse";
+    let data_id = str_field(request, "dataId");
+    let group = str_field(request, "group");
+    let tenant = or_default(str_field(request, "tenant"), DEFAULT_NAMESPACE_ID);
+    if data_id.is_empty() || group.is_empty() {
+        return error_response(TYPE, request_id, ERROR_CODE_INVALID_PARAM, "dataId and group are required");
+    }
+
+    match delete_config(app, data_id, group, tenant, None, Some(addr.ip().to_string())).await {
+        Ok(_) => response(TYPE, request_id, json!({})),
+        Err(e) => error_response(TYPE, request_id, service_error_code(&e), &e),
+    }
+}
+
+/// 批量监听配置：返回 MD5 与服务端不一致的配置，并记录监听以便后续推送变更
+async fn handle_config_batch_listen(app: &AppHandle, addr: SocketAddr, request: &Value, request_id: &str) -> Payload {
+    const TYPE: &str = "ConfigChangeBatchListenResponse";
+    let listen = request.get("listen").and_then(Value::as_bool).unwrap_or(true);
+    let contexts = request
+        .get("configListenContexts")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut changed_configs = Vec::new();
+    for context in &contexts {
+        let data_id = str_field(context, "dataId").to_string();
+        let group = str_field(context, "group").to_string();
+        let tenant = or_default(str_field(context, "tenant"), DEFAULT_NAMESPACE_ID).to_string();
+        let key = (data_id.clone(), group.clone(), tenant.clone());
+
+        if !listen {
+            set_config_listener(app, addr, key, None).await;
+            continue;
+        }
+
+        let server_md5 = match get_config_detail(app, &data_id, &group, &tenant).await {
+            Ok(config) => config.and_then(|c| c.md5).unwrap_or_default(),
+            Err(e) => return error_response(TYPE, request_id, service_error_code(&e), &e),
+        };
+        if server_md5 != str_field(context, "md5") {
+            changed_configs.push(json!({ "dataId": data_id, "group": group, "tenant": str_field(context, "tenant") }));
+        }
+        // 变更已在本次响应中告知客户端，之后只推送新的变更
+        set_config_listener(app, addr, key, Some(server_md5)).await;
+    }
+
+    response(TYPE, request_id, json!({ "changedConfigs": changed_configs }))
+}
+
+/// 解析命名请求中的命名空间、分组和服务名（服务名可能带 group@@ 前缀）
+fn naming_target(request: &Value) -> (String, String, String) {
+    let namespace_id = or_default(str_field(request, "namespace"), DEFAULT_NAMESPACE_ID).to_string();
+    let service_name = str_field(request, "serviceName");
+    let (group_name, service_name) = match service_name.split_once("@@") {
+        Some((group, name)) => (group, name),
+        None => (or_default(str_field(request, "groupName"), DEFAULT_GROUP), service_name),
+    };
+    (namespace_id, group_name.to_string(), service_name.to_string())
+}
+
+/// 注册或注销实例；临时实例绑定到当前连接，连接断开后自动注销
+async fn handle_instance(app: &AppHandle, addr: SocketAddr, request: &Value, request_id: &str) -> Payload {
+    const TYPE: &str = "InstanceResponse";
+    let (namespace_id, group_name, service_name) = naming_target(request);
+    let operation = str_field(request, "type");
+    let instance = request.get("instance").cloned().unwrap_or_else(|| json!({}));
+    let ip = str_field(&instance, "ip").to_string();
+    let port = instance.get("port").and_then(Value::as_i64).unwrap_or(0) as i32;
+    let cluster_name = or_default(str_field(&instance, "clusterName"), DEFAULT_CLUSTER_NAME).to_string();
+    if service_name.is_empty() || ip.is_empty() || port <= 0 {
+        return error_response(TYPE, request_id, ERROR_CODE_INVALID_PARAM, "serviceName, ip and port are required");
+    }
+
+    match operation {
+        "registerInstance" => {
+            let ephemeral = instance.get("ephemeral").and_then(Value::as_bool).unwrap_or(true);
+            let metadata = instance.get("metadata").filter(|m| m.is_object()).map(Value::to_string);
+            let result = async {
+                ensure_service(app, &namespace_id, &group_name, &service_name).await?;
+                register_instance(
+                    app,
+                    RegisterInstanceRequest {
+                        namespace_id: namespace_id.clone(),
+                        group_name: group_name.clone(),
+                        service_name: service_name.clone(),
+                        ip,
+                        port,
+                        weight: instance.get("weight").and_then(Value::as_f64),
+                        healthy: instance.get("healthy").and_then(Value::as_bool),
+                        enabled: instance.get("enabled").and_then(Value::as_bool),
+                        ephemeral: Some(ephemeral),
+                        cluster_name: Some(cluster_name),
+                        metadata,
+                    },
+                )
+                .await
+            }
+            .await;
+
+            match result {
+                Ok(registered) => {
+                    if registered.ephemeral {
+                        add_connection_instance(app, addr, (namespace_id, group_name, service_name, registered.instance_id))
+                            .await;
+                    }
+                    response(TYPE, request_id, json!({ "type": operation }))
+                }
+                Err(e) => error_response(TYPE, request_id, service_error_code(&e), &e),
+            }
+        }
+        "deRegisterInstance" => {
+            let result = async {
+                if let Some(existing) =
+                    find_instance(app, &namespace_id, &group_name, &service_name, &ip, port, &cluster_name).await?
+                {
+                    deregister_instance(app, &namespace_id, &group_name, &service_name, &existing.instance_id).await?;
+                    remove_connection_instance(
+                        app,
+                        addr,
+                        &(namespace_id.clone(), group_name.clone(), service_name.clone(), existing.instance_id),
+                    )
+                    .await;
+                }
+                Ok::<(), String>(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => response(TYPE, request_id, json!({ "type": operation })),
+                Err(e) => error_response(TYPE, request_id, service_error_code(&e), &e),
+            }
+        }
+        other => error_response(
+            TYPE,
+            request_id,
+            ERROR_CODE_INVALID_PARAM,
+            &format!("Unsupported instance request type: {}", other),
+        ),
+    }
+}
+
+/// 订阅或取消订阅服务，订阅后服务变更通过 NotifySubscriberRequest 推送
+async fn handle_subscribe_service(app: &AppHandle, addr: SocketAddr, request: &Value, request_id: &str) -> Payload {
+    const TYPE: &str = "SubscribeServiceResponse";
+    let (namespace_id, group_name, service_name) = naming_target(request);
+    let clusters = str_field(request, "clusters").to_string();
+    let subscribe = request.get("subscribe").and_then(Value::as_bool).unwrap_or(true);
+    if service_name.is_empty() {
+        return error_response(TYPE, request_id, ERROR_CODE_INVALID_PARAM, "serviceName is required");
+    }
+
+    let revision = get_service_revision(app, &namespace_id, &group_name, &service_name).await.revision;
+    let service_info = match build_service_info(app, &namespace_id, &group_name, &service_name, &clusters).await {
+        Ok(service_info) => service_info,
+        Err(e) => return error_response(TYPE, request_id, service_error_code(&e), &e),
+    };
+    set_subscription(
+        app,
+        addr,
+        (namespace_id, group_name, service_name, clusters),
+        subscribe.then_some(revision),
+    )
+    .await;
+
+    response(TYPE, request_id, json!({ "serviceInfo": service_info }))
+}
+
+/// 查询服务实例（不订阅）
+async fn handle_service_query(app: &AppHandle, request: &Value, request_id: &str) -> Payload {
+    const TYPE: &str = "QueryServiceResponse";
+    let (namespace_id, group_name, service_name) = naming_target(request);
+    let healthy_only = request.get("healthyOnly").and_then(Value::as_bool).unwrap_or(false);
+    if service_name.is_empty() {
+        return error_response(TYPE, request_id, ERROR_CODE_INVALID_PARAM, "serviceName is required");
+    }
+
+    match build_service_info(app, &namespace_id, &group_name, &service_name, str_field(request, "cluster")).await {
+        Ok(mut service_info) => {
+            if healthy_only {
+                if let Some(hosts) = service_info["hosts"].as_array_mut() {
+                    hosts.retain(|host| host["healthy"] == true);
+                }
+            }
+            response(TYPE, request_id, json!({ "serviceInfo": service_info }))
+        }
+        Err(e) => error_response(TYPE, request_id, service_error_code(&e), &e),
+    }
+}
@@ -0,0 +1,210 @@
+/**
+ * Nacos 2.x gRPC 协议服务模块
+ * 在主端口 + 1000（客户端端口，默认 9848）和主端口 + 1001（集群端口，默认 9849）上
+ * 提供 Nacos gRPC 服务（HTTP/2 明文）：
+ * - /Request/request：一元请求（服务检查、配置、实例注册、服务订阅等）
+ * - /BiRequestStream/requestBiStream：双向流，用于 ConnectionSetupRequest 和服务端推送
+ */
+
+mod connection;
+mod handler;
+mod payload;
+pub mod wire;
+
+pub use payload::{encode_frame, take_frame, Payload};
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::Response,
+    routing::post,
+    Router,
+};
+use http_body::Frame;
+use tauri::AppHandle;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use connection::{attach_stream, close_connection, start_push_task};
+use handler::handle_request;
+
+/// gRPC 客户端端口相对主端口的偏移（与 Nacos 一致）
+pub const GRPC_PORT_OFFSET: u16 = 1000;
+
+/// gRPC 集群端口相对主端口的偏移（与 Nacos 一致）
+pub const GRPC_CLUSTER_PORT_OFFSET: u16 = 1001;
+
+/// gRPC 状态码
+pub const GRPC_STATUS_OK: &str = "0";
+const GRPC_STATUS_INVALID_ARGUMENT: &str = "3";
+
+/// gRPC 响应体：从通道读取数据帧和尾部帧（grpc-status 通过 HTTP/2 trailers 返回）
+pub struct GrpcBody {
+    rx: mpsc::UnboundedReceiver<Frame<Bytes>>,
+}
+
+impl GrpcBody {
+    /// 创建响应体和对应的发送端，发送端全部释放后响应结束
+    pub fn channel() -> (mpsc::UnboundedSender<Frame<Bytes>>, Self) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (tx, Self { rx })
+    }
+}
+
+impl http_body::Body for GrpcBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        self.rx.poll_recv(cx).map(|frame| frame.map(Ok))
+    }
+}
+
+/// 创建 gRPC 状态尾部帧
+pub fn status_trailers(status: &str, message: Option<&str>) -> Frame<Bytes> {
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", HeaderValue::from_static(status));
+    if let Some(message) = message.and_then(|m| HeaderValue::from_str(&urlencoding::encode(m)).ok()) {
+        trailers.insert("grpc-message", message);
+    }
+    Frame::trailers(trailers)
+}
+
+/// 创建 gRPC 响应
+pub fn grpc_response(body: GrpcBody) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/grpc")
+        .body(Body::new(body))
+        .unwrap()
+}
+
+/// 创建 gRPC 路由
+pub fn create_grpc_router(app: Arc<AppHandle>) -> Router {
+    Router::new()
+        .route("/Request/request", post(unary_request))
+        .route("/BiRequestStream/requestBiStream", post(bi_request_stream))
+        .with_state(app)
+}
+
+/// 一元请求
+/// POST /Request/request
+pub async fn unary_request(
+    State(app): State<Arc<AppHandle>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    body: Bytes,
+) -> Response {
+    let (tx, body_stream) = GrpcBody::channel();
+    let mut buf = body.to_vec();
+    let request = take_frame(&mut buf).and_then(|frame| {
+        frame
+            .ok_or_else(|| "Missing gRPC message".to_string())
+            .and_then(|message| Payload::decode(&message))
+    });
+
+    match request {
+        Ok(request) => {
+            let response = handle_request(&app, addr, &request).await;
+            let _ = tx.send(Frame::data(Bytes::from(encode_frame(&response.encode()))));
+            let _ = tx.send(status_trailers(GRPC_STATUS_OK, None));
+        }
+        Err(e) => {
+            let _ = tx.send(status_trailers(GRPC_STATUS_INVALID_ARGUMENT, Some(&e)));
+        }
+    }
+    grpc_response(body_stream)
+}
+
+/// 双向流
+/// POST /BiRequestStream/requestBiStream
+/// 客户端首先发送 ConnectionSetupRequest，之后服务端通过该流推送变更通知；
+/// 客户端对推送的响应只做记录。流结束即连接断开，注销该连接注册的临时实例
+pub async fn bi_request_stream(
+    State(app): State<Arc<AppHandle>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    body: Body,
+) -> Response {
+    let (tx, body_stream) = GrpcBody::channel();
+    let app = app.as_ref().clone();
+
+    tokio::spawn(async move {
+        let mut body = body;
+        let mut buf = Vec::new();
+        let mut push_task: Option<JoinHandle<()>> = None;
+
+        'stream: while let Some(frame) = std::future::poll_fn(|cx| http_body::Body::poll_frame(Pin::new(&mut body), cx)).await {
+            let frame = match frame {
+                Ok(frame) => frame,
+                Err(e) => {
+                    tracing::debug!("gRPC stream {} interrupted: {}", addr, e);
+                    break;
+                }
+            };
+            let Ok(data) = frame.into_data() else {
+                continue;
+            };
+            buf.extend_from_slice(&data);
+
+            loop {
+                let message = match take_frame(&mut buf) {
+                    Ok(Some(message)) => message,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!("Invalid gRPC stream message from {}: {}", addr, e);
+                        break 'stream;
+                    }
+                };
+                let payload = match Payload::decode(&message) {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        tracing::warn!("Invalid gRPC payload from {}: {}", addr, e);
+                        continue;
+                    }
+                };
+
+                if payload.r#type == "ConnectionSetupRequest" {
+                    let setup = payload.json().unwrap_or_default();
+                    let labels = &setup["labels"];
+                    let app_name = labels["AppName"].as_str().or_else(|| labels["app"].as_str()).unwrap_or("-");
+                    attach_stream(&app, addr, tx.clone(), setup["clientVersion"].as_str().unwrap_or(""), app_name).await;
+                    if push_task.is_none() {
+                        push_task = Some(start_push_task(app.clone(), addr));
+                    }
+                } else {
+                    tracing::debug!("Received {} from gRPC stream {}", payload.r#type, addr);
+                }
+            }
+        }
+
+        if let Some(push_task) = push_task {
+            push_task.abort();
+        }
+        close_connection(&app, addr).await;
+        let _ = tx.send(status_trailers(GRPC_STATUS_OK, None));
+    });
+
+    grpc_response(body_stream)
+}
+
+/// 启动 gRPC 服务，返回服务任务
+pub async fn start_grpc_server(app: AppHandle, port: u16) -> Result<JoinHandle<()>, String> {
+    let addr = format!("127.0.0.1:{}", port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(|e| format!("无法绑定 gRPC 端口 {}: {}", port, e))?;
+    let router = create_grpc_router(Arc::new(app));
+
+    tracing::info!("Nacos gRPC server listening on {}", addr);
+    Ok(tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>()).await {
+            tracing::error!("gRPC server error: {}", e);
+        }
+    }))
+}
@@ -0,0 +1,139 @@
+/**
+ * Nacos gRPC 消息编解码模块
+ * Nacos 2.x 的所有请求和响应都封装在同一个 Payload 消息中：
+ * metadata.type 为请求 / 响应类名，body 为 google.protobuf.Any，其 value 是 JSON 字节；
+ * 这里手写 Payload 的 protobuf 编解码和 gRPC 长度前缀帧
+ *
+ * message Metadata { string type = 3; string clientIp = 8; map<string, string> headers = 7; }
+ * message Payload { Metadata metadata = 2; google.protobuf.Any body = 3; }
+ */
+
+use std::collections::HashMap;
+
+use super::wire::{read_fields, write_bytes_field, write_string_field, FieldValue};
+
+/// gRPC 帧头长度（1 字节压缩标志 + 4 字节大端长度）
+const FRAME_HEADER_LEN: usize = 5;
+
+/// 单条消息最大长度（与 Nacos 默认的 10MB 一致）
+pub const MAX_MESSAGE_LEN: usize = 10 * 1024 * 1024;
+
+/// Nacos gRPC 消息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Payload {
+    /// 请求或响应类名，如 ConfigQueryRequest
+    pub r#type: String,
+    pub client_ip: String,
+    pub headers: HashMap<String, String>,
+    /// JSON 格式的消息体
+    pub body: Vec<u8>,
+}
+
+impl Payload {
+    /// 由类名和 JSON 消息体创建
+    pub fn new(r#type: &str, body: &serde_json::Value) -> Self {
+        Self {
+            r#type: r#type.to_string(),
+            body: body.to_string().into_bytes(),
+            ..Default::default()
+        }
+    }
+
+    /// 解析 JSON 消息体（为空时返回空对象）
+    pub fn json(&self) -> Result<serde_json::Value, String> {
+        if self.body.is_empty() {
+            return Ok(serde_json::json!({}));
+        }
+        serde_json::from_slice(&self.body).map_err(|e| format!("Invalid payload body: {}", e))
+    }
+
+    /// 编码为 protobuf 字节
+    pub fn encode(&self) -> Vec<u8> {
+        let mut metadata = Vec::new();
+        write_string_field(&mut metadata, 3, &self.r#type);
+        let mut headers: Vec<(&String, &String)> = self.headers.iter().collect();
+        headers.sort();
+        for (key, value) in headers {
+            let mut entry = Vec::new();
+            write_string_field(&mut entry, 1, key);
+            write_string_field(&mut entry, 2, value);
+            write_bytes_field(&mut metadata, 7, &entry);
+        }
+        write_string_field(&mut metadata, 8, &self.client_ip);
+
+        let mut any = Vec::new();
+        write_bytes_field(&mut any, 2, &self.body);
+
+        let mut payload = Vec::new();
+        write_bytes_field(&mut payload, 2, &metadata);
+        write_bytes_field(&mut payload, 3, &any);
+        payload
+    }
+
+    /// 从 protobuf 字节解码，未知字段被忽略
+    pub fn decode(data: &[u8]) -> Result<Self, String> {
+        let mut payload = Payload::default();
+        for (field, value) in read_fields(data)? {
+            match (field, value) {
+                (2, FieldValue::Bytes(metadata)) => {
+                    for (field, value) in read_fields(metadata)? {
+                        match (field, value) {
+                            (3, v) => payload.r#type = v.string()?,
+                            (8, v) => payload.client_ip = v.string()?,
+                            (7, FieldValue::Bytes(entry)) => {
+                                let (mut key, mut value) = (String::new(), String::new());
+                                for (field, v) in read_fields(entry)? {
+                                    match field {
+                                        1 => key = v.string()?,
+                                        2 => value = v.string()?,
+                                        _ => {}
+                                    }
+                                }
+                                payload.headers.insert(key, value);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                (3, FieldValue::Bytes(any)) => {
+                    for (field, value) in read_fields(any)? {
+                        if let (2, FieldValue::Bytes(v)) = (field, value) {
+                            payload.body = v.to_vec();
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(payload)
+    }
+}
+
+/// 编码 gRPC 帧（不压缩）
+pub fn encode_frame(message: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + message.len());
+    frame.push(0);
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(message);
+    frame
+}
+
+/// 从缓冲区中取出一个完整的 gRPC 帧，数据不足时返回 None
+pub fn take_frame(buf: &mut Vec<u8>) -> Result<Option<Vec<u8>>, String> {
+    if buf.len() < FRAME_HEADER_LEN {
+        return Ok(None);
+    }
+    if buf[0] != 0 {
+        return Err("Compressed gRPC messages are not supported".to_string());
+    }
+    let len = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]) as usize;
+    if len > MAX_MESSAGE_LEN {
+        return Err(format!("gRPC message too large: {} bytes", len));
+    }
+    if buf.len() < FRAME_HEADER_LEN + len {
+        return Ok(None);
+    }
+    let message = buf[FRAME_HEADER_LEN..FRAME_HEADER_LEN + len].to_vec();
+    buf.drain(..FRAME_HEADER_LEN + len);
+    Ok(Some(message))
+}
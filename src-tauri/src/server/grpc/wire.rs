@@ -0,0 +1,107 @@
+/**
+ * protobuf 线格式编解码
+ * 手写的最小实现：按字段号读写变长整数和长度前缀字段，
+ * 供 Nacos Payload 和 xDS 消息共用
+ */
+
+/// protobuf 线类型
+const WIRE_VARINT: u64 = 0;
+const WIRE_FIXED64: u64 = 1;
+const WIRE_LEN: u64 = 2;
+const WIRE_FIXED32: u64 = 5;
+
+/// 字段值（固定长度字段只跳过不保留）
+pub enum FieldValue<'a> {
+    Varint(u64),
+    Fixed,
+    Bytes(&'a [u8]),
+}
+
+impl<'a> FieldValue<'a> {
+    /// 读取字符串值，类型不符或不是合法 UTF-8 时返回错误
+    pub fn string(&self) -> Result<String, String> {
+        match self {
+            FieldValue::Bytes(bytes) => {
+                String::from_utf8(bytes.to_vec()).map_err(|_| "Invalid UTF-8 string in message".to_string())
+            }
+            _ => Err("Expected length-delimited field".to_string()),
+        }
+    }
+}
+
+pub fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// 写入长度前缀字段（嵌套消息、字节串），空内容也会写入
+pub fn write_bytes_field(buf: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    write_varint(buf, (field << 3) | WIRE_LEN);
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+/// 写入字符串字段，空字符串按 proto3 规则省略
+pub fn write_string_field(buf: &mut Vec<u8>, field: u64, value: &str) {
+    if !value.is_empty() {
+        write_bytes_field(buf, field, value.as_bytes());
+    }
+}
+
+/// 写入变长整数字段（整数、枚举、布尔），0 按 proto3 规则省略
+pub fn write_varint_field(buf: &mut Vec<u8>, field: u64, value: u64) {
+    if value != 0 {
+        write_varint(buf, (field << 3) | WIRE_VARINT);
+        write_varint(buf, value);
+    }
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *data.get(*pos).ok_or("Truncated varint")?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err("Varint too long".to_string())
+}
+
+/// 读取消息的所有字段
+pub fn read_fields(data: &[u8]) -> Result<Vec<(u64, FieldValue<'_>)>, String> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let key = read_varint(data, &mut pos)?;
+        let field = key >> 3;
+        let value = match key & 0x7 {
+            WIRE_VARINT => FieldValue::Varint(read_varint(data, &mut pos)?),
+            WIRE_LEN => {
+                let len = read_varint(data, &mut pos)? as usize;
+                let end = pos.checked_add(len).filter(|end| *end <= data.len()).ok_or("Truncated field")?;
+                let bytes = &data[pos..end];
+                pos = end;
+                FieldValue::Bytes(bytes)
+            }
+            WIRE_FIXED64 => {
+                pos += 8;
+                FieldValue::Fixed
+            }
+            WIRE_FIXED32 => {
+                pos += 4;
+                FieldValue::Fixed
+            }
+            wire_type => return Err(format!("Unsupported wire type {}", wire_type)),
+        };
+        if pos > data.len() {
+            return Err("Truncated field".to_string());
+        }
+        fields.push((field, value));
+    }
+    Ok(fields)
+}
@@ -0,0 +1,252 @@
+/**
+ * 服务目录处理器
+ * 实现控制台服务目录 API（带集群数、实例数、健康实例数和保护阈值触发标记）
+ */
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::service::{
+    list_catalog_services as list_catalog_services_impl,
+    list_catalog_instances as list_catalog_instances_impl,
+    get_catalog_service as get_catalog_service_impl,
+    get_service_exports as get_service_exports_impl,
+    CatalogServiceQuery, CatalogInstanceQuery,
+};
+
+/// 服务目录列表参数
+#[derive(Debug, Deserialize)]
+pub struct CatalogServicesParams {
+    #[serde(default)]
+    pub pageNo: Option<String>,
+    #[serde(default)]
+    pub pageSize: Option<String>,
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupNameParam: Option<String>, // 服务组模糊匹配
+    #[serde(default)]
+    pub serviceNameParam: Option<String>, // 服务名模糊匹配
+    #[serde(default)]
+    pub hasIpCount: Option<String>, // 只返回有实例的服务，默认 "false"
+}
+
+/// 服务目录实例列表参数
+#[derive(Debug, Deserialize)]
+pub struct CatalogInstancesParams {
+    pub serviceName: String,
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub clusterName: Option<String>,
+    #[serde(default)]
+    pub pageNo: Option<String>,
+    #[serde(default)]
+    pub pageSize: Option<String>,
+}
+
+/// 服务目录详情参数
+#[derive(Debug, Deserialize)]
+pub struct CatalogServiceParams {
+    pub serviceName: String,
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+}
+
+/// 解析分页参数
+fn parse_page(value: Option<&str>) -> Result<Option<i64>, axum::http::StatusCode> {
+    match value.filter(|s| !s.is_empty()) {
+        Some(s) => s.parse::<i64>().map(Some).map_err(|_| axum::http::StatusCode::BAD_REQUEST),
+        None => Ok(None),
+    }
+}
+
+/// 分页查询服务目录
+/// GET /nacos/v1/ns/catalog/services
+/// 可选参数: pageNo, pageSize, namespaceId, groupNameParam, serviceNameParam, hasIpCount
+/// 响应: { count, serviceList: [{ name, groupName, clusterCount, ipCount, healthyInstanceCount, triggerFlag }] }
+pub async fn list_catalog_services(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<CatalogServicesParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let query = CatalogServiceQuery {
+        namespace_id: Some(namespace_id),
+        group_name: params.groupNameParam,
+        service_name: params.serviceNameParam,
+        has_ip_count: params.hasIpCount.as_deref()
+            .map(|s| s == "true" || s == "True" || s == "1")
+            .unwrap_or(false),
+        page_no: parse_page(params.pageNo.as_deref())?,
+        page_size: parse_page(params.pageSize.as_deref())?,
+    };
+
+    let response = list_catalog_services_impl(&app, query).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let service_list: Vec<serde_json::Value> = response.page_items
+        .iter()
+        .map(|service| {
+            serde_json::json!({
+                "name": service.service_name,
+                "groupName": service.group_name,
+                "clusterCount": service.cluster_count,
+                "ipCount": service.ip_count,
+                "healthyInstanceCount": service.healthy_instance_count,
+                "triggerFlag": service.trigger_flag
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "count": response.total_count,
+        "serviceList": service_list
+    })))
+}
+
+/// 分页查询服务实例
+/// GET /nacos/v1/ns/catalog/instances
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, clusterName, pageNo, pageSize
+/// 响应: { count, list: [实例] }
+pub async fn list_catalog_instances(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<CatalogInstancesParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    let query = CatalogInstanceQuery {
+        namespace_id,
+        group_name,
+        service_name: params.serviceName,
+        cluster_name: params.clusterName,
+        page_no: parse_page(params.pageNo.as_deref())?,
+        page_size: parse_page(params.pageSize.as_deref())?,
+    };
+
+    let response = list_catalog_instances_impl(&app, query).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let list: Vec<serde_json::Value> = response.page_items
+        .iter()
+        .map(|inst| {
+            serde_json::json!({
+                "instanceId": inst.instance_id,
+                "ip": inst.ip,
+                "port": inst.port,
+                "weight": inst.weight,
+                "healthy": inst.healthy,
+                "enabled": inst.enabled,
+                "ephemeral": inst.ephemeral,
+                "clusterName": inst.cluster_name,
+                "serviceName": inst.service_name,
+                "metadata": inst.metadata.as_ref()
+                    .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                    .unwrap_or(serde_json::json!({}))
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "count": response.total_count,
+        "list": list
+    })))
+}
+
+/// 查询服务目录详情
+/// GET /nacos/v1/ns/catalog/service
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName
+/// 响应: { service: { name, groupName, protectThreshold, selector, metadata, clusterCount, ipCount, healthyInstanceCount, triggerFlag }, clusters: [...], exports: [导出目标] }
+pub async fn get_catalog_service(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<CatalogServiceParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    let detail = get_catalog_service_impl(&app, &namespace_id, &group_name, &params.serviceName).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
+
+    let exports: Vec<serde_json::Value> = get_service_exports_impl(&app, &namespace_id, &group_name, &params.serviceName).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+        .iter()
+        .map(|export| {
+            serde_json::json!({
+                "namespaceId": export.target_namespace_id,
+                "groupName": export.target_group_name,
+                "serviceName": export.target_service_name
+            })
+        })
+        .collect();
+
+    let clusters: Vec<serde_json::Value> = detail.clusters
+        .iter()
+        .map(|cluster| {
+            serde_json::json!({
+                "name": cluster.cluster_name,
+                "serviceName": cluster.service_name,
+                "healthChecker": serde_json::from_str::<serde_json::Value>(&cluster.health_checker)
+                    .unwrap_or(serde_json::json!({ "type": "NONE" })),
+                "metadata": cluster.metadata.as_ref()
+                    .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                    .unwrap_or(serde_json::json!({}))
+            })
+        })
+        .collect();
+
+    let service = &detail.service;
+    Ok(Json(serde_json::json!({
+        "service": {
+            "name": service.service_name,
+            "groupName": service.group_name,
+            "protectThreshold": service.protect_threshold,
+            "selector": service.selector.as_deref()
+                .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                .unwrap_or(serde_json::json!({ "type": "none" })),
+            "metadata": service.metadata.as_ref()
+                .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                .unwrap_or(serde_json::json!({})),
+            "clusterCount": detail.summary.cluster_count,
+            "ipCount": detail.summary.ip_count,
+            "healthyInstanceCount": detail.summary.healthy_instance_count,
+            "triggerFlag": detail.summary.trigger_flag
+        },
+        "clusters": clusters,
+        "exports": exports
+    })))
+}
@@ -0,0 +1,96 @@
+/**
+ * 集群管理处理器
+ * 实现集群管理相关 API
+ */
+
+use axum::{
+    extract::State,
+    response::Response,
+};
+use axum_extra::extract::Form;
+use serde::Deserialize;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::service::{
+    update_cluster as update_cluster_impl,
+    UpdateClusterRequest,
+};
+
+/// 更新集群参数（Nacos API 格式）
+#[derive(Debug, Deserialize)]
+pub struct UpdateClusterParams {
+    pub serviceName: String,
+    pub clusterName: String,
+    #[serde(default)]
+    pub namespaceId: String, // 命名空间，默认 "public"
+    #[serde(default)]
+    pub groupName: String, // 服务组，默认 "DEFAULT_GROUP"
+    #[serde(default)]
+    pub healthChecker: Option<String>, // 健康检查器 JSON，例如 {"type":"HTTP","path":"/health"}
+    #[serde(default)]
+    pub metadata: Option<String>, // 集群元数据 JSON
+    #[serde(default)]
+    pub checkPort: Option<String>, // 检查端口
+    #[serde(default)]
+    pub useInstancePort4Check: Option<String>, // 是否使用实例端口做健康检查
+}
+
+/// 更新集群
+/// PUT /nacos/v1/ns/cluster
+/// 必需参数: serviceName, clusterName
+/// 可选参数: namespaceId, groupName, healthChecker, metadata, checkPort, useInstancePort4Check
+/// 响应: "ok"（成功）
+pub async fn update_cluster(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<UpdateClusterParams>,
+) -> Result<Response, axum::http::StatusCode> {
+    // 处理命名空间和服务组
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    // 解析检查端口
+    let check_port = match params.checkPort.as_deref().filter(|s| !s.is_empty()) {
+        Some(s) => Some(s.parse::<i32>().map_err(|_| axum::http::StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+
+    let use_instance_port = params.useInstancePort4Check.as_deref()
+        .map(|s| s == "true" || s == "True" || s == "1");
+
+    let request = UpdateClusterRequest {
+        namespace_id,
+        group_name,
+        service_name: params.serviceName,
+        cluster_name: params.clusterName,
+        health_checker: params.healthChecker.filter(|s| !s.is_empty()),
+        check_port,
+        use_instance_port,
+        metadata: params.metadata.filter(|s| !s.is_empty()),
+    };
+
+    match update_cluster_impl(&app, request).await {
+        Ok(_) => Ok(Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .body(axum::body::Body::from("ok"))
+            .unwrap()),
+        Err(e) => {
+            if e.contains("not found") {
+                Err(axum::http::StatusCode::NOT_FOUND)
+            } else if e.contains("Invalid") {
+                Err(axum::http::StatusCode::BAD_REQUEST)
+            } else {
+                Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+}
@@ -18,6 +18,7 @@ use urlencoding::decode as url_decode;
 
 use crate::config::{
     get_config_detail, create_config, update_config, delete_config, get_config_history,
+    record_config_subscriber as record_config_subscriber_impl,
     CreateConfigRequest, UpdateConfigRequest,
 };
 
@@ -298,50 +299,6 @@ fn format_changed_configs(changed_configs: &[ConfigListenState]) -> String {
     result
 }
 
-/// 记录订阅者信息
-async fn record_subscriber(
-    app: &Arc<AppHandle>,
-    data_id: &str,
-    group_id: &str,
-    tenant_id: &str,
-    client_ip: &str,
-    client_port: Option<i32>,
-    user_agent: Option<&str>,
-    app_name: Option<&str>,
-    md5: &str,
-) -> Result<(), String> {
-    let db = app
-        .sqlite_plugin()
-        .get_connection("sqlite:nacos.db")
-        .await
-        .map_err(|e| format!("Failed to get database connection: {}", e))?;
-
-    let current_time = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
-
-    // 使用 INSERT OR REPLACE 来更新或插入订阅者信息
-    db.execute(
-        "INSERT OR REPLACE INTO subscribers (data_id, group_id, tenant_id, client_ip, client_port, user_agent, app_name, md5, last_poll_time, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, COALESCE((SELECT created_at FROM subscribers WHERE data_id = ?1 AND group_id = ?2 AND tenant_id = ?3 AND client_ip = ?4 AND client_port = ?5), ?9))",
-        &[
-            ("?1", data_id),
-            ("?2", group_id),
-            ("?3", tenant_id),
-            ("?4", client_ip),
-            ("?5", &client_port.map(|p| p.to_string()).unwrap_or_default()),
-            ("?6", user_agent.unwrap_or("")),
-            ("?7", app_name.unwrap_or("")),
-            ("?8", md5),
-            ("?9", &current_time.to_string()),
-        ],
-    )
-    .await
-    .map_err(|e| format!("Failed to record subscriber: {}", e))?;
-
-    Ok(())
-}
-
 /// 获取配置
 /// GET /nacos/v1/cs/configs
 /// 必需参数: dataId, group（当 search 参数不存在时）
@@ -791,7 +748,7 @@ pub async fn listen_config(
             config.tenant.clone()
         };
         
-        let _ = record_subscriber(
+        let _ = record_config_subscriber_impl(
             &app,
             &config.data_id,
             &config.group,
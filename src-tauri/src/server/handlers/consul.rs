@@ -0,0 +1,453 @@
+/**
+ * Consul 兼容 API 处理器
+ * 实现 Consul HTTP API 的子集：agent 服务注册与注销、TTL 检查、服务目录、
+ * 健康服务查询（支持 index / wait 阻塞查询）以及基于配置管理的 KV 存储；
+ * Consul 客户端不支持路径前缀，这些路由挂载在服务器根路径
+ */
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::Response,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::config::{delete_kv as delete_kv_impl, get_kv_entries as get_kv_entries_impl, put_kv as put_kv_impl};
+use crate::service::{
+    deregister_consul_service as deregister_consul_service_impl,
+    get_consul_catalog_services as get_consul_catalog_services_impl,
+    get_consul_service_index as get_consul_service_index_impl,
+    get_consul_service_instances as get_consul_service_instances_impl,
+    register_consul_service as register_consul_service_impl,
+    update_consul_check as update_consul_check_impl,
+    consul_check_id, consul_check_status, consul_service_id, consul_tags, consul_user_metadata,
+    ConsulServiceRegistration, ConsulTtlCheck, CONSUL_CHECK_CRITICAL, CONSUL_CHECK_PASSING,
+};
+
+/// 节点名（所有服务都注册在本节点上）
+const CONSUL_NODE_NAME: &str = "nacosdesk";
+
+/// 数据中心名
+const CONSUL_DATACENTER: &str = "dc1";
+
+/// 默认阻塞等待时间（与 Consul 一致为 5 分钟）
+const DEFAULT_BLOCKING_WAIT: Duration = Duration::from_secs(300);
+
+/// 最长阻塞等待时间（与 Consul 一致为 10 分钟）
+const MAX_BLOCKING_WAIT: Duration = Duration::from_secs(600);
+
+/// 阻塞查询检查间隔
+const BLOCKING_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 服务注册请求体
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AgentServiceRegistration {
+    #[serde(rename = "ID", default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub port: Option<i32>,
+    #[serde(default)]
+    pub meta: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub check: Option<AgentCheckRegistration>,
+    #[serde(default)]
+    pub checks: Option<Vec<AgentCheckRegistration>>,
+}
+
+/// 检查注册信息（只支持 TTL 检查，其他类型的检查被忽略）
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AgentCheckRegistration {
+    #[serde(rename = "CheckID", default)]
+    pub check_id: Option<String>,
+    #[serde(rename = "TTL", default)]
+    pub ttl: Option<String>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub deregister_critical_service_after: Option<String>,
+}
+
+/// 解析 Consul 时长（如 "500ms"、"10s"、"1m"、"1h"，不带单位时按秒计算）
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let millis = match unit {
+        "ms" => number,
+        "" | "s" => number * 1000.0,
+        "m" => number * 60_000.0,
+        "h" => number * 3_600_000.0,
+        _ => return None,
+    };
+    Some(Duration::from_millis(millis as u64))
+}
+
+/// Base64 编码（KV 值在 JSON 响应中以 Base64 表示）
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        encoded.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        encoded.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        encoded.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        encoded.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    encoded
+}
+
+/// 查询参数是否启用（如 ?passing 或 ?passing=true）
+fn flag_enabled(params: &HashMap<String, String>, name: &str) -> bool {
+    params.get(name).is_some_and(|v| v.is_empty() || v == "true" || v == "1")
+}
+
+/// 带 Consul 索引头的 JSON 响应
+fn consul_response(index: u64, body: serde_json::Value) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("X-Consul-Index", index.to_string())
+        .header("X-Consul-KnownLeader", "true")
+        .header("X-Consul-LastContact", "0")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// 纯文本响应
+fn text_response(status: StatusCode, text: &str) -> Response {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Body::from(text.to_string()))
+        .unwrap()
+}
+
+/// 服务层错误转换为状态码
+fn service_error(e: String) -> StatusCode {
+    if e.contains("is disabled") {
+        StatusCode::FORBIDDEN
+    } else {
+        tracing::error!("Consul request failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// 阻塞查询：请求携带 index 时等待索引大于该值或等待超时，返回当前索引
+async fn wait_for_index<F, Fut>(params: &HashMap<String, String>, mut current_index: F) -> Result<u64, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<u64, String>>,
+{
+    let index = current_index().await?;
+    let Some(requested) = params.get("index").and_then(|i| i.parse::<u64>().ok()).filter(|i| *i > 0) else {
+        return Ok(index);
+    };
+    let wait = params
+        .get("wait")
+        .and_then(|w| parse_duration(w))
+        .unwrap_or(DEFAULT_BLOCKING_WAIT)
+        .min(MAX_BLOCKING_WAIT);
+
+    let start_time = std::time::Instant::now();
+    let mut index = index;
+    while index <= requested && start_time.elapsed() < wait {
+        tokio::time::sleep(BLOCKING_CHECK_INTERVAL.min(wait.saturating_sub(start_time.elapsed()))).await;
+        index = current_index().await?;
+    }
+    Ok(index)
+}
+
+/// 注册服务
+/// PUT /v1/agent/service/register
+/// 请求体: {"ID", "Name", "Tags", "Address", "Port", "Meta", "Check": {"CheckID", "TTL", "Status", "DeregisterCriticalServiceAfter"}}
+/// 响应: 200（成功）
+pub async fn register_service(
+    State(app): State<Arc<AppHandle>>,
+    body: Bytes,
+) -> Result<Response, StatusCode> {
+    let request: AgentServiceRegistration = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => return Ok(text_response(StatusCode::BAD_REQUEST, &format!("Request decode failed: {}", e))),
+    };
+    if request.name.trim().is_empty() {
+        return Ok(text_response(StatusCode::BAD_REQUEST, "Missing service name"));
+    }
+
+    let service_id = request.id.filter(|id| !id.is_empty()).unwrap_or_else(|| request.name.clone());
+
+    // 只支持一个 TTL 检查：取 Check 或 Checks 中的第一个 TTL 检查
+    let checks: Vec<(Option<String>, AgentCheckRegistration)> = request
+        .check
+        .into_iter()
+        .map(|check| (Some(format!("service:{}", service_id)), check))
+        .chain(
+            request
+                .checks
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .map(|(i, check)| (Some(format!("service:{}:{}", service_id, i + 1)), check)),
+        )
+        .collect();
+    let mut ttl_check = None;
+    for (default_id, check) in checks {
+        let Some(ttl) = check.ttl.as_deref() else {
+            continue;
+        };
+        let ttl = parse_duration(ttl).ok_or(StatusCode::BAD_REQUEST)?;
+        let deregister_after = match check.deregister_critical_service_after.as_deref() {
+            Some(after) => Some(parse_duration(after).ok_or(StatusCode::BAD_REQUEST)?.as_millis() as i64),
+            None => None,
+        };
+        ttl_check = Some(ConsulTtlCheck {
+            check_id: check.check_id.filter(|id| !id.is_empty()).or(default_id).unwrap_or_default(),
+            ttl_millis: (ttl.as_millis() as i64).max(1),
+            deregister_after_millis: deregister_after,
+            status: check.status.unwrap_or_else(|| CONSUL_CHECK_CRITICAL.to_string()),
+        });
+        break;
+    }
+
+    let registration = ConsulServiceRegistration {
+        service_id,
+        service_name: request.name,
+        address: request.address.filter(|a| !a.is_empty()).unwrap_or_else(|| "127.0.0.1".to_string()),
+        port: request.port.unwrap_or(0),
+        tags: request.tags.unwrap_or_default(),
+        meta: request.meta.unwrap_or_default(),
+        check: ttl_check,
+    };
+
+    register_consul_service_impl(&app, registration).await.map_err(service_error)?;
+    Ok(text_response(StatusCode::OK, ""))
+}
+
+/// 注销服务
+/// PUT /v1/agent/service/deregister/{serviceId}
+/// 响应: 200（成功），404（服务不存在）
+pub async fn deregister_service(
+    State(app): State<Arc<AppHandle>>,
+    Path(service_id): Path<String>,
+) -> Result<Response, StatusCode> {
+    if deregister_consul_service_impl(&app, &service_id).await.map_err(service_error)? {
+        Ok(text_response(StatusCode::OK, ""))
+    } else {
+        Ok(text_response(StatusCode::NOT_FOUND, &format!("Unknown service ID {:?}", service_id)))
+    }
+}
+
+/// TTL 检查通过
+/// PUT /v1/agent/check/pass/{checkId}
+pub async fn pass_check(
+    State(app): State<Arc<AppHandle>>,
+    Path(check_id): Path<String>,
+) -> Result<Response, StatusCode> {
+    update_check(&app, &check_id, true).await
+}
+
+/// TTL 检查失败
+/// PUT /v1/agent/check/fail/{checkId}
+pub async fn fail_check(
+    State(app): State<Arc<AppHandle>>,
+    Path(check_id): Path<String>,
+) -> Result<Response, StatusCode> {
+    update_check(&app, &check_id, false).await
+}
+
+/// 更新 TTL 检查状态
+async fn update_check(app: &AppHandle, check_id: &str, passing: bool) -> Result<Response, StatusCode> {
+    if update_consul_check_impl(app, check_id, passing).await.map_err(service_error)? {
+        Ok(text_response(StatusCode::OK, ""))
+    } else {
+        Ok(text_response(StatusCode::NOT_FOUND, &format!("Unknown check ID {:?}", check_id)))
+    }
+}
+
+/// 查询服务目录
+/// GET /v1/catalog/services
+/// 可选参数: index, wait（阻塞查询）
+/// 响应: {"服务名": ["标签"]}
+pub async fn catalog_services(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, StatusCode> {
+    let app_ref: &AppHandle = &app;
+    let index = wait_for_index(&params, || async move {
+        get_consul_catalog_services_impl(app_ref).await.map(|(index, _)| index)
+    })
+    .await
+    .map_err(service_error)?;
+
+    let (_, services) = get_consul_catalog_services_impl(&app).await.map_err(service_error)?;
+    Ok(consul_response(index, json!(services)))
+}
+
+/// 查询服务的健康实例
+/// GET /v1/health/service/{serviceName}
+/// 可选参数: passing（只返回检查通过的实例）, tag, index, wait（阻塞查询）
+pub async fn health_service(
+    State(app): State<Arc<AppHandle>>,
+    Path(service_name): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, StatusCode> {
+    let (app_ref, name): (&AppHandle, &str) = (&app, &service_name);
+    let index = wait_for_index(&params, || async move { Ok(get_consul_service_index_impl(app_ref, name).await) })
+        .await
+        .map_err(service_error)?;
+
+    let passing_only = flag_enabled(&params, "passing");
+    let tag = params.get("tag").filter(|t| !t.is_empty());
+    let instances = get_consul_service_instances_impl(&app, &service_name)
+        .await
+        .map_err(service_error)?;
+
+    let entries: Vec<serde_json::Value> = instances
+        .iter()
+        .filter(|inst| !passing_only || consul_check_status(inst) == CONSUL_CHECK_PASSING)
+        .filter(|inst| tag.map_or(true, |tag| consul_tags(inst).contains(tag)))
+        .map(|inst| {
+            let service_id = consul_service_id(inst);
+            let status = consul_check_status(inst);
+            let service_check_id = consul_check_id(inst).unwrap_or_else(|| format!("service:{}", service_id));
+            json!({
+                "Node": {
+                    "ID": "",
+                    "Node": CONSUL_NODE_NAME,
+                    "Address": "127.0.0.1",
+                    "Datacenter": CONSUL_DATACENTER,
+                    "TaggedAddresses": null,
+                    "Meta": {},
+                },
+                "Service": {
+                    "ID": service_id,
+                    "Service": inst.service_name,
+                    "Tags": consul_tags(inst),
+                    "Address": inst.ip,
+                    "Meta": consul_user_metadata(inst),
+                    "Port": inst.port,
+                    "Weights": { "Passing": inst.weight.round().max(1.0) as i64, "Warning": 1 },
+                    "EnableTagOverride": false,
+                },
+                "Checks": [
+                    {
+                        "Node": CONSUL_NODE_NAME,
+                        "CheckID": "serfHealth",
+                        "Name": "Serf Health Status",
+                        "Status": CONSUL_CHECK_PASSING,
+                        "ServiceID": "",
+                        "ServiceName": "",
+                    },
+                    {
+                        "Node": CONSUL_NODE_NAME,
+                        "CheckID": service_check_id,
+                        "Name": format!("Service '{}' check", inst.service_name),
+                        "Status": status,
+                        "ServiceID": service_id,
+                        "ServiceName": inst.service_name,
+                        "Type": "ttl",
+                    },
+                ],
+            })
+        })
+        .collect();
+
+    Ok(consul_response(index, json!(entries)))
+}
+
+/// 读取键值对
+/// GET /v1/kv/{key}
+/// 可选参数: recurse（前缀查询）, keys（只返回键名）, raw（返回原始值）
+pub async fn get_kv(
+    State(app): State<Arc<AppHandle>>,
+    Path(key): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, StatusCode> {
+    let keys_only = params.contains_key("keys");
+    let recurse = keys_only || flag_enabled(&params, "recurse");
+    let entries = get_kv_entries_impl(&app, &key, recurse).await.map_err(service_error)?;
+    if entries.is_empty() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let index = entries.iter().map(|e| e.modify_index).max().unwrap_or(1);
+
+    if keys_only {
+        return Ok(consul_response(index, json!(entries.iter().map(|e| e.key.clone()).collect::<Vec<_>>())));
+    }
+    if params.contains_key("raw") {
+        return Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/octet-stream")
+            .header("X-Consul-Index", index.to_string())
+            .body(Body::from(entries[0].value.clone()))
+            .unwrap());
+    }
+
+    let body: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|entry| {
+            json!({
+                "LockIndex": 0,
+                "Key": entry.key,
+                "Flags": 0,
+                "Value": base64_encode(entry.value.as_bytes()),
+                "CreateIndex": entry.create_index,
+                "ModifyIndex": entry.modify_index,
+            })
+        })
+        .collect();
+    Ok(consul_response(index, json!(body)))
+}
+
+/// 写入键值对（值按 UTF-8 文本保存）
+/// PUT /v1/kv/{key}
+/// 可选参数: cas（检查并设置）
+/// 响应: true / false
+pub async fn put_kv(
+    State(app): State<Arc<AppHandle>>,
+    Path(key): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+    body: Bytes,
+) -> Result<Response, StatusCode> {
+    let cas = match params.get("cas") {
+        Some(cas) => Some(cas.parse::<u64>().map_err(|_| StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let value = String::from_utf8_lossy(&body);
+    let written = put_kv_impl(&app, &key, &value, cas).await.map_err(service_error)?;
+    Ok(consul_response(0, json!(written)))
+}
+
+/// 删除键值对
+/// DELETE /v1/kv/{key}
+/// 可选参数: recurse（删除前缀下的所有键）, cas
+/// 响应: true / false
+pub async fn delete_kv(
+    State(app): State<Arc<AppHandle>>,
+    Path(key): Path<String>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, StatusCode> {
+    let cas = match params.get("cas") {
+        Some(cas) => Some(cas.parse::<u64>().map_err(|_| StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let deleted = delete_kv_impl(&app, &key, flag_enabled(&params, "recurse"), cas)
+        .await
+        .map_err(service_error)?;
+    Ok(consul_response(0, json!(deleted)))
+}
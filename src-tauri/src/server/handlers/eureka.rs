@@ -0,0 +1,625 @@
+/**
+ * Eureka 兼容 API 处理器
+ * 实现 /eureka/apps 下的注册、续约、下线、覆盖状态、全量和增量拉取接口，
+ * 请求体支持 JSON 和 XML，响应格式由 Accept 请求头决定（默认 JSON）
+ */
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::Response,
+};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::service::{
+    cancel_eureka_instance as cancel_eureka_instance_impl,
+    find_eureka_instance as find_eureka_instance_impl,
+    get_eureka_application as get_eureka_application_impl,
+    get_eureka_applications as get_eureka_applications_impl,
+    get_eureka_delta as get_eureka_delta_impl,
+    register_eureka_instance as register_eureka_instance_impl,
+    renew_eureka_instance as renew_eureka_instance_impl,
+    update_eureka_status_override as update_eureka_status_override_impl,
+    eureka_apps_hashcode, eureka_instance_id, eureka_instance_info, eureka_overridden_status, eureka_status,
+    eureka_user_metadata, EurekaApplication, EurekaRegistration, InstanceInfo,
+    DEFAULT_EUREKA_LEASE_DURATION_SECONDS, DEFAULT_EUREKA_RENEWAL_INTERVAL_SECONDS, EUREKA_STATUS_UP,
+};
+
+/// 续约参数
+#[derive(Debug, Deserialize)]
+pub struct RenewParams {
+    #[serde(default)]
+    pub lastDirtyTimestamp: Option<String>,
+}
+
+/// 覆盖状态参数
+#[derive(Debug, Deserialize)]
+pub struct StatusParams {
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// 是否返回 XML（Accept 中要求 XML 且未要求 JSON）
+fn wants_xml(headers: &HeaderMap) -> bool {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+    accept.contains("xml") && !accept.contains("json")
+}
+
+/// 按 Accept 请求头输出 JSON 或 XML，value 为根元素内容
+fn eureka_response(headers: &HeaderMap, root: &str, value: Value) -> Response {
+    if wants_xml(headers) {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>");
+        write_xml_element(&mut xml, root, &value);
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/xml")
+            .body(Body::from(xml))
+            .unwrap()
+    } else {
+        let mut body = Map::new();
+        body.insert(root.to_string(), value);
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(Value::Object(body).to_string()))
+            .unwrap()
+    }
+}
+
+/// 空响应
+fn empty_response(status: StatusCode) -> Response {
+    Response::builder().status(status).body(Body::empty()).unwrap()
+}
+
+/// 把 JSON 值按 Eureka 约定写成 XML："@" 开头的键为属性，"$" 为文本，数组展开为同名元素
+fn write_xml_element(out: &mut String, name: &str, value: &Value) {
+    match value {
+        Value::Object(map) => {
+            out.push('<');
+            out.push_str(name);
+            for (key, attr) in map.iter().filter(|(k, _)| k.starts_with('@')) {
+                out.push_str(&format!(" {}=\"{}\"", &key[1..], xml_escape(&value_text(attr))));
+            }
+            let children: Vec<(&String, &Value)> = map.iter().filter(|(k, _)| !k.starts_with('@')).collect();
+            if children.is_empty() {
+                out.push_str("/>");
+                return;
+            }
+            out.push('>');
+            for (key, child) in children {
+                match (key.as_str(), child) {
+                    ("$", text) => out.push_str(&xml_escape(&value_text(text))),
+                    (_, Value::Array(items)) => items.iter().for_each(|item| write_xml_element(out, key, item)),
+                    _ => write_xml_element(out, key, child),
+                }
+            }
+            out.push_str(&format!("</{}>", name));
+        }
+        Value::Array(items) => items.iter().for_each(|item| write_xml_element(out, name, item)),
+        Value::Null => out.push_str(&format!("<{}/>", name)),
+        scalar => out.push_str(&format!("<{}>{}</{}>", name, xml_escape(&value_text(scalar)), name)),
+    }
+}
+
+/// 标量值转为文本
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// XML 转义
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// XML 反转义
+fn xml_unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// XML 元素
+#[derive(Debug, Default)]
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    text: String,
+}
+
+/// 简单的 XML 读取器，只支持 Eureka 注册请求用到的元素、属性、文本、注释和 CDATA
+struct XmlReader<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlReader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    /// 跳过空白、声明、注释和 DOCTYPE
+    fn skip_misc(&mut self) -> Option<()> {
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.input.len() - trimmed.len();
+            let end_marker = if trimmed.starts_with("<?") {
+                "?>"
+            } else if trimmed.starts_with("<!--") {
+                "-->"
+            } else if trimmed.starts_with("<!") && !trimmed.starts_with("<![CDATA[") {
+                ">"
+            } else {
+                return Some(());
+            };
+            self.pos += trimmed.find(end_marker)? + end_marker.len();
+        }
+    }
+
+    fn parse_element(&mut self) -> Option<XmlElement> {
+        self.skip_misc()?;
+        if !self.rest().starts_with('<') {
+            return None;
+        }
+        self.pos += 1;
+
+        let name_len = self.rest().find(|c: char| c.is_whitespace() || c == '>' || c == '/')?;
+        let mut element = XmlElement {
+            name: self.rest()[..name_len].to_string(),
+            ..Default::default()
+        };
+        self.pos += name_len;
+
+        // 属性
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.input.len() - trimmed.len();
+            if trimmed.starts_with("/>") {
+                self.pos += 2;
+                return Some(element);
+            }
+            if trimmed.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let eq = trimmed.find('=')?;
+            let attr_name = trimmed[..eq].trim().to_string();
+            self.pos += eq + 1;
+            let trimmed = self.rest().trim_start();
+            self.pos = self.input.len() - trimmed.len();
+            let quote = trimmed.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let end = trimmed[1..].find(quote)?;
+            element.attributes.push((attr_name, xml_unescape(&trimmed[1..1 + end])));
+            self.pos += end + 2;
+        }
+
+        // 内容
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += rest.find('>')? + 1;
+                return Some(element);
+            } else if rest.starts_with("<![CDATA[") {
+                let end = rest.find("]]>")?;
+                element.text.push_str(&rest[9..end]);
+                self.pos += end + 3;
+            } else if rest.starts_with("<!--") {
+                self.pos += rest.find("-->")? + 3;
+            } else if rest.starts_with('<') {
+                let child = self.parse_element()?;
+                element.children.push(child);
+            } else {
+                let end = rest.find('<')?;
+                element.text.push_str(&xml_unescape(&rest[..end]));
+                self.pos += end;
+            }
+        }
+    }
+}
+
+/// 解析 XML 文档的根元素
+fn parse_xml(input: &str) -> Option<XmlElement> {
+    XmlReader { input, pos: 0 }.parse_element()
+}
+
+/// 把 XML 元素转为与 Eureka JSON 格式相同的值
+fn xml_to_value(element: &XmlElement) -> Value {
+    if element.children.is_empty() && element.attributes.is_empty() {
+        return Value::String(element.text.trim().to_string());
+    }
+
+    let mut map = Map::new();
+    for (name, value) in &element.attributes {
+        map.insert(format!("@{}", name), Value::String(value.clone()));
+    }
+    if element.children.is_empty() {
+        map.insert("$".to_string(), Value::String(element.text.trim().to_string()));
+    }
+    for child in &element.children {
+        let value = xml_to_value(child);
+        match map.get_mut(&child.name) {
+            Some(Value::Array(items)) => items.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                map.insert(child.name.clone(), value);
+            }
+        }
+    }
+    Value::Object(map)
+}
+
+/// 读取整数值（数字或字符串）
+fn value_i64(value: Option<&Value>) -> Option<i64> {
+    match value? {
+        Value::String(s) => s.trim().parse::<i64>().ok(),
+        other => other.as_i64(),
+    }
+}
+
+/// 读取端口值和启用状态（{"$": 8080, "@enabled": "true"} 或直接为数字）
+fn port_value(value: Option<&Value>) -> Option<(i64, bool)> {
+    match value? {
+        Value::Object(map) => {
+            let enabled = map.get("@enabled").map_or(true, |v| value_text(v) == "true");
+            value_i64(map.get("$")).map(|port| (port, enabled))
+        }
+        other => value_i64(Some(other)).map(|port| (port, true)),
+    }
+}
+
+/// 读取非空字符串字段
+fn string_field(instance: &Value, key: &str) -> Option<String> {
+    instance
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 解析注册请求体（JSON 的 {"instance": {...}} 或 XML 的 <instance>...</instance>）
+fn parse_instance_body(headers: &HeaderMap, body: &[u8]) -> Option<Value> {
+    let text = std::str::from_utf8(body).ok()?;
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+
+    if content_type.contains("xml") || text.trim_start().starts_with('<') {
+        let root = parse_xml(text)?;
+        (root.name == "instance").then(|| xml_to_value(&root))
+    } else {
+        let value: Value = serde_json::from_str(text).ok()?;
+        value.get("instance").cloned()
+    }
+}
+
+/// 把注册信息转换为注册请求
+fn build_registration(app_name: &str, instance: Value) -> Option<EurekaRegistration> {
+    if !instance.is_object() {
+        return None;
+    }
+    let ip = string_field(&instance, "ipAddr").or_else(|| string_field(&instance, "hostName"))?;
+
+    // 非安全端口未启用时使用安全端口
+    let port = match (port_value(instance.get("port")), port_value(instance.get("securePort"))) {
+        (Some((port, true)), _) => port,
+        (_, Some((port, true))) => port,
+        (Some((port, false)), _) => port,
+        _ => return None,
+    };
+    let port = i32::try_from(port).ok().filter(|p| *p > 0 && *p <= 65535)?;
+
+    let instance_id = string_field(&instance, "instanceId")
+        .or_else(|| string_field(&instance, "hostName"))
+        .unwrap_or_else(|| format!("{}:{}:{}", ip, app_name.to_lowercase(), port));
+    let status = string_field(&instance, "status")
+        .map(|s| s.to_uppercase())
+        .unwrap_or_else(|| EUREKA_STATUS_UP.to_string());
+    let lease_duration_seconds = value_i64(instance.get("leaseInfo").and_then(|l| l.get("durationInSecs")))
+        .filter(|d| *d > 0)
+        .unwrap_or(DEFAULT_EUREKA_LEASE_DURATION_SECONDS);
+
+    let metadata: HashMap<String, String> = instance
+        .get("metadata")
+        .and_then(|m| m.as_object())
+        .map(|m| {
+            m.iter()
+                .filter(|(key, _)| !key.starts_with('@'))
+                .map(|(key, value)| (key.clone(), value_text(value)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut info = instance;
+    if let Some(map) = info.as_object_mut() {
+        map.remove("metadata");
+        map.remove("actionType");
+    }
+
+    Some(EurekaRegistration {
+        app_name: app_name.to_string(),
+        instance_id,
+        ip,
+        port,
+        status,
+        lease_duration_seconds,
+        metadata,
+        info,
+    })
+}
+
+/// 把实例转换为 Eureka 实例信息，注册时保存的字段原样返回，状态、地址、租约等由注册表决定
+fn instance_to_value(instance: &InstanceInfo, action_type: Option<&str>) -> Value {
+    let mut info = eureka_instance_info(instance);
+    let lease = info.get("leaseInfo").cloned().unwrap_or(Value::Null);
+    let map = info.as_object_mut().expect("eureka instance info is an object");
+    map.remove("overriddenstatus");
+
+    let base_url = format!("http://{}:{}/", instance.ip, instance.port);
+    let updated_millis = instance.gmt_modified * 1000;
+    let defaults = [
+        ("hostName", Value::String(instance.ip.clone())),
+        ("countryId", json!(1)),
+        ("securePort", json!({ "$": 443, "@enabled": "false" })),
+        (
+            "dataCenterInfo",
+            json!({ "@class": "com.netflix.appinfo.InstanceInfo$DefaultDataCenterInfo", "name": "MyOwn" }),
+        ),
+        ("homePageUrl", Value::String(base_url.clone())),
+        ("statusPageUrl", Value::String(format!("{}actuator/info", base_url))),
+        ("healthCheckUrl", Value::String(format!("{}actuator/health", base_url))),
+        ("vipAddress", Value::String(instance.service_name.clone())),
+        ("secureVipAddress", Value::String(instance.service_name.clone())),
+        ("isCoordinatingDiscoveryServer", Value::String("false".to_string())),
+        ("lastDirtyTimestamp", Value::String(updated_millis.to_string())),
+    ];
+    for (key, value) in defaults {
+        map.entry(key.to_string()).or_insert(value);
+    }
+
+    let metadata = eureka_user_metadata(instance);
+    let metadata = if metadata.is_empty() {
+        json!({ "@class": "java.util.Collections$EmptyMap" })
+    } else {
+        json!(metadata)
+    };
+
+    map.insert("instanceId".to_string(), Value::String(eureka_instance_id(instance)));
+    map.insert("app".to_string(), Value::String(instance.service_name.to_uppercase()));
+    map.insert("ipAddr".to_string(), Value::String(instance.ip.clone()));
+    map.insert("status".to_string(), Value::String(eureka_status(instance)));
+    map.insert("overriddenStatus".to_string(), Value::String(eureka_overridden_status(instance)));
+    map.insert("port".to_string(), json!({ "$": instance.port, "@enabled": "true" }));
+    map.insert("metadata".to_string(), metadata);
+    map.insert(
+        "leaseInfo".to_string(),
+        json!({
+            "renewalIntervalInSecs": value_i64(lease.get("renewalIntervalInSecs")).unwrap_or(DEFAULT_EUREKA_RENEWAL_INTERVAL_SECONDS),
+            "durationInSecs": value_i64(lease.get("durationInSecs")).unwrap_or(DEFAULT_EUREKA_LEASE_DURATION_SECONDS),
+            "registrationTimestamp": instance.gmt_create * 1000,
+            "lastRenewalTimestamp": updated_millis,
+            "evictionTimestamp": 0,
+            "serviceUpTimestamp": instance.gmt_create * 1000,
+        }),
+    );
+    map.insert("lastUpdatedTimestamp".to_string(), Value::String(updated_millis.to_string()));
+    match action_type {
+        Some(action_type) => map.insert("actionType".to_string(), Value::String(action_type.to_string())),
+        None => map.remove("actionType"),
+    };
+    info
+}
+
+/// 把应用转换为 Eureka 应用信息
+fn application_to_value(application: &EurekaApplication) -> Value {
+    json!({
+        "name": application.name,
+        "instance": application.instances.iter().map(|inst| instance_to_value(inst, None)).collect::<Vec<_>>(),
+    })
+}
+
+/// 服务层错误转换为状态码
+fn service_error(e: String) -> StatusCode {
+    if e.contains("is disabled") {
+        StatusCode::FORBIDDEN
+    } else {
+        tracing::error!("Eureka request failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// 注册实例
+/// POST /nacos/eureka/apps/{appName}
+/// 请求体: {"instance": {...}} 或 <instance>...</instance>
+/// 响应: 204（成功）
+pub async fn register_instance(
+    State(app): State<Arc<AppHandle>>,
+    Path(app_name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, StatusCode> {
+    let registration = parse_instance_body(&headers, &body)
+        .and_then(|instance| build_registration(&app_name, instance))
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    register_eureka_instance_impl(&app, registration).await.map_err(service_error)?;
+    Ok(empty_response(StatusCode::NO_CONTENT))
+}
+
+/// 续约（心跳）
+/// PUT /nacos/eureka/apps/{appName}/{instanceId}
+/// 可选参数: status, lastDirtyTimestamp
+/// 响应: 200（成功），404（实例不存在，客户端需重新注册）
+pub async fn renew_instance(
+    State(app): State<Arc<AppHandle>>,
+    Path((app_name, instance_id)): Path<(String, String)>,
+    Query(params): Query<RenewParams>,
+) -> Result<Response, StatusCode> {
+    let last_dirty_timestamp = params.lastDirtyTimestamp.as_deref().and_then(|s| s.parse::<i64>().ok());
+    let renewed = renew_eureka_instance_impl(&app, &app_name, &instance_id, last_dirty_timestamp)
+        .await
+        .map_err(service_error)?;
+
+    Ok(empty_response(if renewed { StatusCode::OK } else { StatusCode::NOT_FOUND }))
+}
+
+/// 下线实例
+/// DELETE /nacos/eureka/apps/{appName}/{instanceId}
+/// 响应: 200（成功），404（实例不存在）
+pub async fn cancel_instance(
+    State(app): State<Arc<AppHandle>>,
+    Path((app_name, instance_id)): Path<(String, String)>,
+) -> Result<Response, StatusCode> {
+    let cancelled = cancel_eureka_instance_impl(&app, &app_name, &instance_id)
+        .await
+        .map_err(service_error)?;
+
+    Ok(empty_response(if cancelled { StatusCode::OK } else { StatusCode::NOT_FOUND }))
+}
+
+/// 设置覆盖状态
+/// PUT /nacos/eureka/apps/{appName}/{instanceId}/status?value=OUT_OF_SERVICE
+/// 响应: 200（成功），404（实例不存在）
+pub async fn update_status(
+    State(app): State<Arc<AppHandle>>,
+    Path((app_name, instance_id)): Path<(String, String)>,
+    Query(params): Query<StatusParams>,
+) -> Result<Response, StatusCode> {
+    let status = params
+        .value
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty())
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    let updated = update_eureka_status_override_impl(&app, &app_name, &instance_id, Some(&status), &status)
+        .await
+        .map_err(service_error)?;
+
+    Ok(empty_response(if updated { StatusCode::OK } else { StatusCode::NOT_FOUND }))
+}
+
+/// 清除覆盖状态
+/// DELETE /nacos/eureka/apps/{appName}/{instanceId}/status
+/// 可选参数: value（清除后的状态，默认 UP）
+/// 响应: 200（成功），404（实例不存在）
+pub async fn delete_status_override(
+    State(app): State<Arc<AppHandle>>,
+    Path((app_name, instance_id)): Path<(String, String)>,
+    Query(params): Query<StatusParams>,
+) -> Result<Response, StatusCode> {
+    let restore_status = params
+        .value
+        .map(|s| s.trim().to_uppercase())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| EUREKA_STATUS_UP.to_string());
+
+    let updated = update_eureka_status_override_impl(&app, &app_name, &instance_id, None, &restore_status)
+        .await
+        .map_err(service_error)?;
+
+    Ok(empty_response(if updated { StatusCode::OK } else { StatusCode::NOT_FOUND }))
+}
+
+/// 全量拉取
+/// GET /nacos/eureka/apps
+pub async fn get_applications(
+    State(app): State<Arc<AppHandle>>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let applications = get_eureka_applications_impl(&app).await.map_err(service_error)?;
+    let hashcode = eureka_apps_hashcode(applications.iter().flat_map(|a| a.instances.iter()));
+
+    Ok(eureka_response(
+        &headers,
+        "applications",
+        json!({
+            "versions__delta": "1",
+            "apps__hashcode": hashcode,
+            "application": applications.iter().map(application_to_value).collect::<Vec<_>>(),
+        }),
+    ))
+}
+
+/// 增量拉取
+/// GET /nacos/eureka/apps/delta
+pub async fn get_delta(
+    State(app): State<Arc<AppHandle>>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let delta = get_eureka_delta_impl(&app).await.map_err(service_error)?;
+
+    // 按应用分组，保持变化顺序
+    let mut grouped: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for change in &delta.changes {
+        grouped
+            .entry(change.instance.service_name.to_uppercase())
+            .or_default()
+            .push(instance_to_value(&change.instance, Some(change.action_type)));
+    }
+
+    Ok(eureka_response(
+        &headers,
+        "applications",
+        json!({
+            "versions__delta": delta.version.to_string(),
+            "apps__hashcode": delta.apps_hashcode,
+            "application": grouped
+                .into_iter()
+                .map(|(name, instances)| json!({ "name": name, "instance": instances }))
+                .collect::<Vec<_>>(),
+        }),
+    ))
+}
+
+/// 查询单个应用
+/// GET /nacos/eureka/apps/{appName}
+pub async fn get_application(
+    State(app): State<Arc<AppHandle>>,
+    Path(app_name): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let application = get_eureka_application_impl(&app, &app_name)
+        .await
+        .map_err(service_error)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(eureka_response(&headers, "application", application_to_value(&application)))
+}
+
+/// 查询单个实例
+/// GET /nacos/eureka/apps/{appName}/{instanceId}
+pub async fn get_instance(
+    State(app): State<Arc<AppHandle>>,
+    Path((app_name, instance_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let instance = find_eureka_instance_impl(&app, &app_name, &instance_id)
+        .await
+        .map_err(service_error)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(eureka_response(&headers, "instance", instance_to_value(&instance, None)))
+}
@@ -0,0 +1,55 @@
+/**
+ * 命名服务容灾快照处理器
+ * 以 zip 下载命名空间的服务注册表，格式为 Nacos 客户端容灾目录（failover/group%40%40service）；
+ * 写入本地目录只通过 Tauri 命令提供，HTTP 接口不接受本地路径
+ */
+
+use axum::{
+    extract::{Query, State},
+    response::Response,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::service::{
+    build_failover_snapshot as build_failover_snapshot_impl,
+    failover_snapshot_zip,
+};
+
+/// 下载容灾快照参数
+#[derive(Debug, Deserialize)]
+pub struct FailoverSnapshotParams {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub failoverSwitch: Option<String>, // true 时包含打开状态的容灾开关文件
+}
+
+/// 下载命名空间的容灾快照
+/// GET /nacos/v1/ns/failover/snapshot
+/// 可选参数: namespaceId, failoverSwitch
+/// 响应: zip 文件（failover/ 目录下每个服务一个文件，文件名为 URL 编码后的 group@@service）
+pub async fn download_failover_snapshot(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<FailoverSnapshotParams>,
+) -> Result<Response, axum::http::StatusCode> {
+    let namespace_id = if params.namespaceId.is_empty() { "public" } else { params.namespaceId.as_str() };
+    let failover_switch = params.failoverSwitch.as_deref() == Some("true");
+
+    let files = build_failover_snapshot_impl(&app, namespace_id, failover_switch).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let zip_buffer = failover_snapshot_zip(&files)
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    use chrono::Local;
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
+    let file_name = format!("nacos_failover_{}_{}.zip", namespace_id, timestamp);
+
+    Ok(Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header("Content-Type", "application/zip")
+        .header("Content-Disposition", format!("attachment;filename={}", file_name))
+        .body(axum::body::Body::from(zip_buffer))
+        .unwrap())
+}
@@ -0,0 +1,98 @@
+/**
+ * 主机视图处理器
+ * 按 IP 反查主机提供的实例、订阅的服务和监听的配置
+ */
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::service::get_host_view as get_host_view_impl;
+
+/// 主机视图参数
+#[derive(Debug, Deserialize)]
+pub struct HostViewParams {
+    pub ip: String,
+}
+
+/// 查询主机视图
+/// GET /nacos/v1/console/host
+/// 必需参数: ip
+/// 响应: { ip, lastHeartbeat, instances: [...], subscribedServices: [...], configListeners: [...] }
+pub async fn get_host_view(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<HostViewParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let view = match get_host_view_impl(&app, &params.ip).await {
+        Ok(view) => view,
+        Err(e) if e.contains("Invalid") => return Err(axum::http::StatusCode::BAD_REQUEST),
+        Err(_) => return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let instances: Vec<serde_json::Value> = view.instances
+        .iter()
+        .map(|host_instance| {
+            let inst = &host_instance.instance;
+            serde_json::json!({
+                "namespaceId": inst.namespace_id,
+                "groupName": inst.group_name,
+                "serviceName": inst.service_name,
+                "instanceId": inst.instance_id,
+                "ip": inst.ip,
+                "port": inst.port,
+                "weight": inst.weight,
+                "healthy": inst.healthy,
+                "enabled": inst.enabled,
+                "ephemeral": inst.ephemeral,
+                "clusterName": inst.cluster_name,
+                "metadata": inst.metadata.as_ref()
+                    .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                    .unwrap_or(serde_json::json!({})),
+                "lastBeat": host_instance.last_beat_millis
+            })
+        })
+        .collect();
+
+    let subscribed_services: Vec<serde_json::Value> = view.subscribed_services
+        .iter()
+        .map(|sub| {
+            serde_json::json!({
+                "namespaceId": sub.namespace_id,
+                "groupName": sub.group_name,
+                "serviceName": sub.service_name,
+                "port": sub.client_port,
+                "app": sub.app_name,
+                "agent": sub.agent,
+                "clusters": sub.clusters,
+                "lastRefTime": sub.last_ref_time
+            })
+        })
+        .collect();
+
+    let config_listeners: Vec<serde_json::Value> = view.config_listeners
+        .iter()
+        .map(|listener| {
+            serde_json::json!({
+                "namespaceId": listener.tenant_id,
+                "dataId": listener.data_id,
+                "group": listener.group_id,
+                "port": listener.client_port,
+                "appName": listener.app_name,
+                "md5": listener.md5,
+                "lastPollTime": listener.last_poll_time
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "ip": view.ip,
+        "lastHeartbeat": view.last_heartbeat_millis,
+        "instances": instances,
+        "subscribedServices": subscribed_services,
+        "configListeners": config_listeners
+    })))
+}
@@ -4,24 +4,150 @@
  */
 
 use axum::{
-    extract::{Query, State},
+    extract::{ConnectInfo, Query, State},
     response::{IntoResponse, Response},
     Json,
 };
 use axum_extra::extract::Form;
 use serde::Deserialize;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tauri::AppHandle;
-use chrono::Utc;
 
 use crate::service::{
     register_instance as register_instance_impl,
     deregister_instance as deregister_instance_impl,
     get_service_instances as get_service_instances_impl,
     update_instance_health as update_instance_health_impl,
-    RegisterInstanceRequest, InstanceInfo,
+    HealthChangeSource,
+    heartbeat_instance as heartbeat_instance_impl,
+    find_instance as find_instance_impl,
+    get_naming_switches as get_naming_switches_impl,
+    ensure_service as ensure_service_impl,
+    record_naming_subscriber as record_naming_subscriber_impl,
+    select_one_healthy_instance as select_one_healthy_instance_impl,
+    get_service_revision as get_service_revision_impl,
+    batch_register_instances as batch_register_instances_impl,
+    batch_deregister_instances as batch_deregister_instances_impl,
+    get_health_timeline as get_health_timeline_impl,
+    apply_zone_affinity as apply_zone_affinity_impl,
+    resolve_consumer_zone as resolve_consumer_zone_impl,
+    compute_instances_checksum,
+    get_metadata_violations as get_metadata_violations_impl,
+    BatchInstanceItem, InstanceMetadataViolations, BatchInstanceRef, BatchInstanceResult,
+    RegisterInstanceRequest, InstanceInfo, SelectInstanceOptions, HealthTimelineQuery, BeatSettings,
+    instance_id_generator_name,
 };
 
+/// 调用方区域请求头
+const CLIENT_ZONE_HEADER: &str = "X-Client-Zone";
+
+/// 元数据违规响应头：告警模式下接受写入，违规信息以 URL 编码的 JSON 返回
+pub(crate) const METADATA_VIOLATIONS_HEADER: &str = "X-Nacos-Metadata-Violations";
+
+/// 查询刚写入的实例违反元数据约定的情况（只在告警模式下可能存在）
+pub(crate) async fn written_metadata_violations(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instance_ids: &[String],
+) -> Vec<InstanceMetadataViolations> {
+    if instance_ids.is_empty() {
+        return Vec::new();
+    }
+    get_metadata_violations_impl(app, namespace_id, group_name, service_name, instance_ids)
+        .await
+        .ok()
+        .flatten()
+        .map(|report| report.instances)
+        .unwrap_or_default()
+}
+
+/// 存在元数据违规时添加违规响应头
+pub(crate) fn with_metadata_violations(mut response: Response, violations: &[InstanceMetadataViolations]) -> Response {
+    if violations.is_empty() {
+        return response;
+    }
+    let json = serde_json::to_string(violations).unwrap_or_default();
+    if let Ok(value) = axum::http::HeaderValue::from_str(&urlencoding::encode(&json)) {
+        response.headers_mut().insert(METADATA_VIOLATIONS_HEADER, value);
+    }
+    response
+}
+
+/// 调用方 IP：优先取参数，其次取代理请求头
+fn request_client_ip(client_ip: Option<String>, headers: &axum::http::HeaderMap) -> Option<String> {
+    client_ip
+        .filter(|ip| !ip.is_empty())
+        .or_else(|| {
+            headers
+                .get("X-Forwarded-For")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.split(',').next())
+                .map(|s| s.trim().to_string())
+        })
+        .or_else(|| {
+            headers
+                .get("X-Real-IP")
+                .and_then(|h| h.to_str().ok())
+                .map(|s| s.trim().to_string())
+        })
+}
+
+/// 调用方区域：优先取参数，其次取请求头
+fn request_zone(zone: Option<String>, headers: &axum::http::HeaderMap) -> Option<String> {
+    zone.filter(|z| !z.is_empty()).or_else(|| {
+        headers
+            .get(CLIENT_ZONE_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(|s| s.trim().to_string())
+    })
+}
+
+/// 按 IP、端口和集群查找实例 ID
+/// 实例 ID 由注册时选择的生成器决定，不能从请求参数直接拼接
+async fn resolve_instance_id(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    ip: &str,
+    port: &str,
+    cluster_name: &str,
+) -> Result<Option<String>, axum::http::StatusCode> {
+    let port = port.parse::<i32>()
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+
+    find_instance_impl(app, namespace_id, group_name, service_name, ip, port, cluster_name)
+        .await
+        .map(|instance| instance.map(|inst| inst.instance_id))
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// 按 IP、端口和集群把实例引用解析为实例 ID，找不到的实例被忽略
+async fn resolve_instance_ids(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    refs: &[(String, i32, String)],
+) -> Result<Vec<String>, axum::http::StatusCode> {
+    let response = get_service_instances_impl(app, namespace_id, group_name, service_name)
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(refs
+        .iter()
+        .filter_map(|(ip, port, cluster)| {
+            response.instances
+                .iter()
+                .find(|inst| &inst.ip == ip && inst.port == *port && &inst.cluster_name == cluster)
+                .map(|inst| inst.instance_id.clone())
+        })
+        .collect())
+}
+
 /// 注册实例参数（Nacos API 格式）
 #[derive(Debug, Deserialize)]
 pub struct RegisterInstanceParams {
@@ -83,6 +209,10 @@ pub struct ListInstancesParams {
     pub udpPort: Option<String>,
     #[serde(default)]
     pub app: Option<String>,
+    #[serde(default)]
+    pub checksum: Option<String>, // 客户端上次收到的校验和，未变化时返回轻量响应
+    #[serde(default)]
+    pub zone: Option<String>, // 调用方区域，同区域实例排在前面
 }
 
 /// 查询实例详情参数
@@ -97,6 +227,24 @@ pub struct GetInstanceParams {
     pub cluster: Option<String>,
 }
 
+/// 选择单个健康实例参数
+#[derive(Debug, Deserialize)]
+pub struct SelectOneInstanceParams {
+    pub serviceName: String,
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub clusters: Option<String>, // 集群列表，逗号分隔
+    #[serde(default)]
+    pub hashKey: Option<String>, // 粘性哈希键
+    #[serde(default)]
+    pub zone: Option<String>, // 调用方区域，优先选择 metadata.zone 相同的实例
+    #[serde(default)]
+    pub clientIP: Option<String>, // 未指定区域时按客户端 IP 标签查找区域
+}
+
 /// 实例心跳参数
 #[derive(Debug, Deserialize)]
 pub struct HeartbeatParams {
@@ -156,14 +304,50 @@ pub struct UpdateInstanceHealthParams {
     pub clusterName: Option<String>,
 }
 
+/// 健康时间线查询参数
+#[derive(Debug, Deserialize)]
+pub struct HealthTimelineParams {
+    pub serviceName: String,
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub ip: Option<String>,
+    #[serde(default)]
+    pub port: Option<i32>,
+    #[serde(default)]
+    pub clusterName: Option<String>,
+    #[serde(default)]
+    pub startTime: Option<i64>, // 起始时间（秒），默认为结束时间前 24 小时
+    #[serde(default)]
+    pub endTime: Option<i64>, // 结束时间（秒），默认为当前时间
+}
+
 /// 注册实例
 /// POST /nacos/v1/ns/instance
 /// 必需参数: ip, port, serviceName
 /// 可选参数: namespaceId, groupName, cluster, weight, healthy, enabled, ephemeral, metadata
-/// 响应: "ok"（成功）
+/// 响应: "ok"（成功），元数据约定为告警模式时违规信息通过 X-Nacos-Metadata-Violations 响应头返回
 pub async fn register_instance(
     State(app): State<Arc<AppHandle>>,
     Form(params): Form<RegisterInstanceParams>,
+) -> Result<Response, axum::http::StatusCode> {
+    let namespace_id = if params.namespaceId.is_empty() { "public" } else { params.namespaceId.as_str() };
+    let group_name = if params.groupName.is_empty() { "DEFAULT_GROUP" } else { params.groupName.as_str() };
+
+    // 与 Nacos v1 一致，服务不存在时自动创建（空服务会被清理任务删除）
+    ensure_service_impl(&app, namespace_id, group_name, &params.serviceName)
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    register_or_update_instance(&app, params).await
+}
+
+/// 注册或更新实例（服务必须已存在）
+async fn register_or_update_instance(
+    app: &AppHandle,
+    params: RegisterInstanceParams,
 ) -> Result<Response, axum::http::StatusCode> {
     // 处理命名空间和服务组
     let namespace_id = if params.namespaceId.is_empty() {
@@ -191,9 +375,9 @@ pub async fn register_instance(
         .map(|s| s == "true" || s == "True" || s == "1")
         .unwrap_or(true);
     
+    // 未指定时由 defaultInstanceEphemeral 开关决定
     let ephemeral = params.ephemeral.as_deref()
-        .map(|s| s == "true" || s == "True" || s == "1")
-        .unwrap_or(true);
+        .map(|s| s == "true" || s == "True" || s == "1");
 
     // 构建注册请求
     let request = RegisterInstanceRequest {
@@ -205,17 +389,32 @@ pub async fn register_instance(
         weight: params.weight,
         healthy: Some(healthy),
         enabled: Some(enabled),
-        ephemeral: Some(ephemeral),
+        ephemeral,
         cluster_name: params.cluster,
         metadata: params.metadata,
     };
 
-    match register_instance_impl(&app, request).await {
-        Ok(_) => Ok(Response::builder()
-            .status(axum::http::StatusCode::OK)
-            .body(axum::body::Body::from("ok"))
-            .unwrap()),
-        Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    let (namespace_id, group_name, service_name) =
+        (request.namespace_id.clone(), request.group_name.clone(), request.service_name.clone());
+    match register_instance_impl(app, request).await {
+        Ok(instance) => {
+            let violations =
+                written_metadata_violations(app, &namespace_id, &group_name, &service_name, &[instance.instance_id]).await;
+            let response = Response::builder()
+                .status(axum::http::StatusCode::OK)
+                .body(axum::body::Body::from("ok"))
+                .unwrap();
+            Ok(with_metadata_violations(response, &violations))
+        }
+        Err(e) => {
+            if e.contains("is disabled") {
+                Err(axum::http::StatusCode::FORBIDDEN)
+            } else if e.contains("Invalid") {
+                Err(axum::http::StatusCode::BAD_REQUEST)
+            } else {
+                Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
     }
 }
 
@@ -227,8 +426,8 @@ pub async fn update_instance(
     State(app): State<Arc<AppHandle>>,
     Form(params): Form<UpdateInstanceParams>,
 ) -> Result<Response, axum::http::StatusCode> {
-    // 更新实例实际上就是重新注册
-    register_instance(State(app), Form(params)).await
+    // 更新实例实际上就是重新注册，但不会自动创建服务
+    register_or_update_instance(&app, params).await
 }
 
 /// 注销实例
@@ -253,13 +452,24 @@ pub async fn deregister_instance(
         params.groupName
     };
 
-    // 构建实例 ID（格式：ip#port#cluster#group）
-    let instance_id = format!("{}#{}#{}#{}", 
-        params.ip,
-        params.port,
+    // 按 IP、端口和集群查找实例，实例不存在时视为已注销
+    let instance_id = match resolve_instance_id(
+        &app,
+        &namespace_id,
+        &group_name,
+        &params.serviceName,
+        &params.ip,
+        &params.port,
         params.cluster.as_deref().unwrap_or("DEFAULT"),
-        group_name
-    );
+    ).await? {
+        Some(instance_id) => instance_id,
+        None => {
+            return Ok(Response::builder()
+                .status(axum::http::StatusCode::OK)
+                .body(axum::body::Body::from("ok"))
+                .unwrap())
+        }
+    };
 
     match deregister_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &instance_id).await {
         Ok(_) => Ok(Response::builder()
@@ -273,11 +483,14 @@ pub async fn deregister_instance(
 /// 查询实例列表
 /// GET /nacos/v1/ns/instance/list
 /// 必需参数: serviceName
-/// 可选参数: namespaceId, groupName, clusters, healthyOnly, clientIP, udpPort, app
+/// 可选参数: namespaceId, groupName, clusters, healthyOnly, clientIP, udpPort, app, zone
+/// 调用方区域取自 zone 参数、X-Client-Zone 请求头或按客户端 IP 的区域标签，同区域实例排在前面
 /// 响应: 实例列表（JSON 格式，包含 hosts 数组）
 pub async fn list_instances(
     State(app): State<Arc<AppHandle>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     Query(params): Query<ListInstancesParams>,
+    headers: axum::http::HeaderMap,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
     // 处理命名空间和服务组
     let namespace_id = if params.namespaceId.is_empty() {
@@ -292,6 +505,35 @@ pub async fn list_instances(
         params.groupName
     };
 
+    // 记录订阅者信息（clientIP 优先取参数，其次取请求头，最后取连接的对端地址）
+    let request_ip = request_client_ip(params.clientIP.clone(), &headers)
+        .or_else(|| connect_info.map(|ConnectInfo(addr)| addr.ip().to_string()));
+    let zone = resolve_consumer_zone_impl(&app, request_zone(params.zone.clone(), &headers), request_ip.as_deref()).await;
+    let client_port = params.udpPort.as_deref()
+        .and_then(|s| s.parse::<i32>().ok())
+        .unwrap_or(0);
+    let agent = headers
+        .get("User-Agent")
+        .or_else(|| headers.get("Client-Version"))
+        .and_then(|h| h.to_str().ok());
+
+    // 无法确定调用方地址时不记录；推送开关只影响推送，不影响订阅者记录
+    if let Some(client_ip) = request_ip.as_deref() {
+        if let Err(e) = record_naming_subscriber_impl(
+            &app,
+            &namespace_id,
+            &group_name,
+            &params.serviceName,
+            client_ip,
+            client_port,
+            params.app.as_deref(),
+            agent,
+            params.clusters.as_deref().unwrap_or(""),
+        ).await {
+            tracing::warn!("Failed to record naming subscriber {} for {}: {}", client_ip, params.serviceName, e);
+        }
+    }
+
     match get_service_instances_impl(&app, &namespace_id, &group_name, &params.serviceName).await {
         Ok(response) => {
             // 转换为 Nacos 格式
@@ -299,9 +541,8 @@ pub async fn list_instances(
                 .map(|s| s == "true" || s == "True" || s == "1")
                 .unwrap_or(false);
 
-            // 根据 Nacos API 标准，响应格式应该包含更多字段
-            let hosts: Vec<serde_json::Value> = response.instances
-                .iter()
+            let instances: Vec<InstanceInfo> = response.instances
+                .into_iter()
                 .filter(|inst| {
                     // 如果指定了集群过滤
                     if let Some(clusters) = &params.clusters {
@@ -311,8 +552,38 @@ pub async fn list_instances(
                         true
                     }
                 })
+                .collect();
+
+            // 区域亲和按过滤健康状态前的实例计算同区域健康比例
+            let instances: Vec<InstanceInfo> = apply_zone_affinity_impl(&app, instances, zone.as_deref())
+                .await
+                .into_iter()
                 .filter(|inst| !healthy_only || inst.healthy)
+                .collect();
+
+            // 校验和与修订号：实例集合未变化时只返回轻量响应
+            let checksum = compute_instances_checksum(instances.iter());
+            let revision = get_service_revision_impl(&app, &namespace_id, &group_name, &params.serviceName).await;
+
+            if params.checksum.as_deref() == Some(checksum.as_str()) {
+                return Ok(Json(serde_json::json!({
+                    "name": format!("{}@@{}", group_name, params.serviceName),
+                    "groupName": group_name,
+                    "clusters": params.clusters.as_deref().unwrap_or(""),
+                    "cacheMillis": 10000,
+                    "lastRefTime": revision.last_modified_millis,
+                    "revision": revision.revision,
+                    "checksum": checksum,
+                    "unchanged": true
+                })));
+            }
+
+            // 根据 Nacos API 标准，响应格式应该包含更多字段
+            let client_beat_interval = get_naming_switches_impl(&app).await.client_beat_interval;
+            let hosts: Vec<serde_json::Value> = instances
+                .iter()
                 .map(|inst| {
+                    let beat = BeatSettings::for_instance(inst, client_beat_interval);
                     serde_json::json!({
                         "instanceId": inst.instance_id,
                         "ip": inst.ip,
@@ -326,10 +597,10 @@ pub async fn list_instances(
                         "metadata": inst.metadata.as_ref()
                             .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
                             .unwrap_or(serde_json::json!({})),
-                        "instanceHeartBeatInterval": 5000,
-                        "instanceIdGenerator": "simple",
-                        "instanceHeartBeatTimeOut": 15000,
-                        "ipDeleteTimeout": 30000
+                        "instanceHeartBeatInterval": beat.interval,
+                        "instanceIdGenerator": instance_id_generator_name(inst.metadata.as_deref()),
+                        "instanceHeartBeatTimeOut": beat.heart_beat_timeout,
+                        "ipDeleteTimeout": beat.ip_delete_timeout
                     })
                 })
                 .collect();
@@ -341,8 +612,10 @@ pub async fn list_instances(
                 "clusters": params.clusters.as_deref().unwrap_or(""),
                 "cacheMillis": 10000,
                 "hosts": hosts,
-                "lastRefTime": Utc::now().timestamp_millis(),
-                "checksum": "",
+                "lastRefTime": revision.last_modified_millis,
+                "revision": revision.revision,
+                "checksum": checksum,
+                "unchanged": false,
                 "allIPs": false,
                 "reachProtectionThreshold": false,
                 "valid": true
@@ -404,6 +677,61 @@ pub async fn get_instance(
     }
 }
 
+/// 按权重选择单个健康实例
+/// GET /nacos/v1/ns/instance/selectOne
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, clusters, hashKey, zone, clientIP
+/// 调用方区域取自 zone 参数、X-Client-Zone 请求头或按客户端 IP 的区域标签
+/// 响应: 实例信息（JSON 格式），没有可用实例时返回 404
+pub async fn select_one_instance(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<SelectOneInstanceParams>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    // 处理命名空间和服务组
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+    
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    let clusters: Vec<String> = params.clusters.as_deref()
+        .map(|s| s.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect())
+        .unwrap_or_default();
+
+    let client_ip = request_client_ip(params.clientIP, &headers);
+    let options = SelectInstanceOptions {
+        clusters,
+        hash_key: params.hashKey,
+        zone: resolve_consumer_zone_impl(&app, request_zone(params.zone, &headers), client_ip.as_deref()).await,
+    };
+
+    match select_one_healthy_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, options).await {
+        Ok(Some(inst)) => Ok(Json(serde_json::json!({
+            "instanceId": inst.instance_id,
+            "ip": inst.ip,
+            "port": inst.port,
+            "weight": inst.weight,
+            "healthy": inst.healthy,
+            "enabled": inst.enabled,
+            "ephemeral": inst.ephemeral,
+            "clusterName": inst.cluster_name,
+            "serviceName": inst.service_name,
+            "metadata": inst.metadata.as_ref()
+                .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                .unwrap_or(serde_json::json!({}))
+        }))),
+        Ok(None) => Err(axum::http::StatusCode::NOT_FOUND),
+        Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
 /// 实例心跳
 /// PUT /nacos/v1/ns/instance/beat
 /// 必需参数: serviceName, beat (心跳信息 JSON 字符串)
@@ -429,70 +757,43 @@ pub async fn heartbeat(
     };
 
     // 解析 beat 参数（如果提供）
-    // beat 参数是 JSON 字符串，包含实例的完整信息
-    if let Some(beat_str) = params.beat {
-        if let Ok(beat_json) = serde_json::from_str::<serde_json::Value>(&beat_str) {
-            // 从 beat JSON 中提取信息
-            let ip = beat_json.get("ip")
-                .and_then(|v| v.as_str())
-                .or_else(|| params.ip.as_deref())
-                .ok_or(axum::http::StatusCode::BAD_REQUEST)?;
-            
-            let port = beat_json.get("port")
-                .and_then(|v| v.as_i64())
-                .or_else(|| params.port.as_ref().and_then(|s| s.parse::<i64>().ok()))
-                .ok_or(axum::http::StatusCode::BAD_REQUEST)?;
-            
-            let cluster_name = beat_json.get("cluster")
-                .and_then(|v| v.as_str())
-                .or_else(|| params.clusterName.as_deref())
-                .unwrap_or("DEFAULT");
-
-            // 构建实例 ID
-            let instance_id = format!("{}#{}#{}#{}", 
-                ip,
-                port,
-                cluster_name,
-                group_name
-            );
-
-            // 更新实例健康状态（心跳表示实例健康）
-            let _ = update_instance_health_impl(
-                &app,
-                &namespace_id,
-                &group_name,
-                &params.serviceName,
-                &instance_id,
-                true,
-            ).await;
-        }
-    } else if let (Some(ip), Some(port_str)) = (params.ip, params.port) {
-        // 如果没有 beat 参数，使用 ip 和 port 参数
-        if let Ok(port) = port_str.parse::<i32>() {
-            // 构建实例 ID
-            let instance_id = format!("{}#{}#{}#{}", 
-                ip,
-                port,
-                params.clusterName.as_deref().unwrap_or("DEFAULT"),
-                group_name
-            );
-
-            // 更新实例健康状态（心跳表示实例健康）
-            let _ = update_instance_health_impl(
-                &app,
-                &namespace_id,
-                &group_name,
-                &params.serviceName,
-                &instance_id,
-                true,
-            ).await;
-        }
-    }
+    // beat 参数是 JSON 字符串，包含实例的完整信息，其中的 ip / port / cluster 优先
+    let beat_json = params.beat.as_deref()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .unwrap_or(serde_json::json!({}));
+
+    let ip = beat_json.get("ip")
+        .and_then(|v| v.as_str())
+        .or(params.ip.as_deref())
+        .ok_or(axum::http::StatusCode::BAD_REQUEST)?;
+
+    let port = beat_json.get("port")
+        .and_then(|v| v.as_i64())
+        .or_else(|| params.port.as_ref().and_then(|s| s.parse::<i64>().ok()))
+        .ok_or(axum::http::StatusCode::BAD_REQUEST)?;
+
+    let cluster_name = beat_json.get("cluster")
+        .and_then(|v| v.as_str())
+        .or(params.clusterName.as_deref())
+        .unwrap_or("DEFAULT");
+
+    // 刷新实例心跳（心跳表示实例健康）
+    let found = heartbeat_instance_impl(
+        &app,
+        &namespace_id,
+        &group_name,
+        &params.serviceName,
+        ip,
+        port as i32,
+        cluster_name,
+    )
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // 返回心跳响应（根据 Nacos 标准格式）
+    // 返回心跳响应（根据 Nacos 标准格式，实例不存在时返回 20404 以便客户端重新注册）
     Ok(Json(serde_json::json!({
-        "clientBeatInterval": 5000,
-        "code": 10200,
+        "clientBeatInterval": get_naming_switches_impl(&app).await.client_beat_interval,
+        "code": if found { 10200 } else { 20404 },
         "lightBeatEnabled": false
     })))
 }
@@ -543,14 +844,11 @@ pub async fn patch_instance(
         params.groupName
     };
 
-    // 构建实例 ID（格式：ip#port#cluster#group）
+    // 按 IP、端口和集群查找实例
     let cluster_name = params.cluster.as_deref().unwrap_or("DEFAULT");
-    let instance_id = format!("{}#{}#{}#{}", 
-        params.ip,
-        params.port,
-        cluster_name,
-        group_name
-    );
+    let instance_id = resolve_instance_id(&app, &namespace_id, &group_name, &params.serviceName, &params.ip, &params.port, cluster_name)
+        .await?
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
     // 解析可选参数
     let weight = params.weight;
@@ -571,13 +869,20 @@ pub async fn patch_instance(
         healthy,
         metadata,
     ).await {
-        Ok(_) => Ok(Response::builder()
-            .status(axum::http::StatusCode::OK)
-            .body(axum::body::Body::from("ok"))
-            .unwrap()),
+        Ok(_) => {
+            let violations =
+                written_metadata_violations(&app, &namespace_id, &group_name, &params.serviceName, &[instance_id]).await;
+            let response = Response::builder()
+                .status(axum::http::StatusCode::OK)
+                .body(axum::body::Body::from("ok"))
+                .unwrap();
+            Ok(with_metadata_violations(response, &violations))
+        }
         Err(e) => {
             if e.contains("not found") {
                 Err(axum::http::StatusCode::NOT_FOUND)
+            } else if e.contains("Invalid") {
+                Err(axum::http::StatusCode::BAD_REQUEST)
             } else {
                 Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
             }
@@ -603,7 +908,7 @@ pub struct BatchUpdateMetadataParams {
 /// PUT /nacos/v1/ns/instance/metadata/batch
 /// 必需参数: serviceName, instances, metadata
 /// 可选参数: namespaceId, groupName, consistencyType
-/// 响应: 更新结果（JSON 格式，包含 updated 数组）
+/// 响应: 更新结果（JSON 格式，包含 updated 数组和告警模式下的 violations 数组）
 pub async fn batch_update_metadata(
     State(app): State<Arc<AppHandle>>,
     Form(params): Form<BatchUpdateMetadataParams>,
@@ -635,13 +940,11 @@ pub async fn batch_update_metadata(
     let instances: Vec<InstanceRef> = serde_json::from_str(&params.instances)
         .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
-    // 构建实例 ID 列表
-    let instance_ids: Vec<String> = instances.iter()
-        .map(|inst| {
-            let cluster = inst.cluster.as_deref().unwrap_or("DEFAULT");
-            format!("{}#{}#{}#{}", inst.ip, inst.port, cluster, group_name)
-        })
+    // 按 IP、端口和集群解析实例 ID 列表
+    let refs: Vec<(String, i32, String)> = instances.into_iter()
+        .map(|inst| (inst.ip, inst.port, inst.cluster.unwrap_or_else(|| "DEFAULT".to_string())))
         .collect();
+    let instance_ids = resolve_instance_ids(&app, &namespace_id, &group_name, &params.serviceName, &refs).await?;
 
     // 解析 metadata（支持 JSON 或 key=value,key=value 格式）
     let metadata_map: std::collections::HashMap<String, String> = if params.metadata.trim_start().starts_with('{') {
@@ -667,9 +970,14 @@ pub async fn batch_update_metadata(
         &instance_ids,
         &metadata_map,
     ).await {
-        Ok(updated) => Ok(Json(serde_json::json!({
-            "updated": updated
-        }))),
+        Ok(updated) => {
+            let violations = written_metadata_violations(&app, &namespace_id, &group_name, &params.serviceName, &updated).await;
+            Ok(Json(serde_json::json!({
+                "updated": updated,
+                "violations": violations
+            })))
+        }
+        Err(e) if e.contains("Invalid") => Err(axum::http::StatusCode::BAD_REQUEST),
         Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -692,7 +1000,7 @@ pub struct BatchDeleteMetadataParams {
 /// DELETE /nacos/v1/ns/instance/metadata/batch
 /// 必需参数: serviceName, instances, metadata（逗号分隔的键列表）
 /// 可选参数: namespaceId, groupName, consistencyType
-/// 响应: 删除结果（JSON 格式，包含 deleted 数组）
+/// 响应: 删除结果（JSON 格式，包含 deleted 数组和告警模式下的 violations 数组）
 pub async fn batch_delete_metadata(
     State(app): State<Arc<AppHandle>>,
     Query(params): Query<BatchDeleteMetadataParams>,
@@ -724,13 +1032,11 @@ pub async fn batch_delete_metadata(
     let instances: Vec<InstanceRef> = serde_json::from_str(&params.instances)
         .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
 
-    // 构建实例 ID 列表
-    let instance_ids: Vec<String> = instances.iter()
-        .map(|inst| {
-            let cluster = inst.cluster.as_deref().unwrap_or("DEFAULT");
-            format!("{}#{}#{}#{}", inst.ip, inst.port, cluster, group_name)
-        })
+    // 按 IP、端口和集群解析实例 ID 列表
+    let refs: Vec<(String, i32, String)> = instances.into_iter()
+        .map(|inst| (inst.ip, inst.port, inst.cluster.unwrap_or_else(|| "DEFAULT".to_string())))
         .collect();
+    let instance_ids = resolve_instance_ids(&app, &namespace_id, &group_name, &params.serviceName, &refs).await?;
 
     // 解析 metadata（逗号分隔的键列表）
     let metadata_keys: Vec<String> = params.metadata
@@ -747,9 +1053,14 @@ pub async fn batch_delete_metadata(
         &instance_ids,
         &metadata_keys,
     ).await {
-        Ok(deleted) => Ok(Json(serde_json::json!({
-            "deleted": deleted
-        }))),
+        Ok(deleted) => {
+            let violations = written_metadata_violations(&app, &namespace_id, &group_name, &params.serviceName, &deleted).await;
+            Ok(Json(serde_json::json!({
+                "deleted": deleted,
+                "violations": violations
+            })))
+        }
+        Err(e) if e.contains("Invalid") => Err(axum::http::StatusCode::BAD_REQUEST),
         Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -824,15 +1135,210 @@ pub async fn update_instance_health_status(
     // 解析健康状态
     let healthy = params.healthy == "true" || params.healthy == "True" || params.healthy == "1";
 
-    // 构建实例 ID（格式：ip#port#cluster#serviceName）
+    // 按 IP、端口和集群查找实例
     let cluster_name = params.clusterName.as_deref().unwrap_or("DEFAULT");
-    let instance_id = format!("{}#{}#{}#{}@@{}", params.ip, port, cluster_name, group_name, params.serviceName);
+    let instance_id = find_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &params.ip, port, cluster_name)
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(|inst| inst.instance_id)
+        .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
-    match update_instance_health_impl(&app, &namespace_id, &group_name, &params.serviceName, &instance_id, healthy).await {
+    match update_instance_health_impl(&app, &namespace_id, &group_name, &params.serviceName, &instance_id, healthy, HealthChangeSource::Manual, "updated via API").await {
         Ok(_) => Ok(Response::builder()
             .status(axum::http::StatusCode::OK)
             .body(axum::body::Body::from("ok"))
             .unwrap()),
+        Err(e) => {
+            if e.contains("not found") {
+                Err(axum::http::StatusCode::NOT_FOUND)
+            } else if e.contains("Health check is still working") {
+                Err(axum::http::StatusCode::BAD_REQUEST)
+            } else {
+                Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+}
+
+/// 查询实例健康时间线和可用率
+/// GET /nacos/v1/ns/instance/health/timeline（指定 ip 和 port，查询单个实例）
+/// GET /nacos/v1/ns/service/health/timeline（查询服务的所有实例）
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, ip, port, clusterName, startTime, endTime
+/// 响应: 窗口内每个实例的健康变更事件（含来源和原因）、健康时长和可用率
+pub async fn get_health_timeline(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<HealthTimelineParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    let query = HealthTimelineQuery {
+        namespace_id,
+        group_name,
+        service_name: params.serviceName,
+        ip: params.ip,
+        port: params.port,
+        cluster_name: params.clusterName,
+        start_time: params.startTime,
+        end_time: params.endTime,
+    };
+
+    let response = match get_health_timeline_impl(&app, query).await {
+        Ok(response) => response,
+        Err(e) if e.contains("Invalid") => return Err(axum::http::StatusCode::BAD_REQUEST),
+        Err(_) => return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let instances: Vec<serde_json::Value> = response.instances
+        .iter()
+        .map(|instance| {
+            let events: Vec<serde_json::Value> = instance.events
+                .iter()
+                .map(|event| {
+                    serde_json::json!({
+                        "id": event.id,
+                        "healthy": event.healthy,
+                        "source": event.source,
+                        "reason": event.reason,
+                        "time": event.gmt_create
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "address": instance.address,
+                "healthy": instance.healthy,
+                "healthySeconds": instance.healthy_seconds,
+                "observedSeconds": instance.observed_seconds,
+                "uptimePercent": instance.uptime_percent,
+                "events": events
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "namespaceId": response.namespace_id,
+        "groupName": response.group_name,
+        "serviceName": response.service_name,
+        "startTime": response.start_time,
+        "endTime": response.end_time,
+        "uptimePercent": response.uptime_percent,
+        "instances": instances
+    })))
+}
+
+/// 批量注册 / 注销实例参数
+#[derive(Debug, Deserialize)]
+pub struct BatchInstancesParams {
+    pub serviceName: String,
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    pub instances: String, // JSON 数组，包含实例列表
+}
+
+/// 批量处理结果转换为 Nacos 格式
+fn batch_results_json(results: Vec<BatchInstanceResult>) -> serde_json::Value {
+    let succeeded = results.iter().filter(|r| r.success).count();
+    let failed = results.len() - succeeded;
+    let results: Vec<serde_json::Value> = results
+        .into_iter()
+        .map(|r| {
+            serde_json::json!({
+                "ip": r.ip,
+                "port": r.port,
+                "clusterName": r.cluster_name,
+                "instanceId": r.instance_id,
+                "success": r.success,
+                "message": r.message
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "succeeded": succeeded,
+        "failed": failed,
+        "results": results
+    })
+}
+
+/// 批量注册实例
+/// POST /nacos/v1/ns/instance/batch
+/// 必需参数: serviceName, instances（JSON 数组，每项包含 ip, port，可选 weight, healthy, enabled, ephemeral, clusterName, metadata）
+/// 可选参数: namespaceId, groupName
+/// 响应: 每个实例的注册结果（JSON 格式，包含 succeeded, failed, results 数组和告警模式下的 violations 数组）
+pub async fn batch_register_instances(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<BatchInstancesParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    // 处理命名空间和服务组
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    let instances: Vec<BatchInstanceItem> = serde_json::from_str(&params.instances)
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+
+    match batch_register_instances_impl(&app, &namespace_id, &group_name, &params.serviceName, instances).await {
+        Ok(results) => {
+            // 告警模式下注册成功但违反元数据约定的实例在响应中报告
+            let registered: Vec<String> = results.iter().filter(|r| r.success).filter_map(|r| r.instance_id.clone()).collect();
+            let violations = written_metadata_violations(&app, &namespace_id, &group_name, &params.serviceName, &registered).await;
+            let mut body = batch_results_json(results);
+            body["violations"] = serde_json::json!(violations);
+            Ok(Json(body))
+        }
+        Err(e) if e.contains("not found") => Err(axum::http::StatusCode::NOT_FOUND),
+        Err(e) if e.contains("is disabled") => Err(axum::http::StatusCode::FORBIDDEN),
+        Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// 批量注销实例
+/// DELETE /nacos/v1/ns/instance/batch
+/// 必需参数: serviceName, instances（JSON 数组，每项包含 ip, port，可选 clusterName）
+/// 可选参数: namespaceId, groupName
+/// 响应: 每个实例的注销结果（JSON 格式，包含 succeeded, failed, results 数组）
+pub async fn batch_deregister_instances(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<BatchInstancesParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    // 处理命名空间和服务组
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    let instances: Vec<BatchInstanceRef> = serde_json::from_str(&params.instances)
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+
+    match batch_deregister_instances_impl(&app, &namespace_id, &group_name, &params.serviceName, instances).await {
+        Ok(results) => Ok(Json(batch_results_json(results))),
         Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
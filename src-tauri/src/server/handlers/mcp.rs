@@ -0,0 +1,414 @@
+/**
+ * MCP 服务注册表处理器
+ * 实现 Nacos 3 的 /v3/admin/ai/mcp 和 /v3/console/ai/mcp 接口，响应格式与 v2 OpenAPI 相同：{ code, message, data }
+ */
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Response,
+};
+use axum_extra::extract::Form;
+use serde::Deserialize;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use super::naming_v2::{
+    v2_error, v2_success, CODE_PARAMETER_MISSING, CODE_PARAMETER_VALIDATE_ERROR, CODE_RESOURCE_NOT_FOUND,
+    CODE_SERVER_ERROR,
+};
+use crate::ai::{
+    create_mcp_server as create_mcp_server_impl,
+    update_mcp_server as update_mcp_server_impl,
+    delete_mcp_server as delete_mcp_server_impl,
+    get_mcp_server as get_mcp_server_impl,
+    list_mcp_servers as list_mcp_servers_impl,
+    list_mcp_server_versions as list_mcp_server_versions_impl,
+    update_mcp_tool_spec as update_mcp_tool_spec_impl,
+    set_mcp_server_enabled as set_mcp_server_enabled_impl,
+    McpServerDetail, McpServerQuery, McpServerRequest, McpVersionSummary,
+};
+
+/// 创建或更新 MCP 服务参数
+#[derive(Debug, Deserialize)]
+pub struct McpServerParams {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub serverSpecification: String, // JSON: { name, version, description, protocol, frontProtocol, exportPath, localServerConfig }
+    #[serde(default)]
+    pub toolSpecification: Option<String>, // JSON: { tools: [...], toolsMeta: {...} }
+    #[serde(default)]
+    pub endpointSpecification: Option<String>, // JSON: { type: "REF" | "DIRECT", data: {...} }
+    #[serde(default)]
+    pub latest: Option<String>, // 更新时是否设为最新版本，默认 "false"
+}
+
+/// 查询 MCP 服务参数
+#[derive(Debug, Deserialize)]
+pub struct McpServerQueryParams {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub mcpName: String,
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// MCP 服务列表参数
+#[derive(Debug, Deserialize)]
+pub struct McpServerListParams {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub mcpName: Option<String>,
+    #[serde(default)]
+    pub search: Option<String>, // "accurate" 或 "blur"，默认 "blur"
+    #[serde(default)]
+    pub pageNo: Option<String>,
+    #[serde(default)]
+    pub pageSize: Option<String>,
+}
+
+/// 修改工具定义参数
+#[derive(Debug, Deserialize)]
+pub struct McpToolSpecParams {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub mcpName: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub toolSpecification: String,
+}
+
+/// 启用/禁用 MCP 服务参数
+#[derive(Debug, Deserialize)]
+pub struct McpStatusParams {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub mcpName: String,
+    #[serde(default)]
+    pub enabled: String,
+}
+
+/// 必需参数缺失
+fn parameter_missing(name: &str) -> Response {
+    v2_error(
+        StatusCode::BAD_REQUEST,
+        CODE_PARAMETER_MISSING,
+        "parameter missing",
+        format!("Required parameter '{}' is not present", name),
+    )
+}
+
+/// 参数校验失败
+fn parameter_invalid(detail: impl Into<String>) -> Response {
+    v2_error(StatusCode::BAD_REQUEST, CODE_PARAMETER_VALIDATE_ERROR, "parameter validate error", detail)
+}
+
+/// 将 ai 模块的错误转换为响应
+fn mcp_error(e: String) -> Response {
+    if e.contains("not found") {
+        v2_error(StatusCode::NOT_FOUND, CODE_RESOURCE_NOT_FOUND, "resource not found", e)
+    } else if e.contains("already exists") {
+        v2_error(StatusCode::CONFLICT, CODE_PARAMETER_VALIDATE_ERROR, "resource already exists", e)
+    } else if e.contains("Invalid") {
+        parameter_invalid(e)
+    } else {
+        v2_error(StatusCode::INTERNAL_SERVER_ERROR, CODE_SERVER_ERROR, "server error", e)
+    }
+}
+
+/// 命名空间默认值
+fn namespace_or_default(namespace_id: &str) -> String {
+    if namespace_id.is_empty() {
+        "public".to_string()
+    } else {
+        namespace_id.to_string()
+    }
+}
+
+/// 解析 JSON 参数
+fn parse_json<T: serde::de::DeserializeOwned>(name: &str, value: &str) -> Result<T, Response> {
+    serde_json::from_str(value).map_err(|e| parameter_invalid(format!("Invalid {}: {}", name, e)))
+}
+
+/// 解析分页参数
+fn parse_page(name: &str, value: Option<&str>) -> Result<Option<i64>, Response> {
+    match value.filter(|s| !s.is_empty()) {
+        Some(s) => s.parse::<i64>().map(Some).map_err(|_| parameter_invalid(format!("Invalid {}: {}", name, s))),
+        None => Ok(None),
+    }
+}
+
+/// 解析创建/更新请求
+fn parse_server_request(params: &McpServerParams) -> Result<McpServerRequest, Response> {
+    if params.serverSpecification.is_empty() {
+        return Err(parameter_missing("serverSpecification"));
+    }
+    let tool_spec = match params.toolSpecification.as_deref().filter(|s| !s.is_empty()) {
+        Some(s) => Some(parse_json("toolSpecification", s)?),
+        None => None,
+    };
+    let endpoint_spec = match params.endpointSpecification.as_deref().filter(|s| !s.is_empty()) {
+        Some(s) => Some(parse_json("endpointSpecification", s)?),
+        None => None,
+    };
+
+    Ok(McpServerRequest {
+        namespace_id: namespace_or_default(&params.namespaceId),
+        server_spec: parse_json("serverSpecification", &params.serverSpecification)?,
+        tool_spec,
+        endpoint_spec,
+    })
+}
+
+/// 版本概要 JSON
+fn version_summary_json(version: &McpVersionSummary) -> serde_json::Value {
+    serde_json::json!({
+        "version": version.version,
+        "isLatest": version.is_latest,
+        "releaseDate": version.gmt_create
+    })
+}
+
+/// MCP 服务详情 JSON
+fn detail_json(detail: &McpServerDetail) -> serde_json::Value {
+    let server = &detail.server;
+    let version = &detail.version;
+    serde_json::json!({
+        "id": server.id,
+        "namespaceId": server.namespace_id,
+        "name": server.name,
+        "description": version.description,
+        "protocol": version.protocol,
+        "frontProtocol": version.front_protocol,
+        "enabled": server.enabled,
+        "versionDetail": {
+            "version": version.version,
+            "isLatest": version.version == server.latest_version,
+            "releaseDate": version.gmt_create
+        },
+        "latestPublishedVersion": server.latest_version,
+        "allVersions": detail.all_versions.iter().map(version_summary_json).collect::<Vec<_>>(),
+        "remoteServerConfig": {
+            "exportPath": version.export_path,
+            "serviceRef": version.endpoint_spec
+        },
+        "localServerConfig": version.local_server_config,
+        "toolSpec": version.tool_spec,
+        "backendEndpoints": detail.backend_endpoints
+    })
+}
+
+/// 查询 MCP 服务详情
+/// GET /nacos/v3/admin/ai/mcp
+/// 必需参数: mcpName
+/// 可选参数: namespaceId, version（默认最新版本）
+/// 响应: { code: 0, message: "success", data: { name, protocol, versionDetail, allVersions, toolSpec, backendEndpoints, ... } }
+pub async fn get_mcp_server(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<McpServerQueryParams>,
+) -> Response {
+    if params.mcpName.is_empty() {
+        return parameter_missing("mcpName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+
+    match get_mcp_server_impl(&app, &namespace_id, &params.mcpName, params.version.as_deref()).await {
+        Ok(Some(detail)) => v2_success(detail_json(&detail)),
+        Ok(None) => mcp_error(format!("MCP server {} not found", params.mcpName)),
+        Err(e) => mcp_error(e),
+    }
+}
+
+/// 分页查询 MCP 服务
+/// GET /nacos/v3/admin/ai/mcp/list
+/// 可选参数: namespaceId, mcpName, search（accurate/blur）, pageNo, pageSize
+/// 响应: { code: 0, message: "success", data: { totalCount, pageNumber, pagesAvailable, pageItems } }
+pub async fn list_mcp_servers(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<McpServerListParams>,
+) -> Response {
+    let (page_no, page_size) = match (
+        parse_page("pageNo", params.pageNo.as_deref()),
+        parse_page("pageSize", params.pageSize.as_deref()),
+    ) {
+        (Ok(page_no), Ok(page_size)) => (page_no, page_size),
+        (Err(response), _) | (_, Err(response)) => return response,
+    };
+
+    let query = McpServerQuery {
+        namespace_id: namespace_or_default(&params.namespaceId),
+        name: params.mcpName,
+        search: params.search,
+        page_no,
+        page_size,
+    };
+
+    match list_mcp_servers_impl(&app, query).await {
+        Ok(response) => {
+            let page_items: Vec<serde_json::Value> = response
+                .page_items
+                .iter()
+                .map(|server| {
+                    serde_json::json!({
+                        "id": server.id,
+                        "namespaceId": server.namespace_id,
+                        "name": server.name,
+                        "description": server.description,
+                        "protocol": server.protocol,
+                        "enabled": server.enabled,
+                        "latestPublishedVersion": server.latest_version
+                    })
+                })
+                .collect();
+            v2_success(serde_json::json!({
+                "totalCount": response.total_count,
+                "pageNumber": response.page_number,
+                "pagesAvailable": response.pages_available,
+                "pageItems": page_items
+            }))
+        }
+        Err(e) => mcp_error(e),
+    }
+}
+
+/// 查询 MCP 服务版本列表
+/// GET /nacos/v3/admin/ai/mcp/versions
+/// 必需参数: mcpName
+/// 可选参数: namespaceId
+/// 响应: { code: 0, message: "success", data: [{ version, isLatest, releaseDate }] }
+pub async fn list_mcp_server_versions(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<McpServerQueryParams>,
+) -> Response {
+    if params.mcpName.is_empty() {
+        return parameter_missing("mcpName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+
+    match list_mcp_server_versions_impl(&app, &namespace_id, &params.mcpName).await {
+        Ok(versions) => v2_success(serde_json::json!(versions.iter().map(version_summary_json).collect::<Vec<_>>())),
+        Err(e) => mcp_error(e),
+    }
+}
+
+/// 创建 MCP 服务
+/// POST /nacos/v3/admin/ai/mcp
+/// 必需参数: serverSpecification
+/// 可选参数: namespaceId, toolSpecification, endpointSpecification（远程协议必需）
+/// 响应: { code: 0, message: "success", data: MCP 服务详情 }
+pub async fn create_mcp_server(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<McpServerParams>,
+) -> Response {
+    let request = match parse_server_request(&params) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
+
+    match create_mcp_server_impl(&app, request).await {
+        Ok(detail) => v2_success(detail_json(&detail)),
+        Err(e) => mcp_error(e),
+    }
+}
+
+/// 更新 MCP 服务（版本不存在时新增版本）
+/// PUT /nacos/v3/admin/ai/mcp
+/// 必需参数: serverSpecification
+/// 可选参数: namespaceId, toolSpecification, endpointSpecification, latest
+/// 响应: { code: 0, message: "success", data: MCP 服务详情 }
+pub async fn update_mcp_server(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<McpServerParams>,
+) -> Response {
+    let request = match parse_server_request(&params) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
+    let latest = params.latest.as_deref()
+        .map(|s| s == "true" || s == "True" || s == "1")
+        .unwrap_or(false);
+
+    match update_mcp_server_impl(&app, request, latest).await {
+        Ok(detail) => v2_success(detail_json(&detail)),
+        Err(e) => mcp_error(e),
+    }
+}
+
+/// 删除 MCP 服务及其所有版本
+/// DELETE /nacos/v3/admin/ai/mcp
+/// 必需参数: mcpName
+/// 可选参数: namespaceId
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn delete_mcp_server(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<McpServerQueryParams>,
+) -> Response {
+    if params.mcpName.is_empty() {
+        return parameter_missing("mcpName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+
+    match delete_mcp_server_impl(&app, &namespace_id, &params.mcpName).await {
+        Ok(_) => v2_success(serde_json::json!("ok")),
+        Err(e) => mcp_error(e),
+    }
+}
+
+/// 修改工具定义
+/// PUT /nacos/v3/admin/ai/mcp/tools
+/// 必需参数: mcpName, toolSpecification
+/// 可选参数: namespaceId, version（默认最新版本）
+/// 响应: { code: 0, message: "success", data: MCP 服务详情 }
+pub async fn update_mcp_tool_spec(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<McpToolSpecParams>,
+) -> Response {
+    if params.mcpName.is_empty() {
+        return parameter_missing("mcpName");
+    }
+    if params.toolSpecification.is_empty() {
+        return parameter_missing("toolSpecification");
+    }
+    let tool_spec = match parse_json("toolSpecification", &params.toolSpecification) {
+        Ok(tool_spec) => tool_spec,
+        Err(response) => return response,
+    };
+    let namespace_id = namespace_or_default(&params.namespaceId);
+
+    match update_mcp_tool_spec_impl(&app, &namespace_id, &params.mcpName, params.version.as_deref(), tool_spec).await {
+        Ok(detail) => v2_success(detail_json(&detail)),
+        Err(e) => mcp_error(e),
+    }
+}
+
+/// 启用或禁用 MCP 服务
+/// PUT /nacos/v3/admin/ai/mcp/status
+/// 必需参数: mcpName, enabled
+/// 可选参数: namespaceId
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn set_mcp_server_status(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<McpStatusParams>,
+) -> Response {
+    if params.mcpName.is_empty() {
+        return parameter_missing("mcpName");
+    }
+    let enabled = match params.enabled.as_str() {
+        "true" | "True" | "1" => true,
+        "false" | "False" | "0" => false,
+        "" => return parameter_missing("enabled"),
+        other => return parameter_invalid(format!("Invalid enabled: {}", other)),
+    };
+    let namespace_id = namespace_or_default(&params.namespaceId);
+
+    match set_mcp_server_enabled_impl(&app, &namespace_id, &params.mcpName, enabled).await {
+        Ok(_) => v2_success(serde_json::json!("ok")),
+        Err(e) => mcp_error(e),
+    }
+}
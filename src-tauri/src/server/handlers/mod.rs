@@ -4,12 +4,21 @@
  */
 
 pub mod auth;
+pub mod catalog;
+pub mod cluster;
 pub mod config;
+pub mod consul;
+pub mod eureka;
+pub mod failover;
 pub mod health;
+pub mod host;
 pub mod instance;
+pub mod mcp;
 pub mod namespace;
+pub mod naming_v2;
 pub mod operator;
 pub mod service;
+pub mod simulator;
 
 use axum::http::StatusCode;
 use axum::response::Response;
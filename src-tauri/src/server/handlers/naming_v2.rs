@@ -0,0 +1,1163 @@
+/**
+ * Nacos v2 命名服务 OpenAPI 处理器
+ * 实现 /nacos/v2/ns 下的接口，统一使用 { code, message, data } 响应格式和 v2 错误码，
+ * 与 v1 接口共用 service 模块
+ */
+
+use axum::{
+    extract::{ConnectInfo, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use axum_extra::extract::Form;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::service::{
+    register_instance as register_instance_impl,
+    deregister_instance as deregister_instance_impl,
+    patch_instance as patch_instance_impl,
+    find_instance as find_instance_impl,
+    get_service_instances as get_service_instances_impl,
+    update_instance_health as update_instance_health_impl,
+    HealthChangeSource,
+    heartbeat_instance as heartbeat_instance_impl,
+    batch_update_instance_metadata as batch_update_instance_metadata_impl,
+    batch_delete_instance_metadata as batch_delete_instance_metadata_impl,
+    record_naming_subscriber as record_naming_subscriber_impl,
+    get_naming_switches as get_naming_switches_impl,
+    get_service_list as get_service_list_impl,
+    get_service_detail as get_service_detail_impl,
+    create_service as create_service_impl,
+    update_service as update_service_impl,
+    delete_service as delete_service_impl,
+    get_service_clusters as get_service_clusters_impl,
+    get_service_revision as get_service_revision_impl,
+    compute_instances_checksum,
+    list_clients as list_clients_impl,
+    get_client_detail as get_client_detail_impl,
+    get_client_published_services as get_client_published_services_impl,
+    get_client_subscribed_services as get_client_subscribed_services_impl,
+    get_service_publisher_clients as get_service_publisher_clients_impl,
+    get_service_subscriber_clients as get_service_subscriber_clients_impl,
+    BeatSettings, CreateServiceRequest, InstanceInfo, RegisterInstanceRequest, ServiceClient, ServiceQueryParams,
+    UpdateServiceRequest,
+};
+use super::instance::{with_metadata_violations, written_metadata_violations};
+
+/// v2 错误码（与 Nacos ErrorCode 保持一致）
+pub const CODE_SUCCESS: i32 = 0;
+pub const CODE_PARAMETER_MISSING: i32 = 10000;
+pub const CODE_PARAMETER_VALIDATE_ERROR: i32 = 20002;
+pub const CODE_RESOURCE_NOT_FOUND: i32 = 20004;
+pub const CODE_WEIGHT_ERROR: i32 = 21001;
+pub const CODE_INSTANCE_METADATA_ERROR: i32 = 21002;
+pub const CODE_INSTANCE_NOT_FOUND: i32 = 21003;
+pub const CODE_SERVICE_METADATA_ERROR: i32 = 21005;
+pub const CODE_SERVICE_ALREADY_EXIST: i32 = 21007;
+pub const CODE_SERVICE_NOT_EXIST: i32 = 21008;
+pub const CODE_HEALTH_CHECK_STILL_RUNNING: i32 = 21011;
+pub const CODE_SERVER_ERROR: i32 = 30000;
+
+/// 成功响应
+pub(crate) fn v2_success(data: serde_json::Value) -> Response {
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "code": CODE_SUCCESS,
+            "message": "success",
+            "data": data
+        })),
+    )
+        .into_response()
+}
+
+/// 错误响应，data 中携带具体错误信息
+pub(crate) fn v2_error(status: StatusCode, code: i32, message: &str, detail: impl Into<String>) -> Response {
+    (
+        status,
+        Json(serde_json::json!({
+            "code": code,
+            "message": message,
+            "data": detail.into()
+        })),
+    )
+        .into_response()
+}
+
+/// 必需参数缺失
+fn parameter_missing(name: &str) -> Response {
+    v2_error(
+        StatusCode::BAD_REQUEST,
+        CODE_PARAMETER_MISSING,
+        "parameter missing",
+        format!("Required parameter '{}' is not present", name),
+    )
+}
+
+/// 参数校验失败
+fn parameter_invalid(detail: impl Into<String>) -> Response {
+    v2_error(StatusCode::BAD_REQUEST, CODE_PARAMETER_VALIDATE_ERROR, "parameter validate error", detail)
+}
+
+/// 将 service 模块的错误转换为 v2 错误响应
+fn service_error(e: String) -> Response {
+    if e.contains("Service not found") {
+        v2_error(StatusCode::NOT_FOUND, CODE_SERVICE_NOT_EXIST, "service not exist", e)
+    } else if e.contains("Instance not found") {
+        v2_error(StatusCode::NOT_FOUND, CODE_INSTANCE_NOT_FOUND, "instance not found", e)
+    } else if e.contains("already exists") {
+        v2_error(StatusCode::BAD_REQUEST, CODE_SERVICE_ALREADY_EXIST, "service already exist", e)
+    } else if e.contains("Health check is still working") {
+        v2_error(StatusCode::BAD_REQUEST, CODE_HEALTH_CHECK_STILL_RUNNING, "health check still running", e)
+    } else if e.contains("is disabled") {
+        v2_error(StatusCode::FORBIDDEN, CODE_SERVER_ERROR, "operation disabled", e)
+    } else if e.contains("Invalid metadata") {
+        v2_error(StatusCode::BAD_REQUEST, CODE_INSTANCE_METADATA_ERROR, "instance metadata error", e)
+    } else if e.contains("Invalid") {
+        parameter_invalid(e)
+    } else {
+        v2_error(StatusCode::INTERNAL_SERVER_ERROR, CODE_SERVER_ERROR, "server error", e)
+    }
+}
+
+/// 命名空间默认值
+fn namespace_or_default(namespace_id: &str) -> String {
+    if namespace_id.is_empty() {
+        "public".to_string()
+    } else {
+        namespace_id.to_string()
+    }
+}
+
+/// 服务组默认值
+fn group_or_default(group_name: &str) -> String {
+    if group_name.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        group_name.to_string()
+    }
+}
+
+/// 集群默认值
+fn cluster_or_default(cluster_name: &str) -> String {
+    if cluster_name.is_empty() {
+        "DEFAULT".to_string()
+    } else {
+        cluster_name.to_string()
+    }
+}
+
+/// 解析布尔参数
+fn parse_bool(value: &str) -> bool {
+    value == "true" || value == "True" || value == "1"
+}
+
+/// 解析元数据 JSON
+fn parse_metadata(metadata: &str) -> Result<HashMap<String, String>, String> {
+    serde_json::from_str(metadata).map_err(|e| format!("Invalid metadata: {}", e))
+}
+
+/// 实例转换为 v2 格式，心跳配置按心跳周期开关和实例元数据计算
+fn instance_to_json(inst: &InstanceInfo, client_beat_interval: i64) -> serde_json::Value {
+    let beat = BeatSettings::for_instance(inst, client_beat_interval);
+    serde_json::json!({
+        "instanceId": inst.instance_id,
+        "ip": inst.ip,
+        "port": inst.port,
+        "weight": inst.weight,
+        "healthy": inst.healthy,
+        "enabled": inst.enabled,
+        "ephemeral": inst.ephemeral,
+        "clusterName": inst.cluster_name,
+        "serviceName": format!("{}@@{}", inst.group_name, inst.service_name),
+        "metadata": inst.metadata.as_ref()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+            .unwrap_or(serde_json::json!({})),
+        "instanceHeartBeatInterval": beat.interval,
+        "instanceHeartBeatTimeOut": beat.heart_beat_timeout,
+        "ipDeleteTimeout": beat.ip_delete_timeout
+    })
+}
+
+/// v2 实例参数（所有字段可选，由处理器校验必需参数以返回 v2 错误码）
+#[derive(Debug, Default, Deserialize)]
+pub struct InstanceV2Params {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub serviceName: String,
+    #[serde(default)]
+    pub clusterName: String,
+    #[serde(default)]
+    pub ip: String,
+    #[serde(default)]
+    pub port: String,
+    #[serde(default)]
+    pub weight: Option<String>,
+    #[serde(default)]
+    pub healthy: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<String>,
+    #[serde(default)]
+    pub ephemeral: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<String>,
+    #[serde(default)]
+    pub beat: Option<String>, // 心跳信息 JSON 字符串
+}
+
+/// 校验 serviceName、ip、port 并返回端口
+fn require_instance_params(params: &InstanceV2Params) -> Result<i32, Response> {
+    if params.serviceName.is_empty() {
+        return Err(parameter_missing("serviceName"));
+    }
+    if params.ip.is_empty() {
+        return Err(parameter_missing("ip"));
+    }
+    if params.port.is_empty() {
+        return Err(parameter_missing("port"));
+    }
+    params.port.parse::<i32>()
+        .ok()
+        .filter(|port| (0..=65535).contains(port))
+        .ok_or_else(|| parameter_invalid(format!("Invalid port: {}", params.port)))
+}
+
+/// 解析权重（0 ~ 10000）
+fn parse_weight(weight: Option<&str>) -> Result<Option<f64>, Response> {
+    match weight.filter(|w| !w.is_empty()) {
+        Some(w) => w.parse::<f64>()
+            .ok()
+            .filter(|w| (0.0..=10000.0).contains(w))
+            .map(Some)
+            .ok_or_else(|| v2_error(StatusCode::BAD_REQUEST, CODE_WEIGHT_ERROR, "weight error", format!("Invalid weight: {}", w))),
+        None => Ok(None),
+    }
+}
+
+/// 注册实例
+/// POST /nacos/v2/ns/instance
+/// 必需参数: serviceName, ip, port
+/// 可选参数: namespaceId, groupName, clusterName, weight, healthy, enabled, ephemeral, metadata
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn register_instance(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<InstanceV2Params>,
+) -> Response {
+    let port = match require_instance_params(&params) {
+        Ok(port) => port,
+        Err(response) => return response,
+    };
+    let weight = match parse_weight(params.weight.as_deref()) {
+        Ok(weight) => weight,
+        Err(response) => return response,
+    };
+    let metadata = params.metadata.filter(|m| !m.is_empty());
+    if let Some(ref metadata) = metadata {
+        if let Err(e) = parse_metadata(metadata) {
+            return v2_error(StatusCode::BAD_REQUEST, CODE_INSTANCE_METADATA_ERROR, "instance metadata error", e);
+        }
+    }
+
+    let request = RegisterInstanceRequest {
+        namespace_id: namespace_or_default(&params.namespaceId),
+        group_name: group_or_default(&params.groupName),
+        service_name: params.serviceName,
+        ip: params.ip,
+        port,
+        weight,
+        healthy: params.healthy.as_deref().map(parse_bool),
+        enabled: params.enabled.as_deref().map(parse_bool),
+        ephemeral: params.ephemeral.as_deref().map(parse_bool),
+        cluster_name: Some(cluster_or_default(&params.clusterName)),
+        metadata,
+    };
+
+    let (namespace_id, group_name, service_name) =
+        (request.namespace_id.clone(), request.group_name.clone(), request.service_name.clone());
+    match register_instance_impl(&app, request).await {
+        Ok(instance) => {
+            let violations =
+                written_metadata_violations(&app, &namespace_id, &group_name, &service_name, &[instance.instance_id]).await;
+            with_metadata_violations(v2_success(serde_json::json!("ok")), &violations)
+        }
+        Err(e) => service_error(e),
+    }
+}
+
+/// 注销实例
+/// DELETE /nacos/v2/ns/instance
+/// 必需参数: serviceName, ip, port
+/// 可选参数: namespaceId, groupName, clusterName, ephemeral
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn deregister_instance(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<InstanceV2Params>,
+) -> Response {
+    let port = match require_instance_params(&params) {
+        Ok(port) => port,
+        Err(response) => return response,
+    };
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+    let cluster_name = cluster_or_default(&params.clusterName);
+
+    let instance = match find_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &params.ip, port, &cluster_name).await {
+        Ok(instance) => instance,
+        Err(e) => return service_error(e),
+    };
+
+    // 实例不存在时按 Nacos 行为直接返回成功
+    if let Some(instance) = instance {
+        if let Err(e) = deregister_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &instance.instance_id).await {
+            return service_error(e);
+        }
+    }
+
+    v2_success(serde_json::json!("ok"))
+}
+
+/// 更新实例
+/// PUT /nacos/v2/ns/instance
+/// 必需参数: serviceName, ip, port
+/// 可选参数: namespaceId, groupName, clusterName, weight, enabled, metadata
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn update_instance(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<InstanceV2Params>,
+) -> Response {
+    let port = match require_instance_params(&params) {
+        Ok(port) => port,
+        Err(response) => return response,
+    };
+    let weight = match parse_weight(params.weight.as_deref()) {
+        Ok(weight) => weight,
+        Err(response) => return response,
+    };
+    let metadata = params.metadata.filter(|m| !m.is_empty());
+    if let Some(ref metadata) = metadata {
+        if let Err(e) = parse_metadata(metadata) {
+            return v2_error(StatusCode::BAD_REQUEST, CODE_INSTANCE_METADATA_ERROR, "instance metadata error", e);
+        }
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+    let cluster_name = cluster_or_default(&params.clusterName);
+
+    let instance = match find_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &params.ip, port, &cluster_name).await {
+        Ok(Some(instance)) => instance,
+        Ok(None) => return service_error("Instance not found".to_string()),
+        Err(e) => return service_error(e),
+    };
+
+    match patch_instance_impl(
+        &app,
+        &namespace_id,
+        &group_name,
+        &params.serviceName,
+        &instance.instance_id,
+        weight,
+        params.enabled.as_deref().map(parse_bool),
+        None,
+        metadata,
+    ).await {
+        Ok(_) => {
+            let violations =
+                written_metadata_violations(&app, &namespace_id, &group_name, &params.serviceName, &[instance.instance_id]).await;
+            with_metadata_violations(v2_success(serde_json::json!("ok")), &violations)
+        }
+        Err(e) => service_error(e),
+    }
+}
+
+/// 查询实例详情
+/// GET /nacos/v2/ns/instance
+/// 必需参数: serviceName, ip, port
+/// 可选参数: namespaceId, groupName, clusterName
+/// 响应: { code: 0, message: "success", data: 实例信息 }
+pub async fn get_instance(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<InstanceV2Params>,
+) -> Response {
+    let port = match require_instance_params(&params) {
+        Ok(port) => port,
+        Err(response) => return response,
+    };
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+    let cluster_name = cluster_or_default(&params.clusterName);
+
+    match find_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &params.ip, port, &cluster_name).await {
+        Ok(Some(instance)) => {
+            let client_beat_interval = get_naming_switches_impl(&app).await.client_beat_interval;
+            v2_success(instance_to_json(&instance, client_beat_interval))
+        }
+        Ok(None) => service_error("Instance not found".to_string()),
+        Err(e) => service_error(e),
+    }
+}
+
+/// v2 实例列表参数
+#[derive(Debug, Deserialize)]
+pub struct ListInstancesV2Params {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub serviceName: String,
+    #[serde(default)]
+    pub clusterName: String, // 集群列表，逗号分隔
+    #[serde(default)]
+    pub ip: String, // 订阅者 IP
+    #[serde(default)]
+    pub port: String, // 订阅者端口
+    #[serde(default)]
+    pub healthyOnly: Option<String>,
+    #[serde(default)]
+    pub app: Option<String>,
+    #[serde(default)]
+    pub checksum: Option<String>, // 客户端上次收到的校验和
+}
+
+/// 查询实例列表
+/// GET /nacos/v2/ns/instance/list
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, clusterName, ip, port, healthyOnly, app
+/// 响应: { code: 0, message: "success", data: 服务信息（包含 hosts） }
+pub async fn list_instances(
+    State(app): State<Arc<AppHandle>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Query(params): Query<ListInstancesV2Params>,
+    headers: axum::http::HeaderMap,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+
+    // 记录订阅者信息（ip 优先取参数，其次取请求头，最后取连接的对端地址）
+    let client_ip = Some(params.ip.clone())
+        .filter(|ip| !ip.is_empty())
+        .or_else(|| {
+            headers
+                .get("X-Forwarded-For")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.split(',').next())
+                .map(|s| s.trim().to_string())
+        })
+        .or_else(|| connect_info.map(|ConnectInfo(addr)| addr.ip().to_string()));
+    let client_port = params.port.parse::<i32>().unwrap_or(0);
+    let agent = headers
+        .get("User-Agent")
+        .or_else(|| headers.get("Client-Version"))
+        .and_then(|h| h.to_str().ok());
+
+    // 无法确定调用方地址时不记录；推送开关只影响推送，不影响订阅者记录
+    if let Some(client_ip) = client_ip.as_deref() {
+        if let Err(e) = record_naming_subscriber_impl(
+            &app,
+            &namespace_id,
+            &group_name,
+            &params.serviceName,
+            client_ip,
+            client_port,
+            params.app.as_deref(),
+            agent,
+            &params.clusterName,
+        ).await {
+            tracing::warn!("Failed to record naming subscriber {} for {}: {}", client_ip, params.serviceName, e);
+        }
+    }
+
+    let healthy_only = params.healthyOnly.as_deref().map(parse_bool).unwrap_or(false);
+    let clusters: Vec<&str> = params.clusterName.split(',').filter(|c| !c.is_empty()).collect();
+
+    match get_service_instances_impl(&app, &namespace_id, &group_name, &params.serviceName).await {
+        Ok(response) => {
+            let instances: Vec<&InstanceInfo> = response.instances
+                .iter()
+                .filter(|inst| clusters.is_empty() || clusters.contains(&inst.cluster_name.as_str()))
+                .filter(|inst| !healthy_only || inst.healthy)
+                .collect();
+
+            let checksum = compute_instances_checksum(instances.iter().copied());
+            let revision = get_service_revision_impl(&app, &namespace_id, &group_name, &params.serviceName).await;
+
+            if params.checksum.as_deref() == Some(checksum.as_str()) {
+                return v2_success(serde_json::json!({
+                    "name": format!("{}@@{}", group_name, params.serviceName),
+                    "groupName": group_name,
+                    "clusters": params.clusterName,
+                    "cacheMillis": 10000,
+                    "lastRefTime": revision.last_modified_millis,
+                    "revision": revision.revision,
+                    "checksum": checksum,
+                    "unchanged": true
+                }));
+            }
+
+            let client_beat_interval = get_naming_switches_impl(&app).await.client_beat_interval;
+            let hosts: Vec<serde_json::Value> = instances
+                .into_iter()
+                .map(|inst| instance_to_json(inst, client_beat_interval))
+                .collect();
+
+            v2_success(serde_json::json!({
+                "name": format!("{}@@{}", group_name, params.serviceName),
+                "groupName": group_name,
+                "clusters": params.clusterName,
+                "cacheMillis": 10000,
+                "hosts": hosts,
+                "lastRefTime": revision.last_modified_millis,
+                "revision": revision.revision,
+                "checksum": checksum,
+                "unchanged": false,
+                "allIPs": false,
+                "reachProtectionThreshold": false,
+                "valid": true
+            }))
+        }
+        Err(e) => service_error(e),
+    }
+}
+
+/// 实例心跳
+/// PUT /nacos/v2/ns/instance/beat
+/// 必需参数: serviceName，以及 beat 或 ip + port
+/// 可选参数: namespaceId, groupName, clusterName
+/// 响应: { code: 0, message: "success", data: { clientBeatInterval, code, lightBeatEnabled } }
+/// 实例不存在时 data.code 为 20404，客户端据此重新注册
+pub async fn heartbeat(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<InstanceV2Params>,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+
+    // beat 参数中的 ip / port / cluster 优先
+    let beat_json = params.beat.as_deref()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .unwrap_or(serde_json::json!({}));
+    let ip = beat_json.get("ip")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or(params.ip.clone());
+    let port = beat_json.get("port")
+        .and_then(|v| v.as_i64())
+        .map(|p| p as i32)
+        .or_else(|| params.port.parse::<i32>().ok());
+    let cluster_name = beat_json.get("cluster")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| cluster_or_default(&params.clusterName));
+
+    let port = match port {
+        Some(port) if !ip.is_empty() => port,
+        _ => return parameter_missing("ip/port"),
+    };
+
+    // 心跳表示实例健康，实例不存在时返回 20404 以便客户端重新注册
+    let code = match heartbeat_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &ip, port, &cluster_name).await {
+        Ok(true) => 10200,
+        Ok(false) => 20404,
+        Err(e) => return service_error(e),
+    };
+
+    v2_success(serde_json::json!({
+        "clientBeatInterval": get_naming_switches_impl(&app).await.client_beat_interval,
+        "code": code,
+        "lightBeatEnabled": true
+    }))
+}
+
+/// v2 批量元数据参数
+#[derive(Debug, Deserialize)]
+pub struct BatchMetadataV2Params {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub serviceName: String,
+    #[serde(default)]
+    pub consistencyType: Option<String>,
+    #[serde(default)]
+    pub instances: Option<String>, // JSON 数组 [{ip, port, clusterName}]，为空表示全部实例
+    #[serde(default)]
+    pub metadata: String, // JSON 对象
+}
+
+/// 解析批量元数据请求涉及的实例 ID
+async fn resolve_batch_instance_ids(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    params: &BatchMetadataV2Params,
+) -> Result<Vec<String>, Response> {
+    #[derive(Debug, Deserialize)]
+    struct InstanceRef {
+        ip: String,
+        port: i32,
+        #[serde(default, alias = "cluster")]
+        clusterName: Option<String>,
+    }
+
+    let refs: Vec<InstanceRef> = match params.instances.as_deref().filter(|s| !s.trim().is_empty()) {
+        Some(s) => serde_json::from_str(s).map_err(|e| parameter_invalid(format!("Invalid instances: {}", e)))?,
+        None => Vec::new(),
+    };
+
+    let response = get_service_instances_impl(app, namespace_id, group_name, &params.serviceName)
+        .await
+        .map_err(service_error)?;
+
+    Ok(response.instances
+        .into_iter()
+        .filter(|inst| {
+            refs.is_empty() || refs.iter().any(|r| {
+                r.ip == inst.ip
+                    && r.port == inst.port
+                    && r.clusterName.as_deref().unwrap_or("DEFAULT") == inst.cluster_name
+            })
+        })
+        .map(|inst| inst.instance_id)
+        .collect())
+}
+
+/// 批量更新实例元数据
+/// PUT /nacos/v2/ns/instance/metadata/batch
+/// 必需参数: serviceName, metadata
+/// 可选参数: namespaceId, groupName, consistencyType, instances
+/// 响应: { code: 0, message: "success", data: { updated: [instanceId], violations: [告警模式下的元数据违规] } }
+pub async fn batch_update_metadata(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<BatchMetadataV2Params>,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    if params.metadata.is_empty() {
+        return parameter_missing("metadata");
+    }
+    let metadata = match parse_metadata(&params.metadata) {
+        Ok(metadata) => metadata,
+        Err(e) => return v2_error(StatusCode::BAD_REQUEST, CODE_INSTANCE_METADATA_ERROR, "instance metadata error", e),
+    };
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+
+    let instance_ids = match resolve_batch_instance_ids(&app, &namespace_id, &group_name, &params).await {
+        Ok(ids) => ids,
+        Err(response) => return response,
+    };
+
+    match batch_update_instance_metadata_impl(&app, &namespace_id, &group_name, &params.serviceName, &instance_ids, &metadata).await {
+        Ok(updated) => {
+            let violations = written_metadata_violations(&app, &namespace_id, &group_name, &params.serviceName, &updated).await;
+            v2_success(serde_json::json!({ "updated": updated, "violations": violations }))
+        }
+        Err(e) => service_error(e),
+    }
+}
+
+/// 批量删除实例元数据
+/// DELETE /nacos/v2/ns/instance/metadata/batch
+/// 必需参数: serviceName, metadata（需要删除的键）
+/// 可选参数: namespaceId, groupName, consistencyType, instances
+/// 响应: { code: 0, message: "success", data: { updated: [instanceId], violations: [告警模式下的元数据违规] } }
+pub async fn batch_delete_metadata(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<BatchMetadataV2Params>,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    if params.metadata.is_empty() {
+        return parameter_missing("metadata");
+    }
+    let metadata = match parse_metadata(&params.metadata) {
+        Ok(metadata) => metadata,
+        Err(e) => return v2_error(StatusCode::BAD_REQUEST, CODE_INSTANCE_METADATA_ERROR, "instance metadata error", e),
+    };
+    let metadata_keys: Vec<String> = metadata.into_keys().collect();
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+
+    let instance_ids = match resolve_batch_instance_ids(&app, &namespace_id, &group_name, &params).await {
+        Ok(ids) => ids,
+        Err(response) => return response,
+    };
+
+    match batch_delete_instance_metadata_impl(&app, &namespace_id, &group_name, &params.serviceName, &instance_ids, &metadata_keys).await {
+        Ok(updated) => {
+            let violations = written_metadata_violations(&app, &namespace_id, &group_name, &params.serviceName, &updated).await;
+            v2_success(serde_json::json!({ "updated": updated, "violations": violations }))
+        }
+        Err(e) => service_error(e),
+    }
+}
+
+/// 更新实例健康状态
+/// PUT /nacos/v2/ns/health/instance
+/// 必需参数: serviceName, ip, port, healthy
+/// 可选参数: namespaceId, groupName, clusterName
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn update_instance_health(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<InstanceV2Params>,
+) -> Response {
+    let port = match require_instance_params(&params) {
+        Ok(port) => port,
+        Err(response) => return response,
+    };
+    let healthy = match params.healthy.as_deref().filter(|h| !h.is_empty()) {
+        Some(healthy) => parse_bool(healthy),
+        None => return parameter_missing("healthy"),
+    };
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+    let cluster_name = cluster_or_default(&params.clusterName);
+
+    let instance = match find_instance_impl(&app, &namespace_id, &group_name, &params.serviceName, &params.ip, port, &cluster_name).await {
+        Ok(Some(instance)) => instance,
+        Ok(None) => return service_error("Instance not found".to_string()),
+        Err(e) => return service_error(e),
+    };
+
+    match update_instance_health_impl(&app, &namespace_id, &group_name, &params.serviceName, &instance.instance_id, healthy, HealthChangeSource::Manual, "updated via API").await {
+        Ok(_) => v2_success(serde_json::json!("ok")),
+        Err(e) => service_error(e),
+    }
+}
+
+/// v2 服务参数
+#[derive(Debug, Deserialize)]
+pub struct ServiceV2Params {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub serviceName: String,
+    #[serde(default)]
+    pub protectThreshold: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<String>,
+    #[serde(default)]
+    pub selector: Option<String>,
+    #[serde(default)]
+    pub ephemeral: Option<String>,
+}
+
+/// 校验服务参数，返回 (protectThreshold, metadata, selector)
+fn parse_service_params(params: &ServiceV2Params) -> Result<(Option<f64>, Option<String>, Option<String>), Response> {
+    if params.serviceName.is_empty() {
+        return Err(parameter_missing("serviceName"));
+    }
+    let protect_threshold = match params.protectThreshold.as_deref().filter(|s| !s.is_empty()) {
+        Some(s) => Some(
+            s.parse::<f64>()
+                .ok()
+                .filter(|t| (0.0..=1.0).contains(t))
+                .ok_or_else(|| parameter_invalid(format!("Invalid protectThreshold: {}", s)))?,
+        ),
+        None => None,
+    };
+    let metadata = params.metadata.clone().filter(|m| !m.is_empty());
+    if let Some(ref metadata) = metadata {
+        parse_metadata(metadata)
+            .map_err(|e| v2_error(StatusCode::BAD_REQUEST, CODE_SERVICE_METADATA_ERROR, "service metadata error", e))?;
+    }
+    let selector = params.selector.clone().filter(|s| !s.is_empty());
+
+    Ok((protect_threshold, metadata, selector))
+}
+
+/// 创建服务
+/// POST /nacos/v2/ns/service
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, protectThreshold, metadata, selector, ephemeral
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn create_service(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<ServiceV2Params>,
+) -> Response {
+    let (protect_threshold, metadata, selector) = match parse_service_params(&params) {
+        Ok(parsed) => parsed,
+        Err(response) => return response,
+    };
+    let selector_type = selector_type_of(selector.as_deref());
+
+    let request = CreateServiceRequest {
+        namespace_id: namespace_or_default(&params.namespaceId),
+        group_name: group_or_default(&params.groupName),
+        service_name: params.serviceName,
+        metadata,
+        protect_threshold,
+        selector_type,
+        selector,
+    };
+
+    match create_service_impl(&app, request).await {
+        Ok(_) => v2_success(serde_json::json!("ok")),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 更新服务
+/// PUT /nacos/v2/ns/service
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, protectThreshold, metadata, selector
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn update_service(
+    State(app): State<Arc<AppHandle>>,
+    Form(params): Form<ServiceV2Params>,
+) -> Response {
+    let (protect_threshold, metadata, selector) = match parse_service_params(&params) {
+        Ok(parsed) => parsed,
+        Err(response) => return response,
+    };
+    let selector_type = selector_type_of(selector.as_deref());
+
+    let request = UpdateServiceRequest {
+        namespace_id: namespace_or_default(&params.namespaceId),
+        group_name: group_or_default(&params.groupName),
+        service_name: params.serviceName,
+        metadata,
+        protect_threshold,
+        selector_type,
+        selector,
+    };
+
+    match update_service_impl(&app, request).await {
+        Ok(_) => v2_success(serde_json::json!("ok")),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 选择器类型
+fn selector_type_of(selector: Option<&str>) -> Option<String> {
+    selector
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(|t| t.to_string()))
+}
+
+/// 删除服务
+/// DELETE /nacos/v2/ns/service
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName
+/// 响应: { code: 0, message: "success", data: "ok" }
+pub async fn delete_service(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ServiceV2Params>,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+
+    match get_service_detail_impl(&app, &namespace_id, &group_name, &params.serviceName).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return service_error("Service not found".to_string()),
+        Err(e) => return service_error(e),
+    }
+
+    match delete_service_impl(&app, &namespace_id, &group_name, &params.serviceName).await {
+        Ok(_) => v2_success(serde_json::json!("ok")),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 查询服务详情
+/// GET /nacos/v2/ns/service
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName
+/// 响应: { code: 0, message: "success", data: 服务信息（包含 clusterMap） }
+pub async fn get_service(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ServiceV2Params>,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+
+    let service = match get_service_detail_impl(&app, &namespace_id, &group_name, &params.serviceName).await {
+        Ok(Some(service)) => service,
+        Ok(None) => return service_error("Service not found".to_string()),
+        Err(e) => return service_error(e),
+    };
+
+    let clusters = match get_service_clusters_impl(&app, &namespace_id, &group_name, &params.serviceName).await {
+        Ok(clusters) => clusters,
+        Err(e) => return service_error(e),
+    };
+
+    let mut cluster_map = serde_json::Map::new();
+    for cluster in clusters {
+        cluster_map.insert(cluster.cluster_name.clone(), serde_json::json!({
+            "healthChecker": serde_json::from_str::<serde_json::Value>(&cluster.health_checker)
+                .unwrap_or(serde_json::json!({ "type": "NONE" })),
+            "checkPort": cluster.check_port,
+            "useInstancePortForCheck": cluster.use_instance_port,
+            "metadata": cluster.metadata.as_ref()
+                .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                .unwrap_or(serde_json::json!({}))
+        }));
+    }
+
+    v2_success(serde_json::json!({
+        "namespaceId": service.namespace_id,
+        "groupName": service.group_name,
+        "serviceName": service.service_name,
+        "clusterMap": cluster_map,
+        "metadata": service.metadata.as_ref()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+            .unwrap_or(serde_json::json!({})),
+        "protectThreshold": service.protect_threshold,
+        "selector": service.selector.as_deref()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+            .unwrap_or(serde_json::json!({ "type": "none" })),
+        "ephemeral": false
+    }))
+}
+
+/// v2 服务列表参数
+#[derive(Debug, Deserialize)]
+pub struct ListServicesV2Params {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub selector: Option<String>,
+    #[serde(default)]
+    pub pageNo: Option<String>,
+    #[serde(default)]
+    pub pageSize: Option<String>,
+}
+
+/// 查询服务列表
+/// GET /nacos/v2/ns/service/list
+/// 可选参数: namespaceId, groupName, selector, pageNo, pageSize
+/// 响应: { code: 0, message: "success", data: { count, services: [serviceName] } }
+pub async fn list_services(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ListServicesV2Params>,
+) -> Response {
+    let page_no = params.pageNo.as_deref().and_then(|s| s.parse::<i64>().ok()).filter(|p| *p > 0).unwrap_or(1);
+    let page_size = params.pageSize.as_deref().and_then(|s| s.parse::<i64>().ok()).filter(|p| *p > 0).unwrap_or(20);
+
+    let query = ServiceQueryParams {
+        namespace_id: Some(namespace_or_default(&params.namespaceId)),
+        group_name: Some(group_or_default(&params.groupName)),
+        service_name: None,
+        page_no: Some(page_no),
+        page_size: Some(page_size),
+    };
+
+    match get_service_list_impl(&app, query).await {
+        Ok(response) => {
+            let services: Vec<String> = response.page_items
+                .into_iter()
+                .map(|service| service.service_name)
+                .collect();
+            v2_success(serde_json::json!({
+                "count": response.total_count,
+                "services": services
+            }))
+        }
+        Err(e) => service_error(e),
+    }
+}
+
+/// 客户端参数
+#[derive(Debug, Deserialize)]
+pub struct ClientV2Params {
+    #[serde(default)]
+    pub clientId: String,
+}
+
+/// 服务下客户端查询参数
+#[derive(Debug, Deserialize)]
+pub struct ServiceClientsV2Params {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String,
+    #[serde(default)]
+    pub serviceName: String,
+    #[serde(default)]
+    pub ip: Option<String>,
+    #[serde(default)]
+    pub port: Option<String>,
+}
+
+/// 查询客户端列表
+/// GET /nacos/v2/ns/client/list
+/// 响应: { code: 0, message: "success", data: [clientId] }
+pub async fn list_clients(State(app): State<Arc<AppHandle>>) -> Response {
+    match list_clients_impl(&app).await {
+        Ok(client_ids) => v2_success(serde_json::json!(client_ids)),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 查询客户端详情
+/// GET /nacos/v2/ns/client
+/// 必需参数: clientId
+/// 响应: { code: 0, message: "success", data: 客户端信息 }
+pub async fn get_client(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ClientV2Params>,
+) -> Response {
+    if params.clientId.is_empty() {
+        return parameter_missing("clientId");
+    }
+
+    match get_client_detail_impl(&app, &params.clientId).await {
+        Ok(Some(client)) => v2_success(serde_json::json!({
+            "clientId": client.client_id,
+            "ephemeral": client.ephemeral,
+            "lastUpdatedTime": client.last_updated_time * 1000,
+            "clientType": "ipport",
+            "connectType": "http",
+            "clientIp": client.client_ip,
+            "clientPort": client.client_port
+        })),
+        Ok(None) => v2_error(StatusCode::NOT_FOUND, CODE_RESOURCE_NOT_FOUND, "resource not found", format!("clientId {} not exist", params.clientId)),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 查询客户端发布的服务
+/// GET /nacos/v2/ns/client/publish/list
+/// 必需参数: clientId
+/// 响应: { code: 0, message: "success", data: [{ namespace, group, serviceName, registeredInstance }] }
+pub async fn list_client_published_services(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ClientV2Params>,
+) -> Response {
+    if params.clientId.is_empty() {
+        return parameter_missing("clientId");
+    }
+
+    match get_client_published_services_impl(&app, &params.clientId).await {
+        Ok(services) => v2_success(serde_json::json!(services
+            .iter()
+            .map(|s| serde_json::json!({
+                "namespace": s.namespace_id,
+                "group": s.group_name,
+                "serviceName": s.service_name,
+                "registeredInstance": {
+                    "ip": s.ip,
+                    "port": s.port,
+                    "cluster": s.cluster_name
+                }
+            }))
+            .collect::<Vec<_>>())),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 查询客户端订阅的服务
+/// GET /nacos/v2/ns/client/subscribe/list
+/// 必需参数: clientId
+/// 响应: { code: 0, message: "success", data: [{ namespace, group, serviceName, subscriberInfo }] }
+pub async fn list_client_subscribed_services(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ClientV2Params>,
+) -> Response {
+    if params.clientId.is_empty() {
+        return parameter_missing("clientId");
+    }
+
+    match get_client_subscribed_services_impl(&app, &params.clientId).await {
+        Ok(services) => v2_success(serde_json::json!(services
+            .iter()
+            .map(|s| serde_json::json!({
+                "namespace": s.namespace_id,
+                "group": s.group_name,
+                "serviceName": s.service_name,
+                "subscriberInfo": {
+                    "app": s.app_name,
+                    "agent": s.agent,
+                    "addr": s.address
+                }
+            }))
+            .collect::<Vec<_>>())),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 服务下客户端转换为 v2 格式
+fn service_clients_to_json(clients: Vec<ServiceClient>) -> serde_json::Value {
+    serde_json::json!(clients
+        .iter()
+        .map(|c| serde_json::json!({
+            "clientId": c.client_id,
+            "ip": c.ip,
+            "port": c.port
+        }))
+        .collect::<Vec<_>>())
+}
+
+/// 查询发布指定服务的客户端
+/// GET /nacos/v2/ns/client/service/publisher/list
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, ip, port
+/// 响应: { code: 0, message: "success", data: [{ clientId, ip, port }] }
+pub async fn list_service_publisher_clients(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ServiceClientsV2Params>,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+    let ip = params.ip.as_deref().filter(|s| !s.is_empty());
+    let port = params.port.as_deref().and_then(|s| s.parse::<i32>().ok());
+
+    match get_service_publisher_clients_impl(&app, &namespace_id, &group_name, &params.serviceName, ip, port).await {
+        Ok(clients) => v2_success(service_clients_to_json(clients)),
+        Err(e) => service_error(e),
+    }
+}
+
+/// 查询订阅指定服务的客户端
+/// GET /nacos/v2/ns/client/service/subscriber/list
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, ip, port
+/// 响应: { code: 0, message: "success", data: [{ clientId, ip, port }] }
+pub async fn list_service_subscriber_clients(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ServiceClientsV2Params>,
+) -> Response {
+    if params.serviceName.is_empty() {
+        return parameter_missing("serviceName");
+    }
+    let namespace_id = namespace_or_default(&params.namespaceId);
+    let group_name = group_or_default(&params.groupName);
+    let ip = params.ip.as_deref().filter(|s| !s.is_empty());
+    let port = params.port.as_deref().and_then(|s| s.parse::<i32>().ok());
+
+    match get_service_subscriber_clients_impl(&app, &namespace_id, &group_name, &params.serviceName, ip, port).await {
+        Ok(clients) => v2_success(service_clients_to_json(clients)),
+        Err(e) => service_error(e),
+    }
+}
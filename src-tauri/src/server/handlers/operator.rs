@@ -12,6 +12,11 @@ use serde::Deserialize;
 use std::sync::Arc;
 use tauri::AppHandle;
 
+use crate::service::{
+    get_naming_switches as get_naming_switches_impl,
+    update_naming_switch as update_naming_switch_impl,
+};
+
 /// 查询系统开关参数
 #[derive(Debug, Deserialize)]
 pub struct GetSwitchesParams {
@@ -39,22 +44,24 @@ pub struct GetServersParams {
 /// GET /nacos/v1/ns/operator/switches
 /// 响应: 系统开关配置（JSON 格式）
 pub async fn get_switches(
-    _State(_app): State<Arc<AppHandle>>,
-    _Query(_params): Query<GetSwitchesParams>,
+    State(app): State<Arc<AppHandle>>,
+    Query(_params): Query<GetSwitchesParams>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    // Standalone 模式返回默认配置
+    let current = get_naming_switches_impl(&app).await;
+
+    // Standalone 模式，可修改的开关取自持久化配置，其余返回默认值
     let switches = serde_json::json!({
         "name": "00-00---000-NACOS_SWITCH_DOMAIN-000---00-00",
         "masters": null,
         "adWeightMap": {},
         "defaultPushCacheMillis": 10000,
-        "clientBeatInterval": 5000,
+        "clientBeatInterval": current.client_beat_interval,
         "defaultCacheMillis": 3000,
         "distroThreshold": 0.7,
-        "healthCheckEnabled": true,
+        "healthCheckEnabled": current.health_check_enabled,
         "distroEnabled": false, // Standalone 模式禁用分布式
         "enableStandalone": true,
-        "pushEnabled": true,
+        "pushEnabled": current.push_enabled,
         "checkTimes": 3,
         "httpHealthParams": {
             "max": 5000,
@@ -74,7 +81,7 @@ pub async fn get_switches(
         "incrementalList": [],
         "serverStatusSynchronizationPeriodMillis": 15000,
         "serviceStatusSynchronizationPeriodMillis": 5000,
-        "disableAddIP": false,
+        "disableAddIP": current.disable_add_ip,
         "sendBeatOnly": false,
         "limitedUrlMap": {},
         "distroServerExpiredMillis": 30000,
@@ -84,7 +91,10 @@ pub async fn get_switches(
         "pushCVersion": "1.0.12",
         "enableAuthentication": false,
         "overriddenServerStatus": "UP",
-        "defaultInstanceEphemeral": true,
+        "defaultInstanceEphemeral": current.default_instance_ephemeral,
+        "zoneAffinityExclusive": current.zone_affinity_exclusive,
+        "zoneAffinityThreshold": current.zone_affinity_threshold,
+        "zoneIpLabels": current.zone_ip_labels,
         "healthCheckWhiteList": [],
         "checksum": null
     });
@@ -97,26 +107,26 @@ pub async fn get_switches(
 /// 必需参数: entry, value
 /// 可选参数: debug
 /// 响应: "ok"（成功）
-/// 注意：Standalone 模式下，开关修改仅在本机生效
+/// 支持的开关: clientBeatInterval, healthCheckEnabled, pushEnabled, defaultInstanceEphemeral, disableAddIP,
+///             zoneAffinityExclusive, zoneAffinityThreshold, zoneIpLabels
+/// 注意：Standalone 模式下，开关修改仅在本机生效，修改后立即生效
 pub async fn update_switch(
-    _State(_app): State<Arc<AppHandle>>,
+    State(app): State<Arc<AppHandle>>,
     Query(params): Query<UpdateSwitchParams>,
 ) -> Result<Response, axum::http::StatusCode> {
-    // Standalone 模式下，开关修改仅在本机生效
-    // 这里可以添加实际的开关存储逻辑（如存储到数据库或配置文件）
-    // 目前仅返回成功响应
-    
-    // 验证开关名和值
-    let _entry = &params.entry;
-    let _value = &params.value;
-    let _debug = params.debug.as_deref().unwrap_or("false") == "true";
-
-    // TODO: 实现实际的开关存储逻辑
-    
-    Ok(Response::builder()
-        .status(axum::http::StatusCode::OK)
-        .body(axum::body::Body::from("ok"))
-        .unwrap())
+    match update_naming_switch_impl(&app, &params.entry, &params.value).await {
+        Ok(_) => Ok(Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .body(axum::body::Body::from("ok"))
+            .unwrap()),
+        Err(e) => {
+            if e.contains("Invalid") {
+                Err(axum::http::StatusCode::BAD_REQUEST)
+            } else {
+                Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
 }
 
 /// 查看系统当前数据指标
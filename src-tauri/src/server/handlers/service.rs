@@ -20,7 +20,19 @@ use crate::service::{
     update_service as update_service_impl,
     delete_service as delete_service_impl,
     get_service_instances as get_service_instances_impl,
-    ServiceQueryParams, CreateServiceRequest, UpdateServiceRequest,
+    get_naming_subscribers as get_naming_subscribers_impl,
+    get_service_clusters as get_service_clusters_impl,
+    get_service_history as get_service_history_impl,
+    find_service_export as find_service_export_impl,
+    get_service_exports as get_service_exports_impl,
+    list_service_exports as list_service_exports_impl,
+    create_service_export as create_service_export_impl,
+    revoke_service_export as revoke_service_export_impl,
+    get_metadata_schema as get_metadata_schema_impl,
+    update_metadata_schema as update_metadata_schema_impl,
+    get_metadata_violations as get_metadata_violations_impl,
+    ServiceQueryParams, ServiceHistoryQuery, CreateServiceRequest, UpdateServiceRequest,
+    CreateServiceExportRequest, ServiceExport, ServiceExportQuery,
 };
 
 /// 查询服务列表参数（Nacos API 格式）
@@ -42,6 +54,53 @@ pub struct GetServiceParams {
     pub serviceName: String,
     #[serde(default)]
     pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String, // 服务组，默认 "DEFAULT_GROUP"
+}
+
+/// 查询服务导出参数
+#[derive(Debug, Deserialize)]
+pub struct ListServiceExportsParams {
+    #[serde(default)]
+    pub sourceNamespaceId: Option<String>,
+    #[serde(default)]
+    pub targetNamespaceId: Option<String>,
+}
+
+/// 导出服务请求
+#[derive(Debug, Deserialize)]
+pub struct CreateServiceExportForm {
+    pub serviceName: String,
+    #[serde(default)]
+    pub namespaceId: String, // 源服务命名空间，默认 "public"
+    #[serde(default)]
+    pub groupName: String, // 源服务组，默认 "DEFAULT_GROUP"
+    pub targetNamespaceId: String,
+    #[serde(default)]
+    pub targetGroupName: Option<String>, // 默认与源服务相同
+    #[serde(default)]
+    pub targetServiceName: Option<String>, // 默认与源服务相同
+}
+
+/// 撤销服务导出参数
+#[derive(Debug, Deserialize)]
+pub struct RevokeServiceExportParams {
+    pub targetNamespaceId: String,
+    #[serde(default)]
+    pub targetGroupName: String, // 默认 "DEFAULT_GROUP"
+    pub targetServiceName: String,
+}
+
+/// 设置实例元数据约定请求
+#[derive(Debug, Deserialize)]
+pub struct UpdateMetadataSchemaForm {
+    pub serviceName: String,
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: String, // 服务组，默认 "DEFAULT_GROUP"
+    #[serde(default)]
+    pub schema: Option<String>, // 约定 JSON，为空时清除约定
 }
 
 /// 创建服务请求（Nacos API 格式）
@@ -104,6 +163,27 @@ pub struct GetSubscribersParams {
     pub aggregation: Option<String>, // 是否聚合，默认 "true"
 }
 
+/// 查询服务变更历史参数
+#[derive(Debug, Deserialize)]
+pub struct ServiceHistoryParams {
+    #[serde(default)]
+    pub namespaceId: String,
+    #[serde(default)]
+    pub groupName: Option<String>,
+    #[serde(default)]
+    pub serviceName: Option<String>,
+    #[serde(default)]
+    pub changeType: Option<String>, // CREATE / UPDATE / DELETE / REGISTER / DEREGISTER / HEALTH / METADATA
+    #[serde(default)]
+    pub startTime: Option<i64>, // 起始时间（秒）
+    #[serde(default)]
+    pub endTime: Option<i64>, // 结束时间（秒）
+    #[serde(default)]
+    pub pageNo: Option<i64>,
+    #[serde(default)]
+    pub pageSize: Option<i64>,
+}
+
 /// 查询服务列表
 /// GET /nacos/v1/ns/service/list
 /// 必需参数: pageNo, pageSize
@@ -174,10 +254,26 @@ pub async fn get_service(
         params.namespaceId
     };
     
-    let group_name = "DEFAULT_GROUP".to_string();
+    let group_name = if params.groupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.groupName
+    };
+
+    // 导出别名展示源服务的详情
+    let exported_from = find_service_export_impl(&app, &namespace_id, &group_name, &params.serviceName).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (source_namespace_id, source_group_name, source_service_name) = match &exported_from {
+        Some(export) => (
+            export.source_namespace_id.clone(),
+            export.source_group_name.clone(),
+            export.source_service_name.clone(),
+        ),
+        None => (namespace_id.clone(), group_name.clone(), params.serviceName.clone()),
+    };
 
     // 查询服务详情
-    let service_detail = get_service_detail_impl(&app, &namespace_id, &group_name, &params.serviceName).await
+    let service_detail = get_service_detail_impl(&app, &source_namespace_id, &source_group_name, &source_service_name).await
         .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(axum::http::StatusCode::NOT_FOUND)?;
 
@@ -206,23 +302,75 @@ pub async fn get_service(
         })
         .collect();
 
+    // 查询集群列表
+    let clusters = get_service_clusters_impl(&app, &source_namespace_id, &source_group_name, &source_service_name).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // 导出目标（导出别名没有自己的导出）
+    let exports = if exported_from.is_some() {
+        Vec::new()
+    } else {
+        get_service_exports_impl(&app, &namespace_id, &group_name, &params.serviceName).await
+            .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?
+    };
+
+    let clusters: Vec<serde_json::Value> = clusters
+        .iter()
+        .map(|cluster| {
+            serde_json::json!({
+                "name": cluster.cluster_name,
+                "healthChecker": serde_json::from_str::<serde_json::Value>(&cluster.health_checker)
+                    .unwrap_or(serde_json::json!({ "type": "NONE" })),
+                "checkPort": cluster.check_port,
+                "useIPPort4Check": cluster.use_instance_port,
+                "metadata": cluster.metadata.as_ref()
+                    .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+                    .unwrap_or(serde_json::json!({}))
+            })
+        })
+        .collect();
+
     let metadata = service_detail.metadata.as_ref()
         .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
         .unwrap_or(serde_json::json!({}));
 
+    let metadata_schema = get_metadata_schema_impl(&app, &source_namespace_id, &source_group_name, &source_service_name).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
     Ok(Json(serde_json::json!({
-        "name": service_detail.service_name,
-        "groupName": service_detail.group_name,
-        "namespaceId": service_detail.namespace_id,
+        "name": params.serviceName,
+        "groupName": group_name,
+        "namespaceId": namespace_id,
         "protectThreshold": service_detail.protect_threshold,
         "metadata": metadata,
+        "metadataSchema": metadata_schema,
         "selector": service_detail.selector.as_deref()
             .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
             .unwrap_or(serde_json::json!({})),
-        "hosts": hosts
+        "clusters": clusters,
+        "hosts": hosts,
+        "exportedFrom": exported_from.as_ref().map(|export| serde_json::json!({
+            "namespaceId": export.source_namespace_id,
+            "groupName": export.source_group_name,
+            "serviceName": export.source_service_name
+        })),
+        "exports": exports.iter().map(export_json).collect::<Vec<_>>()
     })))
 }
 
+/// 服务导出 JSON
+fn export_json(export: &ServiceExport) -> serde_json::Value {
+    serde_json::json!({
+        "sourceNamespaceId": export.source_namespace_id,
+        "sourceGroupName": export.source_group_name,
+        "sourceServiceName": export.source_service_name,
+        "targetNamespaceId": export.target_namespace_id,
+        "targetGroupName": export.target_group_name,
+        "targetServiceName": export.target_service_name,
+        "createTime": export.gmt_create
+    })
+}
+
 /// 创建服务
 /// POST /nacos/v1/ns/service
 /// 必需参数: serviceName
@@ -387,8 +535,6 @@ pub async fn get_subscribers(
     State(app): State<Arc<AppHandle>>,
     Query(params): Query<GetSubscribersParams>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
-    use crate::service::get_service_instances as get_service_instances_impl;
-    
     // 处理命名空间和服务组
     let namespace_id = if params.namespaceId.is_empty() {
         "public".to_string()
@@ -396,110 +542,62 @@ pub async fn get_subscribers(
         params.namespaceId
     };
     
-    let group_name = "DEFAULT_GROUP".to_string();
+    let group_name = params.groupName
+        .filter(|g| !g.is_empty())
+        .unwrap_or_else(|| "DEFAULT_GROUP".to_string());
 
     // 解析分页参数
     let page_no = params.pageNo.as_deref()
-        .and_then(|s| s.parse::<i32>().ok())
+        .and_then(|s| s.parse::<i64>().ok())
         .unwrap_or(1);
     let page_size = params.pageSize.as_deref()
-        .and_then(|s| s.parse::<i32>().ok())
+        .and_then(|s| s.parse::<i64>().ok())
         .unwrap_or(1000);
     
-    // 聚合模式：如果为 true，则从所有实例中提取唯一的订阅者信息
+    // 聚合模式：按订阅者 IP:PORT 聚合
     let aggregation = params.aggregation.as_deref()
         .map(|s| s == "true" || s == "True" || s == "1")
         .unwrap_or(true);
 
-    // 获取服务实例列表
-    match get_service_instances_impl(&app, &namespace_id, &group_name, &params.serviceName).await {
-        Ok(response) => {
-            // 从实例中提取订阅者信息
-            // 在 Standalone 模式下，我们可以从实例的元数据中提取客户端信息
-            // 或者基于实例的 IP 和端口构建订阅者信息
-            let mut subscribers_map = std::collections::HashMap::new();
-            
-            for instance in &response.instances {
-                // 构建订阅者地址字符串（IP:PORT）
-                let addr_str = format!("{}:{}", instance.ip, instance.port);
-                
-                // 从元数据中提取 agent 和 app 信息
-                let mut agent = String::new();
-                let mut app = String::new();
-                
-                if let Some(metadata_str) = &instance.metadata {
-                    if let Ok(metadata) = serde_json::from_str::<serde_json::Value>(metadata_str) {
-                        if let Some(agent_val) = metadata.get("agent").or(metadata.get("user-agent")) {
-                            agent = agent_val.as_str().unwrap_or("").to_string();
-                        }
-                        if let Some(app_val) = metadata.get("app").or(metadata.get("appName")) {
-                            app = app_val.as_str().unwrap_or("").to_string();
-                        }
-                    }
-                }
-                
-                // 构建订阅者键（用于去重）
-                let subscriber_key = if aggregation {
-                    // 聚合模式：使用 IP:PORT 作为唯一键
-                    addr_str.clone()
-                } else {
-                    // 非聚合模式：使用完整信息作为键
-                    format!("{}:{}:{}:{}", instance.ip, instance.port, agent, app)
-                };
-                
-                // 如果不存在或需要更新，则添加/更新订阅者
-                if !subscribers_map.contains_key(&subscriber_key) {
-                    subscribers_map.insert(subscriber_key, serde_json::json!({
-                        "addrStr": addr_str,
-                        "ip": instance.ip,
-                        "port": instance.port,
-                        "agent": agent,
-                        "app": app,
-                        "namespaceId": namespace_id,
-                        "serviceName": params.serviceName,
-                        "cluster": instance.cluster_name
-                    }));
-                }
-            }
-            
-            // 转换为列表并排序
-            let mut subscribers: Vec<serde_json::Value> = subscribers_map.into_values().collect();
-            subscribers.sort_by(|a, b| {
-                let addr_a = a.get("addrStr").and_then(|v| v.as_str()).unwrap_or("");
-                let addr_b = b.get("addrStr").and_then(|v| v.as_str()).unwrap_or("");
-                addr_a.cmp(addr_b)
-            });
-            
-            let total_count = subscribers.len();
-            
-            // 分页处理
-            let start = ((page_no - 1) * page_size).max(0) as usize;
-            let end = (start + page_size as usize).min(total_count);
-            let page_items = if start < total_count {
-                subscribers[start..end].to_vec()
-            } else {
-                Vec::new()
-            };
-            
-            Ok(Json(serde_json::json!({
-                "count": total_count,
-                "subscribers": page_items
-            })))
-        }
-        Err(_) => {
-            // 如果服务不存在，返回空列表
-            Ok(Json(serde_json::json!({
-                "count": 0,
-                "subscribers": []
-            })))
-        }
-    }
+    let response = get_naming_subscribers_impl(
+        &app,
+        &namespace_id,
+        &group_name,
+        &params.serviceName,
+        page_no,
+        page_size,
+        aggregation,
+    )
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let subscribers: Vec<serde_json::Value> = response.page_items
+        .iter()
+        .map(|subscriber| {
+            serde_json::json!({
+                "addrStr": subscriber.addr_str(),
+                "ip": subscriber.client_ip,
+                "port": subscriber.client_port,
+                "agent": subscriber.agent,
+                "app": subscriber.app_name,
+                "namespaceId": subscriber.namespace_id,
+                "serviceName": format!("{}@@{}", subscriber.group_name, subscriber.service_name),
+                "cluster": subscriber.clusters,
+                "lastRefTime": subscriber.last_ref_time
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "count": response.total_count,
+        "subscribers": subscribers
+    })))
 }
 
 /// Console API：查询服务订阅者列表
 /// GET /nacos/v3/console/ns/service/subscribers
 /// 必需参数: serviceName, groupName
-/// 可选参数: namespaceId
+/// 可选参数: namespaceId, pageNo, pageSize, aggregation
 /// 响应: { code: 0, data: { pageItems: [...], totalCount: number } }
 pub async fn console_get_subscribers(
     State(app): State<Arc<AppHandle>>,
@@ -512,113 +610,246 @@ pub async fn console_get_subscribers(
         params.namespaceId
     };
     
-    let group_name = params.groupName.unwrap_or_else(|| "DEFAULT_GROUP".to_string());
+    let group_name = params.groupName
+        .filter(|g| !g.is_empty())
+        .unwrap_or_else(|| "DEFAULT_GROUP".to_string());
 
     // 解析分页参数
     let page_no = params.pageNo.as_deref()
-        .and_then(|s| s.parse::<i32>().ok())
+        .and_then(|s| s.parse::<i64>().ok())
         .unwrap_or(1);
     let page_size = params.pageSize.as_deref()
-        .and_then(|s| s.parse::<i32>().ok())
+        .and_then(|s| s.parse::<i64>().ok())
         .unwrap_or(10);
     
-    // 聚合模式：如果为 true，则从所有实例中提取唯一的订阅者信息
+    // 聚合模式：按订阅者 IP:PORT 聚合
     let aggregation = params.aggregation.as_deref()
         .map(|s| s == "true" || s == "True" || s == "1")
         .unwrap_or(true);
 
-    use crate::service::get_service_instances as get_service_instances_impl;
-    
-    // 获取服务实例列表
-    match get_service_instances_impl(
+    let response = get_naming_subscribers_impl(
         &app,
         &namespace_id,
         &group_name,
         &params.serviceName,
-    ).await {
-        Ok(response) => {
-            // 从实例中提取订阅者信息
-            let mut subscribers_map = std::collections::HashMap::new();
-            
-            for instance in &response.instances {
-                // 构建订阅者地址字符串（IP:PORT）
-                let addr_str = format!("{}:{}", instance.ip, instance.port);
-                
-                // 从元数据中提取 agent 和 app 信息
-                let mut agent = String::new();
-                let mut app = String::new();
-                
-                if let Some(metadata_str) = &instance.metadata {
-                    if let Ok(metadata) = serde_json::from_str::<serde_json::Value>(metadata_str) {
-                        if let Some(agent_val) = metadata.get("agent").or(metadata.get("user-agent")) {
-                            agent = agent_val.as_str().unwrap_or("").to_string();
-                        }
-                        if let Some(app_val) = metadata.get("app").or(metadata.get("appName")) {
-                            app = app_val.as_str().unwrap_or("").to_string();
-                        }
-                    }
-                }
-                
-                // 构建订阅者键（用于去重）
-                let subscriber_key = if aggregation {
-                    addr_str.clone()
-                } else {
-                    format!("{}_{}_{}", addr_str, agent, app)
-                };
-                
-                // 如果不存在或需要更新，则添加/更新订阅者
-                if !subscribers_map.contains_key(&subscriber_key) {
-                    subscribers_map.insert(subscriber_key, serde_json::json!({
-                        "addrStr": addr_str,
-                        "groupName": group_name,
-                        "serviceName": params.serviceName,
-                        "address": addr_str,
-                        "agent": agent,
-                        "appName": app,
-                    }));
-                }
-            }
-            
-            // 转换为列表并排序
-            let mut subscribers: Vec<serde_json::Value> = subscribers_map.into_values().collect();
-            subscribers.sort_by(|a, b| {
-                let addr_a = a.get("addrStr").and_then(|v| v.as_str()).unwrap_or("");
-                let addr_b = b.get("addrStr").and_then(|v| v.as_str()).unwrap_or("");
-                addr_a.cmp(addr_b)
-            });
-            
-            let total_count = subscribers.len();
-            
-            // 分页处理
-            let start = ((page_no - 1) * page_size).max(0) as usize;
-            let end = (start + page_size as usize).min(total_count);
-            let page_items = if start < total_count {
-                subscribers[start..end].to_vec()
-            } else {
-                Vec::new()
-            };
-            
-            Ok(Json(serde_json::json!({
-                "code": 0,
-                "data": {
-                    "pageItems": page_items,
-                    "totalCount": total_count,
-                    "count": total_count,
-                    "subscribers": page_items
-                }
-            })))
-        }
-        Err(_) => {
-            // 如果服务不存在，返回空列表
-            Ok(Json(serde_json::json!({
-                "code": 0,
-                "data": {
-                    "pageItems": [],
-                    "totalCount": 0,
-                    "count": 0,
-                    "subscribers": []
-                }
-            })))
+        page_no,
+        page_size,
+        aggregation,
+    )
+    .await
+    .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let page_items: Vec<serde_json::Value> = response.page_items
+        .iter()
+        .map(|subscriber| {
+            serde_json::json!({
+                "addrStr": subscriber.addr_str(),
+                "groupName": subscriber.group_name,
+                "serviceName": subscriber.service_name,
+                "address": subscriber.addr_str(),
+                "ip": subscriber.client_ip,
+                "port": subscriber.client_port,
+                "agent": subscriber.agent,
+                "appName": subscriber.app_name,
+                "clusters": subscriber.clusters,
+                "lastRefTime": subscriber.last_ref_time,
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "code": 0,
+        "data": {
+            "pageItems": page_items,
+            "totalCount": response.total_count,
+            "pageNumber": response.page_number,
+            "pagesAvailable": response.pages_available,
+            "count": response.total_count,
+            "subscribers": page_items
         }
+    })))
+}
+
+/// 查询服务变更历史
+/// GET /nacos/v1/ns/service/history
+/// 可选参数: namespaceId, groupName, serviceName, changeType, startTime, endTime, pageNo, pageSize
+/// 响应: 变更历史列表（JSON 格式，按时间倒序，包含 count 和 history 数组）
+pub async fn get_service_history(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ServiceHistoryParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    // 处理命名空间
+    let namespace_id = if params.namespaceId.is_empty() {
+        "public".to_string()
+    } else {
+        params.namespaceId
+    };
+
+    let query = ServiceHistoryQuery {
+        namespace_id: Some(namespace_id),
+        group_name: params.groupName,
+        service_name: params.serviceName,
+        change_type: params.changeType,
+        start_time: params.startTime,
+        end_time: params.endTime,
+        page_no: params.pageNo,
+        page_size: params.pageSize,
+    };
+
+    let response = match get_service_history_impl(&app, query).await {
+        Ok(response) => response,
+        Err(e) if e.contains("Invalid change type") => return Err(axum::http::StatusCode::BAD_REQUEST),
+        Err(_) => return Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    };
+
+    let history: Vec<serde_json::Value> = response.page_items
+        .iter()
+        .map(|item| {
+            serde_json::json!({
+                "id": item.id,
+                "namespaceId": item.namespace_id,
+                "groupName": item.group_name,
+                "serviceName": item.service_name,
+                "changeType": item.change_type,
+                "changeDetail": item.change_detail,
+                "createTime": item.gmt_create
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({
+        "count": response.total_count,
+        "pageNumber": response.page_number,
+        "pagesAvailable": response.pages_available,
+        "history": history
+    })))
+}
+
+/// 查询服务导出列表
+/// GET /nacos/v1/ns/service/export
+/// 可选参数: sourceNamespaceId, targetNamespaceId
+/// 响应: { count, exports: [{ sourceNamespaceId, sourceGroupName, sourceServiceName, targetNamespaceId, targetGroupName, targetServiceName, createTime }] }
+pub async fn list_service_exports(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<ListServiceExportsParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let query = ServiceExportQuery {
+        source_namespace_id: params.sourceNamespaceId,
+        target_namespace_id: params.targetNamespaceId,
+    };
+
+    let exports = list_service_exports_impl(&app, query).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(serde_json::json!({
+        "count": exports.len(),
+        "exports": exports.iter().map(export_json).collect::<Vec<_>>()
+    })))
+}
+
+/// 导出服务到其他命名空间
+/// POST /nacos/v1/ns/service/export
+/// 必需参数: serviceName, targetNamespaceId
+/// 可选参数: namespaceId, groupName, targetGroupName, targetServiceName（默认与源服务相同）
+/// 响应: "ok"（成功）
+pub async fn create_service_export(
+    State(app): State<Arc<AppHandle>>,
+    Form(form): Form<CreateServiceExportForm>,
+) -> Result<Response, axum::http::StatusCode> {
+    let request = CreateServiceExportRequest {
+        source_namespace_id: if form.namespaceId.is_empty() { "public".to_string() } else { form.namespaceId },
+        source_group_name: if form.groupName.is_empty() { "DEFAULT_GROUP".to_string() } else { form.groupName },
+        source_service_name: form.serviceName,
+        target_namespace_id: form.targetNamespaceId,
+        target_group_name: form.targetGroupName,
+        target_service_name: form.targetServiceName,
+    };
+
+    match create_service_export_impl(&app, request).await {
+        Ok(_) => Ok(Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .body(axum::body::Body::from("ok"))
+            .unwrap()),
+        Err(e) if e.contains("Invalid") => Err(axum::http::StatusCode::BAD_REQUEST),
+        Err(e) if e.contains("not found") => Err(axum::http::StatusCode::NOT_FOUND),
+        Err(e) if e.contains("already exists") => Err(axum::http::StatusCode::CONFLICT),
+        Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// 撤销服务导出
+/// DELETE /nacos/v1/ns/service/export
+/// 必需参数: targetNamespaceId, targetServiceName
+/// 可选参数: targetGroupName
+/// 响应: "ok"（成功）
+pub async fn revoke_service_export(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<RevokeServiceExportParams>,
+) -> Result<Response, axum::http::StatusCode> {
+    let target_group_name = if params.targetGroupName.is_empty() {
+        "DEFAULT_GROUP".to_string()
+    } else {
+        params.targetGroupName
+    };
+
+    match revoke_service_export_impl(&app, &params.targetNamespaceId, &target_group_name, &params.targetServiceName).await {
+        Ok(_) => Ok(Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .body(axum::body::Body::from("ok"))
+            .unwrap()),
+        Err(e) if e.contains("not found") => Err(axum::http::StatusCode::NOT_FOUND),
+        Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
+
+/// 设置服务的实例元数据约定
+/// PUT /nacos/v1/ns/service/metadata/schema
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName, schema（{ mode: "enforce" | "warn", rules: [{ key, required, allowedValues, pattern }] }，为空时清除约定）
+/// 响应: "ok"（成功）
+pub async fn update_metadata_schema(
+    State(app): State<Arc<AppHandle>>,
+    Form(form): Form<UpdateMetadataSchemaForm>,
+) -> Result<Response, axum::http::StatusCode> {
+    let namespace_id = if form.namespaceId.is_empty() { "public" } else { form.namespaceId.as_str() };
+    let group_name = if form.groupName.is_empty() { "DEFAULT_GROUP" } else { form.groupName.as_str() };
+
+    match update_metadata_schema_impl(&app, namespace_id, group_name, &form.serviceName, form.schema.as_deref()).await {
+        Ok(_) => Ok(Response::builder()
+            .status(axum::http::StatusCode::OK)
+            .body(axum::body::Body::from("ok"))
+            .unwrap()),
+        Err(e) if e.contains("Invalid") => Err(axum::http::StatusCode::BAD_REQUEST),
+        Err(e) if e.contains("not found") => Err(axum::http::StatusCode::NOT_FOUND),
+        Err(_) => Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// 查询违反实例元数据约定的实例
+/// GET /nacos/v1/ns/service/metadata/violations
+/// 必需参数: serviceName
+/// 可选参数: namespaceId, groupName
+/// 响应: { mode, count, instances: [{ instanceId, ip, port, clusterName, violations: [{ key, reason }] }] }，服务没有约定时 mode 为 null
+pub async fn get_metadata_violations(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<GetServiceParams>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let namespace_id = if params.namespaceId.is_empty() { "public" } else { params.namespaceId.as_str() };
+    let group_name = if params.groupName.is_empty() { "DEFAULT_GROUP" } else { params.groupName.as_str() };
+
+    let report = get_metadata_violations_impl(&app, namespace_id, group_name, &params.serviceName, &[]).await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(match report {
+        Some(report) => serde_json::json!({
+            "mode": report.mode,
+            "count": report.instances.len(),
+            "instances": report.instances
+        }),
+        None => serde_json::json!({
+            "mode": null,
+            "count": 0,
+            "instances": []
+        }),
+    }))
+}
@@ -0,0 +1,127 @@
+/**
+ * 服务模拟器处理器
+ * 实现虚拟服务的创建、查询和停止 API
+ */
+
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+use crate::service::{
+    get_simulation as get_simulation_impl,
+    list_simulations as list_simulations_impl,
+    start_simulation as start_simulation_impl,
+    stop_simulation as stop_simulation_impl,
+    SimulationDefinition, SimulationStatus,
+};
+
+/// 模拟名称参数
+#[derive(Debug, Deserialize)]
+pub struct SimulationNameParams {
+    pub name: String,
+}
+
+/// 模拟状态转换为 JSON
+fn simulation_json(status: &SimulationStatus) -> serde_json::Value {
+    let instances: Vec<serde_json::Value> = status.instances
+        .iter()
+        .map(|instance| {
+            let metadata: serde_json::Value = instance.metadata
+                .as_deref()
+                .and_then(|m| serde_json::from_str(m).ok())
+                .unwrap_or_else(|| serde_json::json!({}));
+            serde_json::json!({
+                "instanceId": instance.instance_id,
+                "ip": instance.ip,
+                "port": instance.port,
+                "weight": instance.weight,
+                "healthy": instance.healthy,
+                "enabled": instance.enabled,
+                "clusterName": instance.cluster_name,
+                "metadata": metadata
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "name": status.definition.name,
+        "definition": status.definition,
+        "startedAt": status.started_at,
+        "instanceCount": instances.len(),
+        "instances": instances
+    })
+}
+
+/// 服务层错误转换为状态码
+fn simulation_error(e: String) -> StatusCode {
+    if e.contains("not found") {
+        StatusCode::NOT_FOUND
+    } else if e.contains("Invalid") {
+        StatusCode::BAD_REQUEST
+    } else if e.contains("already exists") {
+        StatusCode::CONFLICT
+    } else if e.contains("is disabled") {
+        StatusCode::FORBIDDEN
+    } else {
+        tracing::error!("Simulator request failed: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+}
+
+/// 创建并启动模拟
+/// POST /nacos/v1/ns/simulator
+/// 请求体: {"name", "serviceName", "instanceCount", "namespaceId", "groupName", "ip", "basePort", "clusterName", "weight",
+///          "metadata": {"version": "v{index}"}, "behaviour": {"type": "steady" | "flapping" | "scaling" | "randomWeight", ...}, "intervalSeconds"}
+/// 响应: 模拟状态（JSON 格式，包含定义和当前实例）
+pub async fn start_simulation(
+    State(app): State<Arc<AppHandle>>,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let definition: SimulationDefinition = serde_json::from_slice(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let status = start_simulation_impl(&app, definition).await.map_err(simulation_error)?;
+    Ok(Json(simulation_json(&status)))
+}
+
+/// 查询模拟状态
+/// GET /nacos/v1/ns/simulator
+/// 必需参数: name
+/// 响应: 模拟状态（JSON 格式）
+pub async fn get_simulation(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<SimulationNameParams>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let status = get_simulation_impl(&app, &params.name).await.map_err(simulation_error)?;
+    Ok(Json(simulation_json(&status)))
+}
+
+/// 查询所有模拟
+/// GET /nacos/v1/ns/simulator/list
+/// 响应: 模拟状态列表（JSON 格式，包含 count 和 simulations 数组）
+pub async fn list_simulations(
+    State(app): State<Arc<AppHandle>>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let statuses = list_simulations_impl(&app).await.map_err(simulation_error)?;
+    let simulations: Vec<serde_json::Value> = statuses.iter().map(simulation_json).collect();
+    Ok(Json(serde_json::json!({
+        "count": simulations.len(),
+        "simulations": simulations
+    })))
+}
+
+/// 停止模拟并注销其实例
+/// DELETE /nacos/v1/ns/simulator
+/// 必需参数: name
+/// 响应: "ok"（成功）
+pub async fn stop_simulation(
+    State(app): State<Arc<AppHandle>>,
+    Query(params): Query<SimulationNameParams>,
+) -> Result<&'static str, StatusCode> {
+    stop_simulation_impl(&app, &params.name).await.map_err(simulation_error)?;
+    Ok("ok")
+}
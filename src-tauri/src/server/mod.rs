@@ -10,9 +10,12 @@ use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
 
+pub mod dns;
+pub mod grpc;
 pub mod handlers;
 pub mod middleware;
 mod router;
+pub mod xds;
 
 #[cfg(test)]
 pub mod tests;
@@ -73,6 +76,24 @@ pub struct ServerConfig {
     pub rate_limit_refill_rate: Option<u32>, // 每秒补充的令牌数（默认 10）
     #[serde(default)]
     pub rate_limit_tokens_per_request: Option<u32>, // 每个请求消耗的令牌数（默认 1）
+    #[serde(default)]
+    pub empty_service_auto_clean: Option<bool>, // 是否自动清理空服务（默认 true）
+    #[serde(default)]
+    pub empty_service_clean_interval: Option<u64>, // 空服务清理周期，秒（默认 60）
+    #[serde(default)]
+    pub empty_service_expired_time: Option<u64>, // 服务无实例多久后被清理，秒（默认 60）
+    #[serde(default)]
+    pub dns_enabled: Option<bool>, // 是否启用 DNS 服务发现接口（默认 false）
+    #[serde(default)]
+    pub dns_port: Option<u16>, // DNS 监听端口，UDP 和 TCP 共用（默认 8853）
+    #[serde(default)]
+    pub dns_ttl: Option<u32>, // DNS 记录 TTL，秒（默认 6）
+    #[serde(default)]
+    pub grpc_enabled: Option<bool>, // 是否启用 Nacos 2.x gRPC 服务，端口为主端口 +1000 / +1001（默认 true）
+    #[serde(default)]
+    pub xds_enabled: Option<bool>, // 是否启用 Envoy xDS（ADS / CDS / EDS）服务（默认 false）
+    #[serde(default)]
+    pub xds_port: Option<u16>, // xDS 监听端口（默认 18848）
 }
 
 impl Default for ServerConfig {
@@ -86,6 +107,15 @@ impl Default for ServerConfig {
             rate_limit_capacity: Some(100),
             rate_limit_refill_rate: Some(10),
             rate_limit_tokens_per_request: Some(1),
+            empty_service_auto_clean: Some(true),
+            empty_service_clean_interval: Some(crate::service::DEFAULT_EMPTY_SERVICE_CLEAN_INTERVAL_SECONDS),
+            empty_service_expired_time: Some(crate::service::DEFAULT_EMPTY_SERVICE_EXPIRED_TIME_SECONDS),
+            dns_enabled: Some(false),
+            dns_port: Some(dns::DEFAULT_DNS_PORT),
+            dns_ttl: Some(dns::DEFAULT_DNS_TTL_SECONDS),
+            grpc_enabled: Some(true),
+            xds_enabled: Some(false),
+            xds_port: Some(xds::DEFAULT_XDS_PORT),
         }
     }
 }
@@ -103,6 +133,12 @@ struct ServerState {
     max_response_time_ms: u64,
     handle: Option<JoinHandle<Result<(), axum::Error>>>,
     shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
+    health_check_handle: Option<JoinHandle<()>>,
+    ephemeral_expiry_handle: Option<JoinHandle<()>>,
+    empty_service_cleaner_handle: Option<JoinHandle<()>>,
+    dns_handle: Option<JoinHandle<()>>,
+    grpc_handles: Vec<JoinHandle<()>>,
+    xds_handle: Option<JoinHandle<()>>,
 }
 
 impl Default for ServerState {
@@ -119,6 +155,12 @@ impl Default for ServerState {
             max_response_time_ms: 0,
             handle: None,
             shutdown_tx: None,
+            health_check_handle: None,
+            ephemeral_expiry_handle: None,
+            empty_service_cleaner_handle: None,
+            dns_handle: None,
+            grpc_handles: Vec::new(),
+            xds_handle: None,
         }
     }
 }
@@ -193,7 +235,8 @@ pub async fn start_api_server(
     let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
     
     // 启动服务器（axum 0.7 使用 axum::serve）
-    let server = axum::serve(listener, router)
+    // 携带连接信息，处理器可以取得调用方的对端地址
+    let server = axum::serve(listener, router.into_make_service_with_connect_info::<std::net::SocketAddr>())
         .with_graceful_shutdown(async {
             shutdown_rx.await.ok();
         });
@@ -209,12 +252,80 @@ pub async fn start_api_server(
         server.await
     });
     
+    // 启动持久化实例健康检查任务
+    let health_check_handle = crate::service::start_health_check_task(app.clone());
+
+    // 临时实例只保存在内存中，清理旧版本遗留的临时实例并启动心跳过期检查任务
+    if let Err(e) = crate::service::purge_persisted_ephemeral_instances(app).await {
+        tracing::warn!("Failed to purge persisted ephemeral instances: {}", e);
+    }
+    let ephemeral_expiry_handle = crate::service::start_ephemeral_expiry_task(app.clone());
+
+    // 启动空服务清理任务（只清理注册实例时自动创建的服务）
+    let empty_service_cleaner_handle = if config.empty_service_auto_clean.unwrap_or(true) {
+        Some(crate::service::start_empty_service_cleaner_task(
+            app.clone(),
+            config.empty_service_clean_interval.unwrap_or(crate::service::DEFAULT_EMPTY_SERVICE_CLEAN_INTERVAL_SECONDS),
+            config.empty_service_expired_time.unwrap_or(crate::service::DEFAULT_EMPTY_SERVICE_EXPIRED_TIME_SECONDS),
+        ))
+    } else {
+        None
+    };
+
+    // 启动 DNS 服务发现接口（可选，绑定失败不影响 HTTP 服务）
+    let dns_handle = if config.dns_enabled.unwrap_or(false) {
+        let dns_port = config.dns_port.unwrap_or(dns::DEFAULT_DNS_PORT);
+        match dns::start_dns_server(app.clone(), dns_port, config.dns_ttl.unwrap_or(dns::DEFAULT_DNS_TTL_SECONDS)).await {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                tracing::warn!("Failed to start DNS server: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // 启动 Nacos 2.x gRPC 服务（客户端端口和集群端口，绑定失败不影响 HTTP 服务）
+    let mut grpc_handles = Vec::new();
+    if config.grpc_enabled.unwrap_or(true) {
+        for offset in [grpc::GRPC_PORT_OFFSET, grpc::GRPC_CLUSTER_PORT_OFFSET] {
+            let Some(grpc_port) = port.checked_add(offset) else {
+                tracing::warn!("gRPC port out of range for main port {}", port);
+                continue;
+            };
+            match grpc::start_grpc_server(app.clone(), grpc_port).await {
+                Ok(handle) => grpc_handles.push(handle),
+                Err(e) => tracing::warn!("Failed to start gRPC server: {}", e),
+            }
+        }
+    }
+
+    // 启动 Envoy xDS 服务（可选，绑定失败不影响 HTTP 服务）
+    let xds_handle = if config.xds_enabled.unwrap_or(false) {
+        match xds::start_xds_server(app.clone(), config.xds_port.unwrap_or(xds::DEFAULT_XDS_PORT)).await {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                tracing::warn!("Failed to start xDS server: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    
     // 更新状态
     state.running = true;
     state.port = Some(port);
     state.start_time = Some(start_time);
     state.handle = Some(handle);
     state.shutdown_tx = Some(shutdown_tx);
+    state.health_check_handle = Some(health_check_handle);
+    state.ephemeral_expiry_handle = Some(ephemeral_expiry_handle);
+    state.empty_service_cleaner_handle = empty_service_cleaner_handle;
+    state.dns_handle = dns_handle;
+    state.grpc_handles = grpc_handles;
+    state.xds_handle = xds_handle;
     
     // 保存配置
     let config = ServerConfig {
@@ -240,6 +351,36 @@ pub async fn stop_api_server(app: &AppHandle) -> Result<(), String> {
         shutdown_tx.send(()).map_err(|_| "无法发送停止信号".to_string())?;
     }
     
+    // 停止健康检查任务
+    if let Some(health_check_handle) = state.health_check_handle.take() {
+        health_check_handle.abort();
+    }
+
+    // 停止临时实例过期检查任务
+    if let Some(ephemeral_expiry_handle) = state.ephemeral_expiry_handle.take() {
+        ephemeral_expiry_handle.abort();
+    }
+
+    // 停止空服务清理任务
+    if let Some(empty_service_cleaner_handle) = state.empty_service_cleaner_handle.take() {
+        empty_service_cleaner_handle.abort();
+    }
+
+    // 停止 DNS 监听
+    if let Some(dns_handle) = state.dns_handle.take() {
+        dns_handle.abort();
+    }
+
+    // 停止 gRPC 服务
+    for grpc_handle in state.grpc_handles.drain(..) {
+        grpc_handle.abort();
+    }
+
+    // 停止 xDS 服务
+    if let Some(xds_handle) = state.xds_handle.take() {
+        xds_handle.abort();
+    }
+    
     // 等待服务器停止
     if let Some(handle) = state.handle.take() {
         tokio::time::timeout(tokio::time::Duration::from_secs(5), handle)
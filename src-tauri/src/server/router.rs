@@ -56,9 +56,13 @@ pub fn create_router(context_path: String, app: Arc<AppHandle>) -> Router {
         .route("/v1/ns/instance/beat", put(handlers::instance::heartbeat))
         .route("/v1/ns/instance", patch(handlers::instance::patch_instance))
         .route("/v1/ns/health/instance", put(handlers::instance::update_instance_health_status))
+        .route("/v1/ns/instance/health/timeline", get(handlers::instance::get_health_timeline))
         .route("/v1/ns/instance/metadata/batch", put(handlers::instance::batch_update_metadata))
         .route("/v1/ns/instance/metadata/batch", delete(handlers::instance::batch_delete_metadata))
         .route("/v1/ns/instance/statuses", get(handlers::instance::get_instance_statuses))
+        .route("/v1/ns/instance/selectOne", get(handlers::instance::select_one_instance))
+        .route("/v1/ns/instance/batch", post(handlers::instance::batch_register_instances))
+        .route("/v1/ns/instance/batch", delete(handlers::instance::batch_deregister_instances))
         
         // 服务管理路由
         .route("/v1/ns/service/list", get(handlers::service::list_services))
@@ -68,9 +72,87 @@ pub fn create_router(context_path: String, app: Arc<AppHandle>) -> Router {
         .route("/v1/ns/service", delete(handlers::service::delete_service))
         .route("/v1/ns/service/names", get(handlers::service::search_service_names))
         .route("/v1/ns/service/subscribers", get(handlers::service::get_subscribers))
+        .route("/v1/ns/service/history", get(handlers::service::get_service_history))
+        .route("/v1/ns/service/export", get(handlers::service::list_service_exports))
+        .route("/v1/ns/service/export", post(handlers::service::create_service_export))
+        .route("/v1/ns/service/export", delete(handlers::service::revoke_service_export))
+        .route("/v1/ns/service/metadata/schema", put(handlers::service::update_metadata_schema))
+        .route("/v1/ns/service/metadata/violations", get(handlers::service::get_metadata_violations))
+        .route("/v1/ns/service/health/timeline", get(handlers::instance::get_health_timeline))
         // Console API：服务订阅者列表
         .route("/v3/console/ns/service/subscribers", get(handlers::service::console_get_subscribers))
         
+        // 容灾快照路由
+        .route("/v1/ns/failover/snapshot", get(handlers::failover::download_failover_snapshot))
+
+        // 服务目录路由
+        .route("/v1/ns/catalog/services", get(handlers::catalog::list_catalog_services))
+        .route("/v1/ns/catalog/instances", get(handlers::catalog::list_catalog_instances))
+        .route("/v1/ns/catalog/service", get(handlers::catalog::get_catalog_service))
+        
+        // 集群管理路由
+        .route("/v1/ns/cluster", put(handlers::cluster::update_cluster))
+        
+        // 服务模拟器路由
+        .route("/v1/ns/simulator", post(handlers::simulator::start_simulation))
+        .route("/v1/ns/simulator", get(handlers::simulator::get_simulation))
+        .route("/v1/ns/simulator", delete(handlers::simulator::stop_simulation))
+        .route("/v1/ns/simulator/list", get(handlers::simulator::list_simulations))
+        
+        // 命名服务 v2 OpenAPI 路由
+        .route("/v2/ns/instance", post(handlers::naming_v2::register_instance))
+        .route("/v2/ns/instance", delete(handlers::naming_v2::deregister_instance))
+        .route("/v2/ns/instance", put(handlers::naming_v2::update_instance))
+        .route("/v2/ns/instance", get(handlers::naming_v2::get_instance))
+        .route("/v2/ns/instance/list", get(handlers::naming_v2::list_instances))
+        .route("/v2/ns/instance/beat", put(handlers::naming_v2::heartbeat))
+        .route("/v2/ns/instance/metadata/batch", put(handlers::naming_v2::batch_update_metadata))
+        .route("/v2/ns/instance/metadata/batch", delete(handlers::naming_v2::batch_delete_metadata))
+        .route("/v2/ns/health/instance", put(handlers::naming_v2::update_instance_health))
+        .route("/v2/ns/service", post(handlers::naming_v2::create_service))
+        .route("/v2/ns/service", delete(handlers::naming_v2::delete_service))
+        .route("/v2/ns/service", put(handlers::naming_v2::update_service))
+        .route("/v2/ns/service", get(handlers::naming_v2::get_service))
+        .route("/v2/ns/service/list", get(handlers::naming_v2::list_services))
+        .route("/v2/ns/client/list", get(handlers::naming_v2::list_clients))
+        .route("/v2/ns/client", get(handlers::naming_v2::get_client))
+        .route("/v2/ns/client/publish/list", get(handlers::naming_v2::list_client_published_services))
+        .route("/v2/ns/client/subscribe/list", get(handlers::naming_v2::list_client_subscribed_services))
+        .route("/v2/ns/client/service/publisher/list", get(handlers::naming_v2::list_service_publisher_clients))
+        .route("/v2/ns/client/service/subscriber/list", get(handlers::naming_v2::list_service_subscriber_clients))
+        
+        // Eureka 兼容路由
+        .route("/eureka/apps", get(handlers::eureka::get_applications))
+        .route("/eureka/apps/delta", get(handlers::eureka::get_delta))
+        .route("/eureka/apps/:app_name", get(handlers::eureka::get_application))
+        .route("/eureka/apps/:app_name", post(handlers::eureka::register_instance))
+        .route("/eureka/apps/:app_name/:instance_id", get(handlers::eureka::get_instance))
+        .route("/eureka/apps/:app_name/:instance_id", put(handlers::eureka::renew_instance))
+        .route("/eureka/apps/:app_name/:instance_id", delete(handlers::eureka::cancel_instance))
+        .route("/eureka/apps/:app_name/:instance_id/status", put(handlers::eureka::update_status))
+        .route("/eureka/apps/:app_name/:instance_id/status", delete(handlers::eureka::delete_status_override))
+        
+        // MCP 服务注册表路由（管理和控制台接口相同）
+        .route("/v3/admin/ai/mcp", get(handlers::mcp::get_mcp_server))
+        .route("/v3/admin/ai/mcp", post(handlers::mcp::create_mcp_server))
+        .route("/v3/admin/ai/mcp", put(handlers::mcp::update_mcp_server))
+        .route("/v3/admin/ai/mcp", delete(handlers::mcp::delete_mcp_server))
+        .route("/v3/admin/ai/mcp/list", get(handlers::mcp::list_mcp_servers))
+        .route("/v3/admin/ai/mcp/versions", get(handlers::mcp::list_mcp_server_versions))
+        .route("/v3/admin/ai/mcp/tools", put(handlers::mcp::update_mcp_tool_spec))
+        .route("/v3/admin/ai/mcp/status", put(handlers::mcp::set_mcp_server_status))
+        .route("/v3/console/ai/mcp", get(handlers::mcp::get_mcp_server))
+        .route("/v3/console/ai/mcp", post(handlers::mcp::create_mcp_server))
+        .route("/v3/console/ai/mcp", put(handlers::mcp::update_mcp_server))
+        .route("/v3/console/ai/mcp", delete(handlers::mcp::delete_mcp_server))
+        .route("/v3/console/ai/mcp/list", get(handlers::mcp::list_mcp_servers))
+        .route("/v3/console/ai/mcp/versions", get(handlers::mcp::list_mcp_server_versions))
+        .route("/v3/console/ai/mcp/tools", put(handlers::mcp::update_mcp_tool_spec))
+        .route("/v3/console/ai/mcp/status", put(handlers::mcp::set_mcp_server_status))
+        
+        // 主机视图路由
+        .route("/v1/console/host", get(handlers::host::get_host_view))
+        
         // 命名空间管理路由
         .route("/v1/console/namespaces", get(handlers::namespace::list_namespaces))
         .route("/v1/console/namespaces", post(handlers::namespace::create_namespace))
@@ -79,9 +161,36 @@ pub fn create_router(context_path: String, app: Arc<AppHandle>) -> Router {
         
         // 认证路由
         .route("/v1/auth/users/login", post(handlers::auth::login))
-        .route("/v1/auth/users", get(handlers::auth::list_users))
-        
-        // 应用中间件（注意顺序：IP 白名单应该在最早应用，限流在 IP 白名单之后，metrics 和 access_log 中间件需要在 with_state 之后应用）
+        .route("/v1/auth/users", get(handlers::auth::list_users));
+    let api_router = with_middleware(api_router, &app);
+
+    // Consul 兼容路由（Consul 客户端不支持路径前缀，挂载在根路径）
+    let consul_router = Router::new()
+        .route("/v1/agent/service/register", put(handlers::consul::register_service))
+        .route("/v1/agent/service/deregister/:service_id", put(handlers::consul::deregister_service))
+        .route("/v1/agent/check/pass/:check_id", put(handlers::consul::pass_check))
+        .route("/v1/agent/check/fail/:check_id", put(handlers::consul::fail_check))
+        .route("/v1/catalog/services", get(handlers::consul::catalog_services))
+        .route("/v1/health/service/:service_name", get(handlers::consul::health_service))
+        .route("/v1/kv/*key", get(handlers::consul::get_kv))
+        .route("/v1/kv/*key", put(handlers::consul::put_kv))
+        .route("/v1/kv/*key", delete(handlers::consul::delete_kv));
+    let consul_router = with_middleware(consul_router, &app);
+
+    // 如果 context_path 不是 "/"，则添加前缀
+    if context_path != "/" {
+        Router::new()
+            .nest(&context_path, api_router)
+            .merge(consul_router)
+            .fallback(handlers::not_found)
+    } else {
+        api_router.merge(consul_router).fallback(handlers::not_found)
+    }
+}
+
+/// 应用中间件（注意顺序：IP 白名单应该在最早应用，限流在 IP 白名单之后，metrics 和 access_log 中间件需要在 with_state 之后应用）
+fn with_middleware(router: Router<Arc<AppHandle>>, app: &Arc<AppHandle>) -> Router {
+    router
         .layer(middleware::create_cors_layer())
         .layer(middleware::create_trace_layer())
         // 添加 AppHandle 作为 State
@@ -93,15 +202,6 @@ pub fn create_router(context_path: String, app: Arc<AppHandle>) -> Router {
         // 添加访问日志中间件
         .layer(from_fn_with_state(app.clone(), middleware::access_log::access_log_middleware))
         // 添加监控统计中间件
-        .layer(from_fn_with_state(app.clone(), middleware::metrics::metrics_middleware));
-
-    // 如果 context_path 不是 "/"，则添加前缀
-    if context_path != "/" {
-        Router::new()
-            .nest(&context_path, api_router)
-            .fallback(handlers::not_found)
-    } else {
-        api_router.fallback(handlers::not_found)
-    }
+        .layer(from_fn_with_state(app.clone(), middleware::metrics::metrics_middleware))
 }
 
@@ -0,0 +1,128 @@
+/**
+ * 服务目录集成测试
+ * 测试服务目录的集群数、实例数、健康实例数、保护阈值触发标记、hasIpCount 过滤和分页
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 通过 API 注册实例
+    async fn register(router: &Router, body: &str) {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// 发送 GET 请求并返回状态码和 JSON 响应体
+    async fn get_json(router: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method("GET")
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 准备测试数据：svc-a 两个集群各一个实例（一个不健康），svc-b 一个持久化实例，
+    /// svc-down 一个不健康实例，svc-empty 没有实例
+    async fn setup(test_db: &TestDatabase) -> Router {
+        for service in ["svc-a", "svc-b", "svc-down", "svc-empty"] {
+            test_db.insert_test_service(service, "public", "DEFAULT_GROUP").await.unwrap();
+        }
+        test_db.insert_test_instance("svc-b", "public", "DEFAULT_GROUP", "10.0.1.1", 8080).await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "serviceName=svc-a&ip=10.0.0.1&port=8080&cluster=c1").await;
+        register(&router, "serviceName=svc-a&ip=10.0.0.2&port=8080&cluster=c2&healthy=false").await;
+        register(&router, "serviceName=svc-down&ip=10.0.2.1&port=8080&healthy=false").await;
+        router
+    }
+
+    /// 测试用例：服务目录统计、过滤和分页
+    /// GET /nacos/v1/ns/catalog/services
+    #[tokio::test]
+    async fn test_catalog_services() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = setup(&test_db).await;
+
+        let (status, body) = get_json(&router, "/nacos/v1/ns/catalog/services?pageNo=1&pageSize=10").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["count"], 4);
+
+        let services = body["serviceList"].as_array().unwrap();
+        let find = |name: &str| services.iter().find(|s| s["name"] == name).unwrap().clone();
+        let svc_a = find("svc-a");
+        assert_eq!(svc_a["clusterCount"], 2);
+        assert_eq!(svc_a["ipCount"], 2);
+        assert_eq!(svc_a["healthyInstanceCount"], 1);
+        assert_eq!(svc_a["triggerFlag"], false);
+        assert_eq!(find("svc-b")["ipCount"], 1);
+        assert_eq!(find("svc-b")["healthyInstanceCount"], 1);
+        assert_eq!(find("svc-down")["triggerFlag"], true);
+        assert_eq!(find("svc-empty")["ipCount"], 0);
+        assert_eq!(find("svc-empty")["clusterCount"], 0);
+
+        // 只返回有实例的服务，并分页
+        let (_, body) = get_json(&router, "/nacos/v1/ns/catalog/services?hasIpCount=true&pageNo=1&pageSize=2").await;
+        assert_eq!(body["count"], 3);
+        assert_eq!(body["serviceList"].as_array().unwrap().len(), 2);
+        let (_, body) = get_json(&router, "/nacos/v1/ns/catalog/services?hasIpCount=true&pageNo=2&pageSize=2").await;
+        assert_eq!(body["serviceList"].as_array().unwrap().len(), 1);
+
+        // 服务名模糊匹配
+        let (_, body) = get_json(&router, "/nacos/v1/ns/catalog/services?serviceNameParam=down").await;
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["serviceList"][0]["name"], "svc-down");
+
+        let (status, _) = get_json(&router, "/nacos/v1/ns/catalog/services?pageNo=abc").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：服务目录实例列表和服务详情
+    /// GET /nacos/v1/ns/catalog/instances 和 GET /nacos/v1/ns/catalog/service
+    #[tokio::test]
+    async fn test_catalog_instances_and_service() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = setup(&test_db).await;
+
+        let (status, body) = get_json(&router, "/nacos/v1/ns/catalog/instances?serviceName=svc-a&clusterName=c1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["list"][0]["ip"], "10.0.0.1");
+
+        let (_, body) = get_json(&router, "/nacos/v1/ns/catalog/instances?serviceName=svc-a&pageNo=2&pageSize=1").await;
+        assert_eq!(body["count"], 2);
+        assert_eq!(body["list"].as_array().unwrap().len(), 1);
+        assert_eq!(body["list"][0]["clusterName"], "c2");
+
+        let (status, body) = get_json(&router, "/nacos/v1/ns/catalog/service?serviceName=svc-a").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["service"]["name"], "svc-a");
+        assert_eq!(body["service"]["ipCount"], 2);
+        assert_eq!(body["service"]["clusterCount"], 2);
+        assert_eq!(body["service"]["healthyInstanceCount"], 1);
+
+        let (status, _) = get_json(&router, "/nacos/v1/ns/catalog/service?serviceName=missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+/**
+ * 集群管理 API 集成测试
+ * 测试集群自动创建、集群更新以及集群健康检查配置对实例健康状态的影响
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    /// 测试用例：首次注册实例时自动创建集群，服务详情包含集群信息
+    /// POST /nacos/v1/ns/instance -> GET /nacos/v1/ns/service
+    #[tokio::test]
+    async fn test_register_instance_creates_cluster() {
+        let test_db = TestDatabase::new().await.unwrap();
+
+        test_db.insert_test_service("cluster-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance?ip=127.0.0.1&port=8080&serviceName=cluster-service&namespaceId=public&cluster=cluster-a")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v1/ns/service?serviceName=cluster-service&namespaceId=public")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        let clusters = body["clusters"].as_array().unwrap();
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0]["name"], "cluster-a");
+        assert_eq!(clusters[0]["healthChecker"]["type"], "TCP");
+        assert_eq!(clusters[0]["checkPort"], 80);
+        assert_eq!(clusters[0]["useIPPort4Check"], true);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：更新集群健康检查器、检查端口和元数据
+    /// PUT /nacos/v1/ns/cluster
+    #[tokio::test]
+    async fn test_update_cluster() {
+        let test_db = TestDatabase::new().await.unwrap();
+
+        test_db.insert_test_service("cluster-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/nacos/v1/ns/cluster")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(
+                "serviceName=cluster-service&clusterName=DEFAULT&namespaceId=public&checkPort=8081&useInstancePort4Check=false&healthChecker=%7B%22type%22%3A%22HTTP%22%2C%22path%22%3A%22%2Fhealth%22%7D&metadata=%7B%22zone%22%3A%22a%22%7D",
+            ))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v1/ns/service?serviceName=cluster-service&namespaceId=public")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        let cluster = &body["clusters"][0];
+        assert_eq!(cluster["name"], "DEFAULT");
+        assert_eq!(cluster["healthChecker"]["type"], "HTTP");
+        assert_eq!(cluster["healthChecker"]["path"], "/health");
+        assert_eq!(cluster["checkPort"], 8081);
+        assert_eq!(cluster["useIPPort4Check"], false);
+        assert_eq!(cluster["metadata"]["zone"], "a");
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：服务不存在时更新集群返回 404
+    /// PUT /nacos/v1/ns/cluster
+    #[tokio::test]
+    async fn test_update_cluster_service_not_found() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/nacos/v1/ns/cluster")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from("serviceName=missing-service&clusterName=DEFAULT&namespaceId=public"))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：持久化实例所在集群启用健康检查时，不允许手动修改健康状态
+    /// PUT /nacos/v1/ns/health/instance
+    #[tokio::test]
+    async fn test_manual_health_update_requires_none_checker() {
+        let test_db = TestDatabase::new().await.unwrap();
+
+        test_db.insert_test_service("persistent-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance?ip=127.0.0.1&port=18080&serviceName=persistent-service&namespaceId=public&ephemeral=false")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // 默认 TCP 检查器，手动修改被拒绝
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/nacos/v1/ns/health/instance?serviceName=persistent-service&namespaceId=public&ip=127.0.0.1&port=18080&healthy=false")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_ne!(response.status(), StatusCode::OK);
+
+        // 关闭集群健康检查后允许手动修改
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/nacos/v1/ns/cluster")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(
+                "serviceName=persistent-service&clusterName=DEFAULT&namespaceId=public&healthChecker=%7B%22type%22%3A%22NONE%22%7D",
+            ))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method("PUT")
+            .uri("/nacos/v1/ns/health/instance?serviceName=persistent-service&namespaceId=public&ip=127.0.0.1&port=18080&healthy=false")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_ne!(response.status(), StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
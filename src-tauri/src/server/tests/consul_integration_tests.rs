@@ -0,0 +1,188 @@
+/**
+ * Consul 兼容 API 集成测试
+ * 测试 agent 服务注册、TTL 检查、健康服务阻塞查询、服务目录和 KV 存储
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use crate::service::run_ephemeral_expiry_round;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求，返回状态码、X-Consul-Index 和响应文本
+    async fn send(router: &Router, method: &str, uri: &str, body: &str) -> (StatusCode, u64, String) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let index = response
+            .headers()
+            .get("X-Consul-Index")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, index, String::from_utf8_lossy(&body_bytes).to_string())
+    }
+
+    /// 测试用例：注册带 TTL 检查的服务，检查通过后出现在 passing 查询中，注销后消失
+    /// PUT /v1/agent/service/register -> PUT /v1/agent/check/pass/{checkId} -> GET /v1/health/service/{name}
+    #[tokio::test]
+    async fn test_consul_service_with_ttl_check() {
+        let test_db = TestDatabase::new().await.unwrap();
+        // Consul 路由挂载在根路径，不受 context_path 影响
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let registration = r#"{"ID":"web-1","Name":"web","Tags":["v1","primary"],"Address":"10.0.0.1","Port":8080,
+            "Meta":{"zone":"a"},"Check":{"TTL":"15s","DeregisterCriticalServiceAfter":"1m"}}"#;
+        let (status, _, _) = send(&router, "PUT", "/v1/agent/service/register", registration).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // TTL 检查初始为 critical
+        let (status, index, body) = send(&router, "GET", "/v1/health/service/web?passing", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap().as_array().unwrap().len(), 0);
+        let (_, _, body) = send(&router, "GET", "/v1/health/service/web", "").await;
+        let entries: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(entries[0]["Checks"][1]["CheckID"], "service:web-1");
+        assert_eq!(entries[0]["Checks"][1]["Status"], "critical");
+
+        // 索引未变化时阻塞查询在 wait 超时后返回
+        let (status, blocked_index, _) = send(&router, "GET", &format!("/v1/health/service/web?index={}&wait=1s", index), "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(blocked_index, index);
+
+        let (status, _, _) = send(&router, "PUT", "/v1/agent/check/pass/service:web-1", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _, _) = send(&router, "PUT", "/v1/agent/check/pass/service:unknown", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (_, new_index, body) = send(&router, "GET", &format!("/v1/health/service/web?passing&index={}&wait=1s", index), "").await;
+        assert!(new_index > index);
+        let entries: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(entries.as_array().unwrap().len(), 1);
+        assert_eq!(entries[0]["Service"]["ID"], "web-1");
+        assert_eq!(entries[0]["Service"]["Address"], "10.0.0.1");
+        assert_eq!(entries[0]["Service"]["Port"], 8080);
+        assert_eq!(entries[0]["Service"]["Meta"]["zone"], "a");
+        assert_eq!(entries[0]["Checks"][1]["Status"], "passing");
+
+        // 按标签过滤
+        let (_, _, body) = send(&router, "GET", "/v1/health/service/web?tag=v2", "").await;
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap().as_array().unwrap().len(), 0);
+
+        // Consul 注册的服务对 Nacos 客户端可见
+        let (_, _, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=web", "").await;
+        let nacos: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(nacos["hosts"][0]["ip"], "10.0.0.1");
+
+        let (status, _, body) = send(&router, "GET", "/v1/catalog/services", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let services: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(services["web"], serde_json::json!(["primary", "v1"]));
+
+        let (status, _, _) = send(&router, "PUT", "/v1/agent/service/deregister/web-1", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _, _) = send(&router, "PUT", "/v1/agent/service/deregister/web-1", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (_, _, body) = send(&router, "GET", "/v1/health/service/web", "").await;
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap().as_array().unwrap().len(), 0);
+
+        let (status, _, _) = send(&router, "PUT", "/v1/agent/service/register", "{\"ID\":\"x\"}").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：TTL 检查失败时服务变为 critical，持续 critical 超过 DeregisterCriticalServiceAfter 后被注销
+    /// PUT /v1/agent/check/fail/{checkId}
+    #[tokio::test]
+    async fn test_consul_check_fail_deregisters_critical_service() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let registration = r#"{"ID":"api-1","Name":"api","Address":"10.0.0.2","Port":8080,
+            "Check":{"TTL":"1m","DeregisterCriticalServiceAfter":"100ms"}}"#;
+        send(&router, "PUT", "/v1/agent/service/register", registration).await;
+        send(&router, "PUT", "/v1/agent/check/pass/service:api-1", "").await;
+
+        let (status, _, _) = send(&router, "PUT", "/v1/agent/check/fail/service:api-1", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, _, body) = send(&router, "GET", "/v1/health/service/api", "").await;
+        let entries: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(entries[0]["Checks"][1]["Status"], "critical");
+
+        // 检查失败不刷新 TTL，critical 超过 100ms 后被移除
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        run_ephemeral_expiry_round(&test_db.app).await;
+        let (_, _, body) = send(&router, "GET", "/v1/health/service/api", "").await;
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap().as_array().unwrap().len(), 0);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：KV 写入、读取（Base64 / raw / 前缀）、CAS 和删除，KV 保存在配置管理中
+    /// PUT /v1/kv/{key} -> GET /v1/kv/{key} -> DELETE /v1/kv/{key}
+    #[tokio::test]
+    async fn test_consul_kv() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, _, body) = send(&router, "PUT", "/v1/kv/app/db/url", "jdbc:mysql://db").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "true");
+        let (_, _, body) = send(&router, "PUT", "/v1/kv/app/name", "demo").await;
+        assert_eq!(body, "true");
+
+        let (status, _, body) = send(&router, "GET", "/v1/kv/app/name", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let entries: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(entries[0]["Key"], "app/name");
+        assert_eq!(entries[0]["Value"], "ZGVtbw==");
+        let modify_index = entries[0]["ModifyIndex"].as_u64().unwrap();
+
+        let (_, _, body) = send(&router, "GET", "/v1/kv/app/name?raw", "").await;
+        assert_eq!(body, "demo");
+        let (_, _, body) = send(&router, "GET", "/v1/kv/app/?keys", "").await;
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&body).unwrap(), serde_json::json!(["app/db/url", "app/name"]));
+        let (status, _, _) = send(&router, "GET", "/v1/kv/missing", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // KV 在配置管理中可见
+        let (_, _, body) = send(&router, "GET", "/nacos/v1/cs/configs?dataId=app/name&group=CONSUL_KV", "").await;
+        assert_eq!(body, "demo");
+
+        // CAS：索引不一致时不写入，cas=0 仅在键不存在时写入
+        let (_, _, body) = send(&router, "PUT", &format!("/v1/kv/app/name?cas={}", modify_index + 100), "stale").await;
+        assert_eq!(body, "false");
+        let (_, _, body) = send(&router, "PUT", "/v1/kv/app/name?cas=0", "again").await;
+        assert_eq!(body, "false");
+        let (_, _, body) = send(&router, "PUT", &format!("/v1/kv/app/name?cas={}", modify_index), "demo2").await;
+        assert_eq!(body, "true");
+        let (_, _, body) = send(&router, "GET", "/v1/kv/app/name", "").await;
+        let entries: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert!(entries[0]["ModifyIndex"].as_u64().unwrap() > modify_index);
+
+        // 并发的 CAS 写入使用同一个索引时只有一个成功
+        let modify_index = entries[0]["ModifyIndex"].as_u64().unwrap();
+        let uri = format!("/v1/kv/app/name?cas={}", modify_index);
+        let (first, second) = tokio::join!(send(&router, "PUT", &uri, "first"), send(&router, "PUT", &uri, "second"));
+        assert_eq!([first.2, second.2].iter().filter(|body| body.as_str() == "true").count(), 1);
+
+        let (_, _, body) = send(&router, "DELETE", "/v1/kv/app?recurse", "").await;
+        assert_eq!(body, "true");
+        let (status, _, _) = send(&router, "GET", "/v1/kv/app?recurse", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
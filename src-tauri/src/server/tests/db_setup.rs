@@ -26,6 +26,7 @@ impl TestDatabase {
         
         // 创建测试用的 Tauri app（使用 mock_app）
         let app = tauri::test::mock_app();
+        crate::service::manage_ephemeral_registry(app.handle());
         let app_handle = Arc::new(app.handle());
         
         Ok(Self {
@@ -54,6 +55,15 @@ impl TestDatabase {
             ("008_api_logs_table.sql", include_str!("../../db/migrations/008_api_logs_table.sql")),
             ("009_config_info_beta.sql", include_str!("../../db/migrations/009_config_info_beta.sql")),
             ("010_performance_indexes.sql", include_str!("../../db/migrations/010_performance_indexes.sql")),
+            ("011_naming_subscribers.sql", include_str!("../../db/migrations/011_naming_subscribers.sql")),
+            ("012_cluster_info.sql", include_str!("../../db/migrations/012_cluster_info.sql")),
+            ("013_service_create_source.sql", include_str!("../../db/migrations/013_service_create_source.sql")),
+            ("014_naming_switches.sql", include_str!("../../db/migrations/014_naming_switches.sql")),
+            ("015_instance_health_event.sql", include_str!("../../db/migrations/015_instance_health_event.sql")),
+            ("016_mcp_server.sql", include_str!("../../db/migrations/016_mcp_server.sql")),
+            ("017_service_export.sql", include_str!("../../db/migrations/017_service_export.sql")),
+            ("018_service_metadata_schema.sql", include_str!("../../db/migrations/018_service_metadata_schema.sql")),
+            ("019_consul_kv_index.sql", include_str!("../../db/migrations/019_consul_kv_index.sql")),
         ];
         
         // 使用 sqlx 直接执行迁移
@@ -101,9 +111,17 @@ impl TestDatabase {
         sqlx::query("DELETE FROM config_info").execute(&pool).await?;
         sqlx::query("DELETE FROM config_history_info").execute(&pool).await?;
         sqlx::query("DELETE FROM service_info").execute(&pool).await?;
+        sqlx::query("DELETE FROM service_history_info").execute(&pool).await?;
         sqlx::query("DELETE FROM instance_info").execute(&pool).await?;
         sqlx::query("DELETE FROM tenant_info").execute(&pool).await?;
         sqlx::query("DELETE FROM subscribers").execute(&pool).await?;
+        sqlx::query("DELETE FROM naming_subscribers").execute(&pool).await?;
+        sqlx::query("DELETE FROM cluster_info").execute(&pool).await?;
+        sqlx::query("DELETE FROM naming_switches").execute(&pool).await?;
+        sqlx::query("DELETE FROM instance_health_event").execute(&pool).await?;
+        sqlx::query("DELETE FROM mcp_server").execute(&pool).await?;
+        sqlx::query("DELETE FROM mcp_server_version").execute(&pool).await?;
+        sqlx::query("DELETE FROM service_export").execute(&pool).await?;
         sqlx::query("DELETE FROM tokens").execute(&pool).await?;
         sqlx::query("DELETE FROM users WHERE username != 'nacos'").execute(&pool).await?;
         
@@ -197,7 +215,7 @@ impl TestDatabase {
         let database_url = format!("sqlite:{}", self.db_path.display());
         let pool = sqlx::SqlitePool::connect(&database_url).await?;
         
-        let instance_id = format!("{}#{}#{}#{}", ip, port, namespace_id, "DEFAULT");
+        let instance_id = format!("{}#{}#DEFAULT#{}@@{}", ip, port, group_name, service_name);
         
         sqlx::query(
             "INSERT OR REPLACE INTO instance_info (instance_id, service_name, namespace_id, group_name, ip, port, healthy, enabled, ephemeral) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)"
@@ -218,6 +236,33 @@ impl TestDatabase {
         Ok(())
     }
     
+    /// 插入测试健康时间线事件
+    pub async fn insert_test_health_event(
+        &self,
+        service_name: &str,
+        address: &str,
+        healthy: bool,
+        source: &str,
+        gmt_create: i64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let database_url = format!("sqlite:{}", self.db_path.display());
+        let pool = sqlx::SqlitePool::connect(&database_url).await?;
+        
+        sqlx::query(
+            "INSERT INTO instance_health_event (namespace_id, group_name, service_name, address, healthy, source, reason, gmt_create) VALUES ('public', 'DEFAULT_GROUP', ?, ?, ?, ?, 'test', ?)"
+        )
+        .bind(service_name)
+        .bind(address)
+        .bind(healthy)
+        .bind(source)
+        .bind(gmt_create)
+        .execute(&pool)
+        .await?;
+        
+        pool.close().await;
+        Ok(())
+    }
+    
     /// 插入测试命名空间
     pub async fn insert_test_namespace(
         &self,
@@ -275,6 +320,44 @@ impl TestDatabase {
         Ok(())
     }
     
+    /// 插入测试服务订阅者（实例列表查询者）
+    pub async fn insert_test_naming_subscriber(
+        &self,
+        service_name: &str,
+        namespace_id: &str,
+        group_name: &str,
+        client_ip: &str,
+        client_port: i32,
+        clusters: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let database_url = format!("sqlite:{}", self.db_path.display());
+        let pool = sqlx::SqlitePool::connect(&database_url).await?;
+        
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        
+        sqlx::query(
+            "INSERT OR REPLACE INTO naming_subscribers (namespace_id, group_name, service_name, client_ip, client_port, app_name, agent, clusters, last_ref_time, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+        )
+        .bind(namespace_id)
+        .bind(group_name)
+        .bind(service_name)
+        .bind(client_ip)
+        .bind(client_port)
+        .bind("test-app")
+        .bind("Nacos-Java-Client:v2.3.0")
+        .bind(clusters)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await?;
+        
+        pool.close().await;
+        Ok(())
+    }
+    
     /// 获取配置 ID
     pub async fn get_config_id(
         &self,
@@ -0,0 +1,173 @@
+/**
+ * DNS 服务发现接口集成测试
+ * 测试 A / SRV 查询只返回健康且启用的实例、权重映射、不存在的服务返回 NXDOMAIN，以及 UDP 监听
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::dns::{handle_dns_packet, start_dns_server};
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    const TYPE_A: u16 = 1;
+    const TYPE_SRV: u16 = 33;
+
+    /// 发送请求
+    async fn send(router: &Router, method: &str, uri: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    /// 构造 DNS 查询报文
+    fn build_query(id: u16, name: &str, qtype: u16) -> Vec<u8> {
+        let mut packet = Vec::new();
+        packet.extend_from_slice(&id.to_be_bytes());
+        packet.extend_from_slice(&0x0100u16.to_be_bytes());
+        packet.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]);
+        for label in name.split('.') {
+            packet.push(label.len() as u8);
+            packet.extend_from_slice(label.as_bytes());
+        }
+        packet.push(0);
+        packet.extend_from_slice(&qtype.to_be_bytes());
+        packet.extend_from_slice(&1u16.to_be_bytes());
+        packet
+    }
+
+    /// 跳过域名（支持压缩指针），返回域名之后的位置
+    fn skip_name(packet: &[u8], mut offset: usize) -> usize {
+        loop {
+            let len = packet[offset] as usize;
+            if len == 0 {
+                return offset + 1;
+            }
+            if len & 0xc0 == 0xc0 {
+                return offset + 2;
+            }
+            offset += 1 + len;
+        }
+    }
+
+    /// 解析响应：返回响应码和回答记录（类型，RDATA）
+    fn parse_response(packet: &[u8]) -> (u8, Vec<(u16, Vec<u8>)>) {
+        let rcode = packet[3] & 0x0f;
+        let qdcount = u16::from_be_bytes([packet[4], packet[5]]);
+        let ancount = u16::from_be_bytes([packet[6], packet[7]]);
+        let mut offset = 12;
+        for _ in 0..qdcount {
+            offset = skip_name(packet, offset) + 4;
+        }
+        let mut answers = Vec::new();
+        for _ in 0..ancount {
+            offset = skip_name(packet, offset);
+            let rtype = u16::from_be_bytes([packet[offset], packet[offset + 1]]);
+            let rdlength = u16::from_be_bytes([packet[offset + 8], packet[offset + 9]]) as usize;
+            offset += 10;
+            answers.push((rtype, packet[offset..offset + rdlength].to_vec()));
+            offset += rdlength;
+        }
+        (rcode, answers)
+    }
+
+    /// 准备测试服务：两个健康实例、一个不健康实例和一个禁用实例
+    async fn setup(test_db: &TestDatabase) {
+        test_db.insert_test_service("dns-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        for query in [
+            "ip=10.0.0.1&port=8080&weight=2",
+            "ip=10.0.0.2&port=9090&weight=0.5",
+            "ip=10.0.0.3&port=8080&healthy=false&ephemeral=false",
+            "ip=10.0.0.4&port=8080&enabled=false",
+        ] {
+            let status = send(&router, "POST", &format!("/nacos/v1/ns/instance?serviceName=dns-service&{}", query)).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+    }
+
+    /// 测试用例：A 和 SRV 查询只返回健康且启用的实例，不存在的服务返回 NXDOMAIN
+    #[tokio::test]
+    async fn test_dns_queries() {
+        let test_db = TestDatabase::new().await.unwrap();
+        setup(&test_db).await;
+
+        let query = build_query(1, "dns-service.DEFAULT_GROUP.public.nacos.local", TYPE_A);
+        let response = handle_dns_packet(&test_db.app, &query, 30, None).await.unwrap();
+        assert_eq!(&response[..2], &1u16.to_be_bytes());
+        let (rcode, answers) = parse_response(&response);
+        assert_eq!(rcode, 0);
+        let mut ips: Vec<Vec<u8>> = answers.iter().map(|(_, rdata)| rdata.clone()).collect();
+        ips.sort();
+        assert_eq!(ips, vec![vec![10, 0, 0, 1], vec![10, 0, 0, 2]]);
+
+        // 省略 group 和 namespace 时使用默认值
+        let query = build_query(2, "dns-service.nacos.local", TYPE_SRV);
+        let response = handle_dns_packet(&test_db.app, &query, 30, None).await.unwrap();
+        let (rcode, answers) = parse_response(&response);
+        assert_eq!(rcode, 0);
+        assert_eq!(answers.len(), 2);
+        let srv: Vec<(u16, u16)> = answers
+            .iter()
+            .map(|(rtype, rdata)| {
+                assert_eq!(*rtype, TYPE_SRV);
+                (u16::from_be_bytes([rdata[2], rdata[3]]), u16::from_be_bytes([rdata[4], rdata[5]]))
+            })
+            .collect();
+        assert!(srv.contains(&(200, 8080)));
+        assert!(srv.contains(&(50, 9090)));
+
+        let query = build_query(3, "missing-service.DEFAULT_GROUP.public.nacos.local", TYPE_A);
+        let (rcode, answers) = parse_response(&handle_dns_packet(&test_db.app, &query, 30, None).await.unwrap());
+        assert_eq!(rcode, 3);
+        assert!(answers.is_empty());
+
+        // 不属于 nacos.local 的域名拒绝解析
+        let query = build_query(4, "example.com", TYPE_A);
+        let (rcode, _) = parse_response(&handle_dns_packet(&test_db.app, &query, 30, None).await.unwrap());
+        assert_eq!(rcode, 5);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：通过 UDP 监听查询，TTL 使用配置值
+    #[tokio::test]
+    async fn test_dns_udp_listener() {
+        let test_db = TestDatabase::new().await.unwrap();
+        setup(&test_db).await;
+
+        let port = std::net::UdpSocket::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let handle = start_dns_server(test_db.app.clone(), port, 42).await.unwrap();
+
+        let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client
+            .send_to(&build_query(7, "dns-service.DEFAULT_GROUP.public.nacos.local", TYPE_A), ("127.0.0.1", port))
+            .await
+            .unwrap();
+        let mut buf = [0u8; 512];
+        let (len, _) = tokio::time::timeout(std::time::Duration::from_secs(5), client.recv_from(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        let (rcode, answers) = parse_response(&buf[..len]);
+        assert_eq!(rcode, 0);
+        assert_eq!(answers.len(), 2);
+
+        // 第一条回答的 TTL 位于问题之后：名称指针(2) + 类型(2) + 类(2)
+        let answer_offset = skip_name(&buf, 12) + 4;
+        let ttl_offset = answer_offset + 6;
+        assert_eq!(u32::from_be_bytes(buf[ttl_offset..ttl_offset + 4].try_into().unwrap()), 42);
+
+        handle.abort();
+        test_db.cleanup().await.unwrap();
+    }
+}
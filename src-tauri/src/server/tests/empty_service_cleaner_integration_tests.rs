@@ -0,0 +1,95 @@
+/**
+ * 空服务自动清理集成测试
+ * 测试注册实例时自动创建服务、空服务过期后被删除并写入历史，
+ * 以及显式创建的服务和带 persist 标记的服务不会被清理
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use crate::service::run_empty_service_clean_round;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送表单请求并解析 JSON 响应
+    async fn send(router: &Router, method: &str, uri: &str, form: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(form.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：注册实例自动创建服务，实例注销后空服务被清理并记录历史
+    /// POST /nacos/v1/ns/instance -> DELETE /nacos/v1/ns/instance
+    #[tokio::test]
+    async fn test_auto_created_empty_service_cleaned() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=auto-service&ip=10.0.0.1&port=8080", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(&router, "GET", "/nacos/v1/ns/service?serviceName=auto-service", "").await;
+        assert_eq!(status, StatusCode::OK);
+
+        // 有实例的服务不会被清理
+        assert_eq!(run_empty_service_clean_round(&test_db.app, 0).await.unwrap(), 0);
+
+        send(&router, "DELETE", "/nacos/v1/ns/instance?serviceName=auto-service&ip=10.0.0.1&port=8080", "").await;
+
+        // 未到过期时间时只开始计时
+        assert_eq!(run_empty_service_clean_round(&test_db.app, 3600).await.unwrap(), 0);
+        assert_eq!(run_empty_service_clean_round(&test_db.app, 0).await.unwrap(), 1);
+
+        let (status, _) = send(&router, "GET", "/nacos/v1/ns/service?serviceName=auto-service", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/service/history?serviceName=auto-service&changeType=DELETE", "").await;
+        assert_eq!(body["count"], 1);
+        assert!(body["history"][0]["changeDetail"].as_str().unwrap().starts_with("Empty service cleaned"));
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：显式创建的服务和带 persist 标记的服务不会被清理
+    /// POST /nacos/v1/ns/service -> PUT /nacos/v1/ns/service
+    #[tokio::test]
+    async fn test_explicit_and_persist_services_kept() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/service?serviceName=explicit-service", "").await;
+        assert_eq!(status, StatusCode::OK);
+
+        send(&router, "POST", "/nacos/v1/ns/instance?serviceName=persist-service&ip=10.0.0.1&port=8080", "").await;
+        send(&router, "DELETE", "/nacos/v1/ns/instance?serviceName=persist-service&ip=10.0.0.1&port=8080", "").await;
+        let metadata = urlencoding::encode(r#"{"persist":"true"}"#);
+        let (status, _) = send(
+            &router,
+            "PUT",
+            "/nacos/v1/ns/service",
+            &format!("serviceName=persist-service&protectThreshold=0&metadata={}", metadata),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        assert_eq!(run_empty_service_clean_round(&test_db.app, 0).await.unwrap(), 0);
+
+        for service_name in ["explicit-service", "persist-service"] {
+            let (status, _) = send(&router, "GET", &format!("/nacos/v1/ns/service?serviceName={}", service_name), "").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,112 @@
+/**
+ * 临时实例内存注册表集成测试
+ * 测试临时实例不写入 instance_info、与持久化实例合并展示以及心跳超时后的过期处理
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use crate::service::run_ephemeral_expiry_round;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求并解析 JSON 响应
+    async fn send(router: &Router, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 查询数据库中的实例数量
+    async fn count_persisted_instances(test_db: &TestDatabase, service_name: &str) -> i64 {
+        let pool = sqlx::SqlitePool::connect(&format!("sqlite:{}", test_db.db_path.display())).await.unwrap();
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM instance_info WHERE service_name = ?")
+            .bind(service_name)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+        count
+    }
+
+    /// 测试用例：临时实例只保存在内存中，与持久化实例一起出现在列表中
+    /// POST /nacos/v1/ns/instance -> GET /nacos/v1/ns/instance/list
+    #[tokio::test]
+    async fn test_ephemeral_instances_kept_in_memory() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("ephemeral-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=ephemeral-service&ip=10.0.0.1&port=8080&ephemeral=true").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=ephemeral-service&ip=10.0.0.2&port=8080&ephemeral=false").await;
+        assert_eq!(status, StatusCode::OK);
+
+        // 只有持久化实例写入数据库
+        assert_eq!(count_persisted_instances(&test_db, "ephemeral-service").await, 1);
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=ephemeral-service").await;
+        let hosts = body["hosts"].as_array().unwrap();
+        assert_eq!(hosts.len(), 2);
+        assert!(hosts.iter().any(|h| h["ip"] == "10.0.0.1" && h["ephemeral"] == true));
+        assert!(hosts.iter().any(|h| h["ip"] == "10.0.0.2" && h["ephemeral"] == false));
+
+        // 注销临时实例
+        let (status, _) = send(&router, "DELETE", "/nacos/v2/ns/instance?serviceName=ephemeral-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=ephemeral-service").await;
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 1);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：心跳超时的临时实例先被标记为不健康，超过删除超时后被移除
+    /// PUT /nacos/v1/ns/instance/beat
+    #[tokio::test]
+    async fn test_ephemeral_instance_expiry() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("expiry-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 通过保留元数据缩短超时时间
+        let metadata = urlencoding::encode(r#"{"preserved.heart.beat.timeout":"50","preserved.ip.delete.timeout":"300"}"#);
+        let (status, _) = send(&router, "POST", &format!("/nacos/v1/ns/instance?serviceName=expiry-service&ip=10.0.0.1&port=8080&metadata={}", metadata)).await;
+        assert_eq!(status, StatusCode::OK);
+
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        run_ephemeral_expiry_round(&test_db.app).await;
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=expiry-service").await;
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 1);
+        assert_eq!(body["hosts"][0]["healthy"], false);
+
+        // 心跳恢复健康
+        let (_, body) = send(&router, "PUT", "/nacos/v1/ns/instance/beat?serviceName=expiry-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(body["code"], 10200);
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=expiry-service").await;
+        assert_eq!(body["hosts"][0]["healthy"], true);
+
+        tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+        run_ephemeral_expiry_round(&test_db.app).await;
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=expiry-service").await;
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 0);
+
+        let (_, body) = send(&router, "PUT", "/nacos/v1/ns/instance/beat?serviceName=expiry-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(body["code"], 20404);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
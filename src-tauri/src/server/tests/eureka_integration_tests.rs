@@ -0,0 +1,149 @@
+/**
+ * Eureka 兼容 API 集成测试
+ * 测试 JSON / XML 注册、续约、覆盖状态、下线、全量和增量拉取，
+ * 以及 Nacos 客户端与 Eureka 客户端共享同一注册表
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求，返回状态码和响应文本
+    async fn send(router: &Router, method: &str, uri: &str, content_type: &str, accept: &str, body: &str) -> (StatusCode, String) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", content_type)
+            .header("Accept", accept)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8_lossy(&body_bytes).to_string())
+    }
+
+    /// 发送 JSON 请求并解析 JSON 响应
+    async fn send_json(router: &Router, method: &str, uri: &str, body: &str) -> (StatusCode, serde_json::Value) {
+        let (status, text) = send(router, method, uri, "application/json", "application/json", body).await;
+        (status, serde_json::from_str(&text).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：JSON 注册、续约、覆盖状态、下线，以及增量拉取
+    /// POST /nacos/eureka/apps/{app} -> PUT /{id} -> PUT /{id}/status -> DELETE /{id}
+    #[tokio::test]
+    async fn test_eureka_json_lifecycle() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 首次全量拉取建立增量快照
+        let (status, body) = send_json(&router, "GET", "/nacos/eureka/apps", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["applications"]["application"].as_array().unwrap().len(), 0);
+
+        let registration = r#"{"instance":{"instanceId":"host-1:ORDER-SERVICE:8080","hostName":"host-1","app":"ORDER-SERVICE",
+            "ipAddr":"10.0.0.1","status":"UP","port":{"$":8080,"@enabled":"true"},"vipAddress":"order-service",
+            "leaseInfo":{"renewalIntervalInSecs":30,"durationInSecs":90},"metadata":{"zone":"zone-a"}}}"#;
+        let (status, _) = send_json(&router, "POST", "/nacos/eureka/apps/ORDER-SERVICE", registration).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (_, body) = send_json(&router, "GET", "/nacos/eureka/apps/delta", "").await;
+        let instance = &body["applications"]["application"][0]["instance"][0];
+        assert_eq!(instance["actionType"], "ADDED");
+        assert_eq!(instance["instanceId"], "host-1:ORDER-SERVICE:8080");
+        assert_eq!(body["applications"]["apps__hashcode"], "UP_1_");
+
+        let (status, body) = send_json(&router, "GET", "/nacos/eureka/apps/ORDER-SERVICE/host-1:ORDER-SERVICE:8080", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["instance"]["status"], "UP");
+        assert_eq!(body["instance"]["hostName"], "host-1");
+        assert_eq!(body["instance"]["metadata"]["zone"], "zone-a");
+        assert_eq!(body["instance"]["port"]["$"], 8080);
+
+        // 续约即心跳，未知实例返回 404 促使客户端重新注册
+        let (status, _) = send_json(&router, "PUT", "/nacos/eureka/apps/ORDER-SERVICE/host-1:ORDER-SERVICE:8080?status=UP", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send_json(&router, "PUT", "/nacos/eureka/apps/ORDER-SERVICE/unknown-instance", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // 覆盖状态为 OUT_OF_SERVICE 后 Nacos 视图中实例被禁用
+        let (status, _) = send_json(&router, "PUT", "/nacos/eureka/apps/ORDER-SERVICE/host-1:ORDER-SERVICE:8080/status?value=OUT_OF_SERVICE", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send_json(&router, "GET", "/nacos/eureka/apps/ORDER-SERVICE", "").await;
+        assert_eq!(body["application"]["instance"][0]["status"], "OUT_OF_SERVICE");
+        assert_eq!(body["application"]["instance"][0]["overriddenStatus"], "OUT_OF_SERVICE");
+        let (_, body) = send_json(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=ORDER-SERVICE", "").await;
+        assert_eq!(body["hosts"][0]["enabled"], false);
+
+        let (status, _) = send_json(&router, "DELETE", "/nacos/eureka/apps/ORDER-SERVICE/host-1:ORDER-SERVICE:8080/status?value=UP", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send_json(&router, "GET", "/nacos/eureka/apps/ORDER-SERVICE", "").await;
+        assert_eq!(body["application"]["instance"][0]["status"], "UP");
+
+        let (status, _) = send_json(&router, "DELETE", "/nacos/eureka/apps/ORDER-SERVICE/host-1:ORDER-SERVICE:8080", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send_json(&router, "GET", "/nacos/eureka/apps/ORDER-SERVICE", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (_, body) = send_json(&router, "GET", "/nacos/eureka/apps/delta", "").await;
+        assert_eq!(body["applications"]["application"][0]["instance"][0]["actionType"], "DELETED");
+        assert_eq!(body["applications"]["apps__hashcode"], "");
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：XML 注册和 XML 响应，Nacos 注册的实例对 Eureka 客户端可见
+    /// POST /nacos/eureka/apps/{app} (application/xml) -> GET /nacos/eureka/apps (Accept: application/xml)
+    #[tokio::test]
+    async fn test_eureka_xml_and_shared_registry() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let registration = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <instance>
+              <instanceId>host-2:PAYMENT:9090</instanceId>
+              <hostName>host-2</hostName>
+              <app>PAYMENT</app>
+              <ipAddr>10.0.0.2</ipAddr>
+              <status>UP</status>
+              <port enabled="true">9090</port>
+              <securePort enabled="false">443</securePort>
+              <dataCenterInfo class="com.netflix.appinfo.InstanceInfo$DefaultDataCenterInfo"><name>MyOwn</name></dataCenterInfo>
+              <metadata><version>1.0 &amp; beta</version></metadata>
+            </instance>"#;
+        let (status, _) = send(&router, "POST", "/nacos/eureka/apps/PAYMENT", "application/xml", "application/xml", registration).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        // Nacos 客户端注册的实例在 Eureka 中以大写应用名出现
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=inventory&ip=10.0.0.3&port=7070", "application/x-www-form-urlencoded", "*/*", "").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, xml) = send(&router, "GET", "/nacos/eureka/apps", "application/xml", "application/xml", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(xml.contains("<name>PAYMENT</name>"));
+        assert!(xml.contains("<name>INVENTORY</name>"));
+        assert!(xml.contains("<port enabled=\"true\">9090</port>"));
+        assert!(xml.contains("<version>1.0 &amp; beta</version>"));
+        assert!(xml.contains("<apps__hashcode>UP_2_</apps__hashcode>"));
+
+        // Eureka 注册的实例对 Nacos 客户端可见，应用名不区分大小写
+        let (_, body) = send_json(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=PAYMENT", "").await;
+        assert_eq!(body["hosts"][0]["ip"], "10.0.0.2");
+        assert_eq!(body["hosts"][0]["port"], 9090);
+        let (status, body) = send_json(&router, "GET", "/nacos/eureka/apps/payment", "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["application"]["instance"][0]["metadata"]["version"], "1.0 & beta");
+
+        let (status, _) = send(&router, "POST", "/nacos/eureka/apps/PAYMENT", "application/xml", "application/xml", "<instance><app>PAYMENT</app></instance>").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
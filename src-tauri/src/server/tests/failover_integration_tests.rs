@@ -0,0 +1,121 @@
+/**
+ * 命名服务容灾快照集成测试
+ * 测试导出 Nacos 客户端容灾目录格式的快照：zip 下载和写入本地目录（Tauri 命令），
+ * 文件名按 Nacos 客户端的方式 URL 编码
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::service::{export_failover_snapshot, FailoverExportRequest};
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use std::io::Read;
+    use tower::ServiceExt;
+
+    /// 发送表单请求并返回状态码和响应体
+    async fn send(router: &Router, method: &str, uri: &str, body: &str) -> (StatusCode, Vec<u8>) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, body.to_vec())
+    }
+
+    /// 注册 orders 服务的实例，并在 other 命名空间注册同名服务
+    async fn register_instances(router: &Router) {
+        let (status, _) = send(router, "POST", "/nacos/v1/ns/instance", "serviceName=orders&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(router, "POST", "/nacos/v1/ns/instance", "serviceName=orders&groupName=PAY&ip=10.0.0.2&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(router, "POST", "/nacos/v1/ns/instance", "serviceName=orders&namespaceId=other&ip=10.0.1.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    /// 测试用例：下载容灾快照 zip
+    /// GET /nacos/v1/ns/failover/snapshot
+    #[tokio::test]
+    async fn test_download_failover_snapshot() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register_instances(&router).await;
+
+        let (status, body) = send(&router, "GET", "/nacos/v1/ns/failover/snapshot?failoverSwitch=true", "").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body)).unwrap();
+        let mut content = String::new();
+        archive.by_name("failover/DEFAULT_GROUP%40%40orders").unwrap().read_to_string(&mut content).unwrap();
+        let service_info: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(service_info["name"], "orders");
+        assert_eq!(service_info["groupName"], "DEFAULT_GROUP");
+        assert_eq!(service_info["hosts"].as_array().unwrap().len(), 1);
+        assert_eq!(service_info["hosts"][0]["ip"], "10.0.0.1");
+        assert!(archive.by_name("failover/PAY%40%40orders").is_ok());
+
+        let mut switch = String::new();
+        archive
+            .by_name("failover/00-00---000-VIPSRV_FAILOVER_SWITCH-000---00-00")
+            .unwrap()
+            .read_to_string(&mut switch)
+            .unwrap();
+        assert_eq!(switch, "1");
+
+        // 只包含指定命名空间的服务
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/failover/snapshot?namespaceId=other", "").await;
+        let archive = zip::ZipArchive::new(std::io::Cursor::new(body)).unwrap();
+        let names: Vec<&str> = archive.file_names().filter(|name| !name.ends_with('/')).collect();
+        assert_eq!(names, vec!["failover/DEFAULT_GROUP%40%40orders"]);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：把容灾快照写入本地目录，服务名中的路径字符不会逃出 failover 目录
+    /// export_failover_snapshot
+    #[tokio::test]
+    async fn test_export_failover_snapshot_to_directory() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register_instances(&router).await;
+        test_db.insert_test_service("../escaped", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let request = |output_path: String| FailoverExportRequest {
+            namespace_id: "public".to_string(),
+            output_path,
+            zip: false,
+            failover_switch: false,
+        };
+        let result = export_failover_snapshot(&test_db.app, request(String::new())).await;
+        assert!(result.unwrap_err().starts_with("Invalid"));
+
+        let cache_dir = test_db.temp_dir.path().join("naming-cache");
+        let summary = export_failover_snapshot(&test_db.app, request(cache_dir.to_string_lossy().into_owned()))
+            .await
+            .unwrap();
+        assert_eq!(summary.service_count, 3);
+        assert_eq!(summary.instance_count, 2);
+
+        let failover_dir = cache_dir.join("failover");
+        let content = std::fs::read_to_string(failover_dir.join("PAY%40%40orders")).unwrap();
+        let service_info: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(service_info["hosts"][0]["ip"], "10.0.0.2");
+        assert!(failover_dir.join("DEFAULT_GROUP%40%40..%2Fescaped").exists());
+        assert!(!cache_dir.join("escaped").exists());
+        assert!(!failover_dir.join("00-00---000-VIPSRV_FAILOVER_SWITCH-000---00-00").exists());
+
+        // HTTP 接口不接受本地路径
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/failover/snapshot", "path=/tmp").await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,245 @@
+/**
+ * Nacos gRPC 协议集成测试
+ * 测试一元请求（服务检查、配置发布和查询、实例注册、服务订阅），
+ * 以及双向流的服务端推送和连接断开后注销实例
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::grpc::{create_grpc_router, encode_frame, take_frame, GrpcBody, Payload};
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::{Body, Bytes},
+        extract::ConnectInfo,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use http_body::Frame;
+    use http_body_util::BodyExt;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    /// 编码一条 gRPC 请求消息
+    fn request_frame(r#type: &str, body: serde_json::Value) -> Vec<u8> {
+        encode_frame(&Payload::new(r#type, &body).encode())
+    }
+
+    /// 解码响应消息，返回 (类型名, JSON)
+    fn decode_message(buf: &mut Vec<u8>) -> Option<(String, serde_json::Value)> {
+        let message = take_frame(buf).unwrap()?;
+        let payload = Payload::decode(&message).unwrap();
+        let body = payload.json().unwrap();
+        Some((payload.r#type, body))
+    }
+
+    /// 发送一元请求
+    async fn call(router: &Router, addr: SocketAddr, r#type: &str, body: serde_json::Value) -> (String, serde_json::Value) {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/Request/request")
+            .header("Content-Type", "application/grpc")
+            .body(Body::from(request_frame(r#type, body)))
+            .unwrap();
+        request.extensions_mut().insert(ConnectInfo(addr));
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let collected = response.into_body().collect().await.unwrap();
+        assert_eq!(collected.trailers().unwrap()["grpc-status"], "0");
+        decode_message(&mut collected.to_bytes().to_vec()).unwrap()
+    }
+
+    /// 通过 HTTP 接口查询实例数量
+    async fn http_instance_count(router: &Router, service_name: &str) -> usize {
+        let request = Request::builder()
+            .uri(format!("/nacos/v1/ns/instance/list?serviceName={}", service_name))
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body["hosts"].as_array().map_or(0, |hosts| hosts.len())
+    }
+
+    /// 通过 HTTP 接口查询 JSON
+    async fn http_get(router: &Router, uri: &str) -> serde_json::Value {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// 测试用例：一元请求处理配置和命名请求
+    /// POST /Request/request
+    #[tokio::test]
+    async fn test_grpc_unary_requests() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let grpc = create_grpc_router(test_db.app.clone());
+        let http = create_router("/nacos".to_string(), test_db.app.clone());
+        let addr: SocketAddr = "127.0.0.1:50001".parse().unwrap();
+
+        let (r#type, body) = call(&grpc, addr, "ServerCheckRequest", serde_json::json!({ "requestId": "1" })).await;
+        assert_eq!(r#type, "ServerCheckResponse");
+        assert_eq!(body["resultCode"], 200);
+        assert_eq!(body["requestId"], "1");
+        assert!(body["connectionId"].as_str().unwrap().ends_with("_127.0.0.1_50001"));
+
+        let publish = serde_json::json!({ "dataId": "app.yaml", "group": "DEFAULT_GROUP", "tenant": "", "content": "a: 1",
+            "additionMap": { "type": "yaml" } });
+        let (r#type, body) = call(&grpc, addr, "ConfigPublishRequest", publish).await;
+        assert_eq!(r#type, "ConfigPublishResponse");
+        assert_eq!(body["success"], true);
+
+        let query = serde_json::json!({ "dataId": "app.yaml", "group": "DEFAULT_GROUP", "tenant": "" });
+        let (r#type, body) = call(&grpc, addr, "ConfigQueryRequest", query).await;
+        assert_eq!(r#type, "ConfigQueryResponse");
+        assert_eq!(body["content"], "a: 1");
+        assert_eq!(body["contentType"], "yaml");
+        let md5 = body["md5"].as_str().unwrap().to_string();
+
+        // casMd5 不一致时发布失败
+        let cas = serde_json::json!({ "dataId": "app.yaml", "group": "DEFAULT_GROUP", "content": "a: 2", "casMd5": "stale" });
+        let (_, body) = call(&grpc, addr, "ConfigPublishRequest", cas).await;
+        assert_eq!(body["success"], false);
+        let cas = serde_json::json!({ "dataId": "app.yaml", "group": "DEFAULT_GROUP", "content": "a: 2", "casMd5": md5 });
+        let (_, body) = call(&grpc, addr, "ConfigPublishRequest", cas).await;
+        assert_eq!(body["success"], true);
+
+        let missing = serde_json::json!({ "dataId": "missing.yaml", "group": "DEFAULT_GROUP" });
+        let (_, body) = call(&grpc, addr, "ConfigQueryRequest", missing).await;
+        assert_eq!(body["success"], false);
+        assert_eq!(body["errorCode"], 300);
+
+        // 批量监听：客户端 MD5 过期的配置被返回
+        let listen = serde_json::json!({ "listen": true, "configListenContexts": [
+            { "dataId": "app.yaml", "group": "DEFAULT_GROUP", "tenant": "", "md5": md5 }
+        ] });
+        let (r#type, body) = call(&grpc, addr, "ConfigBatchListenRequest", listen).await;
+        assert_eq!(r#type, "ConfigChangeBatchListenResponse");
+        assert_eq!(body["changedConfigs"][0]["dataId"], "app.yaml");
+
+        // 注册的实例对 HTTP 客户端可见，服务自动创建
+        let register = serde_json::json!({ "namespace": "public", "serviceName": "order-service", "groupName": "DEFAULT_GROUP",
+            "type": "registerInstance", "instance": { "ip": "10.0.0.1", "port": 8080, "weight": 1.0, "healthy": true,
+            "enabled": true, "ephemeral": true, "clusterName": "DEFAULT", "metadata": { "version": "1" } } });
+        let (r#type, body) = call(&grpc, addr, "InstanceRequest", register).await;
+        assert_eq!(r#type, "InstanceResponse");
+        assert_eq!(body["type"], "registerInstance");
+        assert_eq!(http_instance_count(&http, "order-service").await, 1);
+
+        let subscribe = serde_json::json!({ "namespace": "public", "serviceName": "order-service", "groupName": "DEFAULT_GROUP",
+            "clusters": "", "subscribe": true });
+        let (r#type, body) = call(&grpc, addr, "SubscribeServiceRequest", subscribe).await;
+        assert_eq!(r#type, "SubscribeServiceResponse");
+        assert_eq!(body["serviceInfo"]["name"], "order-service");
+        assert_eq!(body["serviceInfo"]["hosts"][0]["ip"], "10.0.0.1");
+        assert_eq!(body["serviceInfo"]["hosts"][0]["metadata"]["version"], "1");
+
+        let deregister = serde_json::json!({ "namespace": "public", "serviceName": "order-service", "groupName": "DEFAULT_GROUP",
+            "type": "deRegisterInstance", "instance": { "ip": "10.0.0.1", "port": 8080, "clusterName": "DEFAULT" } });
+        let (_, body) = call(&grpc, addr, "InstanceRequest", deregister).await;
+        assert_eq!(body["success"], true);
+        assert_eq!(http_instance_count(&http, "order-service").await, 0);
+
+        let (r#type, body) = call(&grpc, addr, "UnknownRequest", serde_json::json!({})).await;
+        assert_eq!(r#type, "ErrorResponse");
+        assert_eq!(body["errorCode"], 302);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：双向流推送配置变更和服务变更，流结束后注销该连接注册的实例
+    /// POST /BiRequestStream/requestBiStream
+    #[tokio::test]
+    async fn test_grpc_bi_stream_push_and_disconnect() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let grpc = create_grpc_router(test_db.app.clone());
+        let http = create_router("/nacos".to_string(), test_db.app.clone());
+        let addr: SocketAddr = "127.0.0.1:50002".parse().unwrap();
+
+        // 建立双向流
+        let (client_tx, client_body) = GrpcBody::channel();
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/BiRequestStream/requestBiStream")
+            .header("Content-Type", "application/grpc")
+            .body(Body::new(client_body))
+            .unwrap();
+        request.extensions_mut().insert(ConnectInfo(addr));
+        let setup = request_frame("ConnectionSetupRequest", serde_json::json!({ "clientVersion": "Nacos-Java-Client:v2.3.0",
+            "labels": { "AppName": "demo" } }));
+        client_tx.send(Frame::data(Bytes::from(setup))).unwrap();
+        let response = grpc.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut stream = response.into_body();
+
+        let listen = serde_json::json!({ "listen": true, "configListenContexts": [
+            { "dataId": "push.yaml", "group": "DEFAULT_GROUP", "tenant": "", "md5": "" }
+        ] });
+        let (_, body) = call(&grpc, addr, "ConfigBatchListenRequest", listen).await;
+        assert_eq!(body["changedConfigs"].as_array().unwrap().len(), 0);
+        let subscribe = serde_json::json!({ "serviceName": "push-service", "subscribe": true });
+        let (_, body) = call(&grpc, addr, "SubscribeServiceRequest", subscribe).await;
+        assert_eq!(body["serviceInfo"]["hosts"].as_array().unwrap().len(), 0);
+
+        // 监听的配置和订阅的服务记录为订阅者
+        let subscribers = "/nacos/v1/ns/service/subscribers?serviceName=push-service&namespaceId=public";
+        let listeners = "/nacos/v1/cs/configs/listener?dataId=push.yaml&group=DEFAULT_GROUP";
+        assert_eq!(http_get(&http, subscribers).await["count"], 1);
+        assert_eq!(http_get(&http, listeners).await["listeners"][0]["port"], 50002);
+
+        let publish = serde_json::json!({ "dataId": "push.yaml", "group": "DEFAULT_GROUP", "content": "b: 1" });
+        call(&grpc, addr, "ConfigPublishRequest", publish).await;
+        let register = serde_json::json!({ "serviceName": "push-service", "type": "registerInstance",
+            "instance": { "ip": "10.0.0.2", "port": 9090 } });
+        call(&grpc, addr, "InstanceRequest", register).await;
+
+        // 配置和服务变更事件触发推送
+        let (mut config_pushed, mut service_pushed) = (false, false);
+        let mut buf = Vec::new();
+        while !(config_pushed && service_pushed) {
+            let frame = tokio::time::timeout(Duration::from_secs(5), stream.frame())
+                .await
+                .expect("push timeout")
+                .unwrap()
+                .unwrap();
+            buf.extend_from_slice(&frame.into_data().unwrap());
+            while let Some((r#type, body)) = decode_message(&mut buf) {
+                match r#type.as_str() {
+                    "ConfigChangeNotifyRequest" => {
+                        assert_eq!(body["dataId"], "push.yaml");
+                        config_pushed = true;
+                    }
+                    "NotifySubscriberRequest" => {
+                        if body["serviceInfo"]["hosts"].as_array().unwrap().len() == 1 {
+                            assert_eq!(body["serviceInfo"]["hosts"][0]["ip"], "10.0.0.2");
+                            service_pushed = true;
+                        }
+                    }
+                    other => panic!("unexpected push {}", other),
+                }
+            }
+        }
+
+        // 连接存活期间实例不会因心跳超时而过期，流结束后实例被注销
+        assert_eq!(http_instance_count(&http, "push-service").await, 1);
+        drop(client_tx);
+        let trailers = loop {
+            let frame = tokio::time::timeout(Duration::from_secs(5), stream.frame())
+                .await
+                .expect("stream end timeout")
+                .unwrap()
+                .unwrap();
+            if let Ok(trailers) = frame.into_trailers() {
+                break trailers;
+            }
+        };
+        assert_eq!(trailers["grpc-status"], "0");
+        assert_eq!(http_instance_count(&http, "push-service").await, 0);
+        assert_eq!(http_get(&http, subscribers).await["count"], 0);
+        assert_eq!(http_get(&http, listeners).await["listeners"].as_array().unwrap().len(), 0);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+/**
+ * 实例健康时间线集成测试
+ * 测试注册、手动修改健康状态和注销被记录到时间线（含来源和原因），
+ * 以及按时间窗口计算实例和服务的可用率
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求，返回状态码和 JSON 响应体（非 JSON 时为 Null）
+    async fn send(router: &Router, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：注册、手动修改健康状态和注销按顺序记录到实例时间线
+    /// GET /nacos/v1/ns/instance/health/timeline
+    #[tokio::test]
+    async fn test_instance_health_timeline_records_transitions() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=timeline-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(&router, "PUT", "/nacos/v1/ns/health/instance?serviceName=timeline-service&ip=10.0.0.1&port=8080&healthy=false").await;
+        assert_eq!(status, StatusCode::OK);
+        // 健康状态未变化时不记录事件
+        let (status, _) = send(&router, "PUT", "/nacos/v1/ns/health/instance?serviceName=timeline-service&ip=10.0.0.1&port=8080&healthy=false").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = send(&router, "GET", "/nacos/v1/ns/instance/health/timeline?serviceName=timeline-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let instances = body["instances"].as_array().unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0]["address"], "10.0.0.1:8080#DEFAULT");
+        assert_eq!(instances[0]["healthy"], false);
+        let events = instances[0]["events"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["source"], "REGISTER");
+        assert_eq!(events[0]["healthy"], true);
+        assert_eq!(events[1]["source"], "MANUAL");
+        assert_eq!(events[1]["healthy"], false);
+        assert_eq!(events[1]["reason"], "updated via API");
+
+        let (status, _) = send(&router, "DELETE", "/nacos/v1/ns/instance?serviceName=timeline-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/service/health/timeline?serviceName=timeline-service").await;
+        let instance = &body["instances"][0];
+        assert!(instance["healthy"].is_null());
+        assert_eq!(instance["events"].as_array().unwrap().last().unwrap()["source"], "DEREGISTER");
+
+        // 参数错误
+        let (status, _) = send(&router, "GET", "/nacos/v1/ns/instance/health/timeline?serviceName=timeline-service&ip=10.0.0.1").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(&router, "GET", "/nacos/v1/ns/service/health/timeline?serviceName=timeline-service&startTime=2000&endTime=1000").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：可用率只统计窗口内实例处于注册状态的时长，窗口开始前的事件决定初始状态
+    /// GET /nacos/v1/ns/service/health/timeline
+    #[tokio::test]
+    async fn test_service_health_timeline_uptime() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let events = [
+            // 窗口内注册，健康 600 秒、不健康 200 秒、健康 200 秒
+            ("10.0.0.1:8080#DEFAULT", true, "REGISTER", 1000),
+            ("10.0.0.1:8080#DEFAULT", false, "HEARTBEAT", 1600),
+            ("10.0.0.1:8080#DEFAULT", true, "HEARTBEAT", 1800),
+            // 窗口开始前注册，窗口内健康 500 秒后注销
+            ("10.0.0.2:8080#DEFAULT", true, "REGISTER", 500),
+            ("10.0.0.2:8080#DEFAULT", false, "DEREGISTER", 1500),
+            // 窗口开始前已注销，不返回
+            ("10.0.0.3:8080#DEFAULT", true, "REGISTER", 100),
+            ("10.0.0.3:8080#DEFAULT", false, "DEREGISTER", 200),
+        ];
+        for (address, healthy, source, time) in events {
+            test_db.insert_test_health_event("uptime-service", address, healthy, source, time).await.unwrap();
+        }
+
+        let (status, body) = send(&router, "GET", "/nacos/v1/ns/service/health/timeline?serviceName=uptime-service&startTime=1000&endTime=2000").await;
+        assert_eq!(status, StatusCode::OK);
+        let instances = body["instances"].as_array().unwrap();
+        assert_eq!(instances.len(), 2);
+
+        assert_eq!(instances[0]["address"], "10.0.0.1:8080#DEFAULT");
+        assert_eq!(instances[0]["observedSeconds"], 1000);
+        assert_eq!(instances[0]["healthySeconds"], 800);
+        assert_eq!(instances[0]["uptimePercent"], 80.0);
+        assert_eq!(instances[0]["events"].as_array().unwrap().len(), 3);
+
+        assert_eq!(instances[1]["address"], "10.0.0.2:8080#DEFAULT");
+        assert_eq!(instances[1]["observedSeconds"], 500);
+        assert_eq!(instances[1]["uptimePercent"], 100.0);
+        assert_eq!(instances[1]["events"].as_array().unwrap().len(), 1);
+
+        assert_eq!(body["uptimePercent"], 86.67);
+
+        // 单个实例
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/health/timeline?serviceName=uptime-service&ip=10.0.0.1&port=8080&startTime=1700&endTime=2000").await;
+        let instances = body["instances"].as_array().unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0]["observedSeconds"], 300);
+        assert_eq!(instances[0]["healthySeconds"], 200);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
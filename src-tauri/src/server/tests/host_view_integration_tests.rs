@@ -0,0 +1,79 @@
+/**
+ * 主机视图集成测试
+ * 测试按 IP 汇总跨命名空间的实例、订阅的服务、监听的配置和最后一次心跳
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    /// 测试用例：主机视图包含实例、服务订阅和配置监听，不包含其他主机的数据
+    /// GET /nacos/v1/console/host?ip=
+    #[tokio::test]
+    async fn test_host_view() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_instance("order-service", "dev", "DEFAULT_GROUP", "10.1.2.3", 9090).await.unwrap();
+        test_db.insert_test_instance("order-service", "dev", "DEFAULT_GROUP", "10.1.2.4", 9090).await.unwrap();
+        test_db.insert_test_naming_subscriber("user-service", "public", "DEFAULT_GROUP", "10.1.2.3", 0, "").await.unwrap();
+        test_db.insert_test_subscriber("app.yaml", "DEFAULT_GROUP", "public", "10.1.2.3", Some(0), Some("abc")).await.unwrap();
+        test_db.insert_test_subscriber("app.yaml", "DEFAULT_GROUP", "public", "10.1.2.4", Some(0), Some("abc")).await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 临时实例通过 API 注册，带有心跳时间
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from("serviceName=pay-service&ip=10.1.2.3&port=8080&namespaceId=public"))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v1/console/host?ip=10.1.2.3")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["ip"], "10.1.2.3");
+        let instances = json["instances"].as_array().unwrap();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(instances[0]["namespaceId"], "dev");
+        assert_eq!(instances[0]["serviceName"], "order-service");
+        assert!(instances[0]["lastBeat"].is_null());
+        assert_eq!(instances[1]["serviceName"], "pay-service");
+        assert!(instances[1]["lastBeat"].as_i64().unwrap() > 0);
+        assert_eq!(json["lastHeartbeat"], instances[1]["lastBeat"]);
+
+        let subscribed = json["subscribedServices"].as_array().unwrap();
+        assert_eq!(subscribed.len(), 1);
+        assert_eq!(subscribed[0]["serviceName"], "user-service");
+
+        let listeners = json["configListeners"].as_array().unwrap();
+        assert_eq!(listeners.len(), 1);
+        assert_eq!(listeners[0]["dataId"], "app.yaml");
+        assert_eq!(listeners[0]["md5"], "abc");
+
+        // 缺少 IP 返回 400
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v1/console/host?ip=")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
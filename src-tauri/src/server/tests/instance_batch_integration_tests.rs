@@ -0,0 +1,125 @@
+/**
+ * 实例批量注册与注销集成测试
+ * 测试批量接口的逐实例结果、持久化与临时实例混合写入以及每批次只递增一次修订号
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送表单请求并解析 JSON 响应
+    async fn send(router: &Router, method: &str, uri: &str, form: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(form.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：批量注册返回逐实例结果，非法实例不影响其他实例，修订号只递增一次
+    /// POST /nacos/v1/ns/instance/batch
+    #[tokio::test]
+    async fn test_batch_register_instances() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("batch-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (_, before) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=batch-service", "").await;
+
+        let instances = serde_json::json!([
+            {"ip": "10.0.0.1", "port": 8080},
+            {"ip": "10.0.0.1", "port": 9090, "clusterName": "GRPC", "ephemeral": false},
+            {"ip": "10.0.0.2", "port": 8080, "weight": -1},
+            {"ip": "10.0.0.1", "port": 8080}
+        ]);
+        let (status, body) = send(
+            &router,
+            "POST",
+            "/nacos/v1/ns/instance/batch",
+            &format!("serviceName=batch-service&instances={}", urlencoding::encode(&instances.to_string())),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["succeeded"], 2);
+        assert_eq!(body["failed"], 2);
+        assert_eq!(body["results"][0]["success"], true);
+        assert_eq!(body["results"][1]["clusterName"], "GRPC");
+        assert_eq!(body["results"][2]["message"], "Invalid weight");
+        assert_eq!(body["results"][3]["message"], "Duplicate instance in batch");
+
+        let (_, after) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=batch-service", "").await;
+        assert_eq!(after["hosts"].as_array().unwrap().len(), 2);
+        assert_eq!(after["revision"].as_u64().unwrap(), before["revision"].as_u64().unwrap() + 1);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：批量注销，不存在的实例标记失败；服务不存在时返回 404
+    /// DELETE /nacos/v1/ns/instance/batch
+    #[tokio::test]
+    async fn test_batch_deregister_instances() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("batch-deregister-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let instances = serde_json::json!([
+            {"ip": "10.0.0.1", "port": 8080},
+            {"ip": "10.0.0.2", "port": 8080, "ephemeral": false}
+        ]);
+        send(
+            &router,
+            "POST",
+            "/nacos/v1/ns/instance/batch",
+            &format!("serviceName=batch-deregister-service&instances={}", urlencoding::encode(&instances.to_string())),
+        )
+        .await;
+
+        let refs = serde_json::json!([
+            {"ip": "10.0.0.1", "port": 8080},
+            {"ip": "10.0.0.2", "port": 8080},
+            {"ip": "10.0.0.3", "port": 8080}
+        ]);
+        let (status, body) = send(
+            &router,
+            "DELETE",
+            "/nacos/v1/ns/instance/batch",
+            &format!("serviceName=batch-deregister-service&instances={}", urlencoding::encode(&refs.to_string())),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["succeeded"], 2);
+        assert_eq!(body["results"][2]["success"], false);
+        assert_eq!(body["results"][2]["message"], "Instance not found");
+
+        let (_, list) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=batch-deregister-service", "").await;
+        assert_eq!(list["hosts"].as_array().unwrap().len(), 0);
+
+        // 与单个注册一致，服务不存在时自动创建
+        let (status, body) = send(
+            &router,
+            "POST",
+            "/nacos/v1/ns/instance/batch",
+            &format!("serviceName=missing-service&instances={}", urlencoding::encode(&instances.to_string())),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["succeeded"], 2);
+
+        let (_, list) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=missing-service", "").await;
+        assert_eq!(list["hosts"].as_array().unwrap().len(), 2);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+/**
+ * 实例列表校验和与修订号集成测试
+ * 测试校验和的稳定性、未变化时的轻量响应以及实例变更后修订号递增
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 查询实例列表
+    async fn list(router: &Router, query: &str) -> serde_json::Value {
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("/nacos/v1/ns/instance/list?{}", query))
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body_bytes).unwrap()
+    }
+
+    /// 注册实例
+    async fn register(router: &Router, body: &str) {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// 测试用例：实例集合不变时校验和稳定，携带校验和返回 unchanged
+    /// GET /nacos/v1/ns/instance/list?checksum=
+    #[tokio::test]
+    async fn test_checksum_stable_and_unchanged_response() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("checksum-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "serviceName=checksum-service&ip=10.0.0.1&port=8080&namespaceId=public").await;
+
+        let first = list(&router, "serviceName=checksum-service&namespaceId=public").await;
+        let second = list(&router, "serviceName=checksum-service&namespaceId=public").await;
+
+        let checksum = first["checksum"].as_str().unwrap().to_string();
+        assert!(!checksum.is_empty());
+        assert_eq!(second["checksum"], first["checksum"]);
+        assert_eq!(second["lastRefTime"], first["lastRefTime"]);
+        assert_eq!(first["unchanged"], false);
+
+        let unchanged = list(&router, &format!("serviceName=checksum-service&namespaceId=public&checksum={}", checksum)).await;
+        assert_eq!(unchanged["unchanged"], true);
+        assert_eq!(unchanged["checksum"], checksum.as_str());
+        assert!(unchanged.get("hosts").is_none());
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：实例变更后校验和变化、修订号递增
+    /// POST /nacos/v1/ns/instance -> GET /nacos/v1/ns/instance/list
+    #[tokio::test]
+    async fn test_revision_increments_on_mutation() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("revision-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "serviceName=revision-service&ip=10.0.0.1&port=8080&namespaceId=public").await;
+
+        let before = list(&router, "serviceName=revision-service&namespaceId=public").await;
+
+        register(&router, "serviceName=revision-service&ip=10.0.0.2&port=8080&namespaceId=public").await;
+
+        let after = list(&router, &format!(
+            "serviceName=revision-service&namespaceId=public&checksum={}",
+            before["checksum"].as_str().unwrap()
+        )).await;
+
+        assert_eq!(after["unchanged"], false);
+        assert_ne!(after["checksum"], before["checksum"]);
+        assert!(after["revision"].as_u64().unwrap() > before["revision"].as_u64().unwrap());
+        assert_eq!(after["hosts"].as_array().unwrap().len(), 2);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+/**
+ * 实例 ID 生成集成测试
+ * 测试 simple 生成器的 ID 格式、重复注册沿用原 ID、snowflake 生成器生成递增整数，
+ * 以及心跳、健康状态和注销接口能找到通过服务层注册的实例
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求并解析 JSON 响应
+    async fn send(router: &Router, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：默认 simple 生成器，按 IP、端口和集群识别实例
+    /// POST /nacos/v1/ns/instance -> PUT /nacos/v1/ns/instance/beat -> DELETE /nacos/v1/ns/instance
+    #[tokio::test]
+    async fn test_simple_instance_id() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("id-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        for _ in 0..2 {
+            let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=id-service&ip=10.0.0.1&port=8080&ephemeral=false").await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=id-service").await;
+        let hosts = body["hosts"].as_array().unwrap();
+        assert_eq!(hosts.len(), 1);
+        assert_eq!(hosts[0]["instanceId"], "10.0.0.1#8080#DEFAULT#DEFAULT_GROUP@@id-service");
+
+        let (_, body) = send(&router, "PUT", "/nacos/v1/ns/instance/beat?serviceName=id-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(body["code"], 10200);
+
+        let (status, _) = send(&router, "DELETE", "/nacos/v1/ns/instance?serviceName=id-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=id-service").await;
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 0);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：通过保留元数据选择 snowflake 生成器，生成递增的整数 ID
+    /// POST /nacos/v1/ns/instance
+    #[tokio::test]
+    async fn test_snowflake_instance_id() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("snowflake-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let metadata = urlencoding::encode(r#"{"preserved.instance.id.generator":"snowflake"}"#);
+        for ip in ["10.0.0.1", "10.0.0.2"] {
+            let (status, _) = send(&router, "POST", &format!("/nacos/v1/ns/instance?serviceName=snowflake-service&ip={}&port=8080&metadata={}", ip, metadata)).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=snowflake-service").await;
+        let id_of = |ip: &str| -> i64 {
+            body["hosts"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|h| h["ip"] == ip)
+                .and_then(|h| h["instanceId"].as_str())
+                .and_then(|id| id.parse().ok())
+                .unwrap()
+        };
+        assert!(id_of("10.0.0.2") > id_of("10.0.0.1"));
+        assert!(body["hosts"].as_array().unwrap().iter().all(|h| h["instanceIdGenerator"] == "snowflake"));
+
+        // 实例 ID 与请求参数无关，健康状态接口仍能找到实例
+        let (status, _) = send(&router, "PUT", "/nacos/v1/ns/health/instance?serviceName=snowflake-service&ip=10.0.0.1&port=8080&healthy=false").await;
+        assert_ne!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
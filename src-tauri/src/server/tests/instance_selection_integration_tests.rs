@@ -0,0 +1,121 @@
+/**
+ * 实例选择 API 集成测试
+ * 测试按权重选择健康实例、粘性哈希和区域优先
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 通过 API 注册实例
+    async fn register(router: &Router, body: &str) {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// 调用 selectOne 并返回响应
+    async fn select_one(router: &Router, query: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method("GET")
+            .uri(format!("/nacos/v1/ns/instance/selectOne?{}", query))
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (status, body)
+    }
+
+    /// 测试用例：只选择健康且启用的实例
+    /// GET /nacos/v1/ns/instance/selectOne
+    #[tokio::test]
+    async fn test_select_one_skips_disabled_instances() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("select-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "serviceName=select-service&ip=10.0.0.1&port=8080&namespaceId=public").await;
+        register(&router, "serviceName=select-service&ip=10.0.0.2&port=8080&namespaceId=public&enabled=false").await;
+
+        for _ in 0..10 {
+            let (status, body) = select_one(&router, "serviceName=select-service&namespaceId=public").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["ip"], "10.0.0.1");
+        }
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：相同的 hashKey 总是选中同一实例
+    /// GET /nacos/v1/ns/instance/selectOne?hashKey=
+    #[tokio::test]
+    async fn test_select_one_sticky_hash() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("sticky-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "serviceName=sticky-service&ip=10.0.0.1&port=8080&namespaceId=public").await;
+        register(&router, "serviceName=sticky-service&ip=10.0.0.2&port=8080&namespaceId=public").await;
+        register(&router, "serviceName=sticky-service&ip=10.0.0.3&port=8080&namespaceId=public").await;
+
+        let (_, first) = select_one(&router, "serviceName=sticky-service&namespaceId=public&hashKey=user-42").await;
+        for _ in 0..5 {
+            let (status, body) = select_one(&router, "serviceName=sticky-service&namespaceId=public&hashKey=user-42").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["ip"], first["ip"]);
+        }
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：优先选择同区域实例，没有同区域实例时退回全部实例
+    /// GET /nacos/v1/ns/instance/selectOne?zone=
+    #[tokio::test]
+    async fn test_select_one_zone_affinity() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("zone-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "serviceName=zone-service&ip=10.0.0.1&port=8080&namespaceId=public&metadata=%7B%22zone%22%3A%22zone-a%22%7D").await;
+        register(&router, "serviceName=zone-service&ip=10.0.0.2&port=8080&namespaceId=public&metadata=%7B%22zone%22%3A%22zone-b%22%7D").await;
+
+        for _ in 0..10 {
+            let (status, body) = select_one(&router, "serviceName=zone-service&namespaceId=public&zone=zone-b").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["ip"], "10.0.0.2");
+        }
+
+        let (status, _) = select_one(&router, "serviceName=zone-service&namespaceId=public&zone=zone-c").await;
+        assert_eq!(status, StatusCode::OK);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：没有可用实例时返回 404
+    /// GET /nacos/v1/ns/instance/selectOne
+    #[tokio::test]
+    async fn test_select_one_no_instance() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("empty-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        let (status, _) = select_one(&router, "serviceName=empty-service&namespaceId=public").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
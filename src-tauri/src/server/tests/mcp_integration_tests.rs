@@ -0,0 +1,199 @@
+/**
+ * MCP 服务注册表集成测试
+ * 测试 MCP 服务的创建、版本管理、工具定义修改、启用/禁用，以及引用命名服务的端点解析
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求并返回状态码和 JSON 响应体
+    async fn send(router: &Router, method: &str, uri: &str, form: Option<Vec<(&str, String)>>) -> (StatusCode, serde_json::Value) {
+        let mut builder = Request::builder().method(method).uri(uri);
+        let body = match form {
+            Some(fields) => {
+                builder = builder.header("Content-Type", "application/x-www-form-urlencoded");
+                let encoded: Vec<String> = fields
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+                    .collect();
+                Body::from(encoded.join("&"))
+            }
+            None => Body::empty(),
+        };
+        let response = router.clone().oneshot(builder.body(body).unwrap()).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 远程 MCP 服务定义
+    fn server_spec(version: &str) -> String {
+        serde_json::json!({
+            "name": "weather",
+            "version": version,
+            "description": format!("weather {}", version),
+            "protocol": "mcp-sse",
+            "exportPath": "/sse"
+        })
+        .to_string()
+    }
+
+    /// 引用 weather-svc 命名服务的端点
+    fn ref_endpoint() -> String {
+        serde_json::json!({ "type": "REF", "data": { "serviceName": "weather-svc" } }).to_string()
+    }
+
+    /// 测试用例：创建、查询、新增版本、修改工具定义和删除
+    /// /nacos/v3/admin/ai/mcp
+    #[tokio::test]
+    async fn test_mcp_server_lifecycle() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("weather-svc", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from("serviceName=weather-svc&ip=10.0.0.1&port=8080"))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let tools = serde_json::json!({ "tools": [{ "name": "forecast", "description": "weather forecast" }] }).to_string();
+        let (status, body) = send(&router, "POST", "/nacos/v3/admin/ai/mcp", Some(vec![
+            ("serverSpecification", server_spec("1.0.0")),
+            ("toolSpecification", tools),
+            ("endpointSpecification", ref_endpoint()),
+        ])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["code"], 0);
+        assert_eq!(body["data"]["latestPublishedVersion"], "1.0.0");
+        assert_eq!(body["data"]["backendEndpoints"][0]["address"], "10.0.0.1");
+        assert_eq!(body["data"]["backendEndpoints"][0]["port"], 8080);
+
+        // 重复创建
+        let (status, _) = send(&router, "POST", "/nacos/v3/admin/ai/mcp", Some(vec![
+            ("serverSpecification", server_spec("1.0.0")),
+            ("endpointSpecification", ref_endpoint()),
+        ])).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // 新增版本并设为最新版本
+        let (status, body) = send(&router, "PUT", "/nacos/v3/console/ai/mcp", Some(vec![
+            ("serverSpecification", server_spec("1.1.0")),
+            ("endpointSpecification", ref_endpoint()),
+            ("latest", "true".to_string()),
+        ])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["latestPublishedVersion"], "1.1.0");
+
+        let (_, body) = send(&router, "GET", "/nacos/v3/admin/ai/mcp/versions?mcpName=weather", None).await;
+        let versions = body["data"].as_array().unwrap();
+        assert_eq!(versions.len(), 2);
+        assert!(versions.iter().any(|v| v["version"] == "1.1.0" && v["isLatest"] == true));
+
+        // 旧版本仍可查询
+        let (_, body) = send(&router, "GET", "/nacos/v3/admin/ai/mcp?mcpName=weather&version=1.0.0", None).await;
+        assert_eq!(body["data"]["description"], "weather 1.0.0");
+        assert_eq!(body["data"]["toolSpec"]["tools"][0]["name"], "forecast");
+
+        // 修改最新版本的工具定义
+        let tools = serde_json::json!({
+            "tools": [{ "name": "forecast" }, { "name": "alerts" }],
+            "toolsMeta": { "alerts": { "enabled": false } }
+        })
+        .to_string();
+        let (status, body) = send(&router, "PUT", "/nacos/v3/admin/ai/mcp/tools", Some(vec![
+            ("mcpName", "weather".to_string()),
+            ("toolSpecification", tools),
+        ])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["versionDetail"]["version"], "1.1.0");
+        assert_eq!(body["data"]["toolSpec"]["tools"].as_array().unwrap().len(), 2);
+
+        let (status, _) = send(&router, "DELETE", "/nacos/v3/admin/ai/mcp?mcpName=weather", None).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = send(&router, "GET", "/nacos/v3/admin/ai/mcp?mcpName=weather", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], 20004);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：参数校验、列表查询和启用/禁用
+    /// /nacos/v3/admin/ai/mcp/list 和 /nacos/v3/admin/ai/mcp/status
+    #[tokio::test]
+    async fn test_mcp_server_validation_and_list() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 远程协议必须指定端点
+        let (status, body) = send(&router, "POST", "/nacos/v3/admin/ai/mcp", Some(vec![
+            ("serverSpecification", server_spec("1.0.0")),
+        ])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], 20002);
+
+        // 引用的命名服务不存在
+        let (status, _) = send(&router, "POST", "/nacos/v3/admin/ai/mcp", Some(vec![
+            ("serverSpecification", server_spec("1.0.0")),
+            ("endpointSpecification", ref_endpoint()),
+        ])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // 工具名称重复
+        let stdio_spec = serde_json::json!({ "name": "files", "version": "0.1.0", "protocol": "stdio" }).to_string();
+        let tools = serde_json::json!({ "tools": [{ "name": "read" }, { "name": "read" }] }).to_string();
+        let (status, _) = send(&router, "POST", "/nacos/v3/admin/ai/mcp", Some(vec![
+            ("serverSpecification", stdio_spec.clone()),
+            ("toolSpecification", tools),
+        ])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // stdio 服务不需要端点
+        let (status, body) = send(&router, "POST", "/nacos/v3/admin/ai/mcp", Some(vec![
+            ("serverSpecification", stdio_spec),
+        ])).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["data"]["backendEndpoints"].as_array().unwrap().is_empty());
+
+        let direct_spec = serde_json::json!({ "name": "files-remote", "version": "0.1.0", "protocol": "mcp-streamable" }).to_string();
+        let direct_endpoint = serde_json::json!({ "type": "DIRECT", "data": { "address": "10.0.0.9", "port": 9000 } }).to_string();
+        let (status, _) = send(&router, "POST", "/nacos/v3/admin/ai/mcp", Some(vec![
+            ("serverSpecification", direct_spec),
+            ("endpointSpecification", direct_endpoint),
+        ])).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (_, body) = send(&router, "GET", "/nacos/v3/admin/ai/mcp/list?mcpName=files", None).await;
+        assert_eq!(body["data"]["totalCount"], 2);
+        let (_, body) = send(&router, "GET", "/nacos/v3/admin/ai/mcp/list?mcpName=files&search=accurate", None).await;
+        assert_eq!(body["data"]["totalCount"], 1);
+        assert_eq!(body["data"]["pageItems"][0]["protocol"], "stdio");
+
+        let (status, _) = send(&router, "PUT", "/nacos/v3/admin/ai/mcp/status", Some(vec![
+            ("mcpName", "files".to_string()),
+            ("enabled", "false".to_string()),
+        ])).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send(&router, "GET", "/nacos/v3/console/ai/mcp?mcpName=files", None).await;
+        assert_eq!(body["data"]["enabled"], false);
+
+        let (status, _) = send(&router, "PUT", "/nacos/v3/admin/ai/mcp/status", Some(vec![
+            ("mcpName", "missing".to_string()),
+            ("enabled", "true".to_string()),
+        ])).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,234 @@
+/**
+ * 实例元数据约定集成测试
+ * 测试服务的元数据约定：强制模式拒绝违规的注册、部分更新和批量更新，告警模式接受写入并报告违规
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送表单请求并返回状态码、元数据违规响应头和 JSON 响应体
+    async fn send(
+        router: &Router,
+        method: &str,
+        uri: &str,
+        form: Vec<(&str, String)>,
+    ) -> (StatusCode, Option<String>, serde_json::Value) {
+        let encoded: Vec<String> = form
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+            .collect();
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(encoded.join("&")))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let violations = response
+            .headers()
+            .get("X-Nacos-Metadata-Violations")
+            .map(|v| urlencoding::decode(v.to_str().unwrap()).unwrap().into_owned());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, violations, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 设置 orders 服务的元数据约定
+    async fn set_schema(router: &Router, mode: &str) -> StatusCode {
+        let schema = serde_json::json!({
+            "mode": mode,
+            "rules": [
+                { "key": "env", "required": true, "allowedValues": ["prod", "test"] },
+                { "key": "version", "pattern": "\\d+\\.\\d+" }
+            ]
+        })
+        .to_string();
+        let (status, _, _) = send(router, "PUT", "/nacos/v1/ns/service/metadata/schema", vec![
+            ("serviceName", "orders".to_string()),
+            ("schema", schema),
+        ]).await;
+        status
+    }
+
+    /// 注册 orders 服务的实例
+    async fn register(router: &Router, ip: &str, metadata: &str) -> (StatusCode, Option<String>) {
+        let (status, violations, _) = send(router, "POST", "/nacos/v1/ns/instance", vec![
+            ("serviceName", "orders".to_string()),
+            ("ip", ip.to_string()),
+            ("port", "8080".to_string()),
+            ("metadata", metadata.to_string()),
+        ]).await;
+        (status, violations)
+    }
+
+    /// 测试用例：强制模式拒绝违规的注册、部分更新和批量更新
+    /// PUT /nacos/v1/ns/service/metadata/schema
+    #[tokio::test]
+    async fn test_metadata_schema_enforce() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("orders", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 约定校验
+        let (status, _, _) = send(&router, "PUT", "/nacos/v1/ns/service/metadata/schema", vec![
+            ("serviceName", "orders".to_string()),
+            ("schema", r#"{"rules":[{"key":"version","pattern":"("}]}"#.to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _, _) = send(&router, "PUT", "/nacos/v1/ns/service/metadata/schema", vec![
+            ("serviceName", "missing".to_string()),
+            ("schema", r#"{"rules":[]}"#.to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(set_schema(&router, "enforce").await, StatusCode::OK);
+
+        // 缺少必需的键、值不在允许范围内、值不匹配正则表达式
+        let (status, _) = register(&router, "10.0.0.1", r#"{"version":"1.0"}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = register(&router, "10.0.0.1", r#"{"env":"dev"}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = register(&router, "10.0.0.1", r#"{"env":"prod","version":"v1"}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, violations) = register(&router, "10.0.0.1", r#"{"env":"prod","version":"1.0"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(violations.is_none());
+
+        // 部分更新按合并后的元数据校验
+        let (status, _, _) = send(&router, "PATCH", "/nacos/v1/ns/instance", vec![
+            ("serviceName", "orders".to_string()),
+            ("ip", "10.0.0.1".to_string()),
+            ("port", "8080".to_string()),
+            ("metadata", r#"{"env":"dev"}"#.to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _, _) = send(&router, "PATCH", "/nacos/v1/ns/instance", vec![
+            ("serviceName", "orders".to_string()),
+            ("ip", "10.0.0.1".to_string()),
+            ("port", "8080".to_string()),
+            ("metadata", r#"{"version":"2.0"}"#.to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // 批量删除必需的键被拒绝，实例元数据保持不变
+        let instances = r#"[{"ip":"10.0.0.1","port":8080}]"#;
+        let (status, _, _) = send(
+            &router,
+            "DELETE",
+            &format!("/nacos/v1/ns/instance/metadata/batch?serviceName=orders&instances={}&metadata=env", urlencoding::encode(instances)),
+            vec![],
+        ).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _, body) = send(&router, "PUT", "/nacos/v1/ns/instance/metadata/batch", vec![
+            ("serviceName", "orders".to_string()),
+            ("instances", instances.to_string()),
+            ("metadata", "env=test".to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["violations"].as_array().unwrap().is_empty());
+
+        let (_, _, body) = send(&router, "GET", "/nacos/v1/ns/service?serviceName=orders", vec![]).await;
+        assert_eq!(body["metadataSchema"]["mode"], "enforce");
+        assert_eq!(body["hosts"][0]["metadata"]["env"], "test");
+        assert_eq!(body["hosts"][0]["metadata"]["version"], "2.0");
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：告警模式接受违规的写入并报告违规
+    /// GET /nacos/v1/ns/service/metadata/violations
+    #[tokio::test]
+    async fn test_metadata_schema_warn() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("orders", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 没有约定时不报告违规
+        let (status, _) = register(&router, "10.0.0.1", r#"{"env":"prod"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, _, body) = send(&router, "GET", "/nacos/v1/ns/service/metadata/violations?serviceName=orders", vec![]).await;
+        assert!(body["mode"].is_null());
+
+        assert_eq!(set_schema(&router, "warn").await, StatusCode::OK);
+
+        let (status, violations) = register(&router, "10.0.0.2", r#"{"env":"dev"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        let violations: serde_json::Value = serde_json::from_str(&violations.unwrap()).unwrap();
+        assert_eq!(violations[0]["ip"], "10.0.0.2");
+        assert_eq!(violations[0]["violations"][0]["key"], "env");
+
+        let (_, _, body) = send(&router, "GET", "/nacos/v1/ns/service/metadata/violations?serviceName=orders", vec![]).await;
+        assert_eq!(body["mode"], "warn");
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["instances"][0]["ip"], "10.0.0.2");
+
+        // v2 批量更新在响应中报告违规
+        let (status, _, body) = send(&router, "PUT", "/nacos/v2/ns/instance/metadata/batch", vec![
+            ("serviceName", "orders".to_string()),
+            ("metadata", r#"{"version":"latest"}"#.to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["updated"].as_array().unwrap().len(), 2);
+        assert_eq!(body["data"]["violations"].as_array().unwrap().len(), 2);
+
+        // 批量注册在响应中报告违规的实例
+        let instances = serde_json::json!([
+            { "ip": "10.0.0.3", "port": 8080, "metadata": { "env": "prod", "version": "1.0" } },
+            { "ip": "10.0.0.4", "port": 8080, "metadata": { "env": "dev" } }
+        ]);
+        let (status, _, body) = send(&router, "POST", "/nacos/v1/ns/instance/batch", vec![
+            ("serviceName", "orders".to_string()),
+            ("instances", instances.to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["succeeded"], 2);
+        assert_eq!(body["violations"].as_array().unwrap().len(), 1);
+        assert_eq!(body["violations"][0]["ip"], "10.0.0.4");
+
+        // 清除约定
+        let (status, _, _) = send(&router, "PUT", "/nacos/v1/ns/service/metadata/schema", vec![
+            ("serviceName", "orders".to_string()),
+        ]).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, _, body) = send(&router, "GET", "/nacos/v1/ns/service?serviceName=orders", vec![]).await;
+        assert!(body["metadataSchema"].is_null());
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：已保存约定中无法编译的正则表达式被报告为违规，而不是被忽略
+    /// GET /nacos/v1/ns/service/metadata/violations
+    #[tokio::test]
+    async fn test_metadata_schema_invalid_stored_pattern() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("orders", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let pool = sqlx::SqlitePool::connect(&format!("sqlite:{}", test_db.db_path.display())).await.unwrap();
+        sqlx::query("UPDATE service_info SET metadata_schema = ? WHERE service_name = 'orders'")
+            .bind(r#"{"mode":"warn","rules":[{"key":"version","pattern":"("}]}"#)
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        let (status, violations) = register(&router, "10.0.0.1", r#"{"version":"1.0"}"#).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(violations.is_some());
+
+        let (_, _, body) = send(&router, "GET", "/nacos/v1/ns/service/metadata/violations?serviceName=orders", vec![]).await;
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["instances"][0]["violations"][0]["key"], "version");
+        assert!(body["instances"][0]["violations"][0]["reason"].as_str().unwrap().contains("not a valid regular expression"));
+
+        test_db.cleanup().await.unwrap();
+    }
+}
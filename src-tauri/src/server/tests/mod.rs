@@ -45,4 +45,53 @@ mod instance_standard_api_tests;
 mod config_standard_api_tests;
 #[cfg(test)]
 mod nacos_compatibility_tests;
-
+#[cfg(test)]
+mod naming_subscriber_integration_tests;
+#[cfg(test)]
+mod cluster_integration_tests;
+#[cfg(test)]
+mod instance_selection_integration_tests;
+#[cfg(test)]
+mod naming_v2_integration_tests;
+#[cfg(test)]
+mod instance_checksum_integration_tests;
+#[cfg(test)]
+mod ephemeral_instance_integration_tests;
+#[cfg(test)]
+mod service_history_integration_tests;
+#[cfg(test)]
+mod instance_batch_integration_tests;
+#[cfg(test)]
+mod instance_id_integration_tests;
+#[cfg(test)]
+mod empty_service_cleaner_integration_tests;
+#[cfg(test)]
+mod naming_switches_integration_tests;
+#[cfg(test)]
+mod dns_integration_tests;
+#[cfg(test)]
+mod eureka_integration_tests;
+#[cfg(test)]
+mod consul_integration_tests;
+#[cfg(test)]
+mod grpc_integration_tests;
+#[cfg(test)]
+mod xds_integration_tests;
+#[cfg(test)]
+mod health_timeline_integration_tests;
+#[cfg(test)]
+mod simulator_integration_tests;
+#[cfg(test)]
+mod zone_affinity_integration_tests;
+#[cfg(test)]
+mod host_view_integration_tests;
+#[cfg(test)]
+mod catalog_integration_tests;
+#[cfg(test)]
+mod mcp_integration_tests;
+#[cfg(test)]
+mod service_export_integration_tests;
+#[cfg(test)]
+mod metadata_schema_integration_tests;
+#[cfg(test)]
+mod failover_integration_tests;
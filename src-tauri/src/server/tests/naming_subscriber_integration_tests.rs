@@ -0,0 +1,125 @@
+/**
+ * 服务订阅者 API 集成测试
+ * 测试实例列表查询记录订阅者，以及订阅者查询的分页、分组和聚合
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    /// 测试用例：查询实例列表后记录为订阅者
+    /// GET /nacos/v1/ns/instance/list -> GET /nacos/v1/ns/service/subscribers
+    #[tokio::test]
+    async fn test_list_instances_records_subscriber() {
+        let test_db = TestDatabase::new().await.unwrap();
+
+        test_db.insert_test_service("subscribed-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v1/ns/instance/list?serviceName=subscribed-service&namespaceId=public&clientIP=10.0.0.8&udpPort=53001&app=order-app&clusters=DEFAULT")
+            .header("User-Agent", "Nacos-Java-Client:v2.3.0")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v1/ns/service/subscribers?serviceName=subscribed-service&namespaceId=public")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(body["count"], 1);
+        let subscriber = &body["subscribers"][0];
+        assert_eq!(subscriber["addrStr"], "10.0.0.8:53001");
+        assert_eq!(subscriber["app"], "order-app");
+        assert_eq!(subscriber["agent"], "Nacos-Java-Client:v2.3.0");
+        assert_eq!(subscriber["cluster"], "DEFAULT");
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：订阅者查询遵循 groupName
+    /// GET /nacos/v1/ns/service/subscribers
+    #[tokio::test]
+    async fn test_get_subscribers_honours_group_name() {
+        let test_db = TestDatabase::new().await.unwrap();
+
+        test_db.insert_test_naming_subscriber("grouped-service", "public", "DEFAULT_GROUP", "10.0.0.1", 0, "").await.unwrap();
+        test_db.insert_test_naming_subscriber("grouped-service", "public", "ORDER_GROUP", "10.0.0.2", 0, "").await.unwrap();
+        test_db.insert_test_naming_subscriber("grouped-service", "public", "ORDER_GROUP", "10.0.0.3", 0, "").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v1/ns/service/subscribers?serviceName=grouped-service&namespaceId=public&groupName=ORDER_GROUP")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+
+        assert_eq!(body["count"], 2);
+        for subscriber in body["subscribers"].as_array().unwrap() {
+            assert_eq!(subscriber["serviceName"], "ORDER_GROUP@@grouped-service");
+        }
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：聚合模式按 IP:PORT 合并不同集群的订阅
+    /// GET /nacos/v3/console/ns/service/subscribers
+    #[tokio::test]
+    async fn test_console_get_subscribers_aggregation_and_paging() {
+        let test_db = TestDatabase::new().await.unwrap();
+
+        test_db.insert_test_naming_subscriber("aggregated-service", "public", "DEFAULT_GROUP", "10.0.0.1", 9000, "cluster-a").await.unwrap();
+        test_db.insert_test_naming_subscriber("aggregated-service", "public", "DEFAULT_GROUP", "10.0.0.1", 9000, "cluster-b").await.unwrap();
+        test_db.insert_test_naming_subscriber("aggregated-service", "public", "DEFAULT_GROUP", "10.0.0.2", 9000, "cluster-a").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 聚合：两个不同的订阅者地址
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v3/console/ns/service/subscribers?serviceName=aggregated-service&groupName=DEFAULT_GROUP&namespaceId=public&aggregation=true")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["code"], 0);
+        assert_eq!(body["data"]["totalCount"], 2);
+
+        // 非聚合 + 分页：三条记录，每页两条
+        let request = Request::builder()
+            .method("GET")
+            .uri("/nacos/v3/console/ns/service/subscribers?serviceName=aggregated-service&groupName=DEFAULT_GROUP&namespaceId=public&aggregation=false&pageNo=2&pageSize=2")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["data"]["totalCount"], 3);
+        assert_eq!(body["data"]["pageItems"].as_array().unwrap().len(), 1);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+/**
+ * 系统开关集成测试
+ * 测试开关持久化与校验，以及 clientBeatInterval、healthCheckEnabled、pushEnabled、
+ * defaultInstanceEphemeral、disableAddIP 修改后立即生效
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use crate::service::run_ephemeral_expiry_round;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求并解析 JSON 响应
+    async fn send(router: &Router, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：修改开关后查询结果和心跳响应立即变化，非法开关名或值返回 400
+    /// PUT /nacos/v1/ns/operator/switches
+    #[tokio::test]
+    async fn test_update_switches_take_effect() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("switch-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, _) = send(&router, "PUT", "/nacos/v1/ns/operator/switches?entry=clientBeatInterval&value=8000").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/operator/switches").await;
+        assert_eq!(body["clientBeatInterval"], 8000);
+
+        send(&router, "POST", "/nacos/v1/ns/instance?serviceName=switch-service&ip=10.0.0.1&port=8080").await;
+        let (_, body) = send(&router, "PUT", "/nacos/v1/ns/instance/beat?serviceName=switch-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(body["clientBeatInterval"], 8000);
+
+        // 实例列表的心跳周期和超时按开关计算
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=switch-service").await;
+        assert_eq!(body["hosts"][0]["instanceHeartBeatInterval"], 8000);
+        assert_eq!(body["hosts"][0]["instanceHeartBeatTimeOut"], 24000);
+        assert_eq!(body["hosts"][0]["ipDeleteTimeout"], 48000);
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/instance/list?serviceName=switch-service").await;
+        assert_eq!(body["data"]["hosts"][0]["instanceHeartBeatInterval"], 8000);
+        assert_eq!(body["data"]["hosts"][0]["instanceHeartBeatTimeOut"], 24000);
+
+        let (status, _) = send(&router, "PUT", "/nacos/v1/ns/operator/switches?entry=unknownSwitch&value=true").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = send(&router, "PUT", "/nacos/v1/ns/operator/switches?entry=pushEnabled&value=maybe").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // 推送关闭只停止推送，查询实例时仍然记录订阅者
+        send(&router, "PUT", "/nacos/v1/ns/operator/switches?entry=pushEnabled&value=false").await;
+        send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=switch-service&clientIP=10.0.0.8&udpPort=53001").await;
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/service/subscribers?serviceName=switch-service&namespaceId=public").await;
+        assert_eq!(body["count"], 1);
+
+        // 健康检查关闭后心跳超时的临时实例保持健康
+        send(&router, "PUT", "/nacos/v1/ns/operator/switches?entry=healthCheckEnabled&value=false").await;
+        let metadata = urlencoding::encode(r#"{"preserved.heart.beat.timeout":"50","preserved.ip.delete.timeout":"50"}"#);
+        send(&router, "POST", &format!("/nacos/v1/ns/instance?serviceName=switch-service&ip=10.0.0.2&port=8080&metadata={}", metadata)).await;
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        run_ephemeral_expiry_round(&test_db.app).await;
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=switch-service").await;
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 2);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：defaultInstanceEphemeral 决定未指定类型的实例，disableAddIP 拒绝注册
+    /// POST /nacos/v1/ns/instance
+    #[tokio::test]
+    async fn test_registration_switches() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("switch-register-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        send(&router, "PUT", "/nacos/v1/ns/operator/switches?entry=defaultInstanceEphemeral&value=false").await;
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=switch-register-service&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=switch-register-service").await;
+        assert_eq!(body["hosts"][0]["ephemeral"], false);
+
+        send(&router, "PUT", "/nacos/v1/ns/operator/switches?entry=disableAddIP&value=true").await;
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/instance?serviceName=switch-register-service&ip=10.0.0.2&port=8080").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/operator/switches").await;
+        assert_eq!(body["disableAddIP"], true);
+        assert_eq!(body["defaultInstanceEphemeral"], false);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,189 @@
+/**
+ * 命名服务 v2 OpenAPI 集成测试
+ * 测试 /nacos/v2/ns 下接口的响应格式、错误码以及与 v1 数据的一致性
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求并解析 v2 响应
+    async fn send(router: &Router, method: &str, uri: &str, form: Option<&str>) -> (StatusCode, serde_json::Value) {
+        let builder = Request::builder().method(method).uri(uri);
+        let request = match form {
+            Some(body) => builder
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        };
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap())
+    }
+
+    /// 测试用例：服务创建、查询、列表以及重复创建
+    /// POST/GET /nacos/v2/ns/service, GET /nacos/v2/ns/service/list
+    #[tokio::test]
+    async fn test_v2_service_crud() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, body) = send(&router, "POST", "/nacos/v2/ns/service", Some("serviceName=v2-service&protectThreshold=0.5&metadata=%7B%22owner%22%3A%22team-a%22%7D")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["code"], 0);
+        assert_eq!(body["data"], "ok");
+
+        let (status, body) = send(&router, "GET", "/nacos/v2/ns/service?serviceName=v2-service", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["serviceName"], "v2-service");
+        assert_eq!(body["data"]["groupName"], "DEFAULT_GROUP");
+        assert_eq!(body["data"]["protectThreshold"], 0.5);
+        assert_eq!(body["data"]["metadata"]["owner"], "team-a");
+
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/service/list?pageNo=1&pageSize=10", None).await;
+        assert_eq!(body["data"]["count"], 1);
+        assert_eq!(body["data"]["services"][0], "v2-service");
+
+        let (status, body) = send(&router, "POST", "/nacos/v2/ns/service", Some("serviceName=v2-service")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], 21007);
+
+        let (status, body) = send(&router, "DELETE", "/nacos/v2/ns/service?serviceName=v2-service", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"], "ok");
+
+        let (status, body) = send(&router, "GET", "/nacos/v2/ns/service?serviceName=v2-service", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], 21008);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：实例注册、查询、更新和注销，v1 接口可见 v2 注册的实例
+    /// /nacos/v2/ns/instance, /nacos/v2/ns/instance/list
+    #[tokio::test]
+    async fn test_v2_instance_lifecycle() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("v2-instance-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, body) = send(&router, "POST", "/nacos/v2/ns/instance", Some("serviceName=v2-instance-service&ip=10.0.0.1&port=8080&weight=2")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"], "ok");
+
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/instance/list?serviceName=v2-instance-service", None).await;
+        assert_eq!(body["code"], 0);
+        assert_eq!(body["data"]["name"], "DEFAULT_GROUP@@v2-instance-service");
+        assert_eq!(body["data"]["hosts"].as_array().unwrap().len(), 1);
+
+        let (status, body) = send(&router, "PUT", "/nacos/v2/ns/instance", Some("serviceName=v2-instance-service&ip=10.0.0.1&port=8080&weight=5")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"], "ok");
+
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/instance?serviceName=v2-instance-service&ip=10.0.0.1&port=8080", None).await;
+        assert_eq!(body["data"]["weight"], 5.0);
+        assert_eq!(body["data"]["clusterName"], "DEFAULT");
+
+        // v1 接口读取到同一实例
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/instance/list?serviceName=v2-instance-service", None).await;
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 1);
+
+        let (status, _) = send(&router, "DELETE", "/nacos/v2/ns/instance?serviceName=v2-instance-service&ip=10.0.0.1&port=8080", None).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = send(&router, "GET", "/nacos/v2/ns/instance?serviceName=v2-instance-service&ip=10.0.0.1&port=8080", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], 21003);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：缺少必需参数、权重非法、服务不存在
+    /// POST /nacos/v2/ns/instance
+    #[tokio::test]
+    async fn test_v2_error_codes() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, body) = send(&router, "POST", "/nacos/v2/ns/instance", Some("ip=10.0.0.1&port=8080")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], 10000);
+        assert_eq!(body["message"], "parameter missing");
+
+        let (status, body) = send(&router, "POST", "/nacos/v2/ns/instance", Some("serviceName=missing&ip=10.0.0.1&port=8080&weight=-1")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], 21001);
+
+        let (status, body) = send(&router, "POST", "/nacos/v2/ns/instance", Some("serviceName=missing&ip=10.0.0.1&port=8080")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], 21008);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：心跳未注册实例返回 20404，批量元数据更新
+    /// PUT /nacos/v2/ns/instance/beat, PUT /nacos/v2/ns/instance/metadata/batch
+    #[tokio::test]
+    async fn test_v2_beat_and_batch_metadata() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("v2-beat-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (_, body) = send(&router, "PUT", "/nacos/v2/ns/instance/beat", Some("serviceName=v2-beat-service&ip=10.0.0.9&port=9000")).await;
+        assert_eq!(body["code"], 0);
+        assert_eq!(body["data"]["code"], 20404);
+
+        send(&router, "POST", "/nacos/v2/ns/instance", Some("serviceName=v2-beat-service&ip=10.0.0.9&port=9000")).await;
+
+        let (_, body) = send(&router, "PUT", "/nacos/v2/ns/instance/beat", Some("serviceName=v2-beat-service&ip=10.0.0.9&port=9000")).await;
+        assert_eq!(body["data"]["code"], 10200);
+
+        let (status, body) = send(&router, "PUT", "/nacos/v2/ns/instance/metadata/batch", Some("serviceName=v2-beat-service&metadata=%7B%22version%22%3A%222.0%22%7D")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["updated"].as_array().unwrap().len(), 1);
+
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/instance?serviceName=v2-beat-service&ip=10.0.0.9&port=9000", None).await;
+        assert_eq!(body["data"]["metadata"]["version"], "2.0");
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：客户端视图
+    /// GET /nacos/v2/ns/client/list, /nacos/v2/ns/client/publish/list, /nacos/v2/ns/client/service/subscriber/list
+    #[tokio::test]
+    async fn test_v2_client_introspection() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("v2-client-service", "public", "DEFAULT_GROUP").await.unwrap();
+        test_db.insert_test_naming_subscriber("v2-client-service", "public", "DEFAULT_GROUP", "10.0.0.20", 0, "").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        send(&router, "POST", "/nacos/v2/ns/instance", Some("serviceName=v2-client-service&ip=10.0.0.10&port=8080")).await;
+
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/client/list", None).await;
+        let clients = body["data"].as_array().unwrap();
+        assert!(clients.contains(&serde_json::json!("10.0.0.10:8080#true")));
+        assert!(clients.contains(&serde_json::json!("10.0.0.20:0#true")));
+
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/client/publish/list?clientId=10.0.0.10:8080%23true", None).await;
+        assert_eq!(body["data"][0]["serviceName"], "v2-client-service");
+        assert_eq!(body["data"][0]["registeredInstance"]["ip"], "10.0.0.10");
+
+        let (_, body) = send(&router, "GET", "/nacos/v2/ns/client/service/subscriber/list?serviceName=v2-client-service", None).await;
+        assert_eq!(body["data"][0]["clientId"], "10.0.0.20:0#true");
+
+        let (status, body) = send(&router, "GET", "/nacos/v2/ns/client?clientId=10.9.9.9:1%23true", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], 20004);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+/**
+ * 服务导出集成测试
+ * 测试跨命名空间导出服务：导出目标解析为源服务实例、服务详情展示、修订号同步、只读和撤销
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送表单请求并返回状态码
+    async fn send_form(router: &Router, method: &str, uri: &str, body: &str) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    /// 发送请求并返回状态码和 JSON 响应体
+    async fn send_json(router: &Router, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：导出目标解析为源服务实例，并随源服务变化，撤销后为空
+    /// /nacos/v1/ns/service/export
+    #[tokio::test]
+    async fn test_service_export_resolution() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("auth", "infra", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let status = send_form(&router, "POST", "/nacos/v1/ns/instance", "serviceName=auth&namespaceId=infra&ip=10.0.0.1&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let status = send_form(
+            &router,
+            "POST",
+            "/nacos/v1/ns/service/export",
+            "serviceName=auth&namespaceId=infra&targetNamespaceId=team-b&targetGroupName=SHARED&targetServiceName=auth-shared",
+        ).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let target = "/nacos/v1/ns/instance/list?serviceName=auth-shared&groupName=SHARED&namespaceId=team-b";
+        let (status, body) = send_json(&router, "GET", target).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 1);
+        assert_eq!(body["hosts"][0]["ip"], "10.0.0.1");
+        let checksum = body["checksum"].as_str().unwrap().to_string();
+        let revision = body["revision"].as_u64().unwrap();
+
+        // 源服务变化后，导出目标的修订号和校验和随之变化
+        let status = send_form(&router, "POST", "/nacos/v1/ns/instance", "serviceName=auth&namespaceId=infra&ip=10.0.0.2&port=8080").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send_json(&router, "GET", &format!("{}&checksum={}", target, checksum)).await;
+        assert!(body.get("unchanged").is_none());
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 2);
+        assert!(body["revision"].as_u64().unwrap() > revision);
+
+        // 服务详情展示导出关系
+        let (status, body) = send_json(&router, "GET", "/nacos/v1/ns/service?serviceName=auth-shared&groupName=SHARED&namespaceId=team-b").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["exportedFrom"]["namespaceId"], "infra");
+        assert_eq!(body["exportedFrom"]["serviceName"], "auth");
+        assert_eq!(body["hosts"].as_array().unwrap().len(), 2);
+        let (_, body) = send_json(&router, "GET", "/nacos/v1/ns/service?serviceName=auth&namespaceId=infra").await;
+        assert_eq!(body["exports"][0]["targetNamespaceId"], "team-b");
+        assert_eq!(body["exports"][0]["targetServiceName"], "auth-shared");
+
+        // 导出目标只读
+        let status = send_form(
+            &router,
+            "POST",
+            "/nacos/v1/ns/instance",
+            "serviceName=auth-shared&groupName=SHARED&namespaceId=team-b&ip=10.0.1.1&port=8080",
+        ).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (_, body) = send_json(&router, "GET", "/nacos/v1/ns/service/export?targetNamespaceId=team-b").await;
+        assert_eq!(body["count"], 1);
+
+        // 撤销后导出目标没有实例
+        let revoke = "/nacos/v1/ns/service/export?targetNamespaceId=team-b&targetGroupName=SHARED&targetServiceName=auth-shared";
+        let (status, _) = send_json(&router, "DELETE", revoke).await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, body) = send_json(&router, "GET", target).await;
+        assert!(body["hosts"].as_array().unwrap().is_empty());
+        let (status, _) = send_json(&router, "DELETE", revoke).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：导出参数校验
+    /// POST /nacos/v1/ns/service/export
+    #[tokio::test]
+    async fn test_service_export_validation() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("billing", "infra", "DEFAULT_GROUP").await.unwrap();
+        test_db.insert_test_service("billing", "team-c", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 源服务不存在
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service/export", "serviceName=missing&namespaceId=infra&targetNamespaceId=team-b").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // 目标与源相同
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service/export", "serviceName=billing&namespaceId=infra&targetNamespaceId=infra").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // 目标命名空间已有同名服务
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service/export", "serviceName=billing&namespaceId=infra&targetNamespaceId=team-c").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service/export", "serviceName=billing&namespaceId=infra&targetNamespaceId=team-b").await;
+        assert_eq!(status, StatusCode::OK);
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service/export", "serviceName=billing&namespaceId=infra&targetNamespaceId=team-b").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+
+        // 导出别名不能再次导出
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service/export", "serviceName=billing&namespaceId=team-b&targetNamespaceId=team-d").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：删除源服务时一并删除其导出，导出目标的名称可以重新使用
+    /// DELETE /nacos/v1/ns/service
+    #[tokio::test]
+    async fn test_service_export_removed_with_source() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("billing", "infra", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service/export", "serviceName=billing&namespaceId=infra&targetNamespaceId=team-b").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = send_json(&router, "DELETE", "/nacos/v1/ns/service?serviceName=billing&namespaceId=infra").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (_, body) = send_json(&router, "GET", "/nacos/v1/ns/service/export?targetNamespaceId=team-b").await;
+        assert_eq!(body["count"], 0);
+        let status = send_form(&router, "POST", "/nacos/v1/ns/service", "serviceName=billing&namespaceId=team-b").await;
+        assert_eq!(status, StatusCode::OK);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
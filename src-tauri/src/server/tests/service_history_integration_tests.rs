@@ -0,0 +1,89 @@
+/**
+ * 服务变更历史集成测试
+ * 测试服务和实例变更写入历史记录，以及按服务、变更类型和时间范围分页查询
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::router::create_router;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 发送请求并解析 JSON 响应
+    async fn send(router: &Router, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 测试用例：服务创建、实例注册和注销都会写入历史，并可按变更类型过滤
+    /// GET /nacos/v1/ns/service/history
+    #[tokio::test]
+    async fn test_service_history_records_changes() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/service?serviceName=history-service").await;
+        assert_eq!(status, StatusCode::OK);
+        send(&router, "POST", "/nacos/v1/ns/instance?serviceName=history-service&ip=10.0.0.1&port=8080").await;
+        send(&router, "DELETE", "/nacos/v2/ns/instance?serviceName=history-service&ip=10.0.0.1&port=8080").await;
+
+        let (status, body) = send(&router, "GET", "/nacos/v1/ns/service/history?serviceName=history-service").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["count"], 3);
+        let change_types: Vec<&str> = body["history"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["changeType"].as_str().unwrap())
+            .collect();
+        assert!(change_types.contains(&"CREATE"));
+        assert!(change_types.contains(&"REGISTER"));
+        assert!(change_types.contains(&"DEREGISTER"));
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/service/history?serviceName=history-service&changeType=deregister").await;
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["history"][0]["changeDetail"], "Instance deregistered: 10.0.0.1:8080#DEFAULT");
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：分页、时间范围过滤和非法变更类型
+    /// GET /nacos/v1/ns/service/history
+    #[tokio::test]
+    async fn test_service_history_pagination_and_filters() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("history-page-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        for port in 8080..8083 {
+            send(&router, "POST", &format!("/nacos/v1/ns/instance?serviceName=history-page-service&ip=10.0.0.1&port={}", port)).await;
+        }
+
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/service/history?serviceName=history-page-service&pageNo=1&pageSize=2").await;
+        assert_eq!(body["count"], 3);
+        assert_eq!(body["pagesAvailable"], 2);
+        assert_eq!(body["history"].as_array().unwrap().len(), 2);
+
+        let future = chrono::Utc::now().timestamp() + 3600;
+        let (_, body) = send(&router, "GET", &format!("/nacos/v1/ns/service/history?serviceName=history-page-service&startTime={}", future)).await;
+        assert_eq!(body["count"], 0);
+
+        let (status, _) = send(&router, "GET", "/nacos/v1/ns/service/history?changeType=UNKNOWN").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+}
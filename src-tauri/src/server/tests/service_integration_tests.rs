@@ -715,10 +715,10 @@ mod tests {
         
         test_db.insert_test_service("test-service-multi-subscribers", "public", "DEFAULT_GROUP").await.unwrap();
         
-        // 注册多个实例（模拟多个订阅者）
-        test_db.insert_test_instance("test-service-multi-subscribers", "public", "DEFAULT_GROUP", "127.0.0.1", 8080).await.unwrap();
-        test_db.insert_test_instance("test-service-multi-subscribers", "public", "DEFAULT_GROUP", "127.0.0.1", 8081).await.unwrap();
-        test_db.insert_test_instance("test-service-multi-subscribers", "public", "DEFAULT_GROUP", "127.0.0.1", 8082).await.unwrap();
+        // 插入多个订阅者
+        test_db.insert_test_naming_subscriber("test-service-multi-subscribers", "public", "DEFAULT_GROUP", "127.0.0.1", 8080, "").await.unwrap();
+        test_db.insert_test_naming_subscriber("test-service-multi-subscribers", "public", "DEFAULT_GROUP", "127.0.0.1", 8081, "").await.unwrap();
+        test_db.insert_test_naming_subscriber("test-service-multi-subscribers", "public", "DEFAULT_GROUP", "127.0.0.1", 8082, "").await.unwrap();
         
         let router = create_router("/nacos".to_string(), test_db.app.clone());
         
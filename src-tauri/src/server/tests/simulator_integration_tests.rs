@@ -0,0 +1,149 @@
+/**
+ * 服务模拟器集成测试
+ * 测试创建模拟注册实例（元数据模板）、重复创建和参数校验、停止后注销实例，
+ * 以及健康抖动和扩缩容行为
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    /// 发送请求，返回状态码和 JSON 响应体（非 JSON 时为 Null）
+    async fn send(router: &Router, method: &str, uri: &str, body: Option<serde_json::Value>) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json")
+            .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    /// 查询服务实例列表
+    async fn list_hosts(router: &Router, service_name: &str) -> Vec<serde_json::Value> {
+        let (_, body) = send(router, "GET", &format!("/nacos/v1/ns/instance/list?serviceName={}", service_name), None).await;
+        body["hosts"].as_array().cloned().unwrap_or_default()
+    }
+
+    /// 轮询实例列表直到满足条件
+    async fn wait_for_hosts(router: &Router, service_name: &str, condition: impl Fn(&[serde_json::Value]) -> bool) -> Vec<serde_json::Value> {
+        for _ in 0..50 {
+            let hosts = list_hosts(router, service_name).await;
+            if condition(&hosts) {
+                return hosts;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("simulation did not reach expected state");
+    }
+
+    /// 测试用例：创建模拟注册实例，停止后注销
+    /// POST /nacos/v1/ns/simulator -> GET /nacos/v1/ns/simulator/list -> DELETE /nacos/v1/ns/simulator
+    #[tokio::test]
+    async fn test_simulator_lifecycle() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        let definition = serde_json::json!({
+            "name": "demo",
+            "serviceName": "sim-service",
+            "instanceCount": 3,
+            "basePort": 31000,
+            "metadata": { "version": "v{index}", "endpoint": "{ip}:{port}" }
+        });
+        let (status, body) = send(&router, "POST", "/nacos/v1/ns/simulator", Some(definition.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["name"], "demo");
+        assert_eq!(body["instanceCount"], 3);
+        assert_eq!(body["definition"]["behaviour"]["type"], "steady");
+
+        let mut hosts = list_hosts(&router, "sim-service").await;
+        hosts.sort_by_key(|h| h["port"].as_i64());
+        assert_eq!(hosts.len(), 3);
+        assert_eq!(hosts[0]["port"], 31000);
+        assert_eq!(hosts[2]["metadata"]["version"], "v2");
+        assert_eq!(hosts[2]["metadata"]["endpoint"], "127.0.0.1:31002");
+        assert_eq!(hosts[0]["metadata"]["simulator"], "demo");
+
+        // 同名模拟不能重复创建，参数错误返回 400
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/simulator", Some(definition)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let invalid = serde_json::json!({ "name": "bad", "serviceName": "sim-service", "instanceCount": 0 });
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/simulator", Some(invalid)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = send(&router, "GET", "/nacos/v1/ns/simulator/list", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["simulations"][0]["instances"].as_array().unwrap().len(), 3);
+
+        let (status, _) = send(&router, "DELETE", "/nacos/v1/ns/simulator?name=demo", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(list_hosts(&router, "sim-service").await.is_empty());
+        let (status, _) = send(&router, "GET", "/nacos/v1/ns/simulator?name=demo", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = send(&router, "DELETE", "/nacos/v1/ns/simulator?name=demo", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：健康抖动和扩缩容行为按周期修改实例
+    /// POST /nacos/v1/ns/simulator
+    #[tokio::test]
+    async fn test_simulator_behaviours() {
+        let test_db = TestDatabase::new().await.unwrap();
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+
+        // 概率为 1 时每个周期翻转所有实例的健康状态
+        let flapping = serde_json::json!({
+            "name": "flapping",
+            "serviceName": "flapping-service",
+            "instanceCount": 2,
+            "intervalSeconds": 1,
+            "behaviour": { "type": "flapping", "probability": 1.0 }
+        });
+        let (status, _) = send(&router, "POST", "/nacos/v1/ns/simulator", Some(flapping)).await;
+        assert_eq!(status, StatusCode::OK);
+        wait_for_hosts(&router, "flapping-service", |hosts| {
+            hosts.len() == 2 && hosts.iter().all(|h| h["healthy"] == false)
+        })
+        .await;
+        // 健康抖动在时间线中记录为模拟器来源
+        let (_, body) = send(&router, "GET", "/nacos/v1/ns/service/health/timeline?serviceName=flapping-service", None).await;
+        let events = body["instances"][0]["events"].as_array().unwrap();
+        assert_eq!(events.last().unwrap()["source"], "SIMULATOR");
+
+        // 从 1 个实例扩容到 2 个
+        let scaling = serde_json::json!({
+            "name": "scaling",
+            "serviceName": "scaling-service",
+            "instanceCount": 1,
+            "intervalSeconds": 1,
+            "behaviour": { "type": "scaling", "minInstances": 1, "maxInstances": 2 }
+        });
+        let (status, body) = send(&router, "POST", "/nacos/v1/ns/simulator", Some(scaling)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["instanceCount"], 1);
+        wait_for_hosts(&router, "scaling-service", |hosts| hosts.len() == 2).await;
+
+        for name in ["flapping", "scaling"] {
+            let (status, _) = send(&router, "DELETE", &format!("/nacos/v1/ns/simulator?name={}", name), None).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        assert!(list_hosts(&router, "scaling-service").await.is_empty());
+
+        test_db.cleanup().await.unwrap();
+    }
+}
@@ -0,0 +1,226 @@
+/**
+ * Envoy xDS 服务集成测试
+ * 测试 ADS 流上的 CDS / EDS 订阅、健康状态和权重映射、ACK 后不重复推送，以及实例变更后按新版本推送
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::grpc::wire::{read_fields, write_bytes_field, write_string_field, FieldValue};
+    use crate::server::grpc::{encode_frame, take_frame, GrpcBody};
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use crate::server::xds::create_xds_router;
+    use axum::{
+        body::{Body, Bytes},
+        extract::ConnectInfo,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use http_body::Frame;
+    use http_body_util::BodyExt;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+    use tokio::sync::mpsc::UnboundedSender;
+    use tower::ServiceExt;
+
+    const CLUSTER_TYPE_URL: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+    const ENDPOINT_TYPE_URL: &str = "type.googleapis.com/envoy.config.endpoint.v3.ClusterLoadAssignment";
+    const CLUSTER_NAME: &str = "xds-service.DEFAULT_GROUP.public.nacos";
+
+    /// DiscoveryResponse 中测试关心的字段
+    struct DiscoveryResponse {
+        version_info: String,
+        type_url: String,
+        nonce: String,
+        resources: Vec<Vec<u8>>,
+    }
+
+    /// 端点：(IP, 端口, 健康状态, 权重)
+    type Endpoint = (String, u64, u64, u64);
+
+    /// 读取消息中指定字段号的所有长度前缀字段
+    fn bytes_fields(data: &[u8], field: u64) -> Vec<Vec<u8>> {
+        read_fields(data)
+            .unwrap()
+            .into_iter()
+            .filter_map(|(f, value)| match value {
+                FieldValue::Bytes(bytes) if f == field => Some(bytes.to_vec()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 读取消息中指定字段号的变长整数
+    fn varint_field(data: &[u8], field: u64) -> u64 {
+        read_fields(data)
+            .unwrap()
+            .into_iter()
+            .find_map(|(f, value)| match value {
+                FieldValue::Varint(v) if f == field => Some(v),
+                _ => None,
+            })
+            .unwrap_or(0)
+    }
+
+    fn string_field(data: &[u8], field: u64) -> String {
+        bytes_fields(data, field).first().map(|b| String::from_utf8(b.clone()).unwrap()).unwrap_or_default()
+    }
+
+    fn decode_response(message: &[u8]) -> DiscoveryResponse {
+        DiscoveryResponse {
+            version_info: string_field(message, 1),
+            type_url: string_field(message, 4),
+            nonce: string_field(message, 5),
+            resources: bytes_fields(message, 2).iter().map(|any| bytes_fields(any, 2).remove(0)).collect(),
+        }
+    }
+
+    /// 解码 ClusterLoadAssignment，返回 (集群名, 端点列表)
+    fn decode_assignment(resource: &[u8]) -> (String, Vec<Endpoint>) {
+        let mut endpoints = Vec::new();
+        for locality_endpoints in bytes_fields(resource, 2) {
+            for lb_endpoint in bytes_fields(&locality_endpoints, 2) {
+                let endpoint = bytes_fields(&lb_endpoint, 1).remove(0);
+                let address = bytes_fields(&endpoint, 1).remove(0);
+                let socket_address = bytes_fields(&address, 1).remove(0);
+                let weight = bytes_fields(&lb_endpoint, 4).remove(0);
+                endpoints.push((
+                    string_field(&socket_address, 2),
+                    varint_field(&socket_address, 3),
+                    varint_field(&lb_endpoint, 2),
+                    varint_field(&weight, 1),
+                ));
+            }
+        }
+        endpoints.sort();
+        (string_field(resource, 1), endpoints)
+    }
+
+    /// 发送 DiscoveryRequest
+    fn send_request(tx: &UnboundedSender<Frame<Bytes>>, type_url: &str, names: &[&str], version: &str, nonce: &str) {
+        let mut node = Vec::new();
+        write_string_field(&mut node, 1, "envoy-test");
+        let mut request = Vec::new();
+        write_string_field(&mut request, 1, version);
+        write_bytes_field(&mut request, 2, &node);
+        for name in names {
+            write_string_field(&mut request, 3, name);
+        }
+        write_string_field(&mut request, 4, type_url);
+        write_string_field(&mut request, 5, nonce);
+        tx.send(Frame::data(Bytes::from(encode_frame(&request)))).unwrap();
+    }
+
+    /// 读取下一条 DiscoveryResponse
+    async fn next_response(stream: &mut Body, buf: &mut Vec<u8>) -> DiscoveryResponse {
+        loop {
+            if let Some(message) = take_frame(buf).unwrap() {
+                return decode_response(&message);
+            }
+            let frame = tokio::time::timeout(Duration::from_secs(5), stream.frame())
+                .await
+                .expect("xDS response timeout")
+                .unwrap()
+                .unwrap();
+            buf.extend_from_slice(&frame.into_data().unwrap());
+        }
+    }
+
+    /// 通过 HTTP 接口注册实例
+    async fn register(router: &Router, query: &str) {
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/nacos/v1/ns/instance?serviceName=xds-service&{}", query))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(router.clone().oneshot(request).await.unwrap().status(), StatusCode::OK);
+    }
+
+    /// 测试用例：ADS 流订阅 CDS 和 EDS，实例变更后推送新版本
+    /// POST /envoy.service.discovery.v3.AggregatedDiscoveryService/StreamAggregatedResources
+    #[tokio::test]
+    async fn test_xds_ads_stream() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("xds-service", "public", "DEFAULT_GROUP").await.unwrap();
+        let http = create_router("/nacos".to_string(), test_db.app.clone());
+        let xds = create_xds_router(test_db.app.clone());
+        register(&http, "ip=10.0.0.1&port=8080&weight=2").await;
+        register(&http, "ip=10.0.0.2&port=9090&enabled=false").await;
+
+        let (client_tx, client_body) = GrpcBody::channel();
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/envoy.service.discovery.v3.AggregatedDiscoveryService/StreamAggregatedResources")
+            .header("Content-Type", "application/grpc")
+            .body(Body::new(client_body))
+            .unwrap();
+        request.extensions_mut().insert(ConnectInfo("127.0.0.1:50100".parse::<SocketAddr>().unwrap()));
+        send_request(&client_tx, CLUSTER_TYPE_URL, &[], "", "");
+        let response = xds.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut stream = response.into_body();
+        let mut buf = Vec::new();
+
+        // CDS：每个服务一个 EDS 集群
+        let cds = next_response(&mut stream, &mut buf).await;
+        assert_eq!(cds.type_url, CLUSTER_TYPE_URL);
+        assert!(!cds.nonce.is_empty());
+        let names: Vec<String> = cds.resources.iter().map(|cluster| string_field(cluster, 1)).collect();
+        assert!(names.contains(&CLUSTER_NAME.to_string()));
+        let cluster = cds.resources.iter().find(|cluster| string_field(cluster, 1) == CLUSTER_NAME).unwrap();
+        assert_eq!(varint_field(cluster, 2), 3);
+
+        // ACK CDS 并订阅 EDS，未知集群返回空的端点列表
+        send_request(&client_tx, CLUSTER_TYPE_URL, &[], &cds.version_info, &cds.nonce);
+        send_request(&client_tx, ENDPOINT_TYPE_URL, &[CLUSTER_NAME, "missing.DEFAULT_GROUP.public.nacos"], "", "");
+        let eds = next_response(&mut stream, &mut buf).await;
+        assert_eq!(eds.type_url, ENDPOINT_TYPE_URL);
+        assert_eq!(eds.version_info, cds.version_info);
+        assert_ne!(eds.nonce, cds.nonce);
+        assert_eq!(eds.resources.len(), 2);
+        let (name, endpoints) = decode_assignment(&eds.resources[0]);
+        assert_eq!(name, CLUSTER_NAME);
+        assert_eq!(
+            endpoints,
+            vec![("10.0.0.1".to_string(), 8080, 1, 200), ("10.0.0.2".to_string(), 9090, 3, 100)]
+        );
+        let (name, endpoints) = decode_assignment(&eds.resources[1]);
+        assert_eq!(name, "missing.DEFAULT_GROUP.public.nacos");
+        assert!(endpoints.is_empty());
+        send_request(&client_tx, ENDPOINT_TYPE_URL, &[CLUSTER_NAME, "missing.DEFAULT_GROUP.public.nacos"], &eds.version_info, &eds.nonce);
+
+        // 实例变更后两种资源都推送新版本
+        register(&http, "ip=10.0.0.3&port=8080").await;
+        let (mut cds_pushed, mut eds_pushed) = (false, false);
+        while !(cds_pushed && eds_pushed) {
+            let push = next_response(&mut stream, &mut buf).await;
+            assert_ne!(push.version_info, eds.version_info);
+            match push.type_url.as_str() {
+                CLUSTER_TYPE_URL => cds_pushed = true,
+                ENDPOINT_TYPE_URL => {
+                    let (_, endpoints) = decode_assignment(&push.resources[0]);
+                    assert_eq!(endpoints.len(), 3);
+                    assert!(endpoints.contains(&("10.0.0.3".to_string(), 8080, 1, 100)));
+                    eds_pushed = true;
+                }
+                other => panic!("unexpected type {}", other),
+            }
+        }
+
+        drop(client_tx);
+        let trailers = loop {
+            let frame = tokio::time::timeout(Duration::from_secs(5), stream.frame())
+                .await
+                .expect("stream end timeout")
+                .unwrap()
+                .unwrap();
+            if let Ok(trailers) = frame.into_trailers() {
+                break trailers;
+            }
+        };
+        assert_eq!(trailers["grpc-status"], "0");
+
+        test_db.cleanup().await.unwrap();
+    }
+}
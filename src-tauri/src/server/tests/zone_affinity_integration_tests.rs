@@ -0,0 +1,137 @@
+/**
+ * 区域亲和集成测试
+ * 测试实例列表按调用方区域排序、独占模式、健康比例退回和按客户端 IP 查找区域
+ */
+
+#[cfg(test)]
+mod tests {
+    use crate::server::router::create_router;
+    use crate::server::tests::db_setup::TestDatabase;
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    /// 通过 API 注册实例
+    async fn register(router: &Router, ip: &str, zone: &str, healthy: bool) {
+        let body = format!(
+            "serviceName=zone-service&ip={}&port=8080&namespaceId=public&healthy={}&metadata=%7B%22zone%22%3A%22{}%22%7D",
+            ip, healthy, zone
+        );
+        let request = Request::builder()
+            .method("POST")
+            .uri("/nacos/v1/ns/instance")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap();
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    /// 修改系统开关
+    async fn set_switch(router: &Router, entry: &str, value: &str) -> StatusCode {
+        let request = Request::builder()
+            .method("PUT")
+            .uri(format!("/nacos/v1/ns/operator/switches?entry={}&value={}", entry, value))
+            .body(Body::empty())
+            .unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    /// 查询实例列表，返回实例 IP 顺序
+    async fn list_ips(router: &Router, query: &str, zone_header: Option<&str>) -> Vec<String> {
+        let mut builder = Request::builder()
+            .method("GET")
+            .uri(format!("/nacos/v1/ns/instance/list?serviceName=zone-service&namespaceId=public&{}", query));
+        if let Some(zone) = zone_header {
+            builder = builder.header("X-Client-Zone", zone);
+        }
+        let response = router.clone().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        json["hosts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|h| h["ip"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    /// 测试用例：同区域实例排在前面，独占模式只返回同区域实例，健康比例低于阈值时退回
+    /// GET /nacos/v1/ns/instance/list?zone=
+    #[tokio::test]
+    async fn test_list_instances_zone_affinity() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("zone-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "10.0.0.1", "zone-a", true).await;
+        register(&router, "10.0.0.2", "zone-b", false).await;
+        register(&router, "10.0.0.3", "zone-b", true).await;
+
+        // 同区域健康实例在最前面，其他区域实例仍然返回
+        let ips = list_ips(&router, "zone=zone-b", None).await;
+        assert_eq!(ips, vec!["10.0.0.3", "10.0.0.2", "10.0.0.1"]);
+        let ips = list_ips(&router, "", Some("zone-a")).await;
+        assert_eq!(ips[0], "10.0.0.1");
+        assert_eq!(ips.len(), 3);
+
+        // 独占模式只返回同区域实例
+        assert_eq!(set_switch(&router, "zoneAffinityExclusive", "true").await, StatusCode::OK);
+        let ips = list_ips(&router, "zone=zone-b", None).await;
+        assert_eq!(ips, vec!["10.0.0.3", "10.0.0.2"]);
+        assert_eq!(list_ips(&router, "zone=zone-b&healthyOnly=true", None).await, vec!["10.0.0.3"]);
+
+        // 同区域健康比例 0.5 低于阈值时退回其他区域
+        assert_eq!(set_switch(&router, "zoneAffinityThreshold", "0.6").await, StatusCode::OK);
+        let ips = list_ips(&router, "zone=zone-b", None).await;
+        assert_eq!(ips.len(), 3);
+        assert_eq!(ips[0], "10.0.0.3");
+
+        // 没有同区域实例时返回全部实例
+        assert_eq!(list_ips(&router, "zone=zone-c", None).await.len(), 3);
+
+        assert_eq!(set_switch(&router, "zoneAffinityThreshold", "1.5").await, StatusCode::BAD_REQUEST);
+
+        test_db.cleanup().await.unwrap();
+    }
+
+    /// 测试用例：未指定区域时按客户端 IP 的区域标签查找
+    /// GET /nacos/v1/ns/instance/list?clientIP= 和 GET /nacos/v1/ns/instance/selectOne?clientIP=
+    #[tokio::test]
+    async fn test_zone_lookup_by_client_ip() {
+        let test_db = TestDatabase::new().await.unwrap();
+        test_db.insert_test_service("zone-service", "public", "DEFAULT_GROUP").await.unwrap();
+
+        let router = create_router("/nacos".to_string(), test_db.app.clone());
+        register(&router, "10.0.0.1", "zone-a", true).await;
+        register(&router, "10.0.0.2", "zone-b", true).await;
+
+        assert_eq!(set_switch(&router, "zoneIpLabels", "192.168.0.0%2F16%3Dzone-a,192.168.2.0%2F24%3Dzone-b").await, StatusCode::OK);
+        assert_eq!(set_switch(&router, "zoneIpLabels", "not-an-ip%3Dzone-a").await, StatusCode::BAD_REQUEST);
+
+        // 多条规则匹配时取前缀最长的一条
+        assert_eq!(list_ips(&router, "clientIP=192.168.1.20", None).await[0], "10.0.0.1");
+        assert_eq!(list_ips(&router, "clientIP=192.168.2.20", None).await[0], "10.0.0.2");
+        // 显式指定的区域优先于 IP 标签
+        assert_eq!(list_ips(&router, "clientIP=192.168.2.20&zone=zone-a", None).await[0], "10.0.0.1");
+
+        for _ in 0..10 {
+            let request = Request::builder()
+                .method("GET")
+                .uri("/nacos/v1/ns/instance/selectOne?serviceName=zone-service&namespaceId=public&clientIP=192.168.2.20")
+                .body(Body::empty())
+                .unwrap();
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(json["ip"], "10.0.0.2");
+        }
+
+        test_db.cleanup().await.unwrap();
+    }
+}
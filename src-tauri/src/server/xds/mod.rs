@@ -0,0 +1,303 @@
+/**
+ * Envoy xDS v3 服务模块
+ * 把注册表中的服务映射为 Envoy 集群（CDS），实例映射为集群端点（EDS），通过 gRPC（HTTP/2 明文）提供：
+ * - AggregatedDiscoveryService/StreamAggregatedResources：ADS，同一个流上按 type_url 区分 CDS 和 EDS
+ * - ClusterDiscoveryService/StreamClusters、EndpointDiscoveryService/StreamEndpoints：独立的 CDS / EDS 流
+ * 每个流在服务变更事件到达时重建快照（并定期全量重建兜底），版本变化时向已订阅的资源类型推送新版本，
+ * 响应带 nonce，按 ACK / NACK 语义处理客户端请求
+ */
+
+mod proto;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{ConnectInfo, State},
+    response::Response,
+    routing::post,
+    Router,
+};
+use http_body::Frame;
+use tauri::AppHandle;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::server::grpc::{encode_frame, grpc_response, status_trailers, take_frame, GrpcBody, GRPC_STATUS_OK};
+use crate::service::{get_xds_snapshot, subscribe_service_changes, XdsSnapshot};
+use proto::{
+    encode_cluster, encode_cluster_load_assignment, encode_discovery_response, DiscoveryRequest, CLUSTER_TYPE_URL,
+    ENDPOINT_TYPE_URL,
+};
+
+/// 默认 xDS 监听端口（与 Nacos Istio 集成一致）
+pub const DEFAULT_XDS_PORT: u16 = 18848;
+
+/// 快照全量重建间隔：兜底处理不产生服务变更事件的修改（例如服务导出）
+const XDS_RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 资源类型订阅状态
+#[derive(Default)]
+struct Subscription {
+    /// 订阅的资源名，为空表示全部（通配）
+    resource_names: Vec<String>,
+    /// 最近一次发送的快照版本和资源名
+    sent: Option<(u64, Vec<String>)>,
+    /// 最近一次发送的 nonce
+    nonce: String,
+}
+
+/// 单个 xDS 流的状态
+struct DiscoveryStream {
+    addr: SocketAddr,
+    default_type_url: Option<&'static str>,
+    subscriptions: HashMap<String, Subscription>,
+    next_nonce: u64,
+    tx: mpsc::UnboundedSender<Frame<Bytes>>,
+}
+
+impl DiscoveryStream {
+    /// 处理客户端请求：首次订阅、ACK、NACK 或资源名变更
+    async fn handle_request(&mut self, app: &AppHandle, request: DiscoveryRequest) -> bool {
+        let type_url = if request.type_url.is_empty() {
+            match self.default_type_url {
+                Some(type_url) => type_url.to_string(),
+                None => return true,
+            }
+        } else {
+            request.type_url
+        };
+        if type_url != CLUSTER_TYPE_URL && type_url != ENDPOINT_TYPE_URL {
+            tracing::debug!("Ignoring unsupported xDS type {} from {}", type_url, self.addr);
+            return true;
+        }
+
+        let subscription = self.subscriptions.entry(type_url.clone()).or_default();
+        // nonce 与最近一次响应不一致的请求已过期，忽略
+        if !request.response_nonce.is_empty() && request.response_nonce != subscription.nonce {
+            tracing::debug!("Ignoring stale xDS request for {} from {}", type_url, self.addr);
+            return true;
+        }
+        subscription.resource_names = request.resource_names;
+        if let Some(error) = request.error_detail {
+            // NACK：记录错误，保持当前订阅，等待下一个快照版本
+            tracing::warn!(
+                "xDS client {} ({}) rejected {} (version {}): {}",
+                self.addr,
+                request.node_id,
+                type_url,
+                request.version_info,
+                error
+            );
+            return true;
+        }
+
+        match get_xds_snapshot(app).await {
+            Ok(snapshot) => self.push(&snapshot, Some(&type_url)),
+            Err(e) => {
+                tracing::warn!("Failed to build xDS snapshot: {}", e);
+                true
+            }
+        }
+    }
+
+    /// 向快照版本或资源名与上次发送不同的订阅推送响应；type_url 为 None 时检查全部订阅
+    fn push(&mut self, snapshot: &XdsSnapshot, only: Option<&str>) -> bool {
+        for (type_url, subscription) in self.subscriptions.iter_mut() {
+            if only.is_some_and(|only| only != type_url) {
+                continue;
+            }
+            let current = Some((snapshot.version, subscription.resource_names.clone()));
+            if subscription.sent == current {
+                continue;
+            }
+
+            let wanted = |name: &str| subscription.resource_names.is_empty() || subscription.resource_names.iter().any(|n| n == name);
+            let resources: Vec<Vec<u8>> = if type_url == CLUSTER_TYPE_URL {
+                snapshot.clusters.iter().filter(|c| wanted(&c.name)).map(encode_cluster).collect()
+            } else if subscription.resource_names.is_empty() {
+                snapshot
+                    .clusters
+                    .iter()
+                    .map(|c| encode_cluster_load_assignment(&c.name, &c.instances))
+                    .collect()
+            } else {
+                // 请求了未知集群时返回空的端点列表
+                subscription
+                    .resource_names
+                    .iter()
+                    .map(|name| {
+                        let instances = snapshot.clusters.iter().find(|c| &c.name == name).map_or(&[][..], |c| &c.instances[..]);
+                        encode_cluster_load_assignment(name, instances)
+                    })
+                    .collect()
+            };
+
+            self.next_nonce += 1;
+            subscription.nonce = self.next_nonce.to_string();
+            subscription.sent = current;
+            let response = encode_discovery_response(&snapshot.version.to_string(), type_url, &subscription.nonce, &resources);
+            if self.tx.send(Frame::data(Bytes::from(encode_frame(&response)))).is_err() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// 流事件
+enum StreamEvent {
+    Frame(Option<Result<Frame<Bytes>, axum::Error>>),
+    /// 服务变更或定期重建，需要检查快照版本
+    Changed,
+    Closed,
+}
+
+/// 创建 xDS 路由
+pub fn create_xds_router(app: Arc<AppHandle>) -> Router {
+    Router::new()
+        .route(
+            "/envoy.service.discovery.v3.AggregatedDiscoveryService/StreamAggregatedResources",
+            post(stream_aggregated_resources),
+        )
+        .route("/envoy.service.cluster.v3.ClusterDiscoveryService/StreamClusters", post(stream_clusters))
+        .route("/envoy.service.endpoint.v3.EndpointDiscoveryService/StreamEndpoints", post(stream_endpoints))
+        .with_state(app)
+}
+
+/// ADS 流
+/// POST /envoy.service.discovery.v3.AggregatedDiscoveryService/StreamAggregatedResources
+pub async fn stream_aggregated_resources(
+    State(app): State<Arc<AppHandle>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    body: Body,
+) -> Response {
+    discovery_stream(app.as_ref().clone(), addr, None, body)
+}
+
+/// CDS 流
+/// POST /envoy.service.cluster.v3.ClusterDiscoveryService/StreamClusters
+pub async fn stream_clusters(
+    State(app): State<Arc<AppHandle>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    body: Body,
+) -> Response {
+    discovery_stream(app.as_ref().clone(), addr, Some(CLUSTER_TYPE_URL), body)
+}
+
+/// EDS 流
+/// POST /envoy.service.endpoint.v3.EndpointDiscoveryService/StreamEndpoints
+pub async fn stream_endpoints(
+    State(app): State<Arc<AppHandle>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    body: Body,
+) -> Response {
+    discovery_stream(app.as_ref().clone(), addr, Some(ENDPOINT_TYPE_URL), body)
+}
+
+/// 处理 xDS 双向流：读取客户端请求，收到服务变更事件时按快照版本推送更新
+fn discovery_stream(app: AppHandle, addr: SocketAddr, default_type_url: Option<&'static str>, body: Body) -> Response {
+    let (tx, body_stream) = GrpcBody::channel();
+
+    tokio::spawn(async move {
+        let mut body = body;
+        let mut buf = Vec::new();
+        let mut service_changes = subscribe_service_changes(&app);
+        let mut resync = tokio::time::interval(XDS_RESYNC_INTERVAL);
+        let mut stream = DiscoveryStream {
+            addr,
+            default_type_url,
+            subscriptions: HashMap::new(),
+            next_nonce: 0,
+            tx: tx.clone(),
+        };
+
+        'stream: loop {
+            let event = tokio::select! {
+                frame = std::future::poll_fn(|cx| http_body::Body::poll_frame(Pin::new(&mut body), cx)) => StreamEvent::Frame(frame),
+                _ = resync.tick() => StreamEvent::Changed,
+                // 落后过多时丢失的事件同样触发一次重建
+                event = service_changes.recv() => match event {
+                    Ok(_) | Err(RecvError::Lagged(_)) => StreamEvent::Changed,
+                    Err(RecvError::Closed) => StreamEvent::Closed,
+                },
+            };
+
+            match event {
+                StreamEvent::Changed => {
+                    // 合并同一批次的变更事件，只重建一次快照
+                    while matches!(service_changes.try_recv(), Ok(_) | Err(TryRecvError::Lagged(_))) {}
+                    if stream.subscriptions.is_empty() {
+                        continue;
+                    }
+                    match get_xds_snapshot(&app).await {
+                        Ok(snapshot) => {
+                            if !stream.push(&snapshot, None) {
+                                break;
+                            }
+                        }
+                        Err(e) => tracing::warn!("Failed to build xDS snapshot: {}", e),
+                    }
+                }
+                StreamEvent::Frame(None) | StreamEvent::Closed => break,
+                StreamEvent::Frame(Some(Err(e))) => {
+                    tracing::debug!("xDS stream {} interrupted: {}", addr, e);
+                    break;
+                }
+                StreamEvent::Frame(Some(Ok(frame))) => {
+                    let Ok(data) = frame.into_data() else {
+                        continue;
+                    };
+                    buf.extend_from_slice(&data);
+
+                    loop {
+                        let message = match take_frame(&mut buf) {
+                            Ok(Some(message)) => message,
+                            Ok(None) => break,
+                            Err(e) => {
+                                tracing::warn!("Invalid xDS stream message from {}: {}", addr, e);
+                                break 'stream;
+                            }
+                        };
+                        let request = match DiscoveryRequest::decode(&message) {
+                            Ok(request) => request,
+                            Err(e) => {
+                                tracing::warn!("Invalid DiscoveryRequest from {}: {}", addr, e);
+                                continue;
+                            }
+                        };
+                        if !stream.handle_request(&app, request).await {
+                            break 'stream;
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::debug!("xDS stream {} closed", addr);
+        let _ = tx.send(status_trailers(GRPC_STATUS_OK, None));
+    });
+
+    grpc_response(body_stream)
+}
+
+/// 启动 xDS 服务，返回服务任务
+pub async fn start_xds_server(app: AppHandle, port: u16) -> Result<JoinHandle<()>, String> {
+    let addr = format!("127.0.0.1:{}", port);
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .map_err(|e| format!("无法绑定 xDS 端口 {}: {}", port, e))?;
+    let router = create_xds_router(Arc::new(app));
+
+    tracing::info!("xDS server listening on {}", addr);
+    Ok(tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>()).await {
+            tracing::error!("xDS server error: {}", e);
+        }
+    }))
+}
@@ -0,0 +1,173 @@
+/**
+ * xDS v3 消息编解码
+ * 只实现 ADS / CDS / EDS 需要的字段：解码 DiscoveryRequest，
+ * 编码 DiscoveryResponse、Cluster（EDS 类型，端点通过 ADS 下发）和 ClusterLoadAssignment
+ */
+
+use std::collections::BTreeMap;
+
+use crate::server::grpc::wire::{read_fields, write_bytes_field, write_string_field, write_varint_field, FieldValue};
+use crate::service::{InstanceInfo, XdsCluster};
+
+/// CDS 资源类型
+pub const CLUSTER_TYPE_URL: &str = "type.googleapis.com/envoy.config.cluster.v3.Cluster";
+
+/// EDS 资源类型
+pub const ENDPOINT_TYPE_URL: &str = "type.googleapis.com/envoy.config.endpoint.v3.ClusterLoadAssignment";
+
+/// Cluster.DiscoveryType.EDS
+const DISCOVERY_TYPE_EDS: u64 = 3;
+
+/// ApiVersion.V3
+const API_VERSION_V3: u64 = 2;
+
+/// core.v3.HealthStatus
+const HEALTH_STATUS_HEALTHY: u64 = 1;
+const HEALTH_STATUS_UNHEALTHY: u64 = 2;
+const HEALTH_STATUS_DRAINING: u64 = 3;
+
+/// 集群连接超时（秒）
+const CONNECT_TIMEOUT_SECONDS: u64 = 5;
+
+/// DiscoveryRequest
+#[derive(Debug, Default)]
+pub struct DiscoveryRequest {
+    pub version_info: String,
+    pub node_id: String,
+    pub resource_names: Vec<String>,
+    pub type_url: String,
+    pub response_nonce: String,
+    /// 客户端拒绝（NACK）上次响应时携带的错误信息
+    pub error_detail: Option<String>,
+}
+
+impl DiscoveryRequest {
+    pub fn decode(data: &[u8]) -> Result<Self, String> {
+        let mut request = DiscoveryRequest::default();
+        for (field, value) in read_fields(data)? {
+            match (field, &value) {
+                (1, _) => request.version_info = value.string()?,
+                (2, FieldValue::Bytes(node)) => {
+                    for (node_field, node_value) in read_fields(node)? {
+                        if node_field == 1 {
+                            request.node_id = node_value.string()?;
+                        }
+                    }
+                }
+                (3, _) => request.resource_names.push(value.string()?),
+                (4, _) => request.type_url = value.string()?,
+                (5, _) => request.response_nonce = value.string()?,
+                (6, FieldValue::Bytes(status)) => {
+                    let mut code = 0;
+                    let mut message = String::new();
+                    for (status_field, status_value) in read_fields(status)? {
+                        match (status_field, &status_value) {
+                            (1, FieldValue::Varint(v)) => code = *v,
+                            (2, _) => message = status_value.string()?,
+                            _ => {}
+                        }
+                    }
+                    request.error_detail = Some(format!("code {}: {}", code, message));
+                }
+                _ => {}
+            }
+        }
+        Ok(request)
+    }
+}
+
+/// 编码 DiscoveryResponse，资源以 Any 包装
+pub fn encode_discovery_response(version_info: &str, type_url: &str, nonce: &str, resources: &[Vec<u8>]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_string_field(&mut buf, 1, version_info);
+    for resource in resources {
+        let mut any = Vec::new();
+        write_string_field(&mut any, 1, type_url);
+        write_bytes_field(&mut any, 2, resource);
+        write_bytes_field(&mut buf, 2, &any);
+    }
+    write_string_field(&mut buf, 4, type_url);
+    write_string_field(&mut buf, 5, nonce);
+    buf
+}
+
+/// 编码 Cluster：EDS 类型，端点通过 ADS 获取，轮询负载均衡
+pub fn encode_cluster(cluster: &XdsCluster) -> Vec<u8> {
+    let mut config_source = Vec::new();
+    write_bytes_field(&mut config_source, 3, &[]);
+    write_varint_field(&mut config_source, 6, API_VERSION_V3);
+
+    let mut eds_cluster_config = Vec::new();
+    write_bytes_field(&mut eds_cluster_config, 1, &config_source);
+    write_string_field(&mut eds_cluster_config, 2, &cluster.name);
+
+    let mut connect_timeout = Vec::new();
+    write_varint_field(&mut connect_timeout, 1, CONNECT_TIMEOUT_SECONDS);
+
+    let mut buf = Vec::new();
+    write_string_field(&mut buf, 1, &cluster.name);
+    write_varint_field(&mut buf, 2, DISCOVERY_TYPE_EDS);
+    write_bytes_field(&mut buf, 3, &eds_cluster_config);
+    write_bytes_field(&mut buf, 4, &connect_timeout);
+    buf
+}
+
+/// 实例映射为 Envoy 健康状态：禁用的实例为 DRAINING，其余按健康状态映射
+fn health_status(instance: &InstanceInfo) -> u64 {
+    if !instance.enabled {
+        HEALTH_STATUS_DRAINING
+    } else if instance.healthy {
+        HEALTH_STATUS_HEALTHY
+    } else {
+        HEALTH_STATUS_UNHEALTHY
+    }
+}
+
+/// 实例权重映射为 Envoy 负载均衡权重（Envoy 要求至少为 1）
+fn load_balancing_weight(instance: &InstanceInfo) -> u64 {
+    (instance.weight * 100.0).round().max(1.0) as u64
+}
+
+fn encode_lb_endpoint(instance: &InstanceInfo) -> Vec<u8> {
+    let mut socket_address = Vec::new();
+    write_string_field(&mut socket_address, 2, &instance.ip);
+    write_varint_field(&mut socket_address, 3, instance.port.max(0) as u64);
+
+    let mut address = Vec::new();
+    write_bytes_field(&mut address, 1, &socket_address);
+
+    let mut endpoint = Vec::new();
+    write_bytes_field(&mut endpoint, 1, &address);
+
+    let mut weight = Vec::new();
+    write_varint_field(&mut weight, 1, load_balancing_weight(instance));
+
+    let mut buf = Vec::new();
+    write_bytes_field(&mut buf, 1, &endpoint);
+    write_varint_field(&mut buf, 2, health_status(instance));
+    write_bytes_field(&mut buf, 4, &weight);
+    buf
+}
+
+/// 编码 ClusterLoadAssignment：实例按 Nacos 集群分组为 locality（zone 为集群名）
+pub fn encode_cluster_load_assignment(cluster_name: &str, instances: &[InstanceInfo]) -> Vec<u8> {
+    let mut localities: BTreeMap<&str, Vec<&InstanceInfo>> = BTreeMap::new();
+    for instance in instances {
+        localities.entry(instance.cluster_name.as_str()).or_default().push(instance);
+    }
+
+    let mut buf = Vec::new();
+    write_string_field(&mut buf, 1, cluster_name);
+    for (zone, instances) in localities {
+        let mut locality = Vec::new();
+        write_string_field(&mut locality, 2, zone);
+
+        let mut locality_endpoints = Vec::new();
+        write_bytes_field(&mut locality_endpoints, 1, &locality);
+        for instance in instances {
+            write_bytes_field(&mut locality_endpoints, 2, &encode_lb_endpoint(instance));
+        }
+        write_bytes_field(&mut buf, 2, &locality_endpoints);
+    }
+    buf
+}
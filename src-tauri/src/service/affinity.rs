@@ -0,0 +1,152 @@
+/**
+ * 区域亲和模块
+ * 根据调用方区域（显式指定或按客户端 IP 标签查找）对实例排序：同区域实例优先或独占，
+ * 同区域健康比例低于阈值时退回其他区域
+ */
+
+use std::net::IpAddr;
+
+use tauri::AppHandle;
+
+use super::{get_naming_switches, InstanceInfo, NamingSwitches};
+
+/// 实例元数据中表示区域的键
+pub const ZONE_METADATA_KEY: &str = "zone";
+
+/// 客户端 IP 到区域的标签规则，network 为网段（单个 IP 视为全长前缀）
+#[derive(Debug, Clone)]
+struct ZoneIpLabel {
+    network: IpAddr,
+    prefix_len: u8,
+    zone: String,
+}
+
+impl ZoneIpLabel {
+    /// 判断 IP 是否属于该网段
+    fn matches(&self, ip: &IpAddr) -> bool {
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                prefix_matches(u32::from(network) as u128, u32::from(*ip) as u128, self.prefix_len, 32)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                prefix_matches(u128::from(network), u128::from(*ip), self.prefix_len, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 比较两个地址的前 prefix_len 位
+fn prefix_matches(network: u128, ip: u128, prefix_len: u8, bits: u8) -> bool {
+    if prefix_len == 0 {
+        return true;
+    }
+    let shift = (bits - prefix_len) as u32;
+    (network >> shift) == (ip >> shift)
+}
+
+/// 校验区域 IP 标签配置
+/// 格式: "10.0.1.0/24=zone-a,10.0.2.8=zone-b"，空字符串表示没有规则
+pub(super) fn validate_zone_ip_labels(value: &str) -> Result<(), String> {
+    parse_zone_ip_labels(value).map(|_| ())
+}
+
+/// 解析区域 IP 标签配置
+fn parse_zone_ip_labels(value: &str) -> Result<Vec<ZoneIpLabel>, String> {
+    value
+        .split(',')
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let invalid = || format!("Invalid zone ip label: {}", entry);
+            let (network, zone) = entry.split_once('=').ok_or_else(invalid)?;
+            let zone = zone.trim();
+            if zone.is_empty() {
+                return Err(invalid());
+            }
+
+            let (address, prefix_len) = match network.trim().split_once('/') {
+                Some((address, prefix)) => (address, Some(prefix.parse::<u8>().map_err(|_| invalid())?)),
+                None => (network.trim(), None),
+            };
+            let network: IpAddr = address.parse().map_err(|_| invalid())?;
+            let max_len = if network.is_ipv4() { 32 } else { 128 };
+            let prefix_len = prefix_len.unwrap_or(max_len);
+            if prefix_len > max_len {
+                return Err(invalid());
+            }
+
+            Ok(ZoneIpLabel { network, prefix_len, zone: zone.to_string() })
+        })
+        .collect()
+}
+
+/// 按客户端 IP 查找区域，多条规则匹配时取前缀最长的一条
+fn lookup_zone_by_ip(labels: &str, client_ip: &str) -> Option<String> {
+    let ip: IpAddr = client_ip.trim().parse().ok()?;
+    parse_zone_ip_labels(labels)
+        .ok()?
+        .into_iter()
+        .filter(|label| label.matches(&ip))
+        .max_by_key(|label| label.prefix_len)
+        .map(|label| label.zone)
+}
+
+/// 确定调用方区域：显式指定的区域优先，其次按客户端 IP 标签查找
+pub async fn resolve_consumer_zone(app: &AppHandle, zone: Option<String>, client_ip: Option<&str>) -> Option<String> {
+    if let Some(zone) = zone.map(|z| z.trim().to_string()).filter(|z| !z.is_empty()) {
+        return Some(zone);
+    }
+    let client_ip = client_ip.filter(|ip| !ip.is_empty())?;
+    let switches = get_naming_switches(app).await;
+    lookup_zone_by_ip(&switches.zone_ip_labels, client_ip)
+}
+
+/// 读取实例的区域元数据
+pub(super) fn instance_zone(instance: &InstanceInfo) -> Option<String> {
+    instance
+        .metadata
+        .as_deref()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .and_then(|m| m.get(ZONE_METADATA_KEY).and_then(|v| v.as_str()).map(|s| s.to_string()))
+}
+
+/// 按区域划分后的实例
+pub(super) struct ZonePartition {
+    /// 同区域实例
+    pub local: Vec<InstanceInfo>,
+    /// 其他区域（包括未设置区域）的实例
+    pub remote: Vec<InstanceInfo>,
+    /// 同区域没有健康实例或健康比例低于阈值，需要退回其他区域
+    pub fallback: bool,
+}
+
+/// 按调用方区域划分实例，健康比例按同区域内健康且启用的实例计算
+pub(super) fn partition_by_zone(instances: Vec<InstanceInfo>, zone: &str, switches: &NamingSwitches) -> ZonePartition {
+    let (local, remote): (Vec<InstanceInfo>, Vec<InstanceInfo>) = instances
+        .into_iter()
+        .partition(|inst| instance_zone(inst).as_deref() == Some(zone));
+
+    let local_healthy = local.iter().filter(|inst| inst.healthy && inst.enabled).count();
+    let fallback = local_healthy == 0
+        || (local_healthy as f64) < switches.zone_affinity_threshold * local.len() as f64;
+
+    ZonePartition { local, remote, fallback }
+}
+
+/// 对实例列表应用区域亲和：同区域实例排在前面（组内健康实例优先），
+/// 独占模式下未触发退回时只返回同区域实例
+pub async fn apply_zone_affinity(app: &AppHandle, instances: Vec<InstanceInfo>, zone: Option<&str>) -> Vec<InstanceInfo> {
+    let Some(zone) = zone.filter(|z| !z.is_empty()) else {
+        return instances;
+    };
+    let switches = get_naming_switches(app).await;
+    let ZonePartition { mut local, remote, fallback } = partition_by_zone(instances, zone, &switches);
+
+    local.sort_by_key(|inst| !(inst.healthy && inst.enabled));
+    if switches.zone_affinity_exclusive && !fallback {
+        return local;
+    }
+    local.extend(remote);
+    local
+}
@@ -0,0 +1,98 @@
+/**
+ * 实例选择模块
+ * 按权重从健康实例中选择一个实例（等价于 Nacos 客户端的 selectOneHealthyInstance），
+ * 支持按调用方 Key 粘性哈希以及按元数据 zone 优先同区域实例
+ */
+
+use tauri::AppHandle;
+
+use super::affinity::{partition_by_zone, ZonePartition};
+use super::{get_naming_switches, get_service_instances, InstanceInfo};
+
+/// 实例选择条件
+#[derive(Debug, Default)]
+pub struct SelectInstanceOptions {
+    /// 限定的集群列表，为空表示不限
+    pub clusters: Vec<String>,
+    /// 粘性哈希键，相同的键在实例集合不变时总是选中同一实例
+    pub hash_key: Option<String>,
+    /// 调用方所在区域，同区域健康比例不低于阈值时只在同区域内选择
+    pub zone: Option<String>,
+}
+
+/// 按权重选择一个健康、启用的实例
+pub async fn select_one_healthy_instance(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    options: SelectInstanceOptions,
+) -> Result<Option<InstanceInfo>, String> {
+    let response = get_service_instances(app, namespace_id, group_name, service_name).await?;
+
+    let mut instances: Vec<InstanceInfo> = response
+        .instances
+        .into_iter()
+        .filter(|inst| options.clusters.is_empty() || options.clusters.contains(&inst.cluster_name))
+        .collect();
+
+    // 同区域实例优先，同区域健康比例低于阈值时退回全部区域
+    if let Some(zone) = options.zone.as_deref().filter(|z| !z.is_empty()) {
+        let switches = get_naming_switches(app).await;
+        let ZonePartition { mut local, remote, fallback } = partition_by_zone(instances, zone, &switches);
+        if fallback || !local.iter().any(is_candidate) {
+            local.extend(remote);
+        }
+        instances = local;
+    }
+
+    let mut candidates: Vec<InstanceInfo> = instances.into_iter().filter(is_candidate).collect();
+
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+
+    // 粘性哈希需要稳定的候选顺序
+    candidates.sort_by(|a, b| a.ip.cmp(&b.ip).then(a.port.cmp(&b.port)));
+
+    let seed = match options.hash_key.as_deref().filter(|k| !k.is_empty()) {
+        Some(key) => hash_seed(key),
+        None => random_seed(),
+    };
+
+    let index = choose_by_weight(&candidates, seed);
+    Ok(candidates.into_iter().nth(index))
+}
+
+/// 可被选中的实例：健康、启用且权重大于 0
+fn is_candidate(instance: &InstanceInfo) -> bool {
+    instance.healthy && instance.enabled && instance.weight > 0.0
+}
+
+/// 按权重累积区间选择下标，seed 映射到 [0, 总权重) 上
+fn choose_by_weight(candidates: &[InstanceInfo], seed: u64) -> usize {
+    let total: f64 = candidates.iter().map(|inst| inst.weight).sum();
+    let point = (seed as f64 / u64::MAX as f64) * total;
+
+    let mut cumulative = 0.0;
+    for (index, inst) in candidates.iter().enumerate() {
+        cumulative += inst.weight;
+        if point < cumulative {
+            return index;
+        }
+    }
+    candidates.len() - 1
+}
+
+/// 由粘性哈希键计算种子
+fn hash_seed(key: &str) -> u64 {
+    let digest = md5::compute(key.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest.0[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// 随机种子
+fn random_seed() -> u64 {
+    uuid::Uuid::new_v4().as_u128() as u64
+}
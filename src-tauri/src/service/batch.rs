@@ -0,0 +1,438 @@
+/**
+ * 实例批量注册与注销模块
+ * 一次处理同一服务下的多个实例：持久化实例和自动创建的集群在单个事务中写入，临时实例在事务提交后写入内存，
+ * 每个批次只递增一次服务修订号，订阅方每批只收到一次变更通知
+ */
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::health_timeline::{record_health_event, HealthChangeSource};
+use super::history::{instance_address, record_instance_history, ServiceChangeType};
+use super::{
+    bump_service_revision, check_registration, current_timestamp, ensure_service, get_ephemeral_instances,
+    new_instance, remove_ephemeral_instance, upsert_ephemeral_instance, InstanceInfo, RegisterInstanceRequest,
+};
+use super::cluster::{DEFAULT_CHECK_PORT, DEFAULT_HEALTH_CHECKER, ENSURE_CLUSTER_SQL};
+
+/// 批量注册的单个实例
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchInstanceItem {
+    pub ip: String,
+    pub port: i32,
+    #[serde(default)]
+    pub weight: Option<f64>,
+    #[serde(default)]
+    pub healthy: Option<bool>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub ephemeral: Option<bool>,
+    #[serde(default, alias = "cluster")]
+    pub cluster_name: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// 批量注销的单个实例
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchInstanceRef {
+    pub ip: String,
+    pub port: i32,
+    #[serde(default, alias = "cluster")]
+    pub cluster_name: Option<String>,
+}
+
+/// 单个实例的处理结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchInstanceResult {
+    pub ip: String,
+    pub port: i32,
+    pub cluster_name: String,
+    pub instance_id: Option<String>,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+impl BatchInstanceResult {
+    fn failed(ip: &str, port: i32, cluster_name: &str, message: &str) -> Self {
+        BatchInstanceResult {
+            ip: ip.to_string(),
+            port,
+            cluster_name: cluster_name.to_string(),
+            instance_id: None,
+            success: false,
+            message: Some(message.to_string()),
+        }
+    }
+
+    fn succeeded(ip: &str, port: i32, cluster_name: &str, instance_id: &str) -> Self {
+        BatchInstanceResult {
+            ip: ip.to_string(),
+            port,
+            cluster_name: cluster_name.to_string(),
+            instance_id: Some(instance_id.to_string()),
+            success: true,
+            message: None,
+        }
+    }
+}
+
+/// 校验实例地址，返回错误信息
+fn validate_address(ip: &str, port: i32) -> Option<&'static str> {
+    if ip.trim().is_empty() {
+        Some("Invalid ip")
+    } else if !(1..=65535).contains(&port) {
+        Some("Invalid port")
+    } else {
+        None
+    }
+}
+
+/// 批量注册实例
+/// 非法的实例在结果中标记失败，其余实例作为一个批次写入；数据库写入失败时整个批次回滚
+pub async fn batch_register_instances(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instances: Vec<BatchInstanceItem>,
+) -> Result<Vec<BatchInstanceResult>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    // 与单个注册一致：服务不存在时自动创建，再执行公共的注册检查
+    ensure_service(app, namespace_id, group_name, service_name).await?;
+    let (switches, schema) = check_registration(app, namespace_id, group_name, service_name).await?;
+    let now = current_timestamp();
+    let mut results: Vec<Option<BatchInstanceResult>> = vec![None; instances.len()];
+    let mut accepted: Vec<(usize, InstanceInfo)> = Vec::new();
+
+    for (index, item) in instances.into_iter().enumerate() {
+        let cluster_name = item.cluster_name.filter(|c| !c.is_empty()).unwrap_or_else(|| "DEFAULT".to_string());
+        let weight = item.weight.unwrap_or(1.0);
+
+        let error = validate_address(&item.ip, item.port)
+            .or_else(|| (!(0.0..=10000.0).contains(&weight)).then_some("Invalid weight"))
+            .or_else(|| {
+                accepted
+                    .iter()
+                    .any(|(_, inst)| inst.ip == item.ip && inst.port == item.port && inst.cluster_name == cluster_name)
+                    .then_some("Duplicate instance in batch")
+            });
+        if let Some(error) = error {
+            results[index] = Some(BatchInstanceResult::failed(&item.ip, item.port, &cluster_name, error));
+            continue;
+        }
+
+        let metadata = item.metadata.map(|m| match m {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        });
+        // 违反元数据约定的实例在强制模式下标记失败
+        if let Some(Err(error)) = schema.as_ref().map(|schema| schema.enforce(service_name, [metadata.as_deref()])) {
+            results[index] = Some(BatchInstanceResult::failed(&item.ip, item.port, &cluster_name, &error));
+            continue;
+        }
+        let request = RegisterInstanceRequest {
+            namespace_id: namespace_id.to_string(),
+            group_name: group_name.to_string(),
+            service_name: service_name.to_string(),
+            ip: item.ip,
+            port: item.port,
+            weight: Some(weight),
+            healthy: item.healthy,
+            enabled: item.enabled,
+            ephemeral: item.ephemeral,
+            cluster_name: Some(cluster_name),
+            metadata,
+        };
+        accepted.push((index, new_instance(&request, &switches)));
+    }
+
+    // 首次注册实例时自动创建集群，与持久化实例在同一个事务中写入
+    if !accepted.is_empty() {
+        let mut tx = db
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let written: Result<(), String> = async {
+            let clusters: BTreeSet<&str> = accepted.iter().map(|(_, inst)| inst.cluster_name.as_str()).collect();
+            for cluster_name in clusters {
+                tx.execute(
+                    ENSURE_CLUSTER_SQL,
+                    &[
+                        ("?1", namespace_id),
+                        ("?2", group_name),
+                        ("?3", service_name),
+                        ("?4", cluster_name),
+                        ("?5", DEFAULT_HEALTH_CHECKER),
+                        ("?6", &DEFAULT_CHECK_PORT.to_string()),
+                        ("?7", "1"),
+                        ("?8", "{}"),
+                        ("?9", &now.to_string()),
+                    ],
+                )
+                .await
+                .map_err(|e| format!("Failed to create cluster: {}", e))?;
+            }
+
+            for (_, instance) in accepted.iter_mut().filter(|(_, inst)| !inst.ephemeral) {
+                // 按 IP、端口、集群匹配已有的持久化实例
+                let existing: Option<(i64, String, i64)> = tx
+                    .query_one(
+                        "SELECT id, instance_id, gmt_create FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND ip = ?4 AND port = ?5 AND cluster_name = ?6",
+                        &[
+                            ("?1", namespace_id),
+                            ("?2", group_name),
+                            ("?3", service_name),
+                            ("?4", &instance.ip),
+                            ("?5", &instance.port.to_string()),
+                            ("?6", &instance.cluster_name),
+                        ],
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to check existing instance: {}", e))?;
+
+                if let Some((id, instance_id, gmt_create)) = existing {
+                    tx.execute(
+                        "UPDATE instance_info SET weight = ?1, healthy = ?2, enabled = ?3, ephemeral = 0, metadata = ?4, gmt_modified = ?5 WHERE id = ?6",
+                        &[
+                            ("?1", &instance.weight.to_string()),
+                            ("?2", &(if instance.healthy { "1" } else { "0" })),
+                            ("?3", &(if instance.enabled { "1" } else { "0" })),
+                            ("?4", &instance.metadata.as_deref().unwrap_or("")),
+                            ("?5", &now.to_string()),
+                            ("?6", &id.to_string()),
+                        ],
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to update instance: {}", e))?;
+
+                    instance.id = Some(id);
+                    instance.instance_id = instance_id;
+                    instance.gmt_create = gmt_create;
+                } else {
+                    tx.execute(
+                        "INSERT INTO instance_info (namespace_id, group_name, service_name, instance_id, ip, port, weight, healthy, enabled, ephemeral, cluster_name, metadata, gmt_create, gmt_modified) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, ?10, ?11, ?12, ?13)",
+                        &[
+                            ("?1", namespace_id),
+                            ("?2", group_name),
+                            ("?3", service_name),
+                            ("?4", &instance.instance_id),
+                            ("?5", &instance.ip),
+                            ("?6", &instance.port.to_string()),
+                            ("?7", &instance.weight.to_string()),
+                            ("?8", &(if instance.healthy { "1" } else { "0" })),
+                            ("?9", &(if instance.enabled { "1" } else { "0" })),
+                            ("?10", &instance.cluster_name),
+                            ("?11", &instance.metadata.as_deref().unwrap_or("")),
+                            ("?12", &now.to_string()),
+                            ("?13", &now.to_string()),
+                        ],
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to register instance: {}", e))?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        // 任一写入失败时显式回滚整个批次
+        match written {
+            Ok(()) => tx
+                .commit()
+                .await
+                .map_err(|e| format!("Failed to commit transaction: {}", e))?,
+            Err(e) => {
+                if let Err(rollback_error) = tx.rollback().await {
+                    tracing::warn!("Failed to roll back batch registration: {}", rollback_error);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    // 临时实例写入内存注册表
+    for (_, instance) in accepted.iter_mut().filter(|(_, inst)| inst.ephemeral) {
+        *instance = upsert_ephemeral_instance(app, instance.clone()).await;
+    }
+
+    if !accepted.is_empty() {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+    }
+
+    for (index, instance) in accepted {
+        record_instance_history(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            ServiceChangeType::Register,
+            &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+            "registered",
+        )
+        .await;
+        record_health_event(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+            instance.healthy,
+            HealthChangeSource::Register,
+            "registered",
+        )
+        .await;
+        results[index] = Some(BatchInstanceResult::succeeded(
+            &instance.ip,
+            instance.port,
+            &instance.cluster_name,
+            &instance.instance_id,
+        ));
+    }
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+/// 批量注销实例
+/// 不存在的实例在结果中标记失败；持久化实例在一个事务中删除
+pub async fn batch_deregister_instances(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instances: Vec<BatchInstanceRef>,
+) -> Result<Vec<BatchInstanceResult>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let ephemeral_instances = get_ephemeral_instances(app, namespace_id, group_name, service_name).await;
+
+    let mut results: Vec<Option<BatchInstanceResult>> = vec![None; instances.len()];
+    let mut ephemeral_removals: Vec<String> = Vec::new();
+    let mut persistent: Vec<(usize, String, i32, String)> = Vec::new();
+
+    for (index, item) in instances.into_iter().enumerate() {
+        let cluster_name = item.cluster_name.filter(|c| !c.is_empty()).unwrap_or_else(|| "DEFAULT".to_string());
+
+        if let Some(error) = validate_address(&item.ip, item.port) {
+            results[index] = Some(BatchInstanceResult::failed(&item.ip, item.port, &cluster_name, error));
+            continue;
+        }
+
+        // 临时实例，事务提交后再从内存中移除
+        if let Some(instance) = ephemeral_instances
+            .iter()
+            .find(|inst| inst.ip == item.ip && inst.port == item.port && inst.cluster_name == cluster_name)
+        {
+            ephemeral_removals.push(instance.instance_id.clone());
+            results[index] = Some(BatchInstanceResult::succeeded(&item.ip, item.port, &cluster_name, &instance.instance_id));
+            continue;
+        }
+
+        persistent.push((index, item.ip, item.port, cluster_name));
+    }
+
+    // 持久化实例的查询和删除在一个事务中进行
+    if !persistent.is_empty() {
+        let mut tx = db
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let deleted: Result<(), String> = async {
+            for (index, ip, port, cluster_name) in &persistent {
+                let existing: Option<(i64, String)> = tx
+                    .query_one(
+                        "SELECT id, instance_id FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND ip = ?4 AND port = ?5 AND cluster_name = ?6",
+                        &[
+                            ("?1", namespace_id),
+                            ("?2", group_name),
+                            ("?3", service_name),
+                            ("?4", ip),
+                            ("?5", &port.to_string()),
+                            ("?6", cluster_name),
+                        ],
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to check instance: {}", e))?;
+
+                let Some((id, instance_id)) = existing else {
+                    results[*index] = Some(BatchInstanceResult::failed(ip, *port, cluster_name, "Instance not found"));
+                    continue;
+                };
+
+                tx.execute("DELETE FROM instance_info WHERE id = ?1", &[("?1", &id.to_string())])
+                    .await
+                    .map_err(|e| format!("Failed to deregister instance: {}", e))?;
+
+                results[*index] = Some(BatchInstanceResult::succeeded(ip, *port, cluster_name, &instance_id));
+            }
+            Ok(())
+        }
+        .await;
+
+        // 任一写入失败时显式回滚整个批次
+        match deleted {
+            Ok(()) => tx
+                .commit()
+                .await
+                .map_err(|e| format!("Failed to commit transaction: {}", e))?,
+            Err(e) => {
+                if let Err(rollback_error) = tx.rollback().await {
+                    tracing::warn!("Failed to roll back batch deregistration: {}", rollback_error);
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    for instance_id in ephemeral_removals {
+        remove_ephemeral_instance(app, namespace_id, group_name, service_name, &instance_id).await;
+    }
+
+    let results: Vec<BatchInstanceResult> = results.into_iter().flatten().collect();
+    let removed: Vec<&BatchInstanceResult> = results.iter().filter(|r| r.success).collect();
+    if !removed.is_empty() {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+    }
+
+    for BatchInstanceResult { ip, port, cluster_name, .. } in removed {
+        record_instance_history(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            ServiceChangeType::Deregister,
+            &instance_address(ip, *port, cluster_name),
+            "deregistered",
+        )
+        .await;
+        record_health_event(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &instance_address(ip, *port, cluster_name),
+            false,
+            HealthChangeSource::Deregister,
+            "deregistered",
+        )
+        .await;
+    }
+
+    Ok(results)
+}
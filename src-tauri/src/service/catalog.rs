@@ -0,0 +1,278 @@
+/**
+ * 服务目录模块
+ * 为控制台服务列表提供带统计信息的服务目录：集群数、实例数、健康实例数和保护阈值触发标记。
+ * 持久化实例的统计在 SQL 中聚合，内存中的临时实例统计合并后再按 hasIpCount 过滤和分页
+ */
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::revision::service_key;
+use super::{
+    all_ephemeral_instances, get_service_clusters, get_service_detail, get_service_instances, ClusterInfo,
+    InstanceInfo, ServiceInfo,
+};
+
+/// 服务目录查询参数
+#[derive(Debug, Deserialize)]
+pub struct CatalogServiceQuery {
+    pub namespace_id: Option<String>,
+    /// 服务组模糊匹配
+    pub group_name: Option<String>,
+    /// 服务名模糊匹配
+    pub service_name: Option<String>,
+    /// 只返回有实例的服务
+    #[serde(default)]
+    pub has_ip_count: bool,
+    pub page_no: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+/// 服务目录条目
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogServiceSummary {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub protect_threshold: f64,
+    pub cluster_count: i64,
+    pub ip_count: i64,
+    pub healthy_instance_count: i64,
+    /// 健康实例比例不高于保护阈值
+    pub trigger_flag: bool,
+}
+
+/// 服务目录列表响应
+#[derive(Debug, Serialize)]
+pub struct CatalogServiceListResponse {
+    pub total_count: i64,
+    pub page_number: i64,
+    pub pages_available: i64,
+    pub page_items: Vec<CatalogServiceSummary>,
+}
+
+/// 服务目录实例查询参数
+#[derive(Debug, Deserialize)]
+pub struct CatalogInstanceQuery {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    /// 限定集群，为空表示全部集群
+    pub cluster_name: Option<String>,
+    pub page_no: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+/// 服务目录实例列表响应
+#[derive(Debug, Serialize)]
+pub struct CatalogInstanceListResponse {
+    pub total_count: i64,
+    pub page_items: Vec<InstanceInfo>,
+}
+
+/// 服务目录详情
+#[derive(Debug, Serialize)]
+pub struct CatalogServiceDetail {
+    pub service: ServiceInfo,
+    pub clusters: Vec<ClusterInfo>,
+    pub summary: CatalogServiceSummary,
+}
+
+/// 临时实例统计（实例数、健康实例数、集群名）
+#[derive(Debug, Default)]
+struct EphemeralStats {
+    ip_count: i64,
+    healthy_count: i64,
+    clusters: BTreeSet<String>,
+}
+
+/// 健康实例比例不高于保护阈值时触发保护
+fn trigger_flag(ip_count: i64, healthy_count: i64, protect_threshold: f64) -> bool {
+    ip_count > 0 && (healthy_count as f64 / ip_count as f64) <= protect_threshold
+}
+
+/// 按服务汇总内存中的临时实例
+async fn ephemeral_stats(app: &AppHandle) -> HashMap<String, EphemeralStats> {
+    let mut stats: HashMap<String, EphemeralStats> = HashMap::new();
+    for inst in all_ephemeral_instances(app).await {
+        let entry = stats
+            .entry(service_key(&inst.namespace_id, &inst.group_name, &inst.service_name))
+            .or_default();
+        entry.ip_count += 1;
+        if inst.healthy {
+            entry.healthy_count += 1;
+        }
+        entry.clusters.insert(inst.cluster_name);
+    }
+    stats
+}
+
+/// 查询服务统计，where_sql 作用于 service_info（别名 s）
+async fn query_service_summaries(
+    app: &AppHandle,
+    where_sql: &str,
+    query_params: &[(&str, &str)],
+) -> Result<Vec<CatalogServiceSummary>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let sql = format!(
+        "SELECT s.namespace_id, s.group_name, s.service_name, s.protect_threshold, \
+         (SELECT COUNT(*) FROM instance_info i WHERE i.namespace_id = s.namespace_id AND i.group_name = s.group_name AND i.service_name = s.service_name), \
+         (SELECT COUNT(*) FROM instance_info i WHERE i.namespace_id = s.namespace_id AND i.group_name = s.group_name AND i.service_name = s.service_name AND i.healthy = 1), \
+         (SELECT GROUP_CONCAT(DISTINCT i.cluster_name) FROM instance_info i WHERE i.namespace_id = s.namespace_id AND i.group_name = s.group_name AND i.service_name = s.service_name), \
+         (SELECT GROUP_CONCAT(DISTINCT c.cluster_name) FROM cluster_info c WHERE c.namespace_id = s.namespace_id AND c.group_name = s.group_name AND c.service_name = s.service_name) \
+         FROM service_info s {} ORDER BY s.gmt_modified DESC, s.service_name",
+        where_sql
+    );
+
+    let rows: Vec<(String, String, String, f64, i64, i64, Option<String>, Option<String>)> = db
+        .query(&sql, query_params)
+        .await
+        .map_err(|e| format!("Failed to query service catalog: {}", e))?;
+
+    let mut ephemeral = ephemeral_stats(app).await;
+    Ok(rows
+        .into_iter()
+        .map(|(namespace_id, group_name, service_name, protect_threshold, ip_count, healthy_count, instance_clusters, configured_clusters)| {
+            let stats = ephemeral
+                .remove(&service_key(&namespace_id, &group_name, &service_name))
+                .unwrap_or_default();
+
+            let mut clusters = stats.clusters;
+            for names in [instance_clusters, configured_clusters].into_iter().flatten() {
+                clusters.extend(names.split(',').filter(|n| !n.is_empty()).map(|n| n.to_string()));
+            }
+
+            let ip_count = ip_count + stats.ip_count;
+            let healthy_instance_count = healthy_count + stats.healthy_count;
+            CatalogServiceSummary {
+                namespace_id,
+                group_name,
+                service_name,
+                protect_threshold,
+                cluster_count: clusters.len() as i64,
+                ip_count,
+                healthy_instance_count,
+                trigger_flag: trigger_flag(ip_count, healthy_instance_count, protect_threshold),
+            }
+        })
+        .collect())
+}
+
+/// 分页查询服务目录
+pub async fn list_catalog_services(
+    app: &AppHandle,
+    query: CatalogServiceQuery,
+) -> Result<CatalogServiceListResponse, String> {
+    let page_no = query.page_no.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(10).max(1);
+
+    let mut where_clauses = Vec::new();
+    let mut query_params: Vec<(&str, &str)> = Vec::new();
+
+    let group_pattern = query.group_name.as_deref().filter(|g| !g.is_empty()).map(|g| format!("%{}%", g));
+    let service_pattern = query.service_name.as_deref().filter(|s| !s.is_empty()).map(|s| format!("%{}%", s));
+
+    if let Some(ref namespace_id) = query.namespace_id {
+        where_clauses.push("s.namespace_id = ?1");
+        query_params.push(("?1", namespace_id));
+    }
+    if let Some(ref group_pattern) = group_pattern {
+        where_clauses.push("s.group_name LIKE ?2");
+        query_params.push(("?2", group_pattern));
+    }
+    if let Some(ref service_pattern) = service_pattern {
+        where_clauses.push("s.service_name LIKE ?3");
+        query_params.push(("?3", service_pattern));
+    }
+
+    let where_sql = if where_clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", where_clauses.join(" AND "))
+    };
+
+    // 临时实例不在数据库中，hasIpCount 过滤和分页在合并统计之后进行
+    let mut services = query_service_summaries(app, &where_sql, &query_params).await?;
+    if query.has_ip_count {
+        services.retain(|s| s.ip_count > 0);
+    }
+
+    let total_count = services.len() as i64;
+    let pages_available = (total_count + page_size - 1) / page_size;
+    let page_items = services
+        .into_iter()
+        .skip(((page_no - 1) * page_size) as usize)
+        .take(page_size as usize)
+        .collect();
+
+    Ok(CatalogServiceListResponse {
+        total_count,
+        page_number: page_no,
+        pages_available,
+        page_items,
+    })
+}
+
+/// 查询服务目录详情（服务信息、集群和统计）
+pub async fn get_catalog_service(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<Option<CatalogServiceDetail>, String> {
+    let Some(service) = get_service_detail(app, namespace_id, group_name, service_name).await? else {
+        return Ok(None);
+    };
+    let clusters = get_service_clusters(app, namespace_id, group_name, service_name).await?;
+
+    let summary = query_service_summaries(
+        app,
+        "WHERE s.namespace_id = ?1 AND s.group_name = ?2 AND s.service_name = ?3",
+        &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+    )
+    .await?
+    .into_iter()
+    .next()
+    .ok_or_else(|| format!("Service {} not found", service_name))?;
+
+    Ok(Some(CatalogServiceDetail { service, clusters, summary }))
+}
+
+/// 分页查询服务实例（按集群过滤）
+pub async fn list_catalog_instances(
+    app: &AppHandle,
+    query: CatalogInstanceQuery,
+) -> Result<CatalogInstanceListResponse, String> {
+    let page_no = query.page_no.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(10).max(1);
+
+    let response = get_service_instances(app, &query.namespace_id, &query.group_name, &query.service_name).await?;
+    let mut instances: Vec<InstanceInfo> = response
+        .instances
+        .into_iter()
+        .filter(|inst| match query.cluster_name.as_deref().filter(|c| !c.is_empty()) {
+            Some(cluster_name) => inst.cluster_name == cluster_name,
+            None => true,
+        })
+        .collect();
+    // 分页需要稳定的顺序
+    instances.sort_by(|a, b| {
+        (&a.cluster_name, &a.ip, a.port).cmp(&(&b.cluster_name, &b.ip, b.port))
+    });
+
+    let total_count = instances.len() as i64;
+    let page_items = instances
+        .into_iter()
+        .skip(((page_no - 1) * page_size) as usize)
+        .take(page_size as usize)
+        .collect();
+
+    Ok(CatalogInstanceListResponse { total_count, page_items })
+}
@@ -0,0 +1,199 @@
+/**
+ * 空服务自动清理模块
+ * 与 Nacos nacos.naming.empty-service.* 配置一致：注册实例时自动创建的服务在连续一段时间没有实例后被删除，
+ * 通过接口显式创建的服务以及元数据带有 persist 标记的服务不会被清理，删除记录写入服务变更历史
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::export::ensure_not_exported;
+use super::history::{record_service_history, ServiceChangeType};
+use super::revision::service_key;
+use super::{current_millis, current_timestamp, get_service_instances, remove_service};
+
+/// 通过接口或控制台显式创建的服务
+pub const SERVICE_CREATE_SOURCE_API: &str = "API";
+
+/// 注册实例时自动创建的服务
+pub const SERVICE_CREATE_SOURCE_REGISTER: &str = "REGISTER";
+
+/// 服务元数据中标记服务永久保留的键（值为 true 时不清理）
+pub const PERSIST_SERVICE_METADATA_KEY: &str = "persist";
+
+/// 默认清理周期（秒），对应 nacos.naming.empty-service.clean-interval
+pub const DEFAULT_EMPTY_SERVICE_CLEAN_INTERVAL_SECONDS: u64 = 60;
+
+/// 默认空服务过期时间（秒），对应 nacos.naming.empty-service.expired-time
+pub const DEFAULT_EMPTY_SERVICE_EXPIRED_TIME_SECONDS: u64 = 60;
+
+/// 服务变为空的时间（服务键 -> 毫秒）
+#[derive(Default)]
+struct EmptyServiceTracker {
+    empty_since: HashMap<String, i64>,
+}
+
+type EmptyServiceTrackerState = Arc<Mutex<EmptyServiceTracker>>;
+
+/// 获取或创建空服务跟踪状态
+fn get_tracker(app: &AppHandle) -> EmptyServiceTrackerState {
+    if let Some(state) = app.try_state::<EmptyServiceTrackerState>() {
+        state.inner().clone()
+    } else {
+        let state: EmptyServiceTrackerState = Arc::new(Mutex::new(EmptyServiceTracker::default()));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 服务元数据是否带有 persist 标记
+fn is_persist_service(metadata: Option<&str>) -> bool {
+    metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| {
+            m.get(PERSIST_SERVICE_METADATA_KEY).map(|v| match v {
+                serde_json::Value::Bool(b) => *b,
+                serde_json::Value::String(s) => s.eq_ignore_ascii_case("true"),
+                _ => false,
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// 注册实例时确保服务存在，不存在则以 REGISTER 来源自动创建，返回是否新建了服务
+pub async fn ensure_service(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<bool, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let existing: Option<(i64,)> = db
+        .query_one(
+            "SELECT id FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3",
+            &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to check service: {}", e))?;
+
+    if existing.is_some() {
+        return Ok(false);
+    }
+    // 导出别名是只读的，不自动创建同名服务
+    ensure_not_exported(app, namespace_id, group_name, service_name).await?;
+
+    let now = current_timestamp().to_string();
+    db.execute(
+        "INSERT OR IGNORE INTO service_info (namespace_id, group_name, service_name, protect_threshold, create_source, gmt_create, gmt_modified) VALUES (?1, ?2, ?3, 0.0, ?4, ?5, ?6)",
+        &[
+            ("?1", namespace_id),
+            ("?2", group_name),
+            ("?3", service_name),
+            ("?4", SERVICE_CREATE_SOURCE_REGISTER),
+            ("?5", &now),
+            ("?6", &now),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to create service: {}", e))?;
+
+    record_service_history(
+        app,
+        namespace_id,
+        group_name,
+        service_name,
+        ServiceChangeType::Create,
+        &format!("Service auto created on register: {}", service_name),
+    )
+    .await?;
+
+    Ok(true)
+}
+
+/// 启动空服务清理任务
+pub fn start_empty_service_cleaner_task(app: AppHandle, clean_interval_seconds: u64, expired_time_seconds: u64) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(clean_interval_seconds.max(1)));
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_empty_service_clean_round(&app, expired_time_seconds).await {
+                tracing::warn!("Failed to clean empty services: {}", e);
+            }
+        }
+    })
+}
+
+/// 执行一轮空服务清理，返回被删除的服务数量
+/// 服务第一次被发现没有实例时开始计时，持续为空超过过期时间后删除；期间有实例注册则重新计时
+pub async fn run_empty_service_clean_round(app: &AppHandle, expired_time_seconds: u64) -> Result<usize, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let services: Vec<(String, String, String, Option<String>)> = db
+        .query(
+            "SELECT namespace_id, group_name, service_name, metadata FROM service_info WHERE create_source != ?1",
+            &[("?1", SERVICE_CREATE_SOURCE_API)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query services: {}", e))?;
+
+    let tracker = get_tracker(app);
+    let now = current_millis();
+    let expired_millis = (expired_time_seconds as i64).saturating_mul(1000);
+
+    let mut expired = Vec::new();
+    {
+        let mut tracker = tracker.lock().await;
+        let mut candidates = HashMap::new();
+        for (namespace_id, group_name, service_name, metadata) in services {
+            if is_persist_service(metadata.as_deref()) {
+                continue;
+            }
+            let instances = get_service_instances(app, &namespace_id, &group_name, &service_name).await?;
+            if !instances.instances.is_empty() {
+                continue;
+            }
+
+            let key = service_key(&namespace_id, &group_name, &service_name);
+            let empty_since = tracker.empty_since.get(&key).copied().unwrap_or(now);
+            if now - empty_since >= expired_millis {
+                expired.push((namespace_id, group_name, service_name, now - empty_since));
+            } else {
+                candidates.insert(key, empty_since);
+            }
+        }
+        // 重新有实例或已删除的服务不再跟踪
+        tracker.empty_since = candidates;
+    }
+
+    let mut cleaned = 0;
+    for (namespace_id, group_name, service_name, empty_millis) in expired {
+        let detail = format!(
+            "Empty service cleaned: {} (no instances for {}s)",
+            service_name,
+            empty_millis / 1000
+        );
+        match remove_service(app, &namespace_id, &group_name, &service_name, &detail).await {
+            Ok(()) => {
+                tracing::info!("Removed empty service {}@@{} in namespace {}", group_name, service_name, namespace_id);
+                cleaned += 1;
+            }
+            Err(e) => tracing::warn!("Failed to remove empty service {}: {}", service_name, e),
+        }
+    }
+
+    Ok(cleaned)
+}
@@ -0,0 +1,347 @@
+/**
+ * 命名客户端视图模块
+ * 以 Nacos 2.x 的客户端（Client）视角汇总发布的实例和订阅的服务。
+ * HTTP 客户端没有长连接，客户端 ID 按 Nacos 的 ipport 规则生成：ip:port#ephemeral
+ */
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use super::{all_ephemeral_instances, get_ephemeral_instances};
+
+/// 客户端发布的实例
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientPublishedService {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub ip: String,
+    pub port: i32,
+    pub cluster_name: String,
+}
+
+/// 客户端订阅的服务
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientSubscribedService {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub app_name: String,
+    pub agent: String,
+    pub address: String,
+}
+
+/// 客户端详情
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientDetail {
+    pub client_id: String,
+    pub ephemeral: bool,
+    pub client_ip: String,
+    pub client_port: i32,
+    pub last_updated_time: i64,
+}
+
+/// 服务下的客户端（发布者或订阅者）
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceClient {
+    pub client_id: String,
+    pub ip: String,
+    pub port: i32,
+}
+
+/// 生成客户端 ID（ip:port#ephemeral）
+pub fn build_client_id(ip: &str, port: i32, ephemeral: bool) -> String {
+    format!("{}:{}#{}", ip, port, ephemeral)
+}
+
+/// 解析客户端 ID，返回 (ip, port, ephemeral)
+pub fn parse_client_id(client_id: &str) -> Option<(String, i32, bool)> {
+    let (address, ephemeral) = match client_id.split_once('#') {
+        Some((address, ephemeral)) => (address, ephemeral != "false"),
+        None => (client_id, true),
+    };
+    let (ip, port) = address.rsplit_once(':')?;
+    let port = port.parse::<i32>().ok()?;
+    Some((ip.to_string(), port, ephemeral))
+}
+
+/// 查询所有客户端 ID（实例发布者和服务订阅者）
+pub async fn list_clients(app: &AppHandle) -> Result<Vec<String>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let mut publishers: Vec<(String, i32, bool)> = db
+        .query("SELECT DISTINCT ip, port, ephemeral FROM instance_info", &[])
+        .await
+        .map_err(|e| format!("Failed to query publishers: {}", e))?;
+    publishers.extend(
+        all_ephemeral_instances(app)
+            .await
+            .into_iter()
+            .map(|inst| (inst.ip, inst.port, inst.ephemeral)),
+    );
+
+    let subscribers: Vec<(String, i32)> = db
+        .query("SELECT DISTINCT client_ip, client_port FROM naming_subscribers", &[])
+        .await
+        .map_err(|e| format!("Failed to query subscribers: {}", e))?;
+
+    let mut client_ids: Vec<String> = publishers
+        .into_iter()
+        .map(|(ip, port, ephemeral)| build_client_id(&ip, port, ephemeral))
+        .chain(subscribers.into_iter().map(|(ip, port)| build_client_id(&ip, port, true)))
+        .collect();
+    client_ids.sort();
+    client_ids.dedup();
+
+    Ok(client_ids)
+}
+
+/// 查询客户端详情
+pub async fn get_client_detail(
+    app: &AppHandle,
+    client_id: &str,
+) -> Result<Option<ClientDetail>, String> {
+    let (ip, port, ephemeral) = parse_client_id(client_id).ok_or_else(|| "Invalid clientId".to_string())?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let published: Option<(Option<i64>,)> = db
+        .query_one(
+            "SELECT MAX(gmt_modified) FROM instance_info WHERE ip = ?1 AND port = ?2 AND ephemeral = ?3",
+            &[
+                ("?1", &ip),
+                ("?2", &port.to_string()),
+                ("?3", &(if ephemeral { "1" } else { "0" })),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to query client: {}", e))?;
+
+    let subscribed: Option<(Option<i64>,)> = db
+        .query_one(
+            "SELECT MAX(last_ref_time) FROM naming_subscribers WHERE client_ip = ?1 AND client_port = ?2",
+            &[("?1", &ip), ("?2", &port.to_string())],
+        )
+        .await
+        .map_err(|e| format!("Failed to query client: {}", e))?;
+
+    // 内存中的临时实例
+    let ephemeral_published = if ephemeral {
+        all_ephemeral_instances(app)
+            .await
+            .into_iter()
+            .filter(|inst| inst.ip == ip && inst.port == port)
+            .map(|inst| inst.gmt_modified)
+            .max()
+    } else {
+        None
+    };
+
+    let published = published.and_then(|(t,)| t).max(ephemeral_published);
+    let subscribed = if ephemeral { subscribed.and_then(|(t,)| t) } else { None };
+
+    let last_updated_time = match (published, subscribed) {
+        (None, None) => return Ok(None),
+        (a, b) => a.unwrap_or(0).max(b.unwrap_or(0)),
+    };
+
+    Ok(Some(ClientDetail {
+        client_id: build_client_id(&ip, port, ephemeral),
+        ephemeral,
+        client_ip: ip,
+        client_port: port,
+        last_updated_time,
+    }))
+}
+
+/// 查询客户端发布的服务
+pub async fn get_client_published_services(
+    app: &AppHandle,
+    client_id: &str,
+) -> Result<Vec<ClientPublishedService>, String> {
+    let (ip, port, ephemeral) = parse_client_id(client_id).ok_or_else(|| "Invalid clientId".to_string())?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let rows: Vec<(String, String, String, String, i32, String)> = db
+        .query(
+            "SELECT namespace_id, group_name, service_name, ip, port, cluster_name FROM instance_info WHERE ip = ?1 AND port = ?2 AND ephemeral = ?3 ORDER BY namespace_id, group_name, service_name",
+            &[
+                ("?1", &ip),
+                ("?2", &port.to_string()),
+                ("?3", &(if ephemeral { "1" } else { "0" })),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to query published services: {}", e))?;
+
+    let mut services: Vec<ClientPublishedService> = rows
+        .into_iter()
+        .map(|(namespace_id, group_name, service_name, ip, port, cluster_name)| ClientPublishedService {
+            namespace_id,
+            group_name,
+            service_name,
+            ip,
+            port,
+            cluster_name,
+        })
+        .collect();
+
+    // 内存中的临时实例
+    if ephemeral {
+        services.extend(
+            all_ephemeral_instances(app)
+                .await
+                .into_iter()
+                .filter(|inst| inst.ip == ip && inst.port == port)
+                .map(|inst| ClientPublishedService {
+                    namespace_id: inst.namespace_id,
+                    group_name: inst.group_name,
+                    service_name: inst.service_name,
+                    ip: inst.ip,
+                    port: inst.port,
+                    cluster_name: inst.cluster_name,
+                }),
+        );
+        services.sort_by(|a, b| {
+            (&a.namespace_id, &a.group_name, &a.service_name).cmp(&(&b.namespace_id, &b.group_name, &b.service_name))
+        });
+    }
+
+    Ok(services)
+}
+
+/// 查询客户端订阅的服务
+pub async fn get_client_subscribed_services(
+    app: &AppHandle,
+    client_id: &str,
+) -> Result<Vec<ClientSubscribedService>, String> {
+    let (ip, port, ephemeral) = parse_client_id(client_id).ok_or_else(|| "Invalid clientId".to_string())?;
+
+    // 订阅关系只存在于临时客户端
+    if !ephemeral {
+        return Ok(Vec::new());
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let rows: Vec<(String, String, String, Option<String>, Option<String>, String, i32)> = db
+        .query(
+            "SELECT namespace_id, group_name, service_name, app_name, agent, client_ip, client_port FROM naming_subscribers WHERE client_ip = ?1 AND client_port = ?2 ORDER BY namespace_id, group_name, service_name",
+            &[("?1", &ip), ("?2", &port.to_string())],
+        )
+        .await
+        .map_err(|e| format!("Failed to query subscribed services: {}", e))?;
+
+    let mut services: Vec<ClientSubscribedService> = rows
+        .into_iter()
+        .map(|(namespace_id, group_name, service_name, app_name, agent, client_ip, client_port)| ClientSubscribedService {
+            namespace_id,
+            group_name,
+            service_name,
+            app_name: app_name.unwrap_or_default(),
+            agent: agent.unwrap_or_default(),
+            address: format!("{}:{}", client_ip, client_port),
+        })
+        .collect();
+    // 同一服务按不同集群订阅时只保留一条
+    services.dedup_by(|a, b| {
+        a.namespace_id == b.namespace_id && a.group_name == b.group_name && a.service_name == b.service_name
+    });
+
+    Ok(services)
+}
+
+/// 查询发布了指定服务实例的客户端，可按 IP / 端口过滤
+pub async fn get_service_publisher_clients(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    ip: Option<&str>,
+    port: Option<i32>,
+) -> Result<Vec<ServiceClient>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let mut rows: Vec<(String, i32, bool)> = db
+        .query(
+            "SELECT DISTINCT ip, port, ephemeral FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 ORDER BY ip, port",
+            &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query publisher clients: {}", e))?;
+
+    // 合并内存中的临时实例
+    rows.extend(
+        get_ephemeral_instances(app, namespace_id, group_name, service_name)
+            .await
+            .into_iter()
+            .map(|inst| (inst.ip, inst.port, inst.ephemeral)),
+    );
+    rows.sort();
+    rows.dedup();
+
+    Ok(rows
+        .into_iter()
+        .filter(|(row_ip, row_port, _)| ip.map_or(true, |ip| ip == row_ip) && port.map_or(true, |port| port == *row_port))
+        .map(|(ip, port, ephemeral)| ServiceClient {
+            client_id: build_client_id(&ip, port, ephemeral),
+            ip,
+            port,
+        })
+        .collect())
+}
+
+/// 查询订阅了指定服务的客户端，可按 IP / 端口过滤
+pub async fn get_service_subscriber_clients(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    ip: Option<&str>,
+    port: Option<i32>,
+) -> Result<Vec<ServiceClient>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let rows: Vec<(String, i32)> = db
+        .query(
+            "SELECT DISTINCT client_ip, client_port FROM naming_subscribers WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 ORDER BY client_ip, client_port",
+            &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query subscriber clients: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|(row_ip, row_port)| ip.map_or(true, |ip| ip == row_ip) && port.map_or(true, |port| port == *row_port))
+        .map(|(ip, port)| ServiceClient {
+            client_id: build_client_id(&ip, port, true),
+            ip,
+            port,
+        })
+        .collect())
+}
@@ -0,0 +1,308 @@
+/**
+ * 集群管理模块
+ * 负责集群的健康检查器配置、检查端口和集群元数据
+ */
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::current_timestamp;
+
+/// 默认健康检查器（与 Nacos 一致，默认 TCP 检查）
+pub const DEFAULT_HEALTH_CHECKER: &str = r#"{"type":"TCP"}"#;
+
+/// 默认检查端口
+pub const DEFAULT_CHECK_PORT: i32 = 80;
+
+/// 集群信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterInfo {
+    pub id: Option<i64>,
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub cluster_name: String,
+    pub health_checker: String,
+    pub check_port: i32,
+    pub use_instance_port: bool,
+    pub metadata: Option<String>,
+    pub gmt_create: i64,
+    pub gmt_modified: i64,
+}
+
+impl ClusterInfo {
+    /// 解析健康检查器配置
+    pub fn checker(&self) -> HealthChecker {
+        HealthChecker::parse(&self.health_checker)
+    }
+}
+
+/// 健康检查器
+#[derive(Debug, Clone, PartialEq)]
+pub enum HealthChecker {
+    /// 不做主动健康检查，实例健康状态由心跳或手动设置决定
+    None,
+    /// TCP 端口探测
+    Tcp,
+    /// HTTP 探测
+    Http {
+        path: String,
+        headers: String,
+        expected_response_code: u16,
+    },
+}
+
+impl HealthChecker {
+    /// 从 Nacos 的 healthChecker JSON 解析，例如 {"type":"HTTP","path":"/health"}
+    /// 不支持的类型（如 MYSQL）按 NONE 处理
+    pub fn parse(health_checker: &str) -> Self {
+        let value: serde_json::Value = serde_json::from_str(health_checker).unwrap_or_default();
+        let checker_type = value
+            .get("type")
+            .and_then(|v| v.as_str())
+            .unwrap_or("TCP")
+            .to_uppercase();
+
+        match checker_type.as_str() {
+            "TCP" => HealthChecker::Tcp,
+            "HTTP" => HealthChecker::Http {
+                path: value
+                    .get("path")
+                    .and_then(|v| v.as_str())
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or("/")
+                    .to_string(),
+                headers: value
+                    .get("headers")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+                expected_response_code: value
+                    .get("expectedResponseCode")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(200) as u16,
+            },
+            _ => HealthChecker::None,
+        }
+    }
+
+    /// 是否启用了主动健康检查
+    pub fn is_active(&self) -> bool {
+        *self != HealthChecker::None
+    }
+}
+
+/// 更新集群请求
+#[derive(Debug, Deserialize)]
+pub struct UpdateClusterRequest {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub cluster_name: String,
+    pub health_checker: Option<String>,
+    pub check_port: Option<i32>,
+    pub use_instance_port: Option<bool>,
+    pub metadata: Option<String>,
+}
+
+/// 查询服务下的集群列表
+pub async fn get_service_clusters(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<Vec<ClusterInfo>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let rows: Vec<(i64, String, String, String, String, String, i32, bool, Option<String>, i64, i64)> = db
+        .query(
+            "SELECT id, namespace_id, group_name, service_name, cluster_name, health_checker, check_port, use_instance_port, metadata, gmt_create, gmt_modified FROM cluster_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 ORDER BY cluster_name",
+            &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query clusters: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(id, namespace_id, group_name, service_name, cluster_name, health_checker, check_port, use_instance_port, metadata, gmt_create, gmt_modified)| {
+            ClusterInfo {
+                id: Some(id),
+                namespace_id,
+                group_name,
+                service_name,
+                cluster_name,
+                health_checker,
+                check_port,
+                use_instance_port,
+                metadata,
+                gmt_create,
+                gmt_modified,
+            }
+        })
+        .collect())
+}
+
+/// 查询集群详情
+pub async fn get_cluster(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    cluster_name: &str,
+) -> Result<Option<ClusterInfo>, String> {
+    let clusters = get_service_clusters(app, namespace_id, group_name, service_name).await?;
+    Ok(clusters.into_iter().find(|c| c.cluster_name == cluster_name))
+}
+
+/// 自动创建集群的 SQL：?1-?4 为命名空间、分组、服务和集群名称，?5-?8 为默认健康检查配置，?9 为创建时间
+pub(super) const ENSURE_CLUSTER_SQL: &str = "INSERT OR IGNORE INTO cluster_info (namespace_id, group_name, service_name, cluster_name, health_checker, check_port, use_instance_port, metadata, gmt_create, gmt_modified) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9)";
+
+/// 确保集群存在（首次注册实例时自动创建，使用默认健康检查配置）
+pub async fn ensure_cluster(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    cluster_name: &str,
+) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let now = current_timestamp();
+
+    db.execute(
+        ENSURE_CLUSTER_SQL,
+        &[
+            ("?1", namespace_id),
+            ("?2", group_name),
+            ("?3", service_name),
+            ("?4", cluster_name),
+            ("?5", DEFAULT_HEALTH_CHECKER),
+            ("?6", &DEFAULT_CHECK_PORT.to_string()),
+            ("?7", "1"),
+            ("?8", "{}"),
+            ("?9", &now.to_string()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to create cluster: {}", e))?;
+
+    Ok(())
+}
+
+/// 更新集群（集群不存在时按 Nacos 行为自动创建）
+pub async fn update_cluster(
+    app: &AppHandle,
+    request: UpdateClusterRequest,
+) -> Result<ClusterInfo, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    // 检查服务是否存在
+    let service_exists: Option<(i64,)> = db
+        .query_one(
+            "SELECT id FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3",
+            &[
+                ("?1", &request.namespace_id),
+                ("?2", &request.group_name),
+                ("?3", &request.service_name),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to check service: {}", e))?;
+
+    if service_exists.is_none() {
+        return Err("Service not found".to_string());
+    }
+
+    // 校验健康检查器 JSON
+    if let Some(ref health_checker) = request.health_checker {
+        serde_json::from_str::<serde_json::Value>(health_checker)
+            .map_err(|e| format!("Invalid healthChecker: {}", e))?;
+    }
+
+    ensure_cluster(
+        app,
+        &request.namespace_id,
+        &request.group_name,
+        &request.service_name,
+        &request.cluster_name,
+    )
+    .await?;
+
+    let current = get_cluster(
+        app,
+        &request.namespace_id,
+        &request.group_name,
+        &request.service_name,
+        &request.cluster_name,
+    )
+    .await?
+    .ok_or_else(|| "Failed to retrieve cluster".to_string())?;
+
+    let health_checker = request.health_checker.unwrap_or(current.health_checker);
+    let check_port = request.check_port.unwrap_or(current.check_port);
+    let use_instance_port = request.use_instance_port.unwrap_or(current.use_instance_port);
+    let metadata = request.metadata.or(current.metadata).unwrap_or_default();
+    let now = current_timestamp();
+
+    db.execute(
+        "UPDATE cluster_info SET health_checker = ?1, check_port = ?2, use_instance_port = ?3, metadata = ?4, gmt_modified = ?5 WHERE namespace_id = ?6 AND group_name = ?7 AND service_name = ?8 AND cluster_name = ?9",
+        &[
+            ("?1", &health_checker),
+            ("?2", &check_port.to_string()),
+            ("?3", &(if use_instance_port { "1" } else { "0" })),
+            ("?4", &metadata),
+            ("?5", &now.to_string()),
+            ("?6", &request.namespace_id),
+            ("?7", &request.group_name),
+            ("?8", &request.service_name),
+            ("?9", &request.cluster_name),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to update cluster: {}", e))?;
+
+    get_cluster(
+        app,
+        &request.namespace_id,
+        &request.group_name,
+        &request.service_name,
+        &request.cluster_name,
+    )
+    .await?
+    .ok_or_else(|| "Failed to retrieve updated cluster".to_string())
+}
+
+/// 删除服务下的所有集群
+pub async fn delete_service_clusters(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "DELETE FROM cluster_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3",
+        &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete clusters: {}", e))?;
+
+    Ok(())
+}
@@ -0,0 +1,294 @@
+/**
+ * Consul 兼容注册表模块
+ * 把 Consul agent 注册的服务映射到 public 命名空间 DEFAULT_GROUP 分组下的临时实例：
+ * 服务 ID、标签和 TTL 检查 ID 保存在实例元数据中，TTL 检查通过即心跳，
+ * TTL 超时后实例变为不健康（critical），超过 DeregisterCriticalServiceAfter 后被移除
+ */
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use super::{
+    deregister_instance, ensure_service, expire_ephemeral_heartbeat, get_service_instances, get_service_revision, register_instance,
+    touch_ephemeral_instance, update_instance_health, HealthChangeSource, InstanceInfo, RegisterInstanceRequest,
+    HEART_BEAT_TIMEOUT_METADATA_KEY, IP_DELETE_TIMEOUT_METADATA_KEY,
+};
+
+/// Consul 服务所在的命名空间
+pub const CONSUL_NAMESPACE_ID: &str = "public";
+
+/// Consul 服务所在的服务分组
+pub const CONSUL_GROUP_NAME: &str = "DEFAULT_GROUP";
+
+/// 实例元数据中保存 Consul 服务 ID 的键
+pub const CONSUL_SERVICE_ID_METADATA_KEY: &str = "preserved.consul.serviceId";
+
+/// 实例元数据中保存 Consul 标签（JSON 数组）的键
+pub const CONSUL_TAGS_METADATA_KEY: &str = "preserved.consul.tags";
+
+/// 实例元数据中保存 TTL 检查 ID 的键
+pub const CONSUL_CHECK_ID_METADATA_KEY: &str = "preserved.consul.checkId";
+
+/// 检查状态
+pub const CONSUL_CHECK_PASSING: &str = "passing";
+pub const CONSUL_CHECK_CRITICAL: &str = "critical";
+
+/// 不过期（未配置 TTL 或 DeregisterCriticalServiceAfter 时使用）
+const NEVER_EXPIRE_MILLIS: i64 = i64::MAX;
+
+/// Consul TTL 检查
+#[derive(Debug, Clone)]
+pub struct ConsulTtlCheck {
+    pub check_id: String,
+    pub ttl_millis: i64,
+    /// 检查持续 critical 超过该时间后注销服务
+    pub deregister_after_millis: Option<i64>,
+    pub status: String,
+}
+
+/// Consul 服务注册请求
+#[derive(Debug, Clone)]
+pub struct ConsulServiceRegistration {
+    pub service_id: String,
+    pub service_name: String,
+    pub address: String,
+    pub port: i32,
+    pub tags: Vec<String>,
+    pub meta: HashMap<String, String>,
+    pub check: Option<ConsulTtlCheck>,
+}
+
+/// 服务目录索引：服务目录指纹变化时递增
+#[derive(Default)]
+struct ConsulCatalogIndex {
+    fingerprint: String,
+    index: u64,
+}
+
+type ConsulCatalogIndexState = Arc<Mutex<ConsulCatalogIndex>>;
+
+/// 获取或创建服务目录索引状态
+fn get_catalog_index_state(app: &AppHandle) -> ConsulCatalogIndexState {
+    if let Some(state) = app.try_state::<ConsulCatalogIndexState>() {
+        state.inner().clone()
+    } else {
+        let state: ConsulCatalogIndexState = Arc::new(Mutex::new(ConsulCatalogIndex::default()));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 解析实例元数据
+fn instance_metadata(instance: &InstanceInfo) -> HashMap<String, String> {
+    instance
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_default()
+}
+
+/// 实例的 Consul 服务 ID，非 Consul 注册的实例使用 Nacos 实例 ID
+pub fn consul_service_id(instance: &InstanceInfo) -> String {
+    instance_metadata(instance)
+        .remove(CONSUL_SERVICE_ID_METADATA_KEY)
+        .unwrap_or_else(|| instance.instance_id.clone())
+}
+
+/// 实例的 Consul 标签
+pub fn consul_tags(instance: &InstanceInfo) -> Vec<String> {
+    instance_metadata(instance)
+        .get(CONSUL_TAGS_METADATA_KEY)
+        .and_then(|tags| serde_json::from_str(tags).ok())
+        .unwrap_or_default()
+}
+
+/// 实例的 TTL 检查 ID
+pub fn consul_check_id(instance: &InstanceInfo) -> Option<String> {
+    instance_metadata(instance).remove(CONSUL_CHECK_ID_METADATA_KEY)
+}
+
+/// 实例的用户元数据（去掉 preserved. 开头的内部键）
+pub fn consul_user_metadata(instance: &InstanceInfo) -> BTreeMap<String, String> {
+    instance_metadata(instance)
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with("preserved."))
+        .collect()
+}
+
+/// 实例的检查状态：健康且启用时为 passing，否则为 critical
+pub fn consul_check_status(instance: &InstanceInfo) -> &'static str {
+    if instance.healthy && instance.enabled {
+        CONSUL_CHECK_PASSING
+    } else {
+        CONSUL_CHECK_CRITICAL
+    }
+}
+
+/// 查询 Consul 命名空间和分组下的所有服务名
+async fn list_service_names(app: &AppHandle) -> Result<Vec<String>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let services: Vec<(String,)> = db
+        .query(
+            "SELECT service_name FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 ORDER BY service_name",
+            &[("?1", CONSUL_NAMESPACE_ID), ("?2", CONSUL_GROUP_NAME)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query services: {}", e))?;
+
+    Ok(services.into_iter().map(|(service_name,)| service_name).collect())
+}
+
+/// 在所有服务中查找满足条件的实例
+async fn find_instance_where<F>(app: &AppHandle, predicate: F) -> Result<Option<InstanceInfo>, String>
+where
+    F: Fn(&InstanceInfo) -> bool,
+{
+    for service_name in list_service_names(app).await? {
+        let response = get_service_instances(app, CONSUL_NAMESPACE_ID, CONSUL_GROUP_NAME, &service_name).await?;
+        if let Some(instance) = response.instances.into_iter().find(|inst| predicate(inst)) {
+            return Ok(Some(instance));
+        }
+    }
+    Ok(None)
+}
+
+/// 按 Consul 服务 ID 查找实例
+pub async fn find_consul_service(app: &AppHandle, service_id: &str) -> Result<Option<InstanceInfo>, String> {
+    find_instance_where(app, |inst| consul_service_id(inst) == service_id).await
+}
+
+/// 注册 Consul 服务，服务不存在时自动创建
+pub async fn register_consul_service(app: &AppHandle, registration: ConsulServiceRegistration) -> Result<InstanceInfo, String> {
+    ensure_service(app, CONSUL_NAMESPACE_ID, CONSUL_GROUP_NAME, &registration.service_name).await?;
+
+    // 同一服务 ID 改变服务名或地址时先移除旧实例
+    if let Some(existing) = find_consul_service(app, &registration.service_id).await? {
+        if existing.service_name != registration.service_name
+            || existing.ip != registration.address
+            || existing.port != registration.port
+        {
+            deregister_instance(app, CONSUL_NAMESPACE_ID, CONSUL_GROUP_NAME, &existing.service_name, &existing.instance_id).await?;
+        }
+    }
+
+    let mut metadata = registration.meta;
+    metadata.insert(CONSUL_SERVICE_ID_METADATA_KEY.to_string(), registration.service_id);
+    metadata.insert(
+        CONSUL_TAGS_METADATA_KEY.to_string(),
+        serde_json::to_string(&registration.tags).unwrap_or_default(),
+    );
+
+    // 没有 TTL 检查的服务始终健康，直到显式注销
+    let (healthy, beat_timeout, delete_timeout) = match &registration.check {
+        Some(check) => {
+            metadata.insert(CONSUL_CHECK_ID_METADATA_KEY.to_string(), check.check_id.clone());
+            let delete_timeout = check
+                .deregister_after_millis
+                .map(|after| after.saturating_add(check.ttl_millis))
+                .unwrap_or(NEVER_EXPIRE_MILLIS);
+            (check.status == CONSUL_CHECK_PASSING, check.ttl_millis, delete_timeout)
+        }
+        None => (true, NEVER_EXPIRE_MILLIS, NEVER_EXPIRE_MILLIS),
+    };
+    metadata.insert(HEART_BEAT_TIMEOUT_METADATA_KEY.to_string(), beat_timeout.to_string());
+    metadata.insert(IP_DELETE_TIMEOUT_METADATA_KEY.to_string(), delete_timeout.to_string());
+
+    register_instance(
+        app,
+        RegisterInstanceRequest {
+            namespace_id: CONSUL_NAMESPACE_ID.to_string(),
+            group_name: CONSUL_GROUP_NAME.to_string(),
+            service_name: registration.service_name,
+            ip: registration.address,
+            port: registration.port,
+            weight: None,
+            healthy: Some(healthy),
+            enabled: Some(true),
+            ephemeral: Some(true),
+            cluster_name: None,
+            metadata: Some(serde_json::to_string(&metadata).unwrap_or_default()),
+        },
+    )
+    .await
+}
+
+/// 注销 Consul 服务，返回服务是否存在
+pub async fn deregister_consul_service(app: &AppHandle, service_id: &str) -> Result<bool, String> {
+    match find_consul_service(app, service_id).await? {
+        Some(instance) => {
+            deregister_instance(app, &instance.namespace_id, &instance.group_name, &instance.service_name, &instance.instance_id).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// 更新 TTL 检查状态，返回检查是否存在
+/// 检查通过时刷新 TTL；检查失败时实例立即变为 critical，DeregisterCriticalServiceAfter 从此刻开始计时
+pub async fn update_consul_check(app: &AppHandle, check_id: &str, passing: bool) -> Result<bool, String> {
+    let Some(instance) = find_instance_where(app, |inst| consul_check_id(inst).as_deref() == Some(check_id)).await? else {
+        return Ok(false);
+    };
+
+    if passing {
+        touch_ephemeral_instance(app, &instance.namespace_id, &instance.group_name, &instance.service_name, &instance.instance_id).await;
+    } else {
+        expire_ephemeral_heartbeat(app, &instance.namespace_id, &instance.group_name, &instance.service_name, &instance.instance_id).await;
+    }
+    if instance.healthy != passing {
+        update_instance_health(
+            app,
+            &instance.namespace_id,
+            &instance.group_name,
+            &instance.service_name,
+            &instance.instance_id,
+            passing,
+            HealthChangeSource::HealthCheck,
+            if passing { "consul TTL check passing" } else { "consul TTL check failing" },
+        )
+        .await?;
+    }
+    Ok(true)
+}
+
+/// 查询服务实例，服务不存在时返回空列表
+pub async fn get_consul_service_instances(app: &AppHandle, service_name: &str) -> Result<Vec<InstanceInfo>, String> {
+    if !list_service_names(app).await?.iter().any(|name| name == service_name) {
+        return Ok(Vec::new());
+    }
+    Ok(get_service_instances(app, CONSUL_NAMESPACE_ID, CONSUL_GROUP_NAME, service_name)
+        .await?
+        .instances)
+}
+
+/// 服务的阻塞查询索引（服务修订号加一，保证大于 0）
+pub async fn get_consul_service_index(app: &AppHandle, service_name: &str) -> u64 {
+    get_service_revision(app, CONSUL_NAMESPACE_ID, CONSUL_GROUP_NAME, service_name).await.revision + 1
+}
+
+/// 查询服务目录（服务名 -> 标签），同时返回目录的阻塞查询索引
+pub async fn get_consul_catalog_services(app: &AppHandle) -> Result<(u64, BTreeMap<String, Vec<String>>), String> {
+    let mut services = BTreeMap::new();
+    for service_name in list_service_names(app).await? {
+        let response = get_service_instances(app, CONSUL_NAMESPACE_ID, CONSUL_GROUP_NAME, &service_name).await?;
+        let tags: BTreeSet<String> = response.instances.iter().flat_map(consul_tags).collect();
+        services.insert(service_name, tags.into_iter().collect::<Vec<_>>());
+    }
+
+    let fingerprint = serde_json::to_string(&services).unwrap_or_default();
+    let state = get_catalog_index_state(app);
+    let mut catalog_index = state.lock().await;
+    if catalog_index.index == 0 || catalog_index.fingerprint != fingerprint {
+        catalog_index.index += 1;
+        catalog_index.fingerprint = fingerprint;
+    }
+    Ok((catalog_index.index, services))
+}
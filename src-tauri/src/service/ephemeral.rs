@@ -0,0 +1,440 @@
+/**
+ * 临时实例内存注册表
+ * 临时实例（ephemeral=true）只保存在内存中，由心跳维持，服务重启后清空；
+ * 持久化实例仍然保存在 instance_info 表中
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+use super::revision::service_key;
+use super::health_timeline::{record_health_event, HealthChangeSource};
+use super::history::{instance_address, record_instance_history, ServiceChangeType};
+use super::{bump_service_revision, current_millis, current_timestamp, get_naming_switches, InstanceInfo};
+
+/// 默认心跳超时时间与心跳周期的倍数，超时后实例被标记为不健康
+const HEART_BEAT_TIMEOUT_MULTIPLIER: i64 = 3;
+
+/// 默认实例删除超时时间与心跳周期的倍数，超时后实例被移除
+const IP_DELETE_TIMEOUT_MULTIPLIER: i64 = 6;
+
+/// 实例元数据中自定义心跳周期的键
+pub const HEART_BEAT_INTERVAL_METADATA_KEY: &str = "preserved.heart.beat.interval";
+
+/// 实例元数据中自定义心跳超时时间的键
+pub const HEART_BEAT_TIMEOUT_METADATA_KEY: &str = "preserved.heart.beat.timeout";
+
+/// 实例元数据中自定义删除超时时间的键
+pub const IP_DELETE_TIMEOUT_METADATA_KEY: &str = "preserved.ip.delete.timeout";
+
+/// 过期检查周期（秒）
+const EXPIRY_CHECK_INTERVAL_SECONDS: u64 = 5;
+
+/// 内存中的临时实例
+#[derive(Debug, Clone)]
+struct EphemeralEntry {
+    instance: InstanceInfo,
+    /// 最后一次心跳时间（毫秒）
+    last_beat_millis: i64,
+}
+
+/// 临时实例注册表（服务键 -> 实例列表）
+type EphemeralRegistry = Arc<RwLock<HashMap<String, Vec<EphemeralEntry>>>>;
+
+/// 注册临时实例注册表，应用启动时调用一次
+pub fn manage_ephemeral_registry(app: &AppHandle) {
+    let state: EphemeralRegistry = Arc::new(RwLock::new(HashMap::new()));
+    app.manage(state);
+}
+
+/// 获取临时实例注册表
+fn get_registry(app: &AppHandle) -> EphemeralRegistry {
+    app.state::<EphemeralRegistry>().inner().clone()
+}
+
+/// 从实例元数据中读取超时时间（毫秒），支持字符串和数字两种写法
+fn metadata_timeout(instance: &InstanceInfo, key: &str, default: i64) -> i64 {
+    instance
+        .metadata
+        .as_deref()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .and_then(|metadata| match metadata.get(key) {
+            Some(serde_json::Value::String(s)) => s.parse::<i64>().ok(),
+            Some(value) => value.as_i64(),
+            None => None,
+        })
+        .filter(|timeout| *timeout > 0)
+        .unwrap_or(default)
+}
+
+/// 实例的心跳配置（毫秒）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BeatSettings {
+    pub interval: i64,
+    pub heart_beat_timeout: i64,
+    pub ip_delete_timeout: i64,
+}
+
+impl BeatSettings {
+    /// 按心跳周期开关计算实例的心跳配置，实例元数据中的自定义值优先
+    /// 未自定义超时时，心跳超时为心跳周期的 3 倍，删除超时为 6 倍（默认周期 5 秒对应 15 秒和 30 秒）
+    pub fn for_instance(instance: &InstanceInfo, client_beat_interval: i64) -> Self {
+        let interval = metadata_timeout(instance, HEART_BEAT_INTERVAL_METADATA_KEY, client_beat_interval);
+        BeatSettings {
+            interval,
+            heart_beat_timeout: metadata_timeout(instance, HEART_BEAT_TIMEOUT_METADATA_KEY, interval * HEART_BEAT_TIMEOUT_MULTIPLIER),
+            ip_delete_timeout: metadata_timeout(instance, IP_DELETE_TIMEOUT_METADATA_KEY, interval * IP_DELETE_TIMEOUT_MULTIPLIER),
+        }
+    }
+}
+
+/// 写入临时实例（IP、端口、集群相同时覆盖，保留原实例 ID 和创建时间）
+pub async fn upsert_ephemeral_instance(app: &AppHandle, mut instance: InstanceInfo) -> InstanceInfo {
+    let registry = get_registry(app);
+    let mut services = registry.write().await;
+    let entries = services
+        .entry(service_key(&instance.namespace_id, &instance.group_name, &instance.service_name))
+        .or_default();
+
+    let now = current_millis();
+    match entries.iter_mut().find(|e| {
+        e.instance.ip == instance.ip && e.instance.port == instance.port && e.instance.cluster_name == instance.cluster_name
+    }) {
+        Some(entry) => {
+            instance.instance_id = entry.instance.instance_id.clone();
+            instance.gmt_create = entry.instance.gmt_create;
+            entry.instance = instance.clone();
+            entry.last_beat_millis = now;
+        }
+        None => entries.push(EphemeralEntry { instance: instance.clone(), last_beat_millis: now }),
+    }
+
+    instance
+}
+
+/// 查询服务下的临时实例
+pub async fn get_ephemeral_instances(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Vec<InstanceInfo> {
+    let registry = get_registry(app);
+    let services = registry.read().await;
+    services
+        .get(&service_key(namespace_id, group_name, service_name))
+        .map(|entries| entries.iter().map(|e| e.instance.clone()).collect())
+        .unwrap_or_default()
+}
+
+/// 查询所有临时实例
+pub async fn all_ephemeral_instances(app: &AppHandle) -> Vec<InstanceInfo> {
+    let registry = get_registry(app);
+    let services = registry.read().await;
+    services
+        .values()
+        .flat_map(|entries| entries.iter().map(|e| e.instance.clone()))
+        .collect()
+}
+
+/// 按 IP 查询所有服务下的临时实例，附带最后一次心跳时间（毫秒）
+pub async fn ephemeral_instances_by_ip(app: &AppHandle, ip: &str) -> Vec<(InstanceInfo, i64)> {
+    let registry = get_registry(app);
+    let services = registry.read().await;
+    services
+        .values()
+        .flat_map(|entries| entries.iter())
+        .filter(|e| e.instance.ip == ip)
+        .map(|e| (e.instance.clone(), e.last_beat_millis))
+        .collect()
+}
+
+/// 按实例 ID 修改临时实例，返回修改后的实例（不存在时返回 None）
+pub async fn update_ephemeral_instance<F>(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instance_id: &str,
+    update: F,
+) -> Option<InstanceInfo>
+where
+    F: FnOnce(&mut InstanceInfo),
+{
+    let registry = get_registry(app);
+    let mut services = registry.write().await;
+    let entry = services
+        .get_mut(&service_key(namespace_id, group_name, service_name))?
+        .iter_mut()
+        .find(|e| e.instance.instance_id == instance_id)?;
+
+    update(&mut entry.instance);
+    entry.instance.gmt_modified = current_timestamp();
+    Some(entry.instance.clone())
+}
+
+/// 按实例 ID 移除临时实例，返回被移除的实例（不存在时返回 None）
+pub async fn remove_ephemeral_instance(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instance_id: &str,
+) -> Option<InstanceInfo> {
+    let registry = get_registry(app);
+    let mut services = registry.write().await;
+    let key = service_key(namespace_id, group_name, service_name);
+    let entries = services.get_mut(&key)?;
+
+    let index = entries.iter().position(|e| e.instance.instance_id == instance_id)?;
+    let removed = entries.remove(index).instance;
+    if entries.is_empty() {
+        services.remove(&key);
+    }
+    Some(removed)
+}
+
+/// 移除服务下的所有临时实例
+pub async fn remove_service_ephemeral_instances(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) {
+    let registry = get_registry(app);
+    registry
+        .write()
+        .await
+        .remove(&service_key(namespace_id, group_name, service_name));
+}
+
+/// 移除命名空间下的所有临时实例
+pub async fn remove_namespace_ephemeral_instances(app: &AppHandle, namespace_id: &str) {
+    let registry = get_registry(app);
+    registry
+        .write()
+        .await
+        .retain(|_, entries| entries.first().is_some_and(|e| e.instance.namespace_id != namespace_id));
+}
+
+/// 刷新临时实例心跳，心跳表示实例健康；返回实例是否存在
+pub async fn beat_ephemeral_instance(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    ip: &str,
+    port: i32,
+    cluster_name: &str,
+) -> bool {
+    let registry = get_registry(app);
+    let became_healthy = {
+        let mut services = registry.write().await;
+        let entry = services
+            .get_mut(&service_key(namespace_id, group_name, service_name))
+            .and_then(|entries| {
+                entries
+                    .iter_mut()
+                    .find(|e| e.instance.ip == ip && e.instance.port == port && e.instance.cluster_name == cluster_name)
+            });
+
+        let Some(entry) = entry else {
+            return false;
+        };
+
+        entry.last_beat_millis = current_millis();
+        if entry.instance.healthy {
+            false
+        } else {
+            entry.instance.healthy = true;
+            entry.instance.gmt_modified = current_timestamp();
+            true
+        }
+    };
+
+    if became_healthy {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+        record_instance_history(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            ServiceChangeType::Health,
+            &instance_address(ip, port, cluster_name),
+            "became healthy",
+        )
+        .await;
+        record_health_event(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &instance_address(ip, port, cluster_name),
+            true,
+            HealthChangeSource::Heartbeat,
+            "heartbeat resumed",
+        )
+        .await;
+    }
+    true
+}
+
+/// 按实例 ID 刷新临时实例心跳时间，不修改健康状态；返回刷新后的实例（不存在时返回 None）
+pub async fn touch_ephemeral_instance(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instance_id: &str,
+) -> Option<InstanceInfo> {
+    let registry = get_registry(app);
+    let mut services = registry.write().await;
+    let entry = services
+        .get_mut(&service_key(namespace_id, group_name, service_name))?
+        .iter_mut()
+        .find(|e| e.instance.instance_id == instance_id)?;
+
+    entry.last_beat_millis = current_millis();
+    Some(entry.instance.clone())
+}
+
+/// 把临时实例的心跳视为刚刚超时（外部检查失败时调用），删除超时从现在开始计算；
+/// 已经不健康的实例保持原来的计时，返回实例是否存在
+pub async fn expire_ephemeral_heartbeat(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instance_id: &str,
+) -> bool {
+    let client_beat_interval = get_naming_switches(app).await.client_beat_interval;
+    let registry = get_registry(app);
+    let mut services = registry.write().await;
+    let Some(entry) = services
+        .get_mut(&service_key(namespace_id, group_name, service_name))
+        .and_then(|entries| entries.iter_mut().find(|e| e.instance.instance_id == instance_id))
+    else {
+        return false;
+    };
+
+    if entry.instance.healthy {
+        let beat = BeatSettings::for_instance(&entry.instance, client_beat_interval);
+        entry.last_beat_millis = current_millis().saturating_sub(beat.heart_beat_timeout);
+    }
+    true
+}
+
+/// 清理旧版本写入 instance_info 的临时实例（临时实例不再持久化）
+pub async fn purge_persisted_ephemeral_instances(app: &AppHandle) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute("DELETE FROM instance_info WHERE ephemeral = 1", &[])
+        .await
+        .map_err(|e| format!("Failed to purge ephemeral instances: {}", e))?;
+
+    Ok(())
+}
+
+/// 启动临时实例过期检查任务
+pub fn start_ephemeral_expiry_task(app: AppHandle) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(EXPIRY_CHECK_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+            run_ephemeral_expiry_round(&app).await;
+        }
+    })
+}
+
+/// 执行一轮过期检查：心跳超时的实例标记为不健康，超过删除超时的实例被移除
+pub async fn run_ephemeral_expiry_round(app: &AppHandle) {
+    // 健康检查开关关闭时不处理心跳超时
+    let switches = get_naming_switches(app).await;
+    if !switches.health_check_enabled {
+        return;
+    }
+
+    let registry = get_registry(app);
+    let now = current_millis();
+    let mut changed_services = Vec::new();
+    let mut changes: Vec<(InstanceInfo, ServiceChangeType, &str)> = Vec::new();
+
+    {
+        let mut services = registry.write().await;
+        for entries in services.values_mut() {
+            let Some(service) = entries.first().map(|e| {
+                (e.instance.namespace_id.clone(), e.instance.group_name.clone(), e.instance.service_name.clone())
+            }) else {
+                continue;
+            };
+            let mut changed = false;
+
+            entries.retain_mut(|entry| {
+                let elapsed = now - entry.last_beat_millis;
+                let beat = BeatSettings::for_instance(&entry.instance, switches.client_beat_interval);
+                if elapsed > beat.ip_delete_timeout {
+                    tracing::info!("Ephemeral instance {} expired, removed", entry.instance.instance_id);
+                    changes.push((entry.instance.clone(), ServiceChangeType::Deregister, "expired"));
+                    changed = true;
+                    return false;
+                }
+                if entry.instance.healthy && elapsed > beat.heart_beat_timeout {
+                    tracing::info!("Ephemeral instance {} heartbeat timeout, marked unhealthy", entry.instance.instance_id);
+                    entry.instance.healthy = false;
+                    entry.instance.gmt_modified = current_timestamp();
+                    changes.push((entry.instance.clone(), ServiceChangeType::Health, "became unhealthy (heartbeat timeout)"));
+                    changed = true;
+                }
+                true
+            });
+
+            if changed {
+                changed_services.push(service);
+            }
+        }
+        services.retain(|_, entries| !entries.is_empty());
+    }
+
+    for (namespace_id, group_name, service_name) in changed_services {
+        bump_service_revision(app, &namespace_id, &group_name, &service_name).await;
+    }
+
+    for (instance, change_type, action) in changes {
+        let address = instance_address(&instance.ip, instance.port, &instance.cluster_name);
+        record_instance_history(
+            app,
+            &instance.namespace_id,
+            &instance.group_name,
+            &instance.service_name,
+            change_type,
+            &address,
+            action,
+        )
+        .await;
+        let (source, reason) = if change_type == ServiceChangeType::Deregister {
+            (HealthChangeSource::Deregister, "expired (heartbeat timeout)")
+        } else {
+            (HealthChangeSource::Heartbeat, "heartbeat timeout")
+        };
+        record_health_event(
+            app,
+            &instance.namespace_id,
+            &instance.group_name,
+            &instance.service_name,
+            &address,
+            false,
+            source,
+            reason,
+        )
+        .await;
+    }
+}
@@ -0,0 +1,538 @@
+/**
+ * Eureka 兼容注册表模块
+ * 把 Eureka 应用和实例映射到 public 命名空间 DEFAULT_GROUP 分组下的服务和临时实例：
+ * 应用名即服务名，续约即心跳，Eureka 专有字段保存在实例元数据中；
+ * 增量拉取通过对比注册表快照得到最近变化的实例，与 Eureka 服务端一样保留 3 分钟
+ */
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use super::{
+    current_millis, deregister_instance, ensure_service, get_service_instances, patch_instance, register_instance,
+    touch_ephemeral_instance, update_instance_health, HealthChangeSource, InstanceInfo, RegisterInstanceRequest,
+    HEART_BEAT_TIMEOUT_METADATA_KEY, IP_DELETE_TIMEOUT_METADATA_KEY,
+};
+
+/// Eureka 应用所在的命名空间
+pub const EUREKA_NAMESPACE_ID: &str = "public";
+
+/// Eureka 应用所在的服务分组
+pub const EUREKA_GROUP_NAME: &str = "DEFAULT_GROUP";
+
+/// 实例元数据中保存 Eureka 实例 ID 的键
+pub const EUREKA_INSTANCE_ID_METADATA_KEY: &str = "preserved.eureka.instanceId";
+
+/// 实例元数据中保存 Eureka 注册信息（JSON）的键
+pub const EUREKA_INSTANCE_INFO_METADATA_KEY: &str = "preserved.eureka.instanceInfo";
+
+/// 实例元数据中保存 Eureka 覆盖状态的键
+pub const EUREKA_OVERRIDDEN_STATUS_METADATA_KEY: &str = "preserved.eureka.overriddenStatus";
+
+/// Eureka 实例状态
+pub const EUREKA_STATUS_UP: &str = "UP";
+pub const EUREKA_STATUS_DOWN: &str = "DOWN";
+pub const EUREKA_STATUS_STARTING: &str = "STARTING";
+pub const EUREKA_STATUS_OUT_OF_SERVICE: &str = "OUT_OF_SERVICE";
+pub const EUREKA_STATUS_UNKNOWN: &str = "UNKNOWN";
+
+/// 增量拉取中的变化类型
+pub const EUREKA_ACTION_ADDED: &str = "ADDED";
+pub const EUREKA_ACTION_MODIFIED: &str = "MODIFIED";
+pub const EUREKA_ACTION_DELETED: &str = "DELETED";
+
+/// 默认租约时长（秒），超过该时间未续约的实例被移除
+pub const DEFAULT_EUREKA_LEASE_DURATION_SECONDS: i64 = 90;
+
+/// 默认续约间隔（秒）
+pub const DEFAULT_EUREKA_RENEWAL_INTERVAL_SECONDS: i64 = 30;
+
+/// 增量变化保留时间（毫秒），对应 Eureka retentionTimeInMSInDeltaQueue
+const DELTA_RETENTION_MILLIS: i64 = 180_000;
+
+/// Eureka 注册请求
+#[derive(Debug, Clone)]
+pub struct EurekaRegistration {
+    pub app_name: String,
+    pub instance_id: String,
+    pub ip: String,
+    pub port: i32,
+    pub status: String,
+    pub lease_duration_seconds: i64,
+    /// 用户元数据
+    pub metadata: HashMap<String, String>,
+    /// 其余 Eureka 注册信息（主机名、VIP 地址、数据中心等），查询时原样返回
+    pub info: serde_json::Value,
+}
+
+/// Eureka 应用
+#[derive(Debug, Clone)]
+pub struct EurekaApplication {
+    /// 应用名（服务名转大写）
+    pub name: String,
+    pub instances: Vec<InstanceInfo>,
+}
+
+/// 增量变化
+#[derive(Debug, Clone)]
+pub struct EurekaInstanceChange {
+    pub action_type: &'static str,
+    pub instance: InstanceInfo,
+}
+
+/// 增量拉取结果
+#[derive(Debug, Clone)]
+pub struct EurekaDelta {
+    pub version: i64,
+    /// 合并增量后完整注册表的哈希码，客户端据此判断是否需要全量拉取
+    pub apps_hashcode: String,
+    pub changes: Vec<EurekaInstanceChange>,
+}
+
+/// 增量跟踪状态：上次拉取时的注册表快照和最近的变化
+#[derive(Default)]
+struct EurekaDeltaTracker {
+    initialized: bool,
+    version: i64,
+    /// 实例键 -> (指纹, 实例)
+    snapshot: HashMap<String, (String, InstanceInfo)>,
+    /// (变化时间毫秒, 变化)
+    recent: VecDeque<(i64, EurekaInstanceChange)>,
+}
+
+type EurekaDeltaState = Arc<Mutex<EurekaDeltaTracker>>;
+
+/// 获取或创建增量跟踪状态
+fn get_delta_tracker(app: &AppHandle) -> EurekaDeltaState {
+    if let Some(state) = app.try_state::<EurekaDeltaState>() {
+        state.inner().clone()
+    } else {
+        let state: EurekaDeltaState = Arc::new(Mutex::new(EurekaDeltaTracker::default()));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 解析实例元数据
+fn instance_metadata(instance: &InstanceInfo) -> HashMap<String, String> {
+    instance
+        .metadata
+        .as_deref()
+        .and_then(|m| serde_json::from_str(m).ok())
+        .unwrap_or_default()
+}
+
+/// 读取实例保存的 Eureka 注册信息，非 Eureka 注册的实例返回空对象
+pub fn eureka_instance_info(instance: &InstanceInfo) -> serde_json::Value {
+    instance_metadata(instance)
+        .get(EUREKA_INSTANCE_INFO_METADATA_KEY)
+        .and_then(|info| serde_json::from_str::<serde_json::Value>(info).ok())
+        .filter(|info| info.is_object())
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+/// 实例的用户元数据（去掉 preserved. 开头的内部键）
+pub fn eureka_user_metadata(instance: &InstanceInfo) -> BTreeMap<String, String> {
+    instance_metadata(instance)
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with("preserved."))
+        .collect()
+}
+
+/// 实例的 Eureka 实例 ID，非 Eureka 注册的实例使用 Nacos 实例 ID
+pub fn eureka_instance_id(instance: &InstanceInfo) -> String {
+    instance_metadata(instance)
+        .remove(EUREKA_INSTANCE_ID_METADATA_KEY)
+        .unwrap_or_else(|| instance.instance_id.clone())
+}
+
+/// 实例的 Eureka 覆盖状态，未设置时为 UNKNOWN
+pub fn eureka_overridden_status(instance: &InstanceInfo) -> String {
+    instance_metadata(instance)
+        .remove(EUREKA_OVERRIDDEN_STATUS_METADATA_KEY)
+        .unwrap_or_else(|| EUREKA_STATUS_UNKNOWN.to_string())
+}
+
+/// 实例的 Eureka 状态：覆盖状态优先，其次由启用和健康状态决定
+pub fn eureka_status(instance: &InstanceInfo) -> String {
+    let overridden = eureka_overridden_status(instance);
+    if overridden != EUREKA_STATUS_UNKNOWN {
+        return overridden;
+    }
+    if !instance.enabled {
+        return EUREKA_STATUS_OUT_OF_SERVICE.to_string();
+    }
+    if !instance.healthy {
+        let registered = eureka_instance_info(instance)
+            .get("status")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_uppercase());
+        return if registered.as_deref() == Some(EUREKA_STATUS_STARTING) {
+            EUREKA_STATUS_STARTING.to_string()
+        } else {
+            EUREKA_STATUS_DOWN.to_string()
+        };
+    }
+    EUREKA_STATUS_UP.to_string()
+}
+
+/// 计算注册表哈希码（按状态名排序的 "状态_数量_" 拼接，与 Eureka 一致）
+pub fn eureka_apps_hashcode<'a>(instances: impl IntoIterator<Item = &'a InstanceInfo>) -> String {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for instance in instances {
+        *counts.entry(eureka_status(instance)).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(status, count)| format!("{}_{}_", status, count))
+        .collect()
+}
+
+/// 状态对应的健康和启用状态
+fn status_to_health(status: &str) -> (bool, bool) {
+    (status == EUREKA_STATUS_UP, status != EUREKA_STATUS_OUT_OF_SERVICE)
+}
+
+/// 按应用名查找服务名（不区分大小写）
+pub async fn resolve_eureka_service_name(app: &AppHandle, app_name: &str) -> Result<Option<String>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let service: Option<(String,)> = db
+        .query_one(
+            "SELECT service_name FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 COLLATE NOCASE ORDER BY service_name = ?3 DESC LIMIT 1",
+            &[("?1", EUREKA_NAMESPACE_ID), ("?2", EUREKA_GROUP_NAME), ("?3", app_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query service: {}", e))?;
+
+    Ok(service.map(|(service_name,)| service_name))
+}
+
+/// 在服务中按 Eureka 实例 ID 查找实例
+async fn find_instance_in_service(
+    app: &AppHandle,
+    service_name: &str,
+    instance_id: &str,
+) -> Result<Option<InstanceInfo>, String> {
+    let response = get_service_instances(app, EUREKA_NAMESPACE_ID, EUREKA_GROUP_NAME, service_name).await?;
+    Ok(response
+        .instances
+        .into_iter()
+        .find(|inst| eureka_instance_id(inst) == instance_id || inst.instance_id == instance_id))
+}
+
+/// 按应用名和 Eureka 实例 ID 查找实例
+pub async fn find_eureka_instance(
+    app: &AppHandle,
+    app_name: &str,
+    instance_id: &str,
+) -> Result<Option<InstanceInfo>, String> {
+    match resolve_eureka_service_name(app, app_name).await? {
+        Some(service_name) => find_instance_in_service(app, &service_name, instance_id).await,
+        None => Ok(None),
+    }
+}
+
+/// 注册 Eureka 实例，应用不存在时自动创建服务
+pub async fn register_eureka_instance(app: &AppHandle, registration: EurekaRegistration) -> Result<InstanceInfo, String> {
+    let service_name = match resolve_eureka_service_name(app, &registration.app_name).await? {
+        Some(service_name) => service_name,
+        None => {
+            ensure_service(app, EUREKA_NAMESPACE_ID, EUREKA_GROUP_NAME, &registration.app_name).await?;
+            registration.app_name.clone()
+        }
+    };
+
+    // 同一实例重新注册时保留服务端设置的覆盖状态；地址变化时先移除旧实例
+    let existing = find_instance_in_service(app, &service_name, &registration.instance_id).await?;
+    let mut overridden_status = None;
+    if let Some(existing) = &existing {
+        overridden_status = Some(eureka_overridden_status(existing)).filter(|s| s != EUREKA_STATUS_UNKNOWN);
+        if existing.ip != registration.ip || existing.port != registration.port {
+            deregister_instance(app, EUREKA_NAMESPACE_ID, EUREKA_GROUP_NAME, &service_name, &existing.instance_id).await?;
+        }
+    }
+
+    let lease_millis = (registration.lease_duration_seconds.max(1) * 1000).to_string();
+    let mut metadata = registration.metadata;
+    metadata.insert(EUREKA_INSTANCE_ID_METADATA_KEY.to_string(), registration.instance_id.clone());
+    metadata.insert(EUREKA_INSTANCE_INFO_METADATA_KEY.to_string(), registration.info.to_string());
+    metadata.insert(HEART_BEAT_TIMEOUT_METADATA_KEY.to_string(), lease_millis.clone());
+    metadata.insert(IP_DELETE_TIMEOUT_METADATA_KEY.to_string(), lease_millis);
+    if let Some(status) = &overridden_status {
+        metadata.insert(EUREKA_OVERRIDDEN_STATUS_METADATA_KEY.to_string(), status.clone());
+    }
+
+    let effective_status = overridden_status.unwrap_or_else(|| registration.status.clone());
+    let (healthy, enabled) = status_to_health(&effective_status);
+
+    register_instance(
+        app,
+        RegisterInstanceRequest {
+            namespace_id: EUREKA_NAMESPACE_ID.to_string(),
+            group_name: EUREKA_GROUP_NAME.to_string(),
+            service_name,
+            ip: registration.ip,
+            port: registration.port,
+            weight: None,
+            healthy: Some(healthy),
+            enabled: Some(enabled),
+            ephemeral: Some(true),
+            cluster_name: None,
+            metadata: Some(serde_json::to_string(&metadata).unwrap_or_default()),
+        },
+    )
+    .await
+}
+
+/// 注销 Eureka 实例，返回实例是否存在
+pub async fn cancel_eureka_instance(app: &AppHandle, app_name: &str, instance_id: &str) -> Result<bool, String> {
+    match find_eureka_instance(app, app_name, instance_id).await? {
+        Some(instance) => {
+            deregister_instance(app, EUREKA_NAMESPACE_ID, EUREKA_GROUP_NAME, &instance.service_name, &instance.instance_id).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// 续约 Eureka 实例（相当于心跳），返回实例是否存在
+/// 客户端携带的 lastDirtyTimestamp 比服务端新时按不存在处理，促使客户端重新注册
+pub async fn renew_eureka_instance(
+    app: &AppHandle,
+    app_name: &str,
+    instance_id: &str,
+    last_dirty_timestamp: Option<i64>,
+) -> Result<bool, String> {
+    let Some(instance) = find_eureka_instance(app, app_name, instance_id).await? else {
+        return Ok(false);
+    };
+
+    if let Some(client_dirty) = last_dirty_timestamp {
+        let info = eureka_instance_info(&instance);
+        let server_dirty = info.get("lastDirtyTimestamp").and_then(|v| match v {
+            serde_json::Value::String(s) => s.parse::<i64>().ok(),
+            value => value.as_i64(),
+        });
+        if server_dirty.is_some_and(|server_dirty| client_dirty > server_dirty) {
+            return Ok(false);
+        }
+    }
+
+    if !instance.ephemeral {
+        return Ok(true);
+    }
+
+    let Some(renewed) = touch_ephemeral_instance(
+        app,
+        &instance.namespace_id,
+        &instance.group_name,
+        &instance.service_name,
+        &instance.instance_id,
+    )
+    .await
+    else {
+        return Ok(false);
+    };
+
+    // 心跳超时被标记为不健康的实例，续约后恢复为注册时的状态
+    let (healthy, _) = status_to_health(&match eureka_overridden_status(&renewed).as_str() {
+        EUREKA_STATUS_UNKNOWN => eureka_instance_info(&renewed)
+            .get("status")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_uppercase())
+            .unwrap_or_else(|| EUREKA_STATUS_UP.to_string()),
+        overridden => overridden.to_string(),
+    });
+    if healthy && !renewed.healthy {
+        update_instance_health(
+            app,
+            &renewed.namespace_id,
+            &renewed.group_name,
+            &renewed.service_name,
+            &renewed.instance_id,
+            true,
+            HealthChangeSource::Heartbeat,
+            "eureka lease renewed",
+        )
+        .await?;
+    }
+    Ok(true)
+}
+
+/// 设置或清除 Eureka 覆盖状态，返回实例是否存在
+/// status 为 None 时清除覆盖状态并恢复为 restore_status
+pub async fn update_eureka_status_override(
+    app: &AppHandle,
+    app_name: &str,
+    instance_id: &str,
+    status: Option<&str>,
+    restore_status: &str,
+) -> Result<bool, String> {
+    let Some(instance) = find_eureka_instance(app, app_name, instance_id).await? else {
+        return Ok(false);
+    };
+
+    let overridden = status.unwrap_or(EUREKA_STATUS_UNKNOWN).to_uppercase();
+    let effective = status.map(|s| s.to_uppercase()).unwrap_or_else(|| restore_status.to_uppercase());
+    let (healthy, enabled) = status_to_health(&effective);
+
+    let mut patch = HashMap::new();
+    patch.insert(EUREKA_OVERRIDDEN_STATUS_METADATA_KEY.to_string(), overridden);
+
+    patch_instance(
+        app,
+        &instance.namespace_id,
+        &instance.group_name,
+        &instance.service_name,
+        &instance.instance_id,
+        None,
+        Some(enabled),
+        Some(healthy),
+        Some(serde_json::to_string(&patch).unwrap_or_default()),
+    )
+    .await?;
+    Ok(true)
+}
+
+/// 查询所有 Eureka 应用（只包含有实例的服务），同时刷新增量跟踪快照
+pub async fn get_eureka_applications(app: &AppHandle) -> Result<Vec<EurekaApplication>, String> {
+    let applications = load_applications(app).await?;
+    refresh_delta(app, &applications).await;
+    Ok(applications)
+}
+
+/// 查询单个 Eureka 应用，应用不存在或没有实例时返回 None
+pub async fn get_eureka_application(app: &AppHandle, app_name: &str) -> Result<Option<EurekaApplication>, String> {
+    let Some(service_name) = resolve_eureka_service_name(app, app_name).await? else {
+        return Ok(None);
+    };
+    let response = get_service_instances(app, EUREKA_NAMESPACE_ID, EUREKA_GROUP_NAME, &service_name).await?;
+    if response.instances.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(EurekaApplication {
+        name: service_name.to_uppercase(),
+        instances: response.instances,
+    }))
+}
+
+/// 增量拉取：返回最近 3 分钟内新增、修改和删除的实例
+pub async fn get_eureka_delta(app: &AppHandle) -> Result<EurekaDelta, String> {
+    let applications = load_applications(app).await?;
+    let version = refresh_delta(app, &applications).await;
+
+    let tracker = get_delta_tracker(app);
+    let tracker = tracker.lock().await;
+    Ok(EurekaDelta {
+        version,
+        apps_hashcode: eureka_apps_hashcode(applications.iter().flat_map(|a| a.instances.iter())),
+        changes: tracker.recent.iter().map(|(_, change)| change.clone()).collect(),
+    })
+}
+
+/// 读取 Eureka 命名空间和分组下的所有服务及实例
+async fn load_applications(app: &AppHandle) -> Result<Vec<EurekaApplication>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let services: Vec<(String,)> = db
+        .query(
+            "SELECT service_name FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 ORDER BY service_name",
+            &[("?1", EUREKA_NAMESPACE_ID), ("?2", EUREKA_GROUP_NAME)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query services: {}", e))?;
+
+    let mut applications = Vec::new();
+    for (service_name,) in services {
+        let response = get_service_instances(app, EUREKA_NAMESPACE_ID, EUREKA_GROUP_NAME, &service_name).await?;
+        if !response.instances.is_empty() {
+            applications.push(EurekaApplication {
+                name: service_name.to_uppercase(),
+                instances: response.instances,
+            });
+        }
+    }
+    Ok(applications)
+}
+
+/// 实例指纹（不含时间戳），用于判断实例是否变化
+fn instance_fingerprint(instance: &InstanceInfo) -> String {
+    format!(
+        "{}|{}|{}|{}|{}|{}|{}",
+        instance.ip,
+        instance.port,
+        instance.weight,
+        instance.healthy,
+        instance.enabled,
+        eureka_status(instance),
+        instance.metadata.as_deref().unwrap_or("")
+    )
+}
+
+/// 对比快照记录变化，清理过期的变化，返回当前增量版本号
+async fn refresh_delta(app: &AppHandle, applications: &[EurekaApplication]) -> i64 {
+    let tracker = get_delta_tracker(app);
+    let mut tracker = tracker.lock().await;
+    let now = current_millis();
+
+    let current: HashMap<String, (String, InstanceInfo)> = applications
+        .iter()
+        .flat_map(|a| a.instances.iter())
+        .map(|inst| {
+            (
+                format!("{}##{}", inst.service_name, inst.instance_id),
+                (instance_fingerprint(inst), inst.clone()),
+            )
+        })
+        .collect();
+
+    // 首次刷新只建立快照，客户端会先做一次全量拉取
+    if tracker.initialized {
+        let mut changes = Vec::new();
+        for (key, (fingerprint, instance)) in &current {
+            match tracker.snapshot.get(key) {
+                None => changes.push(EurekaInstanceChange { action_type: EUREKA_ACTION_ADDED, instance: instance.clone() }),
+                Some((previous, _)) if previous != fingerprint => {
+                    changes.push(EurekaInstanceChange { action_type: EUREKA_ACTION_MODIFIED, instance: instance.clone() })
+                }
+                _ => {}
+            }
+        }
+        for (key, (_, instance)) in &tracker.snapshot {
+            if !current.contains_key(key) {
+                changes.push(EurekaInstanceChange { action_type: EUREKA_ACTION_DELETED, instance: instance.clone() });
+            }
+        }
+
+        if !changes.is_empty() {
+            tracker.version += 1;
+            for change in changes {
+                // 同一实例只保留最新一次变化
+                let key = format!("{}##{}", change.instance.service_name, change.instance.instance_id);
+                tracker
+                    .recent
+                    .retain(|(_, c)| format!("{}##{}", c.instance.service_name, c.instance.instance_id) != key);
+                tracker.recent.push_back((now, change));
+            }
+        }
+    }
+
+    tracker.initialized = true;
+    tracker.snapshot = current;
+    while tracker.recent.front().is_some_and(|(at, _)| now - at > DELTA_RETENTION_MILLIS) {
+        tracker.recent.pop_front();
+    }
+    tracker.version
+}
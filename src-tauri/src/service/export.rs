@@ -0,0 +1,378 @@
+/**
+ * 服务导出模块
+ * 将一个命名空间中的服务以只读别名导出到其他命名空间（可使用不同的分组和服务名）。
+ * 查询导出目标时解析为源服务的实例；源服务修订号变化时同步递增导出目标的修订号，
+ * 使订阅导出目标的客户端也能收到推送，携带 checksum 的轮询也能感知变化。
+ * 按源服务查询导出目标的结果缓存在内存中，导出创建、撤销或源服务删除时失效
+ */
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::RwLock;
+
+use super::revision::service_key;
+use super::{bump_service_revision, current_timestamp, get_service_detail};
+
+/// 服务导出
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceExport {
+    pub id: i64,
+    pub source_namespace_id: String,
+    pub source_group_name: String,
+    pub source_service_name: String,
+    pub target_namespace_id: String,
+    pub target_group_name: String,
+    pub target_service_name: String,
+    pub gmt_create: i64,
+}
+
+/// 创建服务导出请求
+#[derive(Debug, Deserialize)]
+pub struct CreateServiceExportRequest {
+    pub source_namespace_id: String,
+    pub source_group_name: String,
+    pub source_service_name: String,
+    pub target_namespace_id: String,
+    /// 为空时使用源服务的分组
+    pub target_group_name: Option<String>,
+    /// 为空时使用源服务的服务名
+    pub target_service_name: Option<String>,
+}
+
+/// 服务导出查询参数
+#[derive(Debug, Deserialize)]
+pub struct ServiceExportQuery {
+    /// 按源服务命名空间过滤
+    pub source_namespace_id: Option<String>,
+    /// 按导出目标命名空间过滤
+    pub target_namespace_id: Option<String>,
+}
+
+type ServiceExportRow = (i64, String, String, String, String, String, String, i64);
+
+const SERVICE_EXPORT_COLUMNS: &str = "id, source_namespace_id, source_group_name, source_service_name, target_namespace_id, target_group_name, target_service_name, gmt_create";
+
+fn row_to_export(row: ServiceExportRow) -> ServiceExport {
+    let (id, source_namespace_id, source_group_name, source_service_name, target_namespace_id, target_group_name, target_service_name, gmt_create) = row;
+    ServiceExport {
+        id,
+        source_namespace_id,
+        source_group_name,
+        source_service_name,
+        target_namespace_id,
+        target_group_name,
+        target_service_name,
+        gmt_create,
+    }
+}
+
+/// 导出缓存（源服务键 -> 导出列表），None 表示需要从数据库重新加载
+type ServiceExportCache = Arc<RwLock<Option<HashMap<String, Vec<ServiceExport>>>>>;
+
+/// 获取导出缓存（首次调用时创建；并发创建时以先注册的缓存为准）
+fn get_export_cache(app: &AppHandle) -> ServiceExportCache {
+    if let Some(state) = app.try_state::<ServiceExportCache>() {
+        return state.inner().clone();
+    }
+    app.manage(ServiceExportCache::default());
+    app.state::<ServiceExportCache>().inner().clone()
+}
+
+/// 使导出缓存失效，导出写入数据库后调用
+async fn invalidate_export_cache(app: &AppHandle) {
+    *get_export_cache(app).write().await = None;
+}
+
+/// 查询以给定服务为目标的导出（给定服务是导出别名时返回源服务）
+pub async fn find_service_export(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<Option<ServiceExport>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let row: Option<ServiceExportRow> = db
+        .query_one(
+            &format!(
+                "SELECT {} FROM service_export WHERE target_namespace_id = ?1 AND target_group_name = ?2 AND target_service_name = ?3",
+                SERVICE_EXPORT_COLUMNS
+            ),
+            &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query service export: {}", e))?;
+
+    Ok(row.map(row_to_export))
+}
+
+/// 查询给定服务导出到的所有目标（每次实例变更都会调用，结果来自内存缓存）
+pub async fn get_service_exports(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<Vec<ServiceExport>, String> {
+    let key = service_key(namespace_id, group_name, service_name);
+    let cache = get_export_cache(app);
+    if let Some(exports) = cache.read().await.as_ref() {
+        return Ok(exports.get(&key).cloned().unwrap_or_default());
+    }
+
+    // 加载和写入缓存在同一次写锁内完成，避免覆盖并发写入导出后的失效
+    let mut cached = cache.write().await;
+    if cached.is_none() {
+        let db = app
+            .sqlite_plugin()
+            .get_connection("sqlite:nacos.db")
+            .await
+            .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+        let rows: Vec<ServiceExportRow> = db
+            .query(
+                &format!(
+                    "SELECT {} FROM service_export ORDER BY target_namespace_id, target_group_name, target_service_name",
+                    SERVICE_EXPORT_COLUMNS
+                ),
+                &[],
+            )
+            .await
+            .map_err(|e| format!("Failed to query service exports: {}", e))?;
+
+        let mut exports: HashMap<String, Vec<ServiceExport>> = HashMap::new();
+        for export in rows.into_iter().map(row_to_export) {
+            exports
+                .entry(service_key(&export.source_namespace_id, &export.source_group_name, &export.source_service_name))
+                .or_default()
+                .push(export);
+        }
+        *cached = Some(exports);
+    }
+    Ok(cached.as_ref().and_then(|exports| exports.get(&key).cloned()).unwrap_or_default())
+}
+
+/// 查询服务导出列表
+pub async fn list_service_exports(app: &AppHandle, query: ServiceExportQuery) -> Result<Vec<ServiceExport>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let mut where_clauses = Vec::new();
+    let mut query_params: Vec<(&str, &str)> = Vec::new();
+    if let Some(source_namespace_id) = query.source_namespace_id.as_deref().filter(|n| !n.is_empty()) {
+        where_clauses.push("source_namespace_id = ?1");
+        query_params.push(("?1", source_namespace_id));
+    }
+    if let Some(target_namespace_id) = query.target_namespace_id.as_deref().filter(|n| !n.is_empty()) {
+        where_clauses.push("target_namespace_id = ?2");
+        query_params.push(("?2", target_namespace_id));
+    }
+    let where_sql = if where_clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", where_clauses.join(" AND "))
+    };
+
+    let rows: Vec<ServiceExportRow> = db
+        .query(
+            &format!(
+                "SELECT {} FROM service_export {} ORDER BY source_namespace_id, source_group_name, source_service_name, target_namespace_id",
+                SERVICE_EXPORT_COLUMNS, where_sql
+            ),
+            &query_params,
+        )
+        .await
+        .map_err(|e| format!("Failed to query service exports: {}", e))?;
+
+    Ok(rows.into_iter().map(row_to_export).collect())
+}
+
+/// 导出服务
+/// 源服务必须存在；导出目标不能是已有的服务或其他导出
+pub async fn create_service_export(
+    app: &AppHandle,
+    request: CreateServiceExportRequest,
+) -> Result<ServiceExport, String> {
+    let target_group_name = request
+        .target_group_name
+        .filter(|g| !g.is_empty())
+        .unwrap_or_else(|| request.source_group_name.clone());
+    let target_service_name = request
+        .target_service_name
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| request.source_service_name.clone());
+
+    if request.target_namespace_id.is_empty() {
+        return Err("Invalid export: target namespace is required".to_string());
+    }
+    if (request.source_namespace_id.as_str(), request.source_group_name.as_str(), request.source_service_name.as_str())
+        == (request.target_namespace_id.as_str(), target_group_name.as_str(), target_service_name.as_str())
+    {
+        return Err("Invalid export: target must differ from source".to_string());
+    }
+
+    // 导出别名不是真实服务，因此不能再次导出，导出目标总是直接解析到真实服务
+    if get_service_detail(app, &request.source_namespace_id, &request.source_group_name, &request.source_service_name)
+        .await?
+        .is_none()
+    {
+        return Err("Service not found".to_string());
+    }
+    if get_service_detail(app, &request.target_namespace_id, &target_group_name, &target_service_name)
+        .await?
+        .is_some()
+        || find_service_export(app, &request.target_namespace_id, &target_group_name, &target_service_name)
+            .await?
+            .is_some()
+    {
+        return Err(format!(
+            "Service {}@@{} already exists in namespace {}",
+            target_group_name, target_service_name, request.target_namespace_id
+        ));
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "INSERT INTO service_export (source_namespace_id, source_group_name, source_service_name, target_namespace_id, target_group_name, target_service_name, gmt_create) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        &[
+            ("?1", request.source_namespace_id.as_str()),
+            ("?2", request.source_group_name.as_str()),
+            ("?3", request.source_service_name.as_str()),
+            ("?4", request.target_namespace_id.as_str()),
+            ("?5", target_group_name.as_str()),
+            ("?6", target_service_name.as_str()),
+            ("?7", current_timestamp().to_string().as_str()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to create service export: {}", e))?;
+    invalidate_export_cache(app).await;
+
+    // 通知导出目标的订阅者
+    bump_service_revision(app, &request.target_namespace_id, &target_group_name, &target_service_name).await;
+
+    find_service_export(app, &request.target_namespace_id, &target_group_name, &target_service_name)
+        .await?
+        .ok_or_else(|| "Service export not found".to_string())
+}
+
+/// 撤销导出（按导出目标）
+pub async fn revoke_service_export(
+    app: &AppHandle,
+    target_namespace_id: &str,
+    target_group_name: &str,
+    target_service_name: &str,
+) -> Result<(), String> {
+    if find_service_export(app, target_namespace_id, target_group_name, target_service_name).await?.is_none() {
+        return Err("Service export not found".to_string());
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "DELETE FROM service_export WHERE target_namespace_id = ?1 AND target_group_name = ?2 AND target_service_name = ?3",
+        &[("?1", target_namespace_id), ("?2", target_group_name), ("?3", target_service_name)],
+    )
+    .await
+    .map_err(|e| format!("Failed to revoke service export: {}", e))?;
+    invalidate_export_cache(app).await;
+
+    // 通知导出目标的订阅者（实例列表变为空）
+    bump_service_revision(app, target_namespace_id, target_group_name, target_service_name).await;
+
+    Ok(())
+}
+
+/// 删除源服务的所有导出（源服务删除时调用），并通知各导出目标的订阅者
+pub(super) async fn remove_service_exports(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<(), String> {
+    let exports = get_service_exports(app, namespace_id, group_name, service_name).await?;
+    if exports.is_empty() {
+        return Ok(());
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "DELETE FROM service_export WHERE source_namespace_id = ?1 AND source_group_name = ?2 AND source_service_name = ?3",
+        &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete service exports: {}", e))?;
+    invalidate_export_cache(app).await;
+
+    for export in exports {
+        bump_service_revision(app, &export.target_namespace_id, &export.target_group_name, &export.target_service_name).await;
+    }
+    Ok(())
+}
+
+/// 删除源服务或导出目标位于给定命名空间的所有导出（命名空间删除时调用），并通知其他命名空间中导出目标的订阅者
+pub async fn remove_namespace_exports(app: &AppHandle, namespace_id: &str) -> Result<(), String> {
+    let exports = list_service_exports(app, ServiceExportQuery {
+        source_namespace_id: Some(namespace_id.to_string()),
+        target_namespace_id: None,
+    })
+    .await?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "DELETE FROM service_export WHERE source_namespace_id = ?1 OR target_namespace_id = ?1",
+        &[("?1", namespace_id)],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete service exports: {}", e))?;
+    invalidate_export_cache(app).await;
+
+    for export in exports.iter().filter(|export| export.target_namespace_id != namespace_id) {
+        bump_service_revision(app, &export.target_namespace_id, &export.target_group_name, &export.target_service_name).await;
+    }
+    Ok(())
+}
+
+/// 导出目标为只读别名，不允许注册实例或创建同名服务
+pub(super) async fn ensure_not_exported(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<(), String> {
+    match find_service_export(app, namespace_id, group_name, service_name).await? {
+        Some(export) => Err(format!(
+            "Service {}@@{} is a read-only export of {}##{}@@{}, registration is disabled",
+            group_name, service_name, export.source_namespace_id, export.source_group_name, export.source_service_name
+        )),
+        None => Ok(()),
+    }
+}
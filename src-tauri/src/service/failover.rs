@@ -0,0 +1,231 @@
+/**
+ * 命名服务容灾快照模块
+ * 把命名空间的服务注册表导出为 Nacos 客户端的容灾目录格式：failover/ 目录下每个服务一个文件，
+ * 文件名为 URL 编码后的 group@@service，内容为 ServiceInfo JSON。客户端连不上服务端时从该目录读取实例列表，
+ * 可选写入容灾开关文件，使客户端直接使用容灾数据（模拟服务端不可用）
+ */
+
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::AppHandle;
+
+use super::{compute_instances_checksum, get_service_instances, get_service_revision, list_service_exports, ServiceExportQuery};
+
+/// 容灾目录名
+pub const FAILOVER_DIR: &str = "failover";
+
+/// 容灾开关文件名（内容为 1 时客户端优先使用容灾数据）
+pub const FAILOVER_SWITCH_FILE: &str = "00-00---000-VIPSRV_FAILOVER_SWITCH-000---00-00";
+
+/// 容灾快照导出请求
+#[derive(Debug, Deserialize)]
+pub struct FailoverExportRequest {
+    pub namespace_id: String,
+    /// 输出路径：zip 为 true 时是 zip 文件路径，否则是目录（在其中创建 failover/ 目录）
+    pub output_path: String,
+    #[serde(default)]
+    pub zip: bool,
+    /// 是否写入打开状态的容灾开关文件
+    #[serde(default)]
+    pub failover_switch: bool,
+}
+
+/// 容灾快照导出结果
+#[derive(Debug, Serialize)]
+pub struct FailoverExportSummary {
+    /// zip 文件或 failover 目录的路径
+    pub path: String,
+    pub service_count: usize,
+    pub instance_count: usize,
+}
+
+/// 容灾快照文件（文件名不含 failover/ 前缀）
+#[derive(Debug)]
+pub struct FailoverFile {
+    pub name: String,
+    pub content: String,
+}
+
+/// 构建 Nacos ServiceInfo（SubscribeServiceResponse、NotifySubscriberRequest 和容灾文件共用）
+pub async fn build_service_info(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    clusters: &str,
+) -> Result<Value, String> {
+    let cluster_list: Vec<&str> = clusters.split(',').map(str::trim).filter(|c| !c.is_empty()).collect();
+    let instances: Vec<_> = get_service_instances(app, namespace_id, group_name, service_name)
+        .await?
+        .instances
+        .into_iter()
+        .filter(|inst| cluster_list.is_empty() || cluster_list.contains(&inst.cluster_name.as_str()))
+        .collect();
+    let revision = get_service_revision(app, namespace_id, group_name, service_name).await;
+
+    let hosts: Vec<Value> = instances
+        .iter()
+        .map(|inst| {
+            json!({
+                "instanceId": inst.instance_id,
+                "ip": inst.ip,
+                "port": inst.port,
+                "weight": inst.weight,
+                "healthy": inst.healthy,
+                "enabled": inst.enabled,
+                "ephemeral": inst.ephemeral,
+                "clusterName": inst.cluster_name,
+                "serviceName": format!("{}@@{}", group_name, service_name),
+                "metadata": inst.metadata.as_deref()
+                    .and_then(|s| serde_json::from_str::<Value>(s).ok())
+                    .unwrap_or(json!({})),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "name": service_name,
+        "groupName": group_name,
+        "clusters": clusters,
+        "cacheMillis": 10000,
+        "hosts": hosts,
+        "lastRefTime": revision.last_modified_millis,
+        "checksum": compute_instances_checksum(instances.iter()),
+        "allIPs": false,
+        "reachProtectionThreshold": false,
+        "valid": true,
+    }))
+}
+
+/// 容灾文件名：与 Nacos 客户端 ServiceInfo.getKeyEncoded 一致，对 group@@service 做 URL 编码，
+/// 服务名中的 / 和 \ 被编码，文件总是位于 failover/ 目录内
+fn failover_file_name(group_name: &str, service_name: &str) -> String {
+    urlencoding::encode(&format!("{}@@{}", group_name, service_name)).into_owned()
+}
+
+/// 构建命名空间的容灾快照，包含服务和导出到该命名空间的服务别名
+pub async fn build_failover_snapshot(
+    app: &AppHandle,
+    namespace_id: &str,
+    failover_switch: bool,
+) -> Result<Vec<FailoverFile>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let mut services: Vec<(String, String)> = db
+        .query(
+            "SELECT group_name, service_name FROM service_info WHERE namespace_id = ?1",
+            &[("?1", namespace_id)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query services: {}", e))?;
+
+    let exports = list_service_exports(app, ServiceExportQuery {
+        source_namespace_id: None,
+        target_namespace_id: Some(namespace_id.to_string()),
+    })
+    .await?;
+    services.extend(exports.into_iter().map(|export| (export.target_group_name, export.target_service_name)));
+    services.sort();
+
+    let mut files = Vec::with_capacity(services.len() + 1);
+    for (group_name, service_name) in services {
+        let service_info = build_service_info(app, namespace_id, &group_name, &service_name, "").await?;
+        files.push(FailoverFile {
+            name: failover_file_name(&group_name, &service_name),
+            content: service_info.to_string(),
+        });
+    }
+    if failover_switch {
+        files.push(FailoverFile {
+            name: FAILOVER_SWITCH_FILE.to_string(),
+            content: "1".to_string(),
+        });
+    }
+
+    Ok(files)
+}
+
+/// 把容灾快照打包为 zip（文件位于 failover/ 目录下）
+pub fn failover_snapshot_zip(files: &[FailoverFile]) -> Result<Vec<u8>, String> {
+    use zip::write::{FileOptions, ZipWriter};
+    use zip::CompressionMethod;
+
+    let mut zip_buffer = Vec::new();
+    {
+        let mut zip = ZipWriter::new(std::io::Cursor::new(&mut zip_buffer));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.add_directory(FAILOVER_DIR, options)
+            .map_err(|e| format!("Failed to write zip: {}", e))?;
+        for file in files {
+            zip.start_file(format!("{}/{}", FAILOVER_DIR, file.name), options)
+                .map_err(|e| format!("Failed to write zip: {}", e))?;
+            zip.write_all(file.content.as_bytes())
+                .map_err(|e| format!("Failed to write zip: {}", e))?;
+        }
+        zip.finish().map_err(|e| format!("Failed to write zip: {}", e))?;
+    }
+
+    Ok(zip_buffer)
+}
+
+/// 把容灾快照写入目录下的 failover/ 目录，返回 failover 目录路径
+/// 已有的容灾文件会被覆盖，目录中的其他文件保持不变
+async fn write_failover_snapshot(dir: &str, files: &[FailoverFile]) -> Result<String, String> {
+    if dir.trim().is_empty() {
+        return Err("Invalid output path: directory is required".to_string());
+    }
+
+    let failover_dir = Path::new(dir).join(FAILOVER_DIR);
+    tokio::fs::create_dir_all(&failover_dir)
+        .await
+        .map_err(|e| format!("Failed to create failover directory: {}", e))?;
+    for file in files {
+        tokio::fs::write(failover_dir.join(&file.name), &file.content)
+            .await
+            .map_err(|e| format!("Failed to write failover file {}: {}", file.name, e))?;
+    }
+
+    Ok(failover_dir.to_string_lossy().into_owned())
+}
+
+/// 导出命名空间的容灾快照到 zip 文件或目录
+pub async fn export_failover_snapshot(
+    app: &AppHandle,
+    request: FailoverExportRequest,
+) -> Result<FailoverExportSummary, String> {
+    if request.output_path.trim().is_empty() {
+        return Err("Invalid output path: path is required".to_string());
+    }
+
+    let files = build_failover_snapshot(app, &request.namespace_id, request.failover_switch).await?;
+    let service_files: Vec<&FailoverFile> = files.iter().filter(|f| f.name != FAILOVER_SWITCH_FILE).collect();
+    let instance_count = service_files
+        .iter()
+        .filter_map(|f| serde_json::from_str::<Value>(&f.content).ok())
+        .map(|info| info["hosts"].as_array().map_or(0, Vec::len))
+        .sum();
+
+    let path = if request.zip {
+        let zip_buffer = failover_snapshot_zip(&files)?;
+        tokio::fs::write(&request.output_path, zip_buffer)
+            .await
+            .map_err(|e| format!("Failed to write zip file: {}", e))?;
+        request.output_path
+    } else {
+        write_failover_snapshot(&request.output_path, &files).await?
+    };
+
+    Ok(FailoverExportSummary {
+        path,
+        service_count: service_files.len(),
+        instance_count,
+    })
+}
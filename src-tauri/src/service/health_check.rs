@@ -0,0 +1,215 @@
+/**
+ * 持久化实例健康检查模块
+ * 按集群配置的健康检查器（TCP / HTTP / NONE）主动探测持久化实例
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::AppHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::Semaphore;
+use tokio::task::{JoinHandle, JoinSet};
+
+use super::cluster::{ClusterInfo, HealthChecker};
+use super::health_timeline::{record_health_event, HealthChangeSource};
+use super::history::{instance_address, record_instance_history, ServiceChangeType};
+use super::{bump_service_revision, current_timestamp, get_naming_switches};
+
+/// 健康检查周期（秒）
+pub const HEALTH_CHECK_INTERVAL_SECONDS: u64 = 5;
+
+/// 单次探测超时（毫秒）
+const PROBE_TIMEOUT_MILLIS: u64 = 2000;
+
+/// 同时进行的探测数上限
+const MAX_CONCURRENT_PROBES: usize = 32;
+
+/// 启动后台健康检查任务
+pub fn start_health_check_task(app: AppHandle) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(HEALTH_CHECK_INTERVAL_SECONDS));
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_health_check_round(&app).await {
+                tracing::warn!("Health check round failed: {}", e);
+            }
+        }
+    })
+}
+
+/// 执行一轮健康检查：遍历启用了主动检查的集群，探测其中的持久化实例
+pub async fn run_health_check_round(app: &AppHandle) -> Result<(), String> {
+    // 健康检查开关关闭时跳过本轮检查
+    if !get_naming_switches(app).await.health_check_enabled {
+        return Ok(());
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let clusters: Vec<(i64, String, String, String, String, String, i32, bool, Option<String>, i64, i64)> = db
+        .query(
+            "SELECT id, namespace_id, group_name, service_name, cluster_name, health_checker, check_port, use_instance_port, metadata, gmt_create, gmt_modified FROM cluster_info",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("Failed to query clusters: {}", e))?;
+
+    // 先收集所有待探测的实例，再并发探测，避免实例较多时一轮检查超过检查周期
+    let mut probes = JoinSet::new();
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_PROBES));
+    for (id, namespace_id, group_name, service_name, cluster_name, health_checker, check_port, use_instance_port, metadata, gmt_create, gmt_modified) in clusters {
+        let cluster = Arc::new(ClusterInfo {
+            id: Some(id),
+            namespace_id,
+            group_name,
+            service_name,
+            cluster_name,
+            health_checker,
+            check_port,
+            use_instance_port,
+            metadata,
+            gmt_create,
+            gmt_modified,
+        });
+
+        let checker = cluster.checker();
+        if !checker.is_active() {
+            continue;
+        }
+
+        // 仅检查已启用的持久化实例，临时实例由心跳维护
+        let instances: Vec<(i64, String, i32, bool)> = db
+            .query(
+                "SELECT id, ip, port, healthy FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND cluster_name = ?4 AND ephemeral = 0 AND enabled = 1",
+                &[
+                    ("?1", &cluster.namespace_id),
+                    ("?2", &cluster.group_name),
+                    ("?3", &cluster.service_name),
+                    ("?4", &cluster.cluster_name),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to query instances: {}", e))?;
+
+        for (id, ip, port, healthy) in instances {
+            let cluster = cluster.clone();
+            let checker = checker.clone();
+            let semaphore = semaphore.clone();
+            probes.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let probe_port = if cluster.use_instance_port { port } else { cluster.check_port };
+                let result = probe(&checker, &ip, probe_port).await;
+                (cluster, id, ip, port, healthy, result)
+            });
+        }
+    }
+
+    while let Some(joined) = probes.join_next().await {
+        let (cluster, id, ip, port, healthy, result) = match joined {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                tracing::warn!("Health check probe task failed: {}", e);
+                continue;
+            }
+        };
+        if result == healthy {
+            continue;
+        }
+
+        db.execute(
+            "UPDATE instance_info SET healthy = ?1, gmt_modified = ?2 WHERE id = ?3",
+            &[
+                ("?1", &(if result { "1" } else { "0" })),
+                ("?2", &current_timestamp().to_string()),
+                ("?3", &id.to_string()),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to update instance health: {}", e))?;
+
+        bump_service_revision(app, &cluster.namespace_id, &cluster.group_name, &cluster.service_name).await;
+        record_instance_history(
+            app,
+            &cluster.namespace_id,
+            &cluster.group_name,
+            &cluster.service_name,
+            ServiceChangeType::Health,
+            &instance_address(&ip, port, &cluster.cluster_name),
+            if result { "became healthy" } else { "became unhealthy (health check failed)" },
+        )
+        .await;
+        record_health_event(
+            app,
+            &cluster.namespace_id,
+            &cluster.group_name,
+            &cluster.service_name,
+            &instance_address(&ip, port, &cluster.cluster_name),
+            result,
+            HealthChangeSource::HealthCheck,
+            if result { "health check passed" } else { "health check failed" },
+        )
+        .await;
+    }
+
+    Ok(())
+}
+
+/// 按健康检查器探测实例，返回是否健康
+pub async fn probe(checker: &HealthChecker, ip: &str, port: i32) -> bool {
+    let addr = format!("{}:{}", ip, port);
+    let timeout = Duration::from_millis(PROBE_TIMEOUT_MILLIS);
+
+    match checker {
+        HealthChecker::None => true,
+        HealthChecker::Tcp => matches!(
+            tokio::time::timeout(timeout, TcpStream::connect(&addr)).await,
+            Ok(Ok(_))
+        ),
+        HealthChecker::Http { path, headers, expected_response_code } => {
+            let result = tokio::time::timeout(timeout, http_probe(&addr, ip, path, headers)).await;
+            matches!(result, Ok(Ok(code)) if code == *expected_response_code)
+        }
+    }
+}
+
+/// 发送 HTTP GET 请求并返回状态码
+async fn http_probe(addr: &str, host: &str, path: &str, headers: &str) -> Result<u16, String> {
+    let mut stream = TcpStream::connect(addr)
+        .await
+        .map_err(|e| format!("Failed to connect: {}", e))?;
+
+    // headers 格式与 Nacos 一致：key1:value1|key2:value2
+    let extra_headers: String = headers
+        .split('|')
+        .filter(|h| h.contains(':'))
+        .map(|h| format!("{}\r\n", h.trim()))
+        .collect();
+
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: Nacos-Server\r\nConnection: close\r\n{}\r\n",
+        path, host, extra_headers
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    let mut buf = [0u8; 64];
+    let n = stream
+        .read(&mut buf)
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    // 状态行：HTTP/1.1 200 OK
+    String::from_utf8_lossy(&buf[..n])
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| "Invalid HTTP response".to_string())
+}
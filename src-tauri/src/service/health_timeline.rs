@@ -0,0 +1,271 @@
+/**
+ * 实例健康时间线模块
+ * 记录实例注册、注销和健康状态变更（原因和来源），写入 instance_health_event 表，
+ * 按实例或服务查询时间线，并根据时间线计算时间窗口内的可用率
+ */
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::current_timestamp;
+use super::history::instance_address;
+
+/// 默认统计窗口（秒）
+pub const DEFAULT_HEALTH_TIMELINE_WINDOW_SECONDS: i64 = 24 * 60 * 60;
+
+/// 健康状态变更来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthChangeSource {
+    /// 实例注册（记录初始健康状态）
+    Register,
+    /// 实例注销（包括心跳过期被移除），之后不计入统计时间
+    Deregister,
+    /// 客户端心跳（心跳恢复或心跳超时）
+    Heartbeat,
+    /// 服务端主动健康检查或外部检查（如 Consul TTL 检查）
+    HealthCheck,
+    /// 通过 API 或控制台手动修改
+    Manual,
+    /// 服务模拟器模拟的健康抖动
+    Simulator,
+}
+
+impl HealthChangeSource {
+    /// 写入 source 列的值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthChangeSource::Register => "REGISTER",
+            HealthChangeSource::Deregister => "DEREGISTER",
+            HealthChangeSource::Heartbeat => "HEARTBEAT",
+            HealthChangeSource::HealthCheck => "HEALTH_CHECK",
+            HealthChangeSource::Manual => "MANUAL",
+            HealthChangeSource::Simulator => "SIMULATOR",
+        }
+    }
+}
+
+/// 健康时间线事件
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthEventInfo {
+    pub id: i64,
+    pub healthy: bool,
+    pub source: String,
+    pub reason: Option<String>,
+    pub gmt_create: i64,
+}
+
+/// 单个实例的健康时间线
+#[derive(Debug, Clone, Serialize)]
+pub struct InstanceHealthTimeline {
+    /// 实例地址（ip:port#cluster）
+    pub address: String,
+    /// 窗口结束时的健康状态，实例已注销时为 None
+    pub healthy: Option<bool>,
+    /// 窗口内健康的时长（秒）
+    pub healthy_seconds: i64,
+    /// 窗口内实例处于注册状态的时长（秒）
+    pub observed_seconds: i64,
+    /// 可用率（百分比，保留两位小数），没有可统计的时长时为 None
+    pub uptime_percent: Option<f64>,
+    /// 窗口内的事件，按时间正序
+    pub events: Vec<HealthEventInfo>,
+}
+
+/// 健康时间线查询结果
+#[derive(Debug, Serialize)]
+pub struct HealthTimelineResponse {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// 服务整体可用率（所有实例健康时长之和 / 注册时长之和）
+    pub uptime_percent: Option<f64>,
+    pub instances: Vec<InstanceHealthTimeline>,
+}
+
+/// 健康时间线查询条件
+#[derive(Debug, Default, Deserialize)]
+pub struct HealthTimelineQuery {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    /// 指定实例（需同时指定 port），为空时查询服务的所有实例
+    pub ip: Option<String>,
+    pub port: Option<i32>,
+    pub cluster_name: Option<String>,
+    /// 窗口起始时间（秒），默认为结束时间前 24 小时
+    pub start_time: Option<i64>,
+    /// 窗口结束时间（秒），默认为当前时间
+    pub end_time: Option<i64>,
+}
+
+/// 记录健康时间线事件，写入失败只记录日志，不影响实例操作本身
+pub async fn record_health_event(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    address: &str,
+    healthy: bool,
+    source: HealthChangeSource,
+    reason: &str,
+) {
+    let db = match app.sqlite_plugin().get_connection("sqlite:nacos.db").await {
+        Ok(db) => db,
+        Err(e) => {
+            tracing::warn!("Failed to record health event: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = db
+        .execute(
+            "INSERT INTO instance_health_event (namespace_id, group_name, service_name, address, healthy, source, reason, gmt_create) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            &[
+                ("?1", namespace_id),
+                ("?2", group_name),
+                ("?3", service_name),
+                ("?4", address),
+                ("?5", if healthy { "1" } else { "0" }),
+                ("?6", source.as_str()),
+                ("?7", reason),
+                ("?8", &current_timestamp().to_string()),
+            ],
+        )
+        .await
+    {
+        tracing::warn!("Failed to record health event: {}", e);
+    }
+}
+
+/// 百分比，保留两位小数
+fn percent(part: i64, total: i64) -> Option<f64> {
+    (total > 0).then(|| (part as f64 * 10000.0 / total as f64).round() / 100.0)
+}
+
+/// 根据事件序列计算实例在窗口内的时间线：窗口开始前的最后一个事件决定初始状态
+fn build_instance_timeline(address: String, events: Vec<HealthEventInfo>, start_time: i64, end_time: i64) -> Option<InstanceHealthTimeline> {
+    let state_after = |event: &HealthEventInfo| {
+        if event.source == HealthChangeSource::Deregister.as_str() {
+            None
+        } else {
+            Some(event.healthy)
+        }
+    };
+
+    let mut state: Option<bool> = None;
+    let mut cursor = start_time;
+    let mut healthy_seconds = 0;
+    let mut observed_seconds = 0;
+    let mut window_events = Vec::new();
+
+    for event in events {
+        if event.gmt_create < start_time {
+            state = state_after(&event);
+            continue;
+        }
+        if let Some(healthy) = state {
+            let elapsed = event.gmt_create - cursor;
+            observed_seconds += elapsed;
+            if healthy {
+                healthy_seconds += elapsed;
+            }
+        }
+        cursor = event.gmt_create;
+        state = state_after(&event);
+        window_events.push(event);
+    }
+    if let Some(healthy) = state {
+        let elapsed = end_time - cursor;
+        observed_seconds += elapsed;
+        if healthy {
+            healthy_seconds += elapsed;
+        }
+    }
+
+    // 窗口内既没有事件也不处于注册状态的实例不返回
+    if window_events.is_empty() && state.is_none() {
+        return None;
+    }
+
+    Some(InstanceHealthTimeline {
+        address,
+        healthy: state,
+        healthy_seconds,
+        observed_seconds,
+        uptime_percent: percent(healthy_seconds, observed_seconds),
+        events: window_events,
+    })
+}
+
+/// 查询服务或单个实例的健康时间线和可用率
+pub async fn get_health_timeline(app: &AppHandle, query: HealthTimelineQuery) -> Result<HealthTimelineResponse, String> {
+    let end_time = query.end_time.unwrap_or_else(current_timestamp);
+    let start_time = query.start_time.unwrap_or(end_time - DEFAULT_HEALTH_TIMELINE_WINDOW_SECONDS);
+    if start_time > end_time {
+        return Err("Invalid time range: startTime is after endTime".to_string());
+    }
+
+    let address = match (query.ip.as_deref().filter(|ip| !ip.is_empty()), query.port) {
+        (Some(ip), Some(port)) => Some(instance_address(ip, port, query.cluster_name.as_deref().unwrap_or("DEFAULT"))),
+        (Some(_), None) => return Err("Invalid instance: port is required".to_string()),
+        (None, _) => None,
+    };
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let end_time_str = end_time.to_string();
+    let mut sql = "SELECT id, address, healthy, source, reason, gmt_create FROM instance_health_event WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND gmt_create <= ?4".to_string();
+    let mut params: Vec<(&str, &str)> = vec![
+        ("?1", query.namespace_id.as_str()),
+        ("?2", query.group_name.as_str()),
+        ("?3", query.service_name.as_str()),
+        ("?4", end_time_str.as_str()),
+    ];
+    if let Some(address) = &address {
+        sql.push_str(" AND address = ?5");
+        params.push(("?5", address.as_str()));
+    }
+    sql.push_str(" ORDER BY gmt_create ASC, id ASC");
+
+    let rows: Vec<(i64, String, bool, String, Option<String>, i64)> = db
+        .query(&sql, &params)
+        .await
+        .map_err(|e| format!("Failed to query health events: {}", e))?;
+
+    let mut events_by_address: BTreeMap<String, Vec<HealthEventInfo>> = BTreeMap::new();
+    for (id, address, healthy, source, reason, gmt_create) in rows {
+        events_by_address.entry(address).or_default().push(HealthEventInfo {
+            id,
+            healthy,
+            source,
+            reason,
+            gmt_create,
+        });
+    }
+
+    let instances: Vec<InstanceHealthTimeline> = events_by_address
+        .into_iter()
+        .filter_map(|(address, events)| build_instance_timeline(address, events, start_time, end_time))
+        .collect();
+
+    let healthy_seconds = instances.iter().map(|i| i.healthy_seconds).sum();
+    let observed_seconds = instances.iter().map(|i| i.observed_seconds).sum();
+
+    Ok(HealthTimelineResponse {
+        namespace_id: query.namespace_id,
+        group_name: query.group_name,
+        service_name: query.service_name,
+        start_time,
+        end_time,
+        uptime_percent: percent(healthy_seconds, observed_seconds),
+        instances,
+    })
+}
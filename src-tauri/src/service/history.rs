@@ -0,0 +1,255 @@
+/**
+ * 服务变更历史模块
+ * 记录服务创建 / 更新 / 删除以及实例注册、注销、健康状态和元数据变更，
+ * 写入 service_history_info 表并提供分页查询
+ */
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::current_timestamp;
+
+/// 服务变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceChangeType {
+    /// 服务创建
+    Create,
+    /// 服务更新
+    Update,
+    /// 服务删除
+    Delete,
+    /// 实例注册
+    Register,
+    /// 实例注销（包括心跳过期被移除）
+    Deregister,
+    /// 实例健康状态变更
+    Health,
+    /// 实例元数据或属性变更
+    Metadata,
+}
+
+impl ServiceChangeType {
+    /// 写入 change_type 列的值
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceChangeType::Create => "CREATE",
+            ServiceChangeType::Update => "UPDATE",
+            ServiceChangeType::Delete => "DELETE",
+            ServiceChangeType::Register => "REGISTER",
+            ServiceChangeType::Deregister => "DEREGISTER",
+            ServiceChangeType::Health => "HEALTH",
+            ServiceChangeType::Metadata => "METADATA",
+        }
+    }
+
+    /// 解析变更类型（不区分大小写）
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_uppercase().as_str() {
+            "CREATE" => Some(ServiceChangeType::Create),
+            "UPDATE" => Some(ServiceChangeType::Update),
+            "DELETE" => Some(ServiceChangeType::Delete),
+            "REGISTER" => Some(ServiceChangeType::Register),
+            "DEREGISTER" => Some(ServiceChangeType::Deregister),
+            "HEALTH" => Some(ServiceChangeType::Health),
+            "METADATA" => Some(ServiceChangeType::Metadata),
+            _ => None,
+        }
+    }
+}
+
+/// 服务变更历史记录
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceHistoryInfo {
+    pub id: i64,
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub change_type: String,
+    pub change_detail: Option<String>,
+    pub gmt_create: i64,
+}
+
+/// 服务变更历史查询条件
+#[derive(Debug, Default, Deserialize)]
+pub struct ServiceHistoryQuery {
+    pub namespace_id: Option<String>,
+    pub group_name: Option<String>,
+    pub service_name: Option<String>,
+    pub change_type: Option<String>,
+    /// 起始时间（秒，包含）
+    pub start_time: Option<i64>,
+    /// 结束时间（秒，包含）
+    pub end_time: Option<i64>,
+    pub page_no: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+/// 服务变更历史列表响应
+#[derive(Debug, Serialize)]
+pub struct ServiceHistoryListResponse {
+    pub total_count: i64,
+    pub page_number: i64,
+    pub pages_available: i64,
+    pub page_items: Vec<ServiceHistoryInfo>,
+}
+
+/// 实例变更描述中的实例地址（ip:port#cluster）
+pub fn instance_address(ip: &str, port: i32, cluster_name: &str) -> String {
+    format!("{}:{}#{}", ip, port, cluster_name)
+}
+
+/// 记录服务变更历史
+pub async fn record_service_history(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    change_type: ServiceChangeType,
+    change_detail: &str,
+) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "INSERT INTO service_history_info (namespace_id, group_name, service_name, change_type, change_detail, gmt_create) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        &[
+            ("?1", namespace_id),
+            ("?2", group_name),
+            ("?3", service_name),
+            ("?4", change_type.as_str()),
+            ("?5", change_detail),
+            ("?6", &current_timestamp().to_string()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to insert service history: {}", e))?;
+
+    Ok(())
+}
+
+/// 记录实例变更历史，写入失败只记录日志，不影响实例操作本身
+pub async fn record_instance_history(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    change_type: ServiceChangeType,
+    address: &str,
+    action: &str,
+) {
+    let detail = format!("Instance {}: {}", action, address);
+    if let Err(e) = record_service_history(app, namespace_id, group_name, service_name, change_type, &detail).await {
+        tracing::warn!("Failed to record instance history: {}", e);
+    }
+}
+
+/// 分页查询服务变更历史，可按服务、变更类型和时间范围过滤，按时间倒序返回
+pub async fn get_service_history(
+    app: &AppHandle,
+    query: ServiceHistoryQuery,
+) -> Result<ServiceHistoryListResponse, String> {
+    let change_type = match query.change_type.as_deref().filter(|t| !t.is_empty()) {
+        Some(value) => Some(
+            ServiceChangeType::parse(value)
+                .ok_or_else(|| format!("Invalid change type: {}", value))?
+                .as_str()
+                .to_string(),
+        ),
+        None => None,
+    };
+
+    // 构建查询条件
+    let mut where_clauses: Vec<String> = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+
+    let filters = [
+        ("namespace_id = ", query.namespace_id.filter(|v| !v.is_empty())),
+        ("group_name = ", query.group_name.filter(|v| !v.is_empty())),
+        ("service_name = ", query.service_name.filter(|v| !v.is_empty())),
+        ("change_type = ", change_type),
+        ("gmt_create >= ", query.start_time.map(|t| t.to_string())),
+        ("gmt_create <= ", query.end_time.map(|t| t.to_string())),
+    ];
+    for (condition, value) in filters {
+        if let Some(value) = value {
+            values.push(value);
+            where_clauses.push(format!("{}?{}", condition, values.len()));
+        }
+    }
+
+    let where_sql = if where_clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", where_clauses.join(" AND "))
+    };
+
+    let placeholders: Vec<String> = (1..=values.len() + 2).map(|i| format!("?{}", i)).collect();
+
+    let page_no = query.page_no.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(10).max(1);
+    let offset = (page_no - 1) * page_size;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    // 查询总数
+    let count_params: Vec<(&str, &str)> = placeholders
+        .iter()
+        .zip(values.iter())
+        .map(|(p, v)| (p.as_str(), v.as_str()))
+        .collect();
+    let total_count: Option<(i64,)> = db
+        .query_one(&format!("SELECT COUNT(*) FROM service_history_info {}", where_sql), &count_params)
+        .await
+        .map_err(|e| format!("Failed to query service history count: {}", e))?;
+
+    let total_count = total_count.map(|(c,)| c).unwrap_or(0);
+    let pages_available = (total_count + page_size - 1) / page_size;
+
+    // 查询历史列表
+    values.push(page_size.to_string());
+    values.push(offset.to_string());
+    let list_params: Vec<(&str, &str)> = placeholders
+        .iter()
+        .zip(values.iter())
+        .map(|(p, v)| (p.as_str(), v.as_str()))
+        .collect();
+    let rows: Vec<(i64, String, String, String, String, Option<String>, i64)> = db
+        .query(
+            &format!(
+                "SELECT id, namespace_id, group_name, service_name, change_type, change_detail, gmt_create FROM service_history_info {} ORDER BY gmt_create DESC, id DESC LIMIT ?{} OFFSET ?{}",
+                where_sql,
+                values.len() - 1,
+                values.len()
+            ),
+            &list_params,
+        )
+        .await
+        .map_err(|e| format!("Failed to query service history: {}", e))?;
+
+    let page_items = rows
+        .into_iter()
+        .map(|(id, namespace_id, group_name, service_name, change_type, change_detail, gmt_create)| ServiceHistoryInfo {
+            id,
+            namespace_id,
+            group_name,
+            service_name,
+            change_type,
+            change_detail,
+            gmt_create,
+        })
+        .collect();
+
+    Ok(ServiceHistoryListResponse {
+        total_count,
+        page_number: page_no,
+        pages_available,
+        page_items,
+    })
+}
@@ -0,0 +1,170 @@
+/**
+ * 主机视图模块
+ * 按 IP 汇总主机相关的全部信息：跨命名空间提供的实例、订阅的服务、监听的配置和最后一次心跳，
+ * 用于排查故障时反查一台主机的影响范围
+ */
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use super::{cleanup_expired_naming_subscribers, ephemeral_instances_by_ip, InstanceInfo};
+
+/// 主机提供的实例
+#[derive(Debug, Clone, Serialize)]
+pub struct HostInstance {
+    pub instance: InstanceInfo,
+    /// 最后一次心跳时间（毫秒），持久化实例由服务端健康检查，没有心跳
+    pub last_beat_millis: Option<i64>,
+}
+
+/// 主机订阅的服务
+#[derive(Debug, Clone, Serialize)]
+pub struct HostSubscribedService {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub client_port: i32,
+    pub app_name: Option<String>,
+    pub agent: Option<String>,
+    pub clusters: String,
+    pub last_ref_time: i64,
+}
+
+/// 主机监听的配置
+#[derive(Debug, Clone, Serialize)]
+pub struct HostConfigListener {
+    pub tenant_id: String,
+    pub data_id: String,
+    pub group_id: String,
+    pub client_port: Option<i32>,
+    pub app_name: Option<String>,
+    pub md5: Option<String>,
+    pub last_poll_time: i64,
+}
+
+/// 主机视图
+#[derive(Debug, Clone, Serialize)]
+pub struct HostView {
+    pub ip: String,
+    pub instances: Vec<HostInstance>,
+    pub subscribed_services: Vec<HostSubscribedService>,
+    pub config_listeners: Vec<HostConfigListener>,
+    /// 所有实例中最近一次心跳时间（毫秒），没有临时实例时为 None
+    pub last_heartbeat_millis: Option<i64>,
+}
+
+/// 查询主机视图
+pub async fn get_host_view(app: &AppHandle, ip: &str) -> Result<HostView, String> {
+    let ip = ip.trim();
+    if ip.is_empty() {
+        return Err("Invalid ip: ip is required".to_string());
+    }
+
+    // 先清理过期订阅者，只返回仍然活跃的服务订阅
+    cleanup_expired_naming_subscribers(app).await?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    // 持久化实例
+    let rows: Vec<(i64, String, String, String, String, String, i32, f64, bool, bool, bool, String, Option<String>, i64, i64)> = db
+        .query(
+            "SELECT id, namespace_id, group_name, service_name, instance_id, ip, port, weight, healthy, enabled, ephemeral, cluster_name, metadata, gmt_create, gmt_modified FROM instance_info WHERE ip = ?1",
+            &[("?1", ip)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query host instances: {}", e))?;
+
+    let mut instances: Vec<HostInstance> = rows
+        .into_iter()
+        .map(|(id, namespace_id, group_name, service_name, instance_id, ip, port, weight, healthy, enabled, ephemeral, cluster_name, metadata, gmt_create, gmt_modified)| HostInstance {
+            instance: InstanceInfo {
+                id: Some(id),
+                namespace_id,
+                group_name,
+                service_name,
+                instance_id,
+                ip,
+                port,
+                weight,
+                healthy,
+                enabled,
+                ephemeral,
+                cluster_name,
+                metadata,
+                gmt_create,
+                gmt_modified,
+            },
+            last_beat_millis: None,
+        })
+        .collect();
+
+    // 内存中的临时实例
+    instances.extend(
+        ephemeral_instances_by_ip(app, ip)
+            .await
+            .into_iter()
+            .map(|(instance, last_beat_millis)| HostInstance { instance, last_beat_millis: Some(last_beat_millis) }),
+    );
+    instances.sort_by(|a, b| {
+        (&a.instance.namespace_id, &a.instance.group_name, &a.instance.service_name, a.instance.port)
+            .cmp(&(&b.instance.namespace_id, &b.instance.group_name, &b.instance.service_name, b.instance.port))
+    });
+    let last_heartbeat_millis = instances.iter().filter_map(|i| i.last_beat_millis).max();
+
+    // 订阅的服务
+    let rows: Vec<(String, String, String, i32, Option<String>, Option<String>, String, i64)> = db
+        .query(
+            "SELECT namespace_id, group_name, service_name, client_port, app_name, agent, clusters, last_ref_time FROM naming_subscribers WHERE client_ip = ?1 ORDER BY namespace_id, group_name, service_name, client_port",
+            &[("?1", ip)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query host subscriptions: {}", e))?;
+
+    let subscribed_services = rows
+        .into_iter()
+        .map(|(namespace_id, group_name, service_name, client_port, app_name, agent, clusters, last_ref_time)| HostSubscribedService {
+            namespace_id,
+            group_name,
+            service_name,
+            client_port,
+            app_name,
+            agent,
+            clusters,
+            last_ref_time,
+        })
+        .collect();
+
+    // 监听的配置
+    let rows: Vec<(String, String, String, Option<i32>, Option<String>, Option<String>, i64)> = db
+        .query(
+            "SELECT tenant_id, data_id, group_id, client_port, app_name, md5, last_poll_time FROM subscribers WHERE client_ip = ?1 ORDER BY tenant_id, group_id, data_id",
+            &[("?1", ip)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query host config listeners: {}", e))?;
+
+    let config_listeners = rows
+        .into_iter()
+        .map(|(tenant_id, data_id, group_id, client_port, app_name, md5, last_poll_time)| HostConfigListener {
+            tenant_id,
+            data_id,
+            group_id,
+            client_port,
+            app_name,
+            md5,
+            last_poll_time,
+        })
+        .collect();
+
+    Ok(HostView {
+        ip: ip.to_string(),
+        instances,
+        subscribed_services,
+        config_listeners,
+        last_heartbeat_millis,
+    })
+}
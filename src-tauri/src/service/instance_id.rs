@@ -0,0 +1,101 @@
+/**
+ * 实例 ID 生成模块
+ * 与 Nacos 一致的可插拔实例 ID 生成器：默认 simple 生成器按 ip#port#cluster#group@@service 拼接，
+ * snowflake 生成器生成单调递增的整数 ID，通过实例元数据 preserved.instance.id.generator 选择
+ */
+
+use std::sync::Mutex;
+
+/// 选择实例 ID 生成器的保留元数据键
+pub const INSTANCE_ID_GENERATOR_METADATA_KEY: &str = "preserved.instance.id.generator";
+/// simple 生成器名称（默认）
+pub const SIMPLE_INSTANCE_ID_GENERATOR: &str = "simple";
+/// snowflake 生成器名称
+pub const SNOWFLAKE_INSTANCE_ID_GENERATOR: &str = "snowflake";
+
+/// snowflake 起始时间（2020-01-01 00:00:00 UTC，毫秒）
+const SNOWFLAKE_EPOCH_MILLIS: i64 = 1_577_836_800_000;
+const SNOWFLAKE_WORKER_ID: i64 = 1;
+const SNOWFLAKE_WORKER_BITS: u32 = 10;
+const SNOWFLAKE_SEQUENCE_BITS: u32 = 12;
+const SNOWFLAKE_SEQUENCE_MASK: i64 = (1 << SNOWFLAKE_SEQUENCE_BITS) - 1;
+
+/// 实例 ID 生成器
+pub trait InstanceIdGenerator {
+    fn generate(&self, ip: &str, port: i32, cluster_name: &str, group_name: &str, service_name: &str) -> String;
+}
+
+/// simple 生成器：ip#port#cluster#group@@service
+pub struct SimpleInstanceIdGenerator;
+
+impl InstanceIdGenerator for SimpleInstanceIdGenerator {
+    fn generate(&self, ip: &str, port: i32, cluster_name: &str, group_name: &str, service_name: &str) -> String {
+        format!("{}#{}#{}#{}@@{}", ip, port, cluster_name, group_name, service_name)
+    }
+}
+
+/// snowflake 生成器：时间戳 | 节点 ID | 毫秒内序列号，进程内单调递增
+pub struct SnowflakeInstanceIdGenerator;
+
+/// 上一次生成 ID 的（时间戳，序列号）
+static SNOWFLAKE_STATE: Mutex<(i64, i64)> = Mutex::new((0, 0));
+
+impl SnowflakeInstanceIdGenerator {
+    fn next_id(&self) -> i64 {
+        let mut state = SNOWFLAKE_STATE.lock().unwrap();
+        let (last_millis, last_sequence) = *state;
+
+        let now = chrono::Utc::now().timestamp_millis() - SNOWFLAKE_EPOCH_MILLIS;
+        // 时钟回拨或同一毫秒内继续沿用上次的时间戳，保证 ID 递增
+        let (millis, sequence) = if now > last_millis {
+            (now, 0)
+        } else if last_sequence < SNOWFLAKE_SEQUENCE_MASK {
+            (last_millis, last_sequence + 1)
+        } else {
+            (last_millis + 1, 0)
+        };
+        *state = (millis, sequence);
+
+        (millis << (SNOWFLAKE_WORKER_BITS + SNOWFLAKE_SEQUENCE_BITS))
+            | (SNOWFLAKE_WORKER_ID << SNOWFLAKE_SEQUENCE_BITS)
+            | sequence
+    }
+}
+
+impl InstanceIdGenerator for SnowflakeInstanceIdGenerator {
+    fn generate(&self, _ip: &str, _port: i32, _cluster_name: &str, _group_name: &str, _service_name: &str) -> String {
+        self.next_id().to_string()
+    }
+}
+
+/// 从实例元数据 JSON 中读取生成器名称，未指定或无法识别时为 simple
+pub fn instance_id_generator_name(metadata: Option<&str>) -> &'static str {
+    let name = metadata
+        .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+        .and_then(|m| m.get(INSTANCE_ID_GENERATOR_METADATA_KEY).and_then(|v| v.as_str()).map(|v| v.to_lowercase()));
+
+    match name.as_deref() {
+        Some(SNOWFLAKE_INSTANCE_ID_GENERATOR) => SNOWFLAKE_INSTANCE_ID_GENERATOR,
+        _ => SIMPLE_INSTANCE_ID_GENERATOR,
+    }
+}
+
+/// 按生成器名称选择实例 ID 生成器
+fn instance_id_generator(metadata: Option<&str>) -> Box<dyn InstanceIdGenerator> {
+    match instance_id_generator_name(metadata) {
+        SNOWFLAKE_INSTANCE_ID_GENERATOR => Box::new(SnowflakeInstanceIdGenerator),
+        _ => Box::new(SimpleInstanceIdGenerator),
+    }
+}
+
+/// 按实例元数据选择的生成器生成实例 ID
+pub fn generate_instance_id(
+    ip: &str,
+    port: i32,
+    cluster_name: &str,
+    group_name: &str,
+    service_name: &str,
+    metadata: Option<&str>,
+) -> String {
+    instance_id_generator(metadata).generate(ip, port, cluster_name, group_name, service_name)
+}
@@ -0,0 +1,311 @@
+/**
+ * 实例元数据约定模块
+ * 服务可以声明实例元数据的约定（必需的键、允许的值和正则表达式），保存在 service_info.metadata_schema 中。
+ * 注册、更新、部分更新和批量元数据接口写入前按约定校验：强制模式拒绝违规的写入，
+ * 告警模式接受写入，违规信息通过响应和控制台的违规列表报告
+ */
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::{current_timestamp, get_service_detail, get_service_instances, query_service_instances};
+
+/// 元数据约定模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataSchemaMode {
+    /// 拒绝违规的写入
+    #[default]
+    Enforce,
+    /// 接受写入，只报告违规
+    Warn,
+}
+
+/// 单个元数据键的约定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataRule {
+    pub key: String,
+    #[serde(default)]
+    pub required: bool,
+    /// 允许的值，为空表示不限制
+    #[serde(default)]
+    pub allowed_values: Vec<String>,
+    /// 值必须完整匹配的正则表达式
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// 解析约定时编译好的正则表达式，pattern 编译失败时为空
+    #[serde(skip)]
+    regex: Option<Regex>,
+}
+
+/// 服务的元数据约定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataSchema {
+    #[serde(default)]
+    pub mode: MetadataSchemaMode,
+    pub rules: Vec<MetadataRule>,
+}
+
+/// 元数据违规
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataViolation {
+    pub key: String,
+    pub reason: String,
+}
+
+/// 实例的元数据违规
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceMetadataViolations {
+    pub instance_id: String,
+    pub ip: String,
+    pub port: i32,
+    pub cluster_name: String,
+    pub violations: Vec<MetadataViolation>,
+}
+
+/// 服务的元数据违规报告
+#[derive(Debug, Serialize)]
+pub struct MetadataViolationReport {
+    pub mode: MetadataSchemaMode,
+    /// 只包含存在违规的实例
+    pub instances: Vec<InstanceMetadataViolations>,
+}
+
+/// 完整匹配的正则表达式
+fn full_match_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{})$", pattern))
+}
+
+impl MetadataSchema {
+    /// 解析并校验元数据约定 JSON，同时编译各规则的正则表达式
+    pub fn parse(schema: &str) -> Result<Self, String> {
+        let mut schema: MetadataSchema =
+            serde_json::from_str(schema).map_err(|e| format!("Invalid metadata schema: {}", e))?;
+
+        let mut keys = std::collections::HashSet::new();
+        for rule in &schema.rules {
+            if rule.key.trim().is_empty() {
+                return Err("Invalid metadata schema: every rule requires a key".to_string());
+            }
+            if !keys.insert(rule.key.as_str()) {
+                return Err(format!("Invalid metadata schema: duplicate rule for key {}", rule.key));
+            }
+        }
+        for rule in &mut schema.rules {
+            if let Some(pattern) = rule.pattern.as_deref() {
+                rule.regex = Some(
+                    full_match_regex(pattern)
+                        .map_err(|e| format!("Invalid metadata schema: bad pattern for key {}: {}", rule.key, e))?,
+                );
+            }
+        }
+        Ok(schema)
+    }
+
+    /// 加载已保存的元数据约定；正则表达式编译失败的规则保留下来，检查时报告为违规
+    fn load(schema: &str) -> Result<Self, String> {
+        let mut schema: MetadataSchema =
+            serde_json::from_str(schema).map_err(|e| format!("Invalid metadata schema: {}", e))?;
+        for rule in &mut schema.rules {
+            rule.regex = rule.pattern.as_deref().and_then(|pattern| full_match_regex(pattern).ok());
+        }
+        Ok(schema)
+    }
+
+    /// 按约定检查元数据 JSON，返回所有违规
+    pub fn check(&self, metadata: Option<&str>) -> Vec<MetadataViolation> {
+        let metadata: serde_json::Map<String, serde_json::Value> = metadata
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_default();
+
+        let mut violations = Vec::new();
+        for rule in &self.rules {
+            let value = match metadata.get(&rule.key) {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => {
+                    if rule.required {
+                        violations.push(MetadataViolation {
+                            key: rule.key.clone(),
+                            reason: "required key is missing".to_string(),
+                        });
+                    }
+                    continue;
+                }
+            };
+
+            if !rule.allowed_values.is_empty() && !rule.allowed_values.contains(&value) {
+                violations.push(MetadataViolation {
+                    key: rule.key.clone(),
+                    reason: format!("value '{}' is not one of [{}]", value, rule.allowed_values.join(", ")),
+                });
+            }
+            if let Some(pattern) = rule.pattern.as_deref() {
+                match &rule.regex {
+                    Some(regex) if regex.is_match(&value) => {}
+                    Some(_) => violations.push(MetadataViolation {
+                        key: rule.key.clone(),
+                        reason: format!("value '{}' does not match pattern {}", value, pattern),
+                    }),
+                    None => violations.push(MetadataViolation {
+                        key: rule.key.clone(),
+                        reason: format!("pattern {} is not a valid regular expression", pattern),
+                    }),
+                }
+            }
+        }
+        violations
+    }
+
+    /// 写入前校验元数据：强制模式下存在违规时返回错误，告警模式下记录日志后放行
+    pub(super) fn enforce<'a>(
+        &self,
+        service_name: &str,
+        metadata: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> Result<(), String> {
+        let violations: Vec<MetadataViolation> = metadata.into_iter().flat_map(|m| self.check(m)).collect();
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        let summary = violations
+            .iter()
+            .map(|v| format!("{}: {}", v.key, v.reason))
+            .collect::<Vec<_>>()
+            .join("; ");
+        match self.mode {
+            MetadataSchemaMode::Enforce => Err(format!("Invalid metadata: {}", summary)),
+            MetadataSchemaMode::Warn => {
+                tracing::warn!("Metadata schema violations accepted for service {}: {}", service_name, summary);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 查询服务的元数据约定
+pub async fn get_metadata_schema(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<Option<MetadataSchema>, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let row: Option<(Option<String>,)> = db
+        .query_one(
+            "SELECT metadata_schema FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3",
+            &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query metadata schema: {}", e))?;
+
+    match row.and_then(|(schema,)| schema).filter(|s| !s.is_empty()) {
+        Some(schema) => MetadataSchema::load(&schema).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// 设置服务的元数据约定，schema 为空时清除约定
+pub async fn update_metadata_schema(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    schema: Option<&str>,
+) -> Result<(), String> {
+    let schema = match schema.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(schema) => Some(serde_json::to_string(&MetadataSchema::parse(schema)?)
+            .map_err(|e| format!("Failed to serialize metadata schema: {}", e))?),
+        None => None,
+    };
+
+    if get_service_detail(app, namespace_id, group_name, service_name).await?.is_none() {
+        return Err("Service not found".to_string());
+    }
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "UPDATE service_info SET metadata_schema = NULLIF(?1, ''), gmt_modified = ?2 WHERE namespace_id = ?3 AND group_name = ?4 AND service_name = ?5",
+        &[
+            ("?1", schema.as_deref().unwrap_or("")),
+            ("?2", current_timestamp().to_string().as_str()),
+            ("?3", namespace_id),
+            ("?4", group_name),
+            ("?5", service_name),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to update metadata schema: {}", e))?;
+
+    Ok(())
+}
+
+/// 按元数据约定检查服务的实例，instance_ids 为空时检查所有实例
+pub async fn get_metadata_violations(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instance_ids: &[String],
+) -> Result<Option<MetadataViolationReport>, String> {
+    let Some(schema) = get_metadata_schema(app, namespace_id, group_name, service_name).await? else {
+        return Ok(None);
+    };
+
+    let instances = get_service_instances(app, namespace_id, group_name, service_name)
+        .await?
+        .instances
+        .into_iter()
+        .filter(|inst| instance_ids.is_empty() || instance_ids.contains(&inst.instance_id))
+        .filter_map(|inst| {
+            let violations = schema.check(inst.metadata.as_deref());
+            (!violations.is_empty()).then(|| InstanceMetadataViolations {
+                instance_id: inst.instance_id,
+                ip: inst.ip,
+                port: inst.port,
+                cluster_name: inst.cluster_name,
+                violations,
+            })
+        })
+        .collect();
+
+    Ok(Some(MetadataViolationReport { mode: schema.mode, instances }))
+}
+
+/// 按服务的元数据约定校验对已有实例元数据的修改
+/// update 根据实例当前的元数据计算修改后的元数据；服务没有约定时直接放行
+pub(super) async fn enforce_instance_metadata(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    instance_ids: &[String],
+    update: impl Fn(Option<&str>) -> String,
+) -> Result<(), String> {
+    let Some(schema) = get_metadata_schema(app, namespace_id, group_name, service_name).await? else {
+        return Ok(());
+    };
+
+    let updated: Vec<String> = query_service_instances(app, namespace_id, group_name, service_name)
+        .await?
+        .instances
+        .iter()
+        .filter(|inst| instance_ids.contains(&inst.instance_id))
+        .map(|inst| update(inst.metadata.as_deref()))
+        .collect();
+
+    schema.enforce(service_name, updated.iter().map(|m| Some(m.as_str())))
+}
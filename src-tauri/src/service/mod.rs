@@ -3,8 +3,190 @@
  * 负责服务的 CRUD 操作、实例管理和历史记录
  */
 
+mod affinity;
+mod balancer;
+mod batch;
+mod catalog;
+mod cleaner;
+mod client;
+mod cluster;
+mod consul;
+mod ephemeral;
+mod eureka;
+mod export;
+mod failover;
+mod health_check;
+mod health_timeline;
+mod history;
+mod host;
+mod instance_id;
+mod metadata_schema;
+mod revision;
+mod simulator;
+mod subscriber;
+mod switches;
+mod xds;
+
 use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
+pub use subscriber::{
+    record_naming_subscriber,
+    remove_naming_subscriber,
+    get_naming_subscribers,
+    cleanup_expired_naming_subscribers,
+    NamingSubscriber,
+    NamingSubscriberListResponse,
+    NAMING_SUBSCRIBER_EXPIRE_SECONDS,
+};
+pub use cluster::{
+    get_service_clusters,
+    get_cluster,
+    ensure_cluster,
+    update_cluster,
+    delete_service_clusters,
+    ClusterInfo,
+    HealthChecker,
+    UpdateClusterRequest,
+    DEFAULT_HEALTH_CHECKER,
+    DEFAULT_CHECK_PORT,
+};
+pub use client::{
+    list_clients,
+    get_client_detail,
+    get_client_published_services,
+    get_client_subscribed_services,
+    get_service_publisher_clients,
+    get_service_subscriber_clients,
+    build_client_id,
+    parse_client_id,
+    ClientDetail,
+    ClientPublishedService,
+    ClientSubscribedService,
+    ServiceClient,
+};
+pub use batch::{
+    batch_register_instances,
+    batch_deregister_instances,
+    BatchInstanceItem,
+    BatchInstanceRef,
+    BatchInstanceResult,
+};
+pub use instance_id::{
+    generate_instance_id, instance_id_generator_name, InstanceIdGenerator, SimpleInstanceIdGenerator, SnowflakeInstanceIdGenerator,
+    INSTANCE_ID_GENERATOR_METADATA_KEY, SIMPLE_INSTANCE_ID_GENERATOR, SNOWFLAKE_INSTANCE_ID_GENERATOR,
+};
+pub use cleaner::{
+    ensure_service, run_empty_service_clean_round, start_empty_service_cleaner_task,
+    DEFAULT_EMPTY_SERVICE_CLEAN_INTERVAL_SECONDS, DEFAULT_EMPTY_SERVICE_EXPIRED_TIME_SECONDS,
+    PERSIST_SERVICE_METADATA_KEY, SERVICE_CREATE_SOURCE_API, SERVICE_CREATE_SOURCE_REGISTER,
+};
+pub use switches::{
+    get_naming_switches, update_naming_switch, NamingSwitches, SWITCH_CLIENT_BEAT_INTERVAL,
+    SWITCH_DEFAULT_INSTANCE_EPHEMERAL, SWITCH_DISABLE_ADD_IP, SWITCH_HEALTH_CHECK_ENABLED, SWITCH_PUSH_ENABLED,
+    SWITCH_ZONE_AFFINITY_EXCLUSIVE, SWITCH_ZONE_AFFINITY_THRESHOLD, SWITCH_ZONE_IP_LABELS,
+};
+pub use consul::{
+    consul_check_id, consul_check_status, consul_service_id, consul_tags, consul_user_metadata,
+    deregister_consul_service, find_consul_service, get_consul_catalog_services, get_consul_service_index,
+    get_consul_service_instances, register_consul_service, update_consul_check, ConsulServiceRegistration,
+    ConsulTtlCheck, CONSUL_CHECK_CRITICAL, CONSUL_CHECK_ID_METADATA_KEY, CONSUL_CHECK_PASSING, CONSUL_GROUP_NAME,
+    CONSUL_NAMESPACE_ID, CONSUL_SERVICE_ID_METADATA_KEY, CONSUL_TAGS_METADATA_KEY,
+};
+pub use eureka::{
+    cancel_eureka_instance, eureka_apps_hashcode, eureka_instance_id, eureka_instance_info, eureka_overridden_status,
+    eureka_status, eureka_user_metadata, find_eureka_instance, get_eureka_application, get_eureka_applications,
+    get_eureka_delta, register_eureka_instance, renew_eureka_instance, resolve_eureka_service_name,
+    update_eureka_status_override, EurekaApplication, EurekaDelta, EurekaInstanceChange, EurekaRegistration,
+    DEFAULT_EUREKA_LEASE_DURATION_SECONDS, DEFAULT_EUREKA_RENEWAL_INTERVAL_SECONDS, EUREKA_ACTION_ADDED,
+    EUREKA_ACTION_DELETED, EUREKA_ACTION_MODIFIED, EUREKA_GROUP_NAME, EUREKA_INSTANCE_ID_METADATA_KEY,
+    EUREKA_INSTANCE_INFO_METADATA_KEY, EUREKA_NAMESPACE_ID, EUREKA_OVERRIDDEN_STATUS_METADATA_KEY, EUREKA_STATUS_DOWN,
+    EUREKA_STATUS_OUT_OF_SERVICE, EUREKA_STATUS_STARTING, EUREKA_STATUS_UNKNOWN, EUREKA_STATUS_UP,
+};
+pub use simulator::{
+    start_simulation,
+    stop_simulation,
+    get_simulation,
+    list_simulations,
+    SimulationDefinition,
+    SimulationStatus,
+};
+pub use xds::{get_xds_snapshot, XdsCluster, XdsSnapshot};
+pub use balancer::{select_one_healthy_instance, SelectInstanceOptions};
+pub use affinity::{apply_zone_affinity, resolve_consumer_zone, ZONE_METADATA_KEY};
+pub use catalog::{
+    list_catalog_services, list_catalog_instances, get_catalog_service,
+    CatalogServiceQuery, CatalogServiceListResponse, CatalogInstanceQuery, CatalogInstanceListResponse, CatalogServiceDetail,
+};
+pub use host::{get_host_view, HostView};
+pub use revision::{
+    bump_service_revision, get_service_revision, compute_instances_checksum, subscribe_service_changes, ServiceChangeEvent,
+    ServiceRevision,
+};
+pub use health_check::{start_health_check_task, run_health_check_round, HEALTH_CHECK_INTERVAL_SECONDS};
+pub use health_timeline::{
+    record_health_event,
+    get_health_timeline,
+    HealthChangeSource,
+    HealthTimelineQuery,
+    HealthTimelineResponse,
+};
+pub use history::{
+    record_service_history,
+    record_instance_history,
+    get_service_history,
+    instance_address,
+    ServiceChangeType,
+    ServiceHistoryInfo,
+    ServiceHistoryQuery,
+    ServiceHistoryListResponse,
+};
+pub use export::{
+    find_service_export,
+    get_service_exports,
+    list_service_exports,
+    create_service_export,
+    revoke_service_export,
+    remove_namespace_exports,
+    ServiceExport,
+    CreateServiceExportRequest,
+    ServiceExportQuery,
+};
+pub use failover::{
+    build_service_info,
+    build_failover_snapshot,
+    failover_snapshot_zip,
+    export_failover_snapshot,
+    FailoverExportRequest,
+    FailoverExportSummary,
+};
+pub use metadata_schema::{
+    get_metadata_schema,
+    update_metadata_schema,
+    get_metadata_violations,
+    MetadataSchema,
+    InstanceMetadataViolations,
+    MetadataViolationReport,
+};
+pub use ephemeral::{
+    manage_ephemeral_registry,
+    upsert_ephemeral_instance,
+    get_ephemeral_instances,
+    all_ephemeral_instances,
+    ephemeral_instances_by_ip,
+    update_ephemeral_instance,
+    remove_ephemeral_instance,
+    remove_service_ephemeral_instances,
+    remove_namespace_ephemeral_instances,
+    beat_ephemeral_instance,
+    touch_ephemeral_instance,
+    expire_ephemeral_heartbeat,
+    start_ephemeral_expiry_task,
+    run_ephemeral_expiry_round,
+    purge_persisted_ephemeral_instances,
+    BeatSettings,
+    HEART_BEAT_TIMEOUT_METADATA_KEY,
+    IP_DELETE_TIMEOUT_METADATA_KEY,
+};
 
 /// 服务信息
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,7 +204,7 @@ pub struct ServiceInfo {
 }
 
 /// 实例信息
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstanceInfo {
     pub id: Option<i64>,
     pub namespace_id: String,
@@ -106,6 +288,62 @@ pub struct RegisterInstanceRequest {
     pub metadata: Option<String>,
 }
 
+/// 注册实例前的公共检查（单个注册和批量注册共用）：服务必须存在且不是导出别名，且允许新增 IP
+/// 返回命名开关和服务的元数据约定，用于填充默认值和校验实例元数据
+pub(crate) async fn check_registration(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> Result<(NamingSwitches, Option<MetadataSchema>), String> {
+    if get_service_detail(app, namespace_id, group_name, service_name).await?.is_none() {
+        export::ensure_not_exported(app, namespace_id, group_name, service_name).await?;
+        return Err("Service not found".to_string());
+    }
+
+    let switches = get_naming_switches(app).await;
+    if switches.disable_add_ip {
+        return Err("Adding IP is disabled".to_string());
+    }
+
+    let schema = get_metadata_schema(app, namespace_id, group_name, service_name).await?;
+    Ok((switches, schema))
+}
+
+/// 按注册请求构建待写入的实例：填充默认值并生成实例 ID（单个注册和批量注册共用）
+pub(crate) fn new_instance(request: &RegisterInstanceRequest, switches: &NamingSwitches) -> InstanceInfo {
+    let cluster_name = request
+        .cluster_name
+        .clone()
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "DEFAULT".to_string());
+    let now = current_timestamp();
+    InstanceInfo {
+        id: None,
+        namespace_id: request.namespace_id.clone(),
+        group_name: request.group_name.clone(),
+        service_name: request.service_name.clone(),
+        instance_id: generate_instance_id(
+            &request.ip,
+            request.port,
+            &cluster_name,
+            &request.group_name,
+            &request.service_name,
+            request.metadata.as_deref(),
+        ),
+        ip: request.ip.clone(),
+        port: request.port,
+        weight: request.weight.unwrap_or(1.0),
+        healthy: request.healthy.unwrap_or(true),
+        enabled: request.enabled.unwrap_or(true),
+        ephemeral: request.ephemeral.unwrap_or(switches.default_instance_ephemeral),
+        cluster_name,
+        metadata: request.metadata.clone(),
+        gmt_create: now,
+        gmt_modified: now,
+    }
+}
+
 /// 获取当前时间戳（秒）
 fn current_timestamp() -> i64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -115,10 +353,18 @@ fn current_timestamp() -> i64 {
         .as_secs() as i64
 }
 
-/// 生成实例 ID
-fn generate_instance_id(ip: &str, port: i32) -> String {
-    use uuid::Uuid;
-    format!("{}#{}#DEFAULT#DEFAULT_GROUP@@{}", ip, port, Uuid::new_v4())
+/// 获取当前时间戳（毫秒）
+pub(crate) fn current_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// 将元数据补丁合并到现有元数据 JSON 中（新值覆盖旧值）
+fn merge_metadata_json(current: Option<&str>, patch: &std::collections::HashMap<String, String>) -> String {
+    let mut merged: std::collections::HashMap<String, String> = current
+        .and_then(|s| serde_json::from_str(s).ok())
+        .unwrap_or_default();
+    merged.extend(patch.clone());
+    serde_json::to_string(&merged).unwrap_or_default()
 }
 
 /// 查询服务列表
@@ -279,7 +525,12 @@ pub async fn create_service(
         .await
         .map_err(|e| format!("Failed to check existing service: {}", e))?;
 
-    if existing.is_some() {
+    // 导出别名同样占用服务名
+    if existing.is_some()
+        || find_service_export(app, &request.namespace_id, &request.group_name, &request.service_name)
+            .await?
+            .is_some()
+    {
         return Err("Service already exists".to_string());
     }
 
@@ -305,19 +556,15 @@ pub async fn create_service(
     .map_err(|e| format!("Failed to create service: {}", e))?;
 
     // 记录历史
-    db.execute(
-        "INSERT INTO service_history_info (namespace_id, group_name, service_name, change_type, change_detail, gmt_create) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        &[
-            ("?1", &request.namespace_id),
-            ("?2", &request.group_name),
-            ("?3", &request.service_name),
-            ("?4", "CREATE"),
-            ("?5", &format!("Service created: {}", request.service_name)),
-            ("?6", &now.to_string()),
-        ],
+    record_service_history(
+        app,
+        &request.namespace_id,
+        &request.group_name,
+        &request.service_name,
+        ServiceChangeType::Create,
+        &format!("Service created: {}", request.service_name),
     )
-    .await
-    .map_err(|e| format!("Failed to insert service history: {}", e))?;
+    .await?;
 
     // 返回创建的服务
     get_service_detail(app, &request.namespace_id, &request.group_name, &request.service_name)
@@ -370,19 +617,15 @@ pub async fn update_service(
     .map_err(|e| format!("Failed to update service: {}", e))?;
 
     // 记录历史
-    db.execute(
-        "INSERT INTO service_history_info (namespace_id, group_name, service_name, change_type, change_detail, gmt_create) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        &[
-            ("?1", &request.namespace_id),
-            ("?2", &request.group_name),
-            ("?3", &request.service_name),
-            ("?4", "UPDATE"),
-            ("?5", &format!("Service updated: {}", request.service_name)),
-            ("?6", &now.to_string()),
-        ],
+    record_service_history(
+        app,
+        &request.namespace_id,
+        &request.group_name,
+        &request.service_name,
+        ServiceChangeType::Update,
+        &format!("Service updated: {}", request.service_name),
     )
-    .await
-    .map_err(|e| format!("Failed to insert service history: {}", e))?;
+    .await?;
 
     // 返回更新的服务
     get_service_detail(app, &request.namespace_id, &request.group_name, &request.service_name)
@@ -396,6 +639,17 @@ pub async fn delete_service(
     namespace_id: &str,
     group_name: &str,
     service_name: &str,
+) -> Result<(), String> {
+    remove_service(app, namespace_id, group_name, service_name, &format!("Service deleted: {}", service_name)).await
+}
+
+/// 删除服务及其实例和集群，并以给定描述记录删除历史
+async fn remove_service(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    change_detail: &str,
 ) -> Result<(), String> {
     let db = app
         .sqlite_plugin()
@@ -403,22 +657,16 @@ pub async fn delete_service(
         .await
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
-    let now = current_timestamp();
-
     // 记录删除历史
-    db.execute(
-        "INSERT INTO service_history_info (namespace_id, group_name, service_name, change_type, change_detail, gmt_create) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        &[
-            ("?1", namespace_id),
-            ("?2", group_name),
-            ("?3", service_name),
-            ("?4", "DELETE"),
-            ("?5", &format!("Service deleted: {}", service_name)),
-            ("?6", &now.to_string()),
-        ],
+    record_service_history(
+        app,
+        namespace_id,
+        group_name,
+        service_name,
+        ServiceChangeType::Delete,
+        change_detail,
     )
-    .await
-        .map_err(|e| format!("Failed to insert delete history: {}", e))?;
+    .await?;
 
     // 删除服务实例
     db.execute(
@@ -428,6 +676,13 @@ pub async fn delete_service(
     .await
     .map_err(|e| format!("Failed to delete service instances: {}", e))?;
 
+    remove_service_ephemeral_instances(app, namespace_id, group_name, service_name).await;
+
+    bump_service_revision(app, namespace_id, group_name, service_name).await;
+
+    // 删除服务集群
+    delete_service_clusters(app, namespace_id, group_name, service_name).await?;
+
     // 删除服务
     db.execute(
         "DELETE FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3",
@@ -436,15 +691,34 @@ pub async fn delete_service(
     .await
     .map_err(|e| format!("Failed to delete service: {}", e))?;
 
+    // 删除以该服务为源的导出，导出目标不再指向不存在的服务
+    export::remove_service_exports(app, namespace_id, group_name, service_name).await?;
+
     Ok(())
 }
 
 /// 查询服务实例列表
+/// 服务是导出别名时返回源服务的实例
 pub async fn get_service_instances(
     app: &AppHandle,
     namespace_id: &str,
     group_name: &str,
     service_name: &str,
+) -> Result<InstanceListResponse, String> {
+    match find_service_export(app, namespace_id, group_name, service_name).await? {
+        Some(export) => {
+            query_service_instances(app, &export.source_namespace_id, &export.source_group_name, &export.source_service_name).await
+        }
+        None => query_service_instances(app, namespace_id, group_name, service_name).await,
+    }
+}
+
+/// 查询服务自身的实例列表（不解析导出）
+async fn query_service_instances(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
 ) -> Result<InstanceListResponse, String> {
     let db = app
         .sqlite_plugin()
@@ -492,9 +766,66 @@ pub async fn get_service_instances(
         }
     }
 
+    // 合并内存中的临时实例
+    instances.extend(get_ephemeral_instances(app, namespace_id, group_name, service_name).await);
+
     Ok(InstanceListResponse { instances })
 }
 
+/// 按 IP、端口和集群查找实例
+pub async fn find_instance(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    ip: &str,
+    port: i32,
+    cluster_name: &str,
+) -> Result<Option<InstanceInfo>, String> {
+    // 只查找服务自身的实例，导出别名是只读的
+    let response = query_service_instances(app, namespace_id, group_name, service_name).await?;
+    Ok(response
+        .instances
+        .into_iter()
+        .find(|inst| inst.ip == ip && inst.port == port && inst.cluster_name == cluster_name))
+}
+
+/// 处理实例心跳，返回实例是否存在
+/// 临时实例刷新内存中的心跳时间；数据库中的实例仅在不健康时恢复为健康
+pub async fn heartbeat_instance(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    ip: &str,
+    port: i32,
+    cluster_name: &str,
+) -> Result<bool, String> {
+    if beat_ephemeral_instance(app, namespace_id, group_name, service_name, ip, port, cluster_name).await {
+        return Ok(true);
+    }
+
+    match find_instance(app, namespace_id, group_name, service_name, ip, port, cluster_name).await? {
+        Some(instance) => {
+            if !instance.healthy && instance.ephemeral {
+                update_instance_health(
+                    app,
+                    namespace_id,
+                    group_name,
+                    service_name,
+                    &instance.instance_id,
+                    true,
+                    HealthChangeSource::Heartbeat,
+                    "heartbeat resumed",
+                )
+                .await?;
+            }
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
 /// 注册实例
 pub async fn register_instance(
     app: &AppHandle,
@@ -506,46 +837,69 @@ pub async fn register_instance(
         .await
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
-    // 检查服务是否存在
-    let service_exists: Option<(i64,)> = db
-        .query_one(
-            "SELECT id FROM service_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3",
-            &[
-                ("?1", &request.namespace_id),
-                ("?2", &request.group_name),
-                ("?3", &request.service_name),
-            ],
-        )
-        .await
-        .map_err(|e| format!("Failed to check service: {}", e))?;
-
-    if service_exists.is_none() {
-        return Err("Service not found".to_string());
+    let (switches, schema) = check_registration(app, &request.namespace_id, &request.group_name, &request.service_name).await?;
+    if let Some(schema) = schema {
+        schema.enforce(&request.service_name, [request.metadata.as_deref()])?;
     }
 
-    let instance_id = generate_instance_id(&request.ip, request.port);
-    let weight = request.weight.unwrap_or(1.0);
-    let healthy = request.healthy.unwrap_or(true);
-    let enabled = request.enabled.unwrap_or(true);
-    let ephemeral = request.ephemeral.unwrap_or(true);
-    let cluster_name = request.cluster_name.unwrap_or_else(|| "DEFAULT".to_string());
-    let now = current_timestamp();
+    let instance = new_instance(&request, &switches);
+    let InstanceInfo { weight, healthy, enabled, ephemeral, gmt_create: now, .. } = instance;
+    let cluster_name = instance.cluster_name.clone();
+
+    // 首次注册实例时自动创建集群
+    ensure_cluster(app, &request.namespace_id, &request.group_name, &request.service_name, &cluster_name).await?;
+
+    // 临时实例只写入内存注册表
+    if ephemeral {
+        let instance = upsert_ephemeral_instance(app, instance).await;
+
+        bump_service_revision(app, &request.namespace_id, &request.group_name, &request.service_name).await;
+        record_instance_history(
+            app,
+            &instance.namespace_id,
+            &instance.group_name,
+            &instance.service_name,
+            ServiceChangeType::Register,
+            &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+            "registered",
+        )
+        .await;
+        record_health_event(
+            app,
+            &instance.namespace_id,
+            &instance.group_name,
+            &instance.service_name,
+            &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+            instance.healthy,
+            HealthChangeSource::Register,
+            "registered",
+        )
+        .await;
+        return Ok(instance);
+    }
 
-    // 检查实例是否已存在
-    let existing: Option<(i64,)> = db
+    // 检查实例是否已存在（按 IP、端口和集群识别，重复注册沿用原实例 ID）
+    let existing: Option<(i64, String)> = db
         .query_one(
-            "SELECT id FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
+            "SELECT id, instance_id FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND ip = ?4 AND port = ?5 AND cluster_name = ?6",
             &[
                 ("?1", &request.namespace_id),
                 ("?2", &request.group_name),
                 ("?3", &request.service_name),
-                ("?4", &instance_id),
+                ("?4", &request.ip),
+                ("?5", &request.port.to_string()),
+                ("?6", &cluster_name),
             ],
         )
         .await
         .map_err(|e| format!("Failed to check existing instance: {}", e))?;
 
-    if let Some((id,)) = existing {
+    let instance_id = match &existing {
+        Some((_, instance_id)) => instance_id.clone(),
+        None => instance.instance_id.clone(),
+    };
+
+    if let Some((id, _)) = existing {
         // 更新现有实例
         db.execute(
             "UPDATE instance_info SET ip = ?1, port = ?2, weight = ?3, healthy = ?4, enabled = ?5, ephemeral = ?6, cluster_name = ?7, metadata = ?8, gmt_modified = ?9 WHERE id = ?10",
@@ -589,6 +943,29 @@ pub async fn register_instance(
         .map_err(|e| format!("Failed to register instance: {}", e))?;
     }
 
+    bump_service_revision(app, &request.namespace_id, &request.group_name, &request.service_name).await;
+    record_instance_history(
+        app,
+        &request.namespace_id,
+        &request.group_name,
+        &request.service_name,
+        ServiceChangeType::Register,
+        &instance_address(&request.ip, request.port, &cluster_name),
+        "registered",
+    )
+    .await;
+    record_health_event(
+        app,
+        &request.namespace_id,
+        &request.group_name,
+        &request.service_name,
+        &instance_address(&request.ip, request.port, &cluster_name),
+        healthy,
+        HealthChangeSource::Register,
+        "registered",
+    )
+    .await;
+
     // 查询并返回实例
     let instance: Option<(i64, String, String, String, String, String, i32, f64, bool, bool, bool, String, Option<String>, i64, i64)> = db
         .query_one(
@@ -640,6 +1017,45 @@ pub async fn deregister_instance(
         .await
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
+    if let Some(instance) = remove_ephemeral_instance(app, namespace_id, group_name, service_name, instance_id).await {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+        record_instance_history(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            ServiceChangeType::Deregister,
+            &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+            "deregistered",
+        )
+        .await;
+        record_health_event(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+            false,
+            HealthChangeSource::Deregister,
+            "deregistered",
+        )
+        .await;
+        return Ok(());
+    }
+
+    let existing: Option<(String, i32, String)> = db
+        .query_one(
+            "SELECT ip, port, cluster_name FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
+            &[
+                ("?1", namespace_id),
+                ("?2", group_name),
+                ("?3", service_name),
+                ("?4", instance_id),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to check instance: {}", e))?;
+
     // 删除实例
     db.execute(
         "DELETE FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
@@ -653,6 +1069,32 @@ pub async fn deregister_instance(
     .await
     .map_err(|e| format!("Failed to deregister instance: {}", e))?;
 
+    bump_service_revision(app, namespace_id, group_name, service_name).await;
+
+    if let Some((ip, port, cluster_name)) = existing {
+        record_instance_history(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            ServiceChangeType::Deregister,
+            &instance_address(&ip, port, &cluster_name),
+            "deregistered",
+        )
+        .await;
+        record_health_event(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &instance_address(&ip, port, &cluster_name),
+            false,
+            HealthChangeSource::Deregister,
+            "deregistered",
+        )
+        .await;
+    }
+
     Ok(())
 }
 
@@ -666,7 +1108,44 @@ pub async fn update_instance_health(
     service_name: &str,
     instance_id: &str,
     healthy: bool,
+    source: HealthChangeSource,
+    reason: &str,
 ) -> Result<(), String> {
+    let mut previous_healthy = healthy;
+    let updated = update_ephemeral_instance(app, namespace_id, group_name, service_name, instance_id, |inst| {
+        previous_healthy = inst.healthy;
+        inst.healthy = healthy;
+    })
+    .await;
+
+    if let Some(instance) = updated {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+        if previous_healthy != healthy {
+            record_instance_history(
+                app,
+                namespace_id,
+                group_name,
+                service_name,
+                ServiceChangeType::Health,
+                &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+                if healthy { "became healthy" } else { "became unhealthy" },
+            )
+            .await;
+            record_health_event(
+                app,
+                namespace_id,
+                group_name,
+                service_name,
+                &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+                healthy,
+                source,
+                reason,
+            )
+            .await;
+        }
+        return Ok(());
+    }
+
     let db = app
         .sqlite_plugin()
         .get_connection("sqlite:nacos.db")
@@ -674,9 +1153,9 @@ pub async fn update_instance_health(
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
     // 检查实例是否存在
-    let existing: Option<(i64,)> = db
+    let existing: Option<(i64, bool, String, String, i32, bool)> = db
         .query_one(
-            "SELECT id FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
+            "SELECT id, ephemeral, cluster_name, ip, port, healthy FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
             &[
                 ("?1", namespace_id),
                 ("?2", group_name),
@@ -687,8 +1166,15 @@ pub async fn update_instance_health(
         .await
         .map_err(|e| format!("Failed to check instance: {}", e))?;
 
-    if existing.is_none() {
-        return Err("Instance not found".to_string());
+    let (_, ephemeral, cluster_name, ip, port, previous_healthy) = existing.ok_or_else(|| "Instance not found".to_string())?;
+
+    // 持久化实例的集群启用了主动健康检查时，不允许手动修改健康状态
+    if !ephemeral {
+        if let Some(cluster) = get_cluster(app, namespace_id, group_name, service_name, &cluster_name).await? {
+            if cluster.checker().is_active() {
+                return Err("Health check is still working, cluster health checker is not NONE".to_string());
+            }
+        }
     }
 
     let now = current_timestamp();
@@ -708,6 +1194,32 @@ pub async fn update_instance_health(
     .await
     .map_err(|e| format!("Failed to update instance health: {}", e))?;
 
+    bump_service_revision(app, namespace_id, group_name, service_name).await;
+
+    if previous_healthy != healthy {
+        record_instance_history(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            ServiceChangeType::Health,
+            &instance_address(&ip, port, &cluster_name),
+            if healthy { "became healthy" } else { "became unhealthy" },
+        )
+        .await;
+        record_health_event(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &instance_address(&ip, port, &cluster_name),
+            healthy,
+            source,
+            reason,
+        )
+        .await;
+    }
+
     Ok(())
 }
 
@@ -723,6 +1235,62 @@ pub async fn patch_instance(
     healthy: Option<bool>,
     metadata: Option<String>,
 ) -> Result<(), String> {
+    let metadata_patch: Option<std::collections::HashMap<String, String>> = metadata
+        .as_deref()
+        .map(|m| serde_json::from_str(m).unwrap_or_default());
+
+    if let Some(patch) = &metadata_patch {
+        metadata_schema::enforce_instance_metadata(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &[instance_id.to_string()],
+            |current| merge_metadata_json(current, patch),
+        )
+        .await?;
+    }
+
+    let mut previous_healthy = None;
+    let patched = update_ephemeral_instance(app, namespace_id, group_name, service_name, instance_id, |inst| {
+        if let Some(patch) = &metadata_patch {
+            inst.metadata = Some(merge_metadata_json(inst.metadata.as_deref(), patch));
+        }
+        previous_healthy = Some(inst.healthy);
+        inst.weight = weight.unwrap_or(inst.weight);
+        inst.healthy = healthy.unwrap_or(inst.healthy);
+        inst.enabled = enabled.unwrap_or(inst.enabled);
+    })
+    .await;
+
+    if let Some(instance) = patched {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+        record_instance_history(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            ServiceChangeType::Metadata,
+            &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+            "updated",
+        )
+        .await;
+        if previous_healthy != Some(instance.healthy) {
+            record_health_event(
+                app,
+                namespace_id,
+                group_name,
+                service_name,
+                &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+                instance.healthy,
+                HealthChangeSource::Manual,
+                "instance patched",
+            )
+            .await;
+        }
+        return Ok(());
+    }
+
     let db = app
         .sqlite_plugin()
         .get_connection("sqlite:nacos.db")
@@ -730,9 +1298,9 @@ pub async fn patch_instance(
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
     // 检查实例是否存在
-    let existing: Option<(String, f64, bool, bool, Option<String>)> = db
+    let existing: Option<(String, i32, String, f64, bool, bool, Option<String>)> = db
         .query_one(
-            "SELECT ip, weight, healthy, enabled, metadata FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
+            "SELECT ip, port, cluster_name, weight, healthy, enabled, metadata FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
             &[
                 ("?1", namespace_id),
                 ("?2", group_name),
@@ -747,7 +1315,7 @@ pub async fn patch_instance(
         return Err("Instance not found".to_string());
     }
 
-    let (_, current_weight, current_healthy, current_enabled, current_metadata) = existing.unwrap();
+    let (ip, port, cluster_name, current_weight, current_healthy, current_enabled, current_metadata) = existing.unwrap();
     
     // 合并元数据
     let final_metadata = if let Some(new_metadata) = metadata {
@@ -794,6 +1362,31 @@ pub async fn patch_instance(
     .await
     .map_err(|e| format!("Failed to patch instance: {}", e))?;
 
+    bump_service_revision(app, namespace_id, group_name, service_name).await;
+    record_instance_history(
+        app,
+        namespace_id,
+        group_name,
+        service_name,
+        ServiceChangeType::Metadata,
+        &instance_address(&ip, port, &cluster_name),
+        "updated",
+    )
+    .await;
+    if current_healthy != final_healthy {
+        record_health_event(
+            app,
+            namespace_id,
+            group_name,
+            service_name,
+            &instance_address(&ip, port, &cluster_name),
+            final_healthy,
+            HealthChangeSource::Manual,
+            "instance patched",
+        )
+        .await;
+    }
+
     Ok(())
 }
 
@@ -812,16 +1405,41 @@ pub async fn batch_update_instance_metadata(
         .await
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
+    // 任何实例违反元数据约定时整批拒绝
+    metadata_schema::enforce_instance_metadata(app, namespace_id, group_name, service_name, instance_ids, |current| {
+        merge_metadata_json(current, metadata)
+    })
+    .await?;
+
     let mut updated_instances = Vec::new();
     let now = current_timestamp();
     let metadata_json = serde_json::to_string(metadata)
         .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
 
     for instance_id in instance_ids {
+        let patched = update_ephemeral_instance(app, namespace_id, group_name, service_name, instance_id, |inst| {
+            inst.metadata = Some(merge_metadata_json(inst.metadata.as_deref(), metadata));
+        })
+        .await;
+        if let Some(instance) = patched {
+            record_instance_history(
+                app,
+                namespace_id,
+                group_name,
+                service_name,
+                ServiceChangeType::Metadata,
+                &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+                "metadata updated",
+            )
+            .await;
+            updated_instances.push(instance_id.clone());
+            continue;
+        }
+
         // 获取现有元数据
-        let existing: Option<(Option<String>,)> = db
+        let existing: Option<(Option<String>, String, i32, String)> = db
             .query_one(
-                "SELECT metadata FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
+                "SELECT metadata, ip, port, cluster_name FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
                 &[
                     ("?1", namespace_id),
                     ("?2", group_name),
@@ -832,7 +1450,7 @@ pub async fn batch_update_instance_metadata(
             .await
             .map_err(|e| format!("Failed to query instance: {}", e))?;
 
-        if let Some((current_metadata,)) = existing {
+        if let Some((current_metadata, ip, port, cluster_name)) = existing {
             // 合并元数据
             let mut existing_metadata_map: std::collections::HashMap<String, String> = current_metadata
                 .as_ref()
@@ -861,10 +1479,24 @@ pub async fn batch_update_instance_metadata(
             .await
             .map_err(|e| format!("Failed to update instance metadata: {}", e))?;
 
+            record_instance_history(
+                app,
+                namespace_id,
+                group_name,
+                service_name,
+                ServiceChangeType::Metadata,
+                &instance_address(&ip, port, &cluster_name),
+                "metadata updated",
+            )
+            .await;
             updated_instances.push(instance_id.clone());
         }
     }
 
+    if !updated_instances.is_empty() {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+    }
+
     Ok(updated_instances)
 }
 
@@ -883,14 +1515,56 @@ pub async fn batch_delete_instance_metadata(
         .await
         .map_err(|e| format!("Failed to get database connection: {}", e))?;
 
+    // 删除必需的键等违反元数据约定时整批拒绝
+    metadata_schema::enforce_instance_metadata(app, namespace_id, group_name, service_name, instance_ids, |current| {
+        let mut metadata_map: std::collections::HashMap<String, String> = current
+            .and_then(|m| serde_json::from_str(m).ok())
+            .unwrap_or_default();
+        for key in metadata_keys {
+            metadata_map.remove(key);
+        }
+        serde_json::to_string(&metadata_map).unwrap_or_default()
+    })
+    .await?;
+
     let mut deleted_instances = Vec::new();
     let now = current_timestamp();
 
     for instance_id in instance_ids {
+        let mut has_deleted = false;
+        let patched = update_ephemeral_instance(app, namespace_id, group_name, service_name, instance_id, |inst| {
+            let mut metadata_map: std::collections::HashMap<String, String> = inst
+                .metadata
+                .as_deref()
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_default();
+            for key in metadata_keys {
+                has_deleted |= metadata_map.remove(key).is_some();
+            }
+            inst.metadata = serde_json::to_string(&metadata_map).ok();
+        })
+        .await;
+        if let Some(instance) = patched {
+            if has_deleted {
+                record_instance_history(
+                    app,
+                    namespace_id,
+                    group_name,
+                    service_name,
+                    ServiceChangeType::Metadata,
+                    &instance_address(&instance.ip, instance.port, &instance.cluster_name),
+                    "metadata deleted",
+                )
+                .await;
+                deleted_instances.push(instance_id.clone());
+            }
+            continue;
+        }
+
         // 获取现有元数据
-        let existing: Option<(Option<String>,)> = db
+        let existing: Option<(Option<String>, String, i32, String)> = db
             .query_one(
-                "SELECT metadata FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
+                "SELECT metadata, ip, port, cluster_name FROM instance_info WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND instance_id = ?4",
                 &[
                     ("?1", namespace_id),
                     ("?2", group_name),
@@ -901,7 +1575,7 @@ pub async fn batch_delete_instance_metadata(
             .await
             .map_err(|e| format!("Failed to query instance: {}", e))?;
 
-        if let Some((current_metadata,)) = existing {
+        if let Some((current_metadata, ip, port, cluster_name)) = existing {
             // 解析现有元数据
             let mut existing_metadata_map: std::collections::HashMap<String, String> = current_metadata
                 .as_ref()
@@ -936,10 +1610,24 @@ pub async fn batch_delete_instance_metadata(
                 .await
                 .map_err(|e| format!("Failed to update instance metadata: {}", e))?;
 
+                record_instance_history(
+                    app,
+                    namespace_id,
+                    group_name,
+                    service_name,
+                    ServiceChangeType::Metadata,
+                    &instance_address(&ip, port, &cluster_name),
+                    "metadata deleted",
+                )
+                .await;
                 deleted_instances.push(instance_id.clone());
             }
         }
     }
 
+    if !deleted_instances.is_empty() {
+        bump_service_revision(app, namespace_id, group_name, service_name).await;
+    }
+
     Ok(deleted_instances)
 }
@@ -0,0 +1,159 @@
+/**
+ * 服务修订号模块
+ * 为每个服务维护内存中的修订号（实例每次变更递增）和实例集合校验和，
+ * 客户端可携带上次的 checksum 获取轻量的“未变化”响应；
+ * 修订号递增时广播服务变更事件，gRPC 推送任务据此推送订阅的服务
+ */
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::sync::{broadcast, Mutex};
+
+use super::{current_millis, get_service_exports, InstanceInfo};
+
+/// 服务修订信息
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ServiceRevision {
+    /// 修订号，实例每次变更递增
+    pub revision: u64,
+    /// 最后一次变更时间（毫秒）
+    pub last_modified_millis: i64,
+}
+
+/// 服务变更事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceChangeEvent {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+}
+
+/// 服务变更事件通道容量，订阅方落后超过容量时需要全量检查
+const SERVICE_CHANGE_CHANNEL_CAPACITY: usize = 1024;
+
+/// 服务修订号管理器
+type ServiceRevisionManager = Arc<Mutex<HashMap<String, ServiceRevision>>>;
+
+/// 获取或创建服务修订号管理器
+fn get_revision_manager(app: &AppHandle) -> ServiceRevisionManager {
+    if let Some(state) = app.try_state::<ServiceRevisionManager>() {
+        state.inner().clone()
+    } else {
+        let state: ServiceRevisionManager = Arc::new(Mutex::new(HashMap::new()));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 获取服务变更事件通道（首次调用时创建；并发创建时以先注册的通道为准）
+fn get_service_change_sender(app: &AppHandle) -> broadcast::Sender<ServiceChangeEvent> {
+    if let Some(state) = app.try_state::<broadcast::Sender<ServiceChangeEvent>>() {
+        return state.inner().clone();
+    }
+    app.manage(broadcast::channel::<ServiceChangeEvent>(SERVICE_CHANGE_CHANNEL_CAPACITY).0);
+    app.state::<broadcast::Sender<ServiceChangeEvent>>().inner().clone()
+}
+
+/// 订阅服务变更事件
+pub fn subscribe_service_changes(app: &AppHandle) -> broadcast::Receiver<ServiceChangeEvent> {
+    get_service_change_sender(app).subscribe()
+}
+
+/// 服务唯一键
+pub(super) fn service_key(namespace_id: &str, group_name: &str, service_name: &str) -> String {
+    format!("{}##{}@@{}", namespace_id, group_name, service_name)
+}
+
+/// 递增服务修订号（实例注册、注销、健康状态或元数据变更时调用）
+/// 服务被导出时同时递增各导出目标的修订号
+pub async fn bump_service_revision(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> ServiceRevision {
+    let mut changed: Vec<ServiceChangeEvent> = get_service_exports(app, namespace_id, group_name, service_name)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|export| ServiceChangeEvent {
+            namespace_id: export.target_namespace_id,
+            group_name: export.target_group_name,
+            service_name: export.target_service_name,
+        })
+        .collect();
+    changed.push(ServiceChangeEvent {
+        namespace_id: namespace_id.to_string(),
+        group_name: group_name.to_string(),
+        service_name: service_name.to_string(),
+    });
+
+    let revision = {
+        let manager = get_revision_manager(app);
+        let mut revisions = manager.lock().await;
+        let now = current_millis();
+        let mut revision = ServiceRevision { revision: 0, last_modified_millis: 0 };
+        for event in &changed {
+            let entry = revisions
+                .entry(service_key(&event.namespace_id, &event.group_name, &event.service_name))
+                .or_insert(ServiceRevision { revision: 0, last_modified_millis: 0 });
+            entry.revision += 1;
+            entry.last_modified_millis = now;
+            revision = *entry;
+        }
+        revision
+    };
+
+    // 没有订阅方时发送失败，忽略即可
+    let sender = get_service_change_sender(app);
+    for event in changed {
+        let _ = sender.send(event);
+    }
+    revision
+}
+
+/// 查询服务修订号（首次查询时以当前时间初始化）
+pub async fn get_service_revision(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+) -> ServiceRevision {
+    let manager = get_revision_manager(app);
+    let mut revisions = manager.lock().await;
+    *revisions
+        .entry(service_key(namespace_id, group_name, service_name))
+        .or_insert(ServiceRevision { revision: 0, last_modified_millis: current_millis() })
+}
+
+/// 计算实例集合的校验和
+/// 对实例按 IP、端口、集群排序，元数据按键排序，保证相同的实例集合总是得到相同的结果
+pub fn compute_instances_checksum<'a>(instances: impl IntoIterator<Item = &'a InstanceInfo>) -> String {
+    let mut entries: Vec<String> = instances
+        .into_iter()
+        .map(|inst| {
+            let metadata: BTreeMap<String, serde_json::Value> = inst
+                .metadata
+                .as_deref()
+                .and_then(|s| serde_json::from_str(s).ok())
+                .unwrap_or_default();
+            format!(
+                "{}:{}:{}:{}:{}:{}:{}:{}",
+                inst.ip,
+                inst.port,
+                inst.cluster_name,
+                inst.weight,
+                inst.healthy,
+                inst.enabled,
+                inst.ephemeral,
+                serde_json::to_string(&metadata).unwrap_or_default()
+            )
+        })
+        .collect();
+    entries.sort();
+
+    format!("{:x}", md5::compute(entries.join("\n").as_bytes()))
+}
@@ -0,0 +1,441 @@
+/**
+ * 服务模拟器模块
+ * 为前端和网关开发注册虚拟服务：按定义注册 N 个临时实例（元数据支持模板），
+ * 后台任务像真实客户端一样发送心跳保持实例存活（实例过期后重新注册），并按行为配置模拟健康抖动、周期性扩缩容或随机权重变化
+ */
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::health_timeline::HealthChangeSource;
+use super::{
+    current_timestamp, deregister_instance, ensure_service, get_service_instances, heartbeat_instance, patch_instance,
+    register_instance, update_instance_health, InstanceInfo, RegisterInstanceRequest,
+};
+
+/// 标记模拟实例的元数据键，值为模拟名称
+pub const SIMULATOR_METADATA_KEY: &str = "simulator";
+
+/// 默认行为周期（秒）
+pub const DEFAULT_SIMULATION_INTERVAL_SECONDS: u64 = 10;
+
+/// 保持实例存活的周期（秒），小于默认心跳超时
+const SIMULATION_KEEPALIVE_INTERVAL_SECONDS: u64 = 5;
+
+/// 单个模拟的最大实例数
+const MAX_SIMULATED_INSTANCES: u32 = 256;
+
+/// 默认起始端口
+const DEFAULT_SIMULATION_BASE_PORT: i32 = 20000;
+
+/// 模拟行为
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SimulationBehaviour {
+    /// 实例保持稳定
+    #[default]
+    Steady,
+    /// 健康状态抖动：每个周期按概率翻转实例的健康状态
+    Flapping {
+        #[serde(default = "default_flapping_probability")]
+        probability: f64,
+    },
+    /// 周期性扩缩容：每个周期增加一个实例直到上限，再逐个减少到下限
+    #[serde(rename_all = "camelCase")]
+    Scaling { min_instances: u32, max_instances: u32 },
+    /// 随机权重：每个周期为每个实例设置区间内的随机权重
+    #[serde(rename_all = "camelCase")]
+    RandomWeight { min_weight: f64, max_weight: f64 },
+}
+
+fn default_flapping_probability() -> f64 {
+    0.3
+}
+
+/// 虚拟服务定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationDefinition {
+    /// 模拟名称（唯一）
+    pub name: String,
+    #[serde(default)]
+    pub namespace_id: String,
+    #[serde(default)]
+    pub group_name: String,
+    pub service_name: String,
+    /// 初始实例数
+    pub instance_count: u32,
+    /// 实例 IP，默认 127.0.0.1
+    #[serde(default)]
+    pub ip: String,
+    /// 起始端口，第 i 个实例使用 basePort + i，默认 20000
+    #[serde(default)]
+    pub base_port: i32,
+    #[serde(default)]
+    pub cluster_name: String,
+    #[serde(default)]
+    pub weight: Option<f64>,
+    /// 元数据模板，值中的 {index}、{ip}、{port}、{service} 会被替换
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    pub behaviour: SimulationBehaviour,
+    /// 行为周期（秒），默认 10
+    #[serde(default)]
+    pub interval_seconds: u64,
+}
+
+/// 模拟运行状态
+#[derive(Debug, Clone, Serialize)]
+pub struct SimulationStatus {
+    pub definition: SimulationDefinition,
+    pub started_at: i64,
+    /// 注册表中当前属于该模拟的实例
+    pub instances: Vec<InstanceInfo>,
+}
+
+/// 运行中的模拟
+struct Simulation {
+    definition: SimulationDefinition,
+    started_at: i64,
+    task: JoinHandle<()>,
+}
+
+type SimulatorState = Arc<Mutex<HashMap<String, Simulation>>>;
+
+/// 获取或创建模拟器状态
+fn get_simulator_state(app: &AppHandle) -> SimulatorState {
+    if let Some(state) = app.try_state::<SimulatorState>() {
+        state.inner().clone()
+    } else {
+        let state: SimulatorState = Arc::new(Mutex::new(HashMap::new()));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 补全默认值并校验定义
+fn normalize_definition(mut definition: SimulationDefinition) -> Result<SimulationDefinition, String> {
+    definition.name = definition.name.trim().to_string();
+    if definition.name.is_empty() {
+        return Err("Invalid simulation: name is required".to_string());
+    }
+    if definition.service_name.trim().is_empty() {
+        return Err("Invalid simulation: serviceName is required".to_string());
+    }
+    if definition.namespace_id.is_empty() {
+        definition.namespace_id = "public".to_string();
+    }
+    if definition.group_name.is_empty() {
+        definition.group_name = "DEFAULT_GROUP".to_string();
+    }
+    if definition.ip.is_empty() {
+        definition.ip = "127.0.0.1".to_string();
+    }
+    if definition.base_port <= 0 {
+        definition.base_port = DEFAULT_SIMULATION_BASE_PORT;
+    }
+    if definition.cluster_name.is_empty() {
+        definition.cluster_name = "DEFAULT".to_string();
+    }
+    if definition.interval_seconds == 0 {
+        definition.interval_seconds = DEFAULT_SIMULATION_INTERVAL_SECONDS;
+    }
+
+    let max_instances = match &definition.behaviour {
+        SimulationBehaviour::Scaling { min_instances, max_instances } => {
+            if min_instances > max_instances {
+                return Err("Invalid simulation: minInstances is greater than maxInstances".to_string());
+            }
+            definition.instance_count = definition.instance_count.clamp(*min_instances, *max_instances);
+            *max_instances
+        }
+        SimulationBehaviour::Flapping { probability } if !(0.0..=1.0).contains(probability) => {
+            return Err("Invalid simulation: probability must be between 0 and 1".to_string());
+        }
+        SimulationBehaviour::RandomWeight { min_weight, max_weight } if min_weight > max_weight || *min_weight < 0.0 => {
+            return Err("Invalid simulation: invalid weight range".to_string());
+        }
+        _ => definition.instance_count,
+    };
+    if max_instances == 0 || max_instances > MAX_SIMULATED_INSTANCES {
+        return Err(format!("Invalid simulation: instance count must be between 1 and {}", MAX_SIMULATED_INSTANCES));
+    }
+    if definition.base_port as i64 + max_instances as i64 > 65535 {
+        return Err("Invalid simulation: port range exceeds 65535".to_string());
+    }
+
+    Ok(definition)
+}
+
+/// 渲染实例元数据，并加上模拟标记
+fn render_metadata(definition: &SimulationDefinition, index: u32, port: i32) -> String {
+    let mut metadata: BTreeMap<String, String> = definition
+        .metadata
+        .iter()
+        .map(|(key, value)| {
+            let value = value
+                .replace("{index}", &index.to_string())
+                .replace("{ip}", &definition.ip)
+                .replace("{port}", &port.to_string())
+                .replace("{service}", &definition.service_name);
+            (key.clone(), value)
+        })
+        .collect();
+    metadata.insert(SIMULATOR_METADATA_KEY.to_string(), definition.name.clone());
+    serde_json::to_string(&metadata).unwrap_or_default()
+}
+
+/// 注册第 index 个模拟实例，返回实例 ID
+async fn register_simulated_instance(app: &AppHandle, definition: &SimulationDefinition, index: u32) -> Result<String, String> {
+    let port = definition.base_port + index as i32;
+    let instance = register_instance(
+        app,
+        RegisterInstanceRequest {
+            namespace_id: definition.namespace_id.clone(),
+            group_name: definition.group_name.clone(),
+            service_name: definition.service_name.clone(),
+            ip: definition.ip.clone(),
+            port,
+            weight: definition.weight,
+            healthy: Some(true),
+            enabled: Some(true),
+            ephemeral: Some(true),
+            cluster_name: Some(definition.cluster_name.clone()),
+            metadata: Some(render_metadata(definition, index, port)),
+        },
+    )
+    .await?;
+    Ok(instance.instance_id)
+}
+
+/// 查询注册表中属于该模拟的实例
+async fn simulated_instances(app: &AppHandle, definition: &SimulationDefinition) -> Result<Vec<InstanceInfo>, String> {
+    let response = get_service_instances(app, &definition.namespace_id, &definition.group_name, &definition.service_name).await?;
+    Ok(response
+        .instances
+        .into_iter()
+        .filter(|inst| {
+            inst.metadata
+                .as_deref()
+                .and_then(|m| serde_json::from_str::<HashMap<String, String>>(m).ok())
+                .and_then(|m| m.get(SIMULATOR_METADATA_KEY).cloned())
+                .as_deref()
+                == Some(definition.name.as_str())
+        })
+        .collect())
+}
+
+/// xorshift 伪随机数，返回 [0, 1) 区间的值
+fn next_random(seed: &mut u64) -> f64 {
+    *seed ^= *seed << 13;
+    *seed ^= *seed >> 7;
+    *seed ^= *seed << 17;
+    (*seed >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// 模拟任务：定期发送实例心跳，并按行为配置修改实例
+/// 健康抖动中处于不健康状态的实例停止心跳，与真实的故障实例一致
+async fn run_simulation(app: AppHandle, definition: SimulationDefinition, mut instances: BTreeMap<u32, String>) {
+    let mut seed = (chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64) | 1;
+    let mut healthy: HashMap<u32, bool> = instances.keys().map(|index| (*index, true)).collect();
+    let mut scaling_up = true;
+    let mut keepalive = tokio::time::interval(Duration::from_secs(SIMULATION_KEEPALIVE_INTERVAL_SECONDS));
+    let mut behaviour_tick = tokio::time::interval(Duration::from_secs(definition.interval_seconds));
+    behaviour_tick.tick().await;
+
+    let (namespace_id, group_name, service_name) = (&definition.namespace_id, &definition.group_name, &definition.service_name);
+    loop {
+        tokio::select! {
+            _ = keepalive.tick() => {
+                let indexes: Vec<u32> = instances.keys().copied().collect();
+                for index in indexes {
+                    if !healthy.get(&index).copied().unwrap_or(true) {
+                        continue;
+                    }
+                    let port = definition.base_port + index as i32;
+                    let beat = heartbeat_instance(&app, namespace_id, group_name, service_name, &definition.ip, port, &definition.cluster_name).await;
+                    // 实例已过期或被移除时重新注册
+                    let result = match beat {
+                        Ok(true) => Ok(()),
+                        Ok(false) => register_simulated_instance(&app, &definition, index).await.map(|instance_id| {
+                            instances.insert(index, instance_id);
+                        }),
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        tracing::warn!("Simulation {} keepalive failed: {}", definition.name, e);
+                    }
+                }
+            }
+            _ = behaviour_tick.tick() => {
+                let result = match &definition.behaviour {
+                    SimulationBehaviour::Steady => Ok(()),
+                    SimulationBehaviour::Flapping { probability } => {
+                        let mut result = Ok(());
+                        for (index, instance_id) in &instances {
+                            if next_random(&mut seed) >= *probability {
+                                continue;
+                            }
+                            let state = healthy.entry(*index).or_insert(true);
+                            *state = !*state;
+                            result = update_instance_health(
+                                &app,
+                                namespace_id,
+                                group_name,
+                                service_name,
+                                instance_id,
+                                *state,
+                                HealthChangeSource::Simulator,
+                                "simulator: flapping",
+                            )
+                            .await;
+                            if result.is_err() {
+                                break;
+                            }
+                        }
+                        result
+                    }
+                    SimulationBehaviour::Scaling { min_instances, max_instances } => {
+                        let count = instances.len() as u32;
+                        if scaling_up && count >= *max_instances {
+                            scaling_up = false;
+                        } else if !scaling_up && count <= *min_instances {
+                            scaling_up = true;
+                        }
+                        if scaling_up && count < *max_instances {
+                            let index = (0..*max_instances).find(|i| !instances.contains_key(i)).unwrap_or(count);
+                            register_simulated_instance(&app, &definition, index).await.map(|instance_id| {
+                                instances.insert(index, instance_id);
+                            })
+                        } else if !scaling_up && count > *min_instances {
+                            match instances.pop_last() {
+                                Some((_, instance_id)) => {
+                                    deregister_instance(&app, namespace_id, group_name, service_name, &instance_id).await
+                                }
+                                None => Ok(()),
+                            }
+                        } else {
+                            Ok(())
+                        }
+                    }
+                    SimulationBehaviour::RandomWeight { min_weight, max_weight } => {
+                        let mut result = Ok(());
+                        for instance_id in instances.values() {
+                            let weight = min_weight + next_random(&mut seed) * (max_weight - min_weight);
+                            let weight = (weight * 100.0).round() / 100.0;
+                            result = patch_instance(&app, namespace_id, group_name, service_name, instance_id, Some(weight), None, None, None).await;
+                            if result.is_err() {
+                                break;
+                            }
+                        }
+                        result
+                    }
+                };
+                if let Err(e) = result {
+                    tracing::warn!("Simulation {} step failed: {}", definition.name, e);
+                }
+            }
+        }
+    }
+}
+
+/// 启动模拟：注册初始实例并启动后台任务
+pub async fn start_simulation(app: &AppHandle, definition: SimulationDefinition) -> Result<SimulationStatus, String> {
+    let definition = normalize_definition(definition)?;
+    let state = get_simulator_state(app);
+    let mut simulations = state.lock().await;
+    if simulations.contains_key(&definition.name) {
+        return Err(format!("Simulation already exists: {}", definition.name));
+    }
+
+    ensure_service(app, &definition.namespace_id, &definition.group_name, &definition.service_name).await?;
+    let mut instances = BTreeMap::new();
+    for index in 0..definition.instance_count {
+        match register_simulated_instance(app, &definition, index).await {
+            Ok(instance_id) => {
+                instances.insert(index, instance_id);
+            }
+            Err(e) => {
+                for instance_id in instances.values() {
+                    let _ = deregister_instance(app, &definition.namespace_id, &definition.group_name, &definition.service_name, instance_id).await;
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    let started_at = current_timestamp();
+    let task = tokio::spawn(run_simulation(app.clone(), definition.clone(), instances));
+    simulations.insert(
+        definition.name.clone(),
+        Simulation {
+            definition: definition.clone(),
+            started_at,
+            task,
+        },
+    );
+    drop(simulations);
+
+    tracing::info!("Simulation {} started for service {}", definition.name, definition.service_name);
+    let instances = simulated_instances(app, &definition).await?;
+    Ok(SimulationStatus {
+        definition,
+        started_at,
+        instances,
+    })
+}
+
+/// 停止模拟：停止后台任务并注销该模拟注册的实例
+pub async fn stop_simulation(app: &AppHandle, name: &str) -> Result<(), String> {
+    let state = get_simulator_state(app);
+    let simulation = state
+        .lock()
+        .await
+        .remove(name)
+        .ok_or_else(|| format!("Simulation not found: {}", name))?;
+    simulation.task.abort();
+
+    let definition = &simulation.definition;
+    for instance in simulated_instances(app, definition).await? {
+        deregister_instance(app, &definition.namespace_id, &definition.group_name, &definition.service_name, &instance.instance_id).await?;
+    }
+    tracing::info!("Simulation {} stopped", name);
+    Ok(())
+}
+
+/// 查询单个模拟的状态
+pub async fn get_simulation(app: &AppHandle, name: &str) -> Result<SimulationStatus, String> {
+    let (definition, started_at) = {
+        let state = get_simulator_state(app);
+        let simulations = state.lock().await;
+        let simulation = simulations.get(name).ok_or_else(|| format!("Simulation not found: {}", name))?;
+        (simulation.definition.clone(), simulation.started_at)
+    };
+    let instances = simulated_instances(app, &definition).await?;
+    Ok(SimulationStatus {
+        definition,
+        started_at,
+        instances,
+    })
+}
+
+/// 查询所有模拟的状态，按名称排序
+pub async fn list_simulations(app: &AppHandle) -> Result<Vec<SimulationStatus>, String> {
+    let mut names: Vec<String> = get_simulator_state(app).lock().await.keys().cloned().collect();
+    names.sort();
+    let mut statuses = Vec::new();
+    for name in names {
+        // 查询期间被停止的模拟跳过
+        if let Ok(status) = get_simulation(app, &name).await {
+            statuses.push(status);
+        }
+    }
+    Ok(statuses)
+}
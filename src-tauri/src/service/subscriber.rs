@@ -0,0 +1,220 @@
+/**
+ * 服务订阅者模块
+ * 记录查询实例列表和通过 gRPC 订阅服务的消费者（clientIP、应用名、agent、集群），并提供分页查询
+ */
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use super::current_timestamp;
+
+/// 订阅者过期时间（秒），超过该时间未再次查询实例列表的订阅者视为失效
+pub const NAMING_SUBSCRIBER_EXPIRE_SECONDS: i64 = 30;
+
+/// 服务订阅者
+#[derive(Debug, Clone, Serialize)]
+pub struct NamingSubscriber {
+    pub namespace_id: String,
+    pub group_name: String,
+    pub service_name: String,
+    pub client_ip: String,
+    pub client_port: i32,
+    pub app_name: String,
+    pub agent: String,
+    pub clusters: String,
+    pub last_ref_time: i64,
+}
+
+impl NamingSubscriber {
+    /// 订阅者地址（IP:PORT）
+    pub fn addr_str(&self) -> String {
+        format!("{}:{}", self.client_ip, self.client_port)
+    }
+}
+
+/// 服务订阅者列表响应
+#[derive(Debug, Serialize)]
+pub struct NamingSubscriberListResponse {
+    pub total_count: i64,
+    pub page_number: i64,
+    pub pages_available: i64,
+    pub page_items: Vec<NamingSubscriber>,
+}
+
+/// 记录服务订阅者
+/// 同一消费者（IP、端口、集群）重复查询时只刷新 last_ref_time
+pub async fn record_naming_subscriber(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    client_ip: &str,
+    client_port: i32,
+    app_name: Option<&str>,
+    agent: Option<&str>,
+    clusters: &str,
+) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let now = current_timestamp();
+
+    db.execute(
+        "INSERT OR REPLACE INTO naming_subscribers (namespace_id, group_name, service_name, client_ip, client_port, app_name, agent, clusters, last_ref_time, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, COALESCE((SELECT created_at FROM naming_subscribers WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND client_ip = ?4 AND client_port = ?5 AND clusters = ?8), ?9))",
+        &[
+            ("?1", namespace_id),
+            ("?2", group_name),
+            ("?3", service_name),
+            ("?4", client_ip),
+            ("?5", &client_port.to_string()),
+            ("?6", app_name.unwrap_or("")),
+            ("?7", agent.unwrap_or("")),
+            ("?8", clusters),
+            ("?9", &now.to_string()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to record naming subscriber: {}", e))?;
+
+    Ok(())
+}
+
+/// 移除服务订阅者（gRPC 客户端取消订阅或连接断开时调用）
+pub async fn remove_naming_subscriber(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    client_ip: &str,
+    client_port: i32,
+    clusters: &str,
+) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "DELETE FROM naming_subscribers WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 AND client_ip = ?4 AND client_port = ?5 AND clusters = ?6",
+        &[
+            ("?1", namespace_id),
+            ("?2", group_name),
+            ("?3", service_name),
+            ("?4", client_ip),
+            ("?5", &client_port.to_string()),
+            ("?6", clusters),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to remove naming subscriber: {}", e))?;
+
+    Ok(())
+}
+
+/// 清理过期的服务订阅者
+pub async fn cleanup_expired_naming_subscribers(app: &AppHandle) -> Result<(), String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let expire_before = current_timestamp() - NAMING_SUBSCRIBER_EXPIRE_SECONDS;
+
+    db.execute(
+        "DELETE FROM naming_subscribers WHERE last_ref_time < ?1",
+        &[("?1", &expire_before.to_string())],
+    )
+    .await
+    .map_err(|e| format!("Failed to cleanup naming subscribers: {}", e))?;
+
+    Ok(())
+}
+
+/// 分页查询服务订阅者
+/// aggregation 为 true 时按 IP:PORT 聚合（合并集群），否则逐条返回
+pub async fn get_naming_subscribers(
+    app: &AppHandle,
+    namespace_id: &str,
+    group_name: &str,
+    service_name: &str,
+    page_no: i64,
+    page_size: i64,
+    aggregation: bool,
+) -> Result<NamingSubscriberListResponse, String> {
+    // 先清理过期订阅者，保证查询结果只包含仍然活跃的消费者
+    cleanup_expired_naming_subscribers(app).await?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let page_no = page_no.max(1);
+    let page_size = page_size.max(1);
+    let offset = (page_no - 1) * page_size;
+
+    let (count_sql, list_sql) = if aggregation {
+        (
+            "SELECT COUNT(*) FROM (SELECT 1 FROM naming_subscribers WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 GROUP BY client_ip, client_port)",
+            "SELECT client_ip, client_port, MAX(app_name), MAX(agent), GROUP_CONCAT(DISTINCT clusters), MAX(last_ref_time) FROM naming_subscribers WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 GROUP BY client_ip, client_port ORDER BY client_ip, client_port LIMIT ?4 OFFSET ?5",
+        )
+    } else {
+        (
+            "SELECT COUNT(*) FROM naming_subscribers WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3",
+            "SELECT client_ip, client_port, app_name, agent, clusters, last_ref_time FROM naming_subscribers WHERE namespace_id = ?1 AND group_name = ?2 AND service_name = ?3 ORDER BY client_ip, client_port, clusters LIMIT ?4 OFFSET ?5",
+        )
+    };
+
+    let total_count: Option<(i64,)> = db
+        .query_one(
+            count_sql,
+            &[("?1", namespace_id), ("?2", group_name), ("?3", service_name)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query naming subscriber count: {}", e))?;
+
+    let total_count = total_count.map(|(c,)| c).unwrap_or(0);
+    let pages_available = (total_count + page_size - 1) / page_size;
+
+    let rows: Vec<(String, i32, Option<String>, Option<String>, Option<String>, i64)> = db
+        .query(
+            list_sql,
+            &[
+                ("?1", namespace_id),
+                ("?2", group_name),
+                ("?3", service_name),
+                ("?4", &page_size.to_string()),
+                ("?5", &offset.to_string()),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to query naming subscribers: {}", e))?;
+
+    let page_items = rows
+        .into_iter()
+        .map(|(client_ip, client_port, app_name, agent, clusters, last_ref_time)| NamingSubscriber {
+            namespace_id: namespace_id.to_string(),
+            group_name: group_name.to_string(),
+            service_name: service_name.to_string(),
+            client_ip,
+            client_port,
+            app_name: app_name.unwrap_or_default(),
+            agent: agent.unwrap_or_default(),
+            clusters: clusters.unwrap_or_default(),
+            last_ref_time,
+        })
+        .collect();
+
+    Ok(NamingSubscriberListResponse {
+        total_count,
+        page_number: page_no,
+        pages_available,
+        page_items,
+    })
+}
@@ -0,0 +1,198 @@
+/**
+ * 命名服务系统开关模块
+ * 开关保存在 naming_switches 表中，并缓存在内存里；修改后立即生效，无需重启服务器
+ */
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tokio::sync::RwLock;
+
+use super::affinity::validate_zone_ip_labels;
+use super::current_timestamp;
+
+/// 客户端心跳间隔开关（毫秒）
+pub const SWITCH_CLIENT_BEAT_INTERVAL: &str = "clientBeatInterval";
+/// 健康检查开关
+pub const SWITCH_HEALTH_CHECK_ENABLED: &str = "healthCheckEnabled";
+/// 推送开关
+pub const SWITCH_PUSH_ENABLED: &str = "pushEnabled";
+/// 注册时未指定 ephemeral 的实例默认类型
+pub const SWITCH_DEFAULT_INSTANCE_EPHEMERAL: &str = "defaultInstanceEphemeral";
+/// 禁止注册新实例开关
+pub const SWITCH_DISABLE_ADD_IP: &str = "disableAddIP";
+/// 区域亲和独占开关
+pub const SWITCH_ZONE_AFFINITY_EXCLUSIVE: &str = "zoneAffinityExclusive";
+/// 区域亲和退回阈值开关
+pub const SWITCH_ZONE_AFFINITY_THRESHOLD: &str = "zoneAffinityThreshold";
+/// 客户端 IP 区域标签开关
+pub const SWITCH_ZONE_IP_LABELS: &str = "zoneIpLabels";
+
+/// 系统开关
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamingSwitches {
+    /// 心跳响应中返回给客户端的心跳间隔（毫秒）
+    pub client_beat_interval: i64,
+    /// 关闭后不再执行持久化实例健康检查和临时实例心跳过期检查
+    pub health_check_enabled: bool,
+    /// 关闭后不再记录推送订阅者
+    pub push_enabled: bool,
+    /// 注册时未指定 ephemeral 的实例是否为临时实例
+    pub default_instance_ephemeral: bool,
+    /// 开启后拒绝注册新实例
+    #[serde(rename = "disableAddIP")]
+    pub disable_add_ip: bool,
+    /// 开启后实例列表只返回调用方同区域的实例（触发退回时除外）
+    pub zone_affinity_exclusive: bool,
+    /// 同区域健康实例比例低于该值（0~1）时退回其他区域
+    pub zone_affinity_threshold: f64,
+    /// 客户端 IP 到区域的标签规则，格式: "10.0.1.0/24=zone-a,10.0.2.8=zone-b"
+    pub zone_ip_labels: String,
+}
+
+impl Default for NamingSwitches {
+    fn default() -> Self {
+        Self {
+            client_beat_interval: 5000,
+            health_check_enabled: true,
+            push_enabled: true,
+            default_instance_ephemeral: true,
+            disable_add_ip: false,
+            zone_affinity_exclusive: false,
+            zone_affinity_threshold: 0.0,
+            zone_ip_labels: String::new(),
+        }
+    }
+}
+
+impl NamingSwitches {
+    /// 按开关名设置开关值，开关名不支持或值非法时返回错误
+    fn apply(&mut self, entry: &str, value: &str) -> Result<(), String> {
+        let parse_bool = |value: &str| match value.trim().to_lowercase().as_str() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(format!("Invalid value for switch {}: {}", entry, value)),
+        };
+
+        match entry {
+            SWITCH_CLIENT_BEAT_INTERVAL => {
+                self.client_beat_interval = value
+                    .trim()
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|v| *v > 0)
+                    .ok_or_else(|| format!("Invalid value for switch {}: {}", entry, value))?;
+            }
+            SWITCH_HEALTH_CHECK_ENABLED => self.health_check_enabled = parse_bool(value)?,
+            SWITCH_PUSH_ENABLED => self.push_enabled = parse_bool(value)?,
+            SWITCH_DEFAULT_INSTANCE_EPHEMERAL => self.default_instance_ephemeral = parse_bool(value)?,
+            SWITCH_DISABLE_ADD_IP => self.disable_add_ip = parse_bool(value)?,
+            SWITCH_ZONE_AFFINITY_EXCLUSIVE => self.zone_affinity_exclusive = parse_bool(value)?,
+            SWITCH_ZONE_AFFINITY_THRESHOLD => {
+                self.zone_affinity_threshold = value
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|v| (0.0..=1.0).contains(v))
+                    .ok_or_else(|| format!("Invalid value for switch {}: {}", entry, value))?;
+            }
+            SWITCH_ZONE_IP_LABELS => {
+                validate_zone_ip_labels(value)?;
+                self.zone_ip_labels = value.trim().to_string();
+            }
+            _ => return Err(format!("Invalid switch entry: {}", entry)),
+        }
+        Ok(())
+    }
+}
+
+/// 开关缓存（首次访问时从数据库加载）
+type SwitchesState = Arc<RwLock<Option<NamingSwitches>>>;
+
+/// 获取或创建开关缓存
+fn get_switches_state(app: &AppHandle) -> SwitchesState {
+    if let Some(state) = app.try_state::<SwitchesState>() {
+        state.inner().clone()
+    } else {
+        let state: SwitchesState = Arc::new(RwLock::new(None));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 从数据库加载开关，未保存的开关使用默认值
+async fn load_naming_switches(app: &AppHandle) -> Result<NamingSwitches, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let rows: Vec<(String, String)> = db
+        .query("SELECT entry, value FROM naming_switches", &[])
+        .await
+        .map_err(|e| format!("Failed to query switches: {}", e))?;
+
+    let mut switches = NamingSwitches::default();
+    for (entry, value) in rows {
+        if let Err(e) = switches.apply(&entry, &value) {
+            tracing::warn!("Ignoring stored switch: {}", e);
+        }
+    }
+    Ok(switches)
+}
+
+/// 查询当前系统开关
+pub async fn get_naming_switches(app: &AppHandle) -> NamingSwitches {
+    let state = get_switches_state(app);
+    if let Some(switches) = state.read().await.as_ref() {
+        return switches.clone();
+    }
+
+    let mut cached = state.write().await;
+    if let Some(switches) = cached.as_ref() {
+        return switches.clone();
+    }
+    match load_naming_switches(app).await {
+        Ok(switches) => {
+            *cached = Some(switches.clone());
+            switches
+        }
+        Err(e) => {
+            tracing::warn!("Failed to load switches, using defaults: {}", e);
+            NamingSwitches::default()
+        }
+    }
+}
+
+/// 修改系统开关，保存到数据库并立即更新缓存
+pub async fn update_naming_switch(app: &AppHandle, entry: &str, value: &str) -> Result<NamingSwitches, String> {
+    // 确保缓存已加载，修改期间持有写锁，避免并发修改互相覆盖
+    get_naming_switches(app).await;
+    let state = get_switches_state(app);
+    let mut cached = state.write().await;
+    let mut switches = cached.clone().unwrap_or_default();
+    switches.apply(entry, value)?;
+
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    db.execute(
+        "INSERT INTO naming_switches (entry, value, gmt_modified) VALUES (?1, ?2, ?3) ON CONFLICT(entry) DO UPDATE SET value = excluded.value, gmt_modified = excluded.gmt_modified",
+        &[
+            ("?1", entry),
+            ("?2", value.trim()),
+            ("?3", &current_timestamp().to_string()),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to save switch: {}", e))?;
+
+    *cached = Some(switches.clone());
+    Ok(switches)
+}
@@ -0,0 +1,92 @@
+/**
+ * xDS 资源快照模块
+ * 把注册表中的所有服务映射为 Envoy 集群（每个服务一个 EDS 集群），
+ * 快照内容（服务集合和实例集合校验和）变化时版本号递增，用于 ADS 的 version_info
+ */
+
+use std::sync::Arc;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use super::{compute_instances_checksum, get_service_instances, InstanceInfo};
+
+/// 集群名后缀（与 Nacos 的 Istio 集成一致：服务名.分组.命名空间.nacos）
+const XDS_CLUSTER_NAME_SUFFIX: &str = "nacos";
+
+/// 由服务映射的 Envoy 集群
+#[derive(Debug, Clone)]
+pub struct XdsCluster {
+    pub name: String,
+    pub instances: Vec<InstanceInfo>,
+}
+
+/// xDS 资源快照
+#[derive(Debug, Clone)]
+pub struct XdsSnapshot {
+    pub version: u64,
+    pub clusters: Vec<XdsCluster>,
+}
+
+/// 快照版本状态：快照指纹变化时递增
+#[derive(Default)]
+struct XdsSnapshotVersion {
+    fingerprint: String,
+    version: u64,
+}
+
+type XdsSnapshotVersionState = Arc<Mutex<XdsSnapshotVersion>>;
+
+/// 获取或创建快照版本状态
+fn get_snapshot_version_state(app: &AppHandle) -> XdsSnapshotVersionState {
+    if let Some(state) = app.try_state::<XdsSnapshotVersionState>() {
+        state.inner().clone()
+    } else {
+        let state: XdsSnapshotVersionState = Arc::new(Mutex::new(XdsSnapshotVersion::default()));
+        app.manage(state.clone());
+        state
+    }
+}
+
+/// 服务对应的 Envoy 集群名
+fn xds_cluster_name(namespace_id: &str, group_name: &str, service_name: &str) -> String {
+    format!("{}.{}.{}.{}", service_name, group_name, namespace_id, XDS_CLUSTER_NAME_SUFFIX)
+}
+
+/// 构建当前注册表的 xDS 快照
+pub async fn get_xds_snapshot(app: &AppHandle) -> Result<XdsSnapshot, String> {
+    let db = app
+        .sqlite_plugin()
+        .get_connection("sqlite:nacos.db")
+        .await
+        .map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let services: Vec<(String, String, String)> = db
+        .query(
+            "SELECT namespace_id, group_name, service_name FROM service_info ORDER BY namespace_id, group_name, service_name",
+            &[],
+        )
+        .await
+        .map_err(|e| format!("Failed to query services: {}", e))?;
+
+    let mut clusters = Vec::new();
+    let mut fingerprint = Vec::new();
+    for (namespace_id, group_name, service_name) in services {
+        let instances = get_service_instances(app, &namespace_id, &group_name, &service_name).await?.instances;
+        let name = xds_cluster_name(&namespace_id, &group_name, &service_name);
+        fingerprint.push(format!("{}:{}", name, compute_instances_checksum(instances.iter())));
+        clusters.push(XdsCluster { name, instances });
+    }
+    let fingerprint = format!("{:x}", md5::compute(fingerprint.join("\n").as_bytes()));
+
+    let state = get_snapshot_version_state(app);
+    let mut snapshot_version = state.lock().await;
+    if snapshot_version.version == 0 || snapshot_version.fingerprint != fingerprint {
+        snapshot_version.version += 1;
+        snapshot_version.fingerprint = fingerprint;
+    }
+    Ok(XdsSnapshot {
+        version: snapshot_version.version,
+        clusters,
+    })
+}
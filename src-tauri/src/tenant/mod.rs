@@ -249,7 +249,15 @@ pub async fn delete_namespace(
         return Err("Namespace not found".to_string());
     }
 
-    // 删除命名空间下的所有配置
+    // 删除命名空间下的所有配置，并通知监听这些配置的客户端
+    let configs: Vec<(String, String)> = db
+        .query(
+            "SELECT data_id, group_id FROM config_info WHERE tenant_id = ?1",
+            &[("?1", tenant_id)],
+        )
+        .await
+        .map_err(|e| format!("Failed to query configs: {}", e))?;
+
     db.execute(
         "DELETE FROM config_info WHERE tenant_id = ?1",
         &[("?1", tenant_id)],
@@ -257,6 +265,10 @@ pub async fn delete_namespace(
     .await
     .map_err(|e| format!("Failed to delete configs: {}", e))?;
 
+    for (data_id, group_id) in configs {
+        crate::config::notify_config_change(app, &data_id, &group_id, tenant_id);
+    }
+
     // 删除命名空间下的所有服务
     db.execute(
         "DELETE FROM service_info WHERE namespace_id = ?1",
@@ -273,6 +285,9 @@ pub async fn delete_namespace(
     .await
     .map_err(|e| format!("Failed to delete instances: {}", e))?;
 
+    crate::service::remove_namespace_ephemeral_instances(app, tenant_id).await;
+    crate::service::remove_namespace_exports(app, tenant_id).await?;
+
     // 删除命名空间
     db.execute(
         "DELETE FROM tenant_info WHERE kp = ?1 AND tenant_id = ?2",